@@ -0,0 +1,78 @@
+//! Times scripts that spend most of their time looking up attributes through parents.
+//!
+//! Run with `cargo bench -p quest-bin`. Each script's run a few times, and the fastest is reported.
+//! To compare against another build, set `QUEST_BIN` to its executable.
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+const RUNS: usize = 10;
+
+const BENCHES: &[(&str, &str)] = &[
+	// every call creates stackframes, whose parents are the function's scope and the caller.
+	("recursive calls", "
+		fib = n -> { if(n < 2, { n }, { fib(n - 1) + fib(n - 2) }) };
+		fib(18);
+	"),
+	("inherited lookups", "
+		Base = { VALUE = 1; :0 }();
+		Middle = { __parents__ = [Base]; :0 }();
+		Leaf = { __parents__ = [Middle]; :0 }();
+
+		total = 0;
+		i = 0;
+		while({ i < 20000 }, {
+			total += Leaf.VALUE;
+			i += 1;
+		});
+	"),
+	("deep hierarchy", "
+		Class = { VALUE = 1; :0 }();
+		i = 0;
+		while({ i < 30 }, {
+			Class = { __parents__ = [Class]; :0 }();
+			i += 1;
+		});
+
+		total = 0;
+		i = 0;
+		while({ i < 20000 }, {
+			total += Class.VALUE;
+			i += 1;
+		});
+	"),
+	("diamond", "
+		Named = { name = \"named\"; :0 }();
+		Greeter = { __parents__ = [Named]; :0 }();
+		Shouter = { __parents__ = [Named]; :0 }();
+		Robot = { __parents__ = [Greeter, Shouter]; :0 }();
+
+		i = 0;
+		while({ i < 20000 }, {
+			Robot.name;
+			i += 1;
+		});
+	"),
+];
+
+fn time(exe: &str, script: &str) -> Duration {
+	let start = Instant::now();
+	let status = Command::new(exe)
+		.arg("-e")
+		.arg(script)
+		.stdin(Stdio::null())
+		.status()
+		.unwrap_or_else(|err| panic!("couldn't run `{}`: {:?}", exe, err));
+	let elapsed = start.elapsed();
+
+	assert!(status.success(), "`{}` failed", script);
+	elapsed
+}
+
+fn main() {
+	let exe = std::env::var("QUEST_BIN").unwrap_or_else(|_| env!("CARGO_BIN_EXE_quest-bin").to_string());
+
+	for (name, script) in BENCHES {
+		let best = (0..RUNS).map(|_| time(&exe, script)).min().expect("`RUNS` isn't zero");
+		println!("{:<20} {:>8.1}ms", name, best.as_secs_f64() * 1000.0);
+	}
+}
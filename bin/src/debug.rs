@@ -0,0 +1,249 @@
+//! The `debug` subcommand, which runs a Quest file in a debugger that's controlled from STDIN.
+
+use crate::error::Result;
+use clap::Clap;
+use quest_core::Object;
+use quest_core::types::{List, Text};
+use quest_parser::debugger::{self, Breakpoint, Debugger, Frame, Frontend, Pause, Reason, Resume};
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+
+/// Run a Quest file in a debugger, which can pause it and look at its variables
+#[derive(Clap, Debug)]
+pub struct DebugOpts {
+	/// Pause at `file:line`, or `line` in any file. Execution also pauses at the first line, and
+	/// wherever `Kernel.breakpoint()` is called.
+	#[clap(short = "b", long = "break", number_of_values = 1)]
+	breakpoints: Vec<Breakpoint>,
+
+	/// The file to debug.
+	file: PathBuf,
+
+	/// Arguments to pass to the file.
+	args: Vec<String>
+}
+
+const HELP: &str = "\
+commands:
+  c, continue          run until the next breakpoint
+  s, step              run the next line, pausing in any block it calls
+  n, next              run the next line, without pausing in blocks it calls
+  f, finish            run until the current block returns
+  b, break [FILE:]LINE pause at LINE (in FILE, if given)
+  b, break             list the breakpoints
+  d, delete [N]        delete breakpoint N, or all of them
+  p, print [EXPR]      evaluate EXPR in the selected frame, or print the frame's keys
+  bt, where            list the stackframes, innermost first
+  up [N], down [N]     select the frame N (default 1) frames out or in
+  l, list              show the code around the selected frame's line
+  q, quit              stop debugging, and exit
+  h, help              show this message";
+
+/// How many characters of a value are printed, so big ones (eg blocks) don't flood the screen.
+const MAX_VALUE_WIDTH: usize = 80;
+
+/// Asks the user what to do when paused.
+#[derive(Debug, Default)]
+struct Console {
+	sources: HashMap<PathBuf, Vec<String>>,
+	selected: usize
+}
+
+/// A short description of `value`.
+fn inspect(value: &Object) -> String {
+	let inspected = value.call_attr_lit("inspect", &[])
+		.and_then(|text| text.call_downcast::<Text>().map(|text| text.to_string()))
+		.unwrap_or_else(|_| format!("<{}>", value.typename()));
+	let mut line = inspected.lines().next().unwrap_or_default().to_string();
+
+	if line.chars().count() > MAX_VALUE_WIDTH || line.len() != inspected.len() {
+		line = line.chars().take(MAX_VALUE_WIDTH).collect::<String>() + "...";
+	}
+
+	line
+}
+
+impl Console {
+	fn source_line(&mut self, file: &Path, lineno: usize) -> Option<&str> {
+		let lines = self.sources.entry(file.to_path_buf()).or_insert_with(|| {
+			std::fs::read_to_string(file)
+				.map(|source| source.lines().map(str::to_string).collect())
+				.unwrap_or_default()
+		});
+
+		lines.get(lineno.checked_sub(1)?).map(String::as_str)
+	}
+
+	/// Describes where `frame` is, such as `foo.qs:3`.
+	fn location(file: Option<&Path>, lineno: Option<usize>) -> String {
+		let file = file.map_or_else(|| "<eval>".to_string(), |file| file.display().to_string());
+
+		match lineno {
+			Some(lineno) => format!("{}:{}", file, lineno),
+			None => "<not running a line>".to_string()
+		}
+	}
+
+	fn show_line(&mut self, file: Option<&Path>, lineno: usize) {
+		if let Some(line) = file.and_then(|file| self.source_line(file, lineno)) {
+			println!(" {:<3}| {}", lineno, line);
+		}
+	}
+
+	fn list(&mut self, frame: &Frame) {
+		let (file, lineno) =
+			match (&frame.file, frame.lineno) {
+				(Some(file), Some(lineno)) => (file.clone(), lineno),
+				_ => return println!("no code to show for this frame")
+			};
+
+		for current in lineno.saturating_sub(5).max(1)..=lineno + 5 {
+			if let Some(line) = self.source_line(&file, current) {
+				let marker = if current == lineno { "->" } else { "  " };
+				println!("{} {:<3}| {}", marker, current, line);
+			}
+		}
+	}
+
+	fn backtrace(&mut self, frames: &[Frame]) {
+		for (idx, frame) in frames.iter().enumerate() {
+			let marker = if idx == self.selected { ">" } else { " " };
+			println!("{}#{} {}", marker, idx, Self::location(frame.file.as_deref(), frame.lineno));
+
+			if let Some(lineno) = frame.lineno {
+				self.show_line(frame.file.as_deref(), lineno);
+			}
+		}
+	}
+
+	/// Prints each of the frame's `__keys__`, and its value.
+	fn print_frame(frame: &Frame) {
+		let keys = frame.binding.call_attr_lit("__keys__", &[])
+			.and_then(|keys| keys.call_downcast::<List>().map(|keys| keys.iter().cloned().collect::<Vec<_>>()));
+
+		match keys {
+			Ok(keys) => for key in keys {
+				let value = frame.binding.get_attr(&key).map_or_else(|err| format!("<error: {}>", err), |value| inspect(&value));
+				let name = key.downcast::<Text>().map_or_else(|| inspect(&key), |key| key.to_string());
+				println!("  {} = {}", name, value);
+			},
+			Err(err) => println!("can't get the frame's keys: {}", err)
+		}
+	}
+
+	fn print_breakpoints(breakpoints: &[Breakpoint]) {
+		if breakpoints.is_empty() {
+			println!("no breakpoints");
+		}
+
+		for (idx, breakpoint) in breakpoints.iter().enumerate() {
+			println!("  {}: {}", idx + 1, breakpoint);
+		}
+	}
+
+	/// Runs the command `command`, returning how to resume if it's one that does.
+	fn run_command(&mut self, command: &str, pause: &Pause, breakpoints: &mut Vec<Breakpoint>) -> Option<Resume> {
+		let (name, rest) =
+			match command.find(char::is_whitespace) {
+				Some(idx) => (&command[..idx], command[idx..].trim()),
+				None => (command, "")
+			};
+
+		let count = || rest.parse::<usize>().unwrap_or(1);
+		let frame = &pause.frames[self.selected];
+
+		match name {
+			"c" | "continue" => return Some(Resume::Continue),
+			"s" | "step" => return Some(Resume::Step),
+			"n" | "next" => return Some(Resume::Next),
+			"f" | "finish" => return Some(Resume::Finish),
+			"b" | "break" if rest.is_empty() => Self::print_breakpoints(breakpoints),
+			"b" | "break" => match rest.parse::<Breakpoint>() {
+				Ok(breakpoint) => {
+					println!("breakpoint {} at {}", breakpoints.len() + 1, breakpoint);
+					breakpoints.push(breakpoint);
+				},
+				Err(err) => println!("{}", err)
+			},
+			"d" | "delete" if rest.is_empty() => breakpoints.clear(),
+			"d" | "delete" => match rest.parse::<usize>() {
+				Ok(idx) if 1 <= idx && idx <= breakpoints.len() => { breakpoints.remove(idx - 1); },
+				_ => println!("no breakpoint `{}`", rest)
+			},
+			"p" | "print" if rest.is_empty() => Self::print_frame(frame),
+			"p" | "print" => match debugger::eval(&frame.binding, rest) {
+				Ok(value) => println!("{}", inspect(&value)),
+				Err(err) => println!("error: {}", err)
+			},
+			"bt" | "where" | "backtrace" => self.backtrace(&pause.frames),
+			"up" | "down" => {
+				self.selected =
+					if name == "up" {
+						(self.selected + count()).min(pause.frames.len() - 1)
+					} else {
+						self.selected.saturating_sub(count())
+					};
+
+				let frame = &pause.frames[self.selected];
+				println!("#{} {}", self.selected, Self::location(frame.file.as_deref(), frame.lineno));
+			},
+			"l" | "list" => self.list(frame),
+			"q" | "quit" => std::process::exit(0),
+			"h" | "help" => println!("{}", HELP),
+			"" => {},
+			_ => println!("unknown command `{}`; try `help`", name)
+		}
+
+		None
+	}
+}
+
+impl Frontend for Console {
+	fn pause(&mut self, pause: &Pause, breakpoints: &mut Vec<Breakpoint>) -> Resume {
+		self.selected = 0;
+
+		match pause.reason {
+			Reason::Breakpoint(idx) => println!("breakpoint {} at {}", idx + 1, breakpoints[idx]),
+			Reason::Call => println!("`breakpoint()` called"),
+			Reason::Step => {}
+		}
+
+		println!("paused at {}", Self::location(pause.file.as_deref(), Some(pause.lineno)));
+		self.show_line(pause.file.as_deref(), pause.lineno);
+
+		let stdin = std::io::stdin();
+		let mut command = String::new();
+
+		loop {
+			print!("(qdb) ");
+			let _ = std::io::stdout().flush();
+
+			command.clear();
+
+			// if there's nothing more to read, just run the rest of the program.
+			if !matches!(stdin.lock().read_line(&mut command), Ok(read) if read != 0) {
+				println!();
+				breakpoints.clear();
+				return Resume::Continue;
+			}
+
+			if let Some(resume) = self.run_command(command.trim(), pause, breakpoints) {
+				return resume;
+			}
+		}
+	}
+}
+
+/// Runs the file in `opts` in the debugger.
+pub fn run(DebugOpts { breakpoints, file, args }: DebugOpts) -> Result<bool> {
+	let mut args: Vec<Object> = args.into_iter().map(Object::from).collect();
+	args.insert(0, file.display().to_string().into());
+
+	debugger::attach(Debugger::new(breakpoints, Console::default()).stepping());
+	let result = crate::run::run_file(&file, args.iter().collect());
+	debugger::detach();
+
+	result?;
+	Ok(true)
+}
@@ -0,0 +1,80 @@
+//! The `doc` subcommand, which generates documentation for a Quest module from its `##` comments.
+
+use crate::error::Result;
+use clap::Clap;
+use std::fmt::{self, Display, Formatter};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Generate documentation for a Quest module from the `##` doc comments before its assignments
+#[derive(Clap, Debug)]
+pub struct DocOpts {
+	/// How to write the documentation: `markdown`, or `html` for a standalone page.
+	#[clap(long, default_value = "markdown")]
+	format: Format,
+
+	/// The file to write the documentation to. If it isn't supplied, it's printed to STDOUT.
+	#[clap(short, long)]
+	output: Option<PathBuf>,
+
+	/// The module to document.
+	file: PathBuf
+}
+
+/// How the documentation is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+	Markdown,
+	Html
+}
+
+/// A format that isn't `markdown` or `html`.
+#[derive(Debug)]
+pub struct BadFormat(String);
+
+impl Display for BadFormat {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "unknown format `{}`: expected `markdown` or `html`", self.0)
+	}
+}
+
+impl std::error::Error for BadFormat {}
+
+impl FromStr for Format {
+	type Err = BadFormat;
+
+	fn from_str(input: &str) -> std::result::Result<Self, Self::Err> {
+		match input {
+			"markdown" | "md" => Ok(Self::Markdown),
+			"html" => Ok(Self::Html),
+			_ => Err(BadFormat(input.to_string()))
+		}
+	}
+}
+
+/// Documents the module in `opts`, returning whether it could be parsed.
+pub fn run(DocOpts { format, output, file }: DocOpts) -> Result<bool> {
+	let source = std::fs::read_to_string(&file)?;
+
+	let module =
+		match quest_parser::doc::document(&source, Some(file)) {
+			Ok(module) => module,
+			Err(err) => {
+				eprintln!("{}", err);
+				return Ok(false);
+			}
+		};
+
+	let docs =
+		match format {
+			Format::Markdown => module.markdown(),
+			Format::Html => module.html()
+		};
+
+	match output {
+		Some(output) => std::fs::write(output, docs)?,
+		None => print!("{}", docs)
+	}
+
+	Ok(true)
+}
@@ -4,7 +4,9 @@ use std::fmt::{self, Display, Formatter};
 pub enum Error {
 	Quest(quest_core::Error),
 	Parser(quest_parser::Error),
-	Io(std::io::Error)
+	Io(std::io::Error),
+	Package(String),
+	Usage(String)
 }
 
 impl From<std::io::Error> for Error {
@@ -31,6 +33,8 @@ impl Display for Error {
 			Error::Quest(err) => Display::fmt(&err, f),
 			Error::Parser(err) => Display::fmt(&err, f),
 			Error::Io(err) => Display::fmt(&err, f),
+			Error::Package(err) => Display::fmt(&err, f),
+			Error::Usage(err) => Display::fmt(&err, f),
 		}
 	}
 }
@@ -39,7 +43,8 @@ impl std::error::Error for Error {
 		match self {
 			Error::Quest(err) => Some(err),
 			Error::Parser(err) => Some(err),
-			Error::Io(err) => Some(err)
+			Error::Io(err) => Some(err),
+			Error::Package(_) | Error::Usage(_) => None
 		}
 	}
 }
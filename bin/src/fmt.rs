@@ -0,0 +1,91 @@
+//! The `fmt` subcommand, which formats Quest files.
+
+use crate::error::{Error, Result};
+use clap::Clap;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Format Quest files in the standard style
+#[derive(Clap, Debug)]
+pub struct FmtOpts {
+	/// Report the files that aren't formatted, instead of formatting them.
+	#[clap(long)]
+	check: bool,
+
+	/// The files to format; `.qs` files in directories are formatted too. If none (or just `-`) is
+	/// supplied, STDIN is formatted and written to STDOUT.
+	files: Vec<PathBuf>
+}
+
+/// Adds the `.qs` files within `dir` to `files`.
+pub(crate) fn find_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+	let mut entries = std::fs::read_dir(dir)?
+		.map(|entry| entry.map(|entry| entry.path()))
+		.collect::<std::io::Result<Vec<_>>>()?;
+	entries.sort();
+
+	for path in entries {
+		if path.is_dir() {
+			find_files(&path, files)?;
+		} else if path.extension().is_some_and(|ext| ext == "qs") {
+			files.push(path);
+		}
+	}
+
+	Ok(())
+}
+
+/// Formats the files in `opts`, returning whether they were all formatted (or could be).
+pub fn run(FmtOpts { check, files }: FmtOpts) -> Result<bool> {
+	let is_stdin = |file: &PathBuf| file.to_str() == Some("-");
+
+	if files.iter().any(is_stdin) && files.len() != 1 {
+		return Err(Error::Usage("`-` (STDIN) can't be formatted along with other files".to_string()));
+	}
+
+	if files.is_empty() || files.iter().any(is_stdin) {
+		let mut source = String::new();
+		std::io::stdin().read_to_string(&mut source)?;
+
+		if check {
+			return Ok(quest_parser::fmt::is_formatted(&source, None)?);
+		}
+
+		let formatted = quest_parser::fmt::format(&source, None)?;
+		std::io::stdout().write_all(formatted.as_bytes())?;
+		return Ok(true);
+	}
+
+	let mut paths = vec![];
+
+	for file in files {
+		if file.is_dir() {
+			find_files(&file, &mut paths)?;
+		} else {
+			paths.push(file);
+		}
+	}
+
+	let mut all_formatted = true;
+
+	for path in paths {
+		let source = std::fs::read_to_string(&path)?;
+
+		if check {
+			if !quest_parser::fmt::is_formatted(&source, Some(path.clone()))? {
+				println!("{} isn't formatted", path.display());
+				all_formatted = false;
+			}
+
+			continue;
+		}
+
+		let formatted = quest_parser::fmt::format(&source, Some(path.clone()))?;
+
+		if formatted != source {
+			std::fs::write(&path, formatted)?;
+		}
+	}
+
+	Ok(all_formatted)
+}
@@ -0,0 +1,61 @@
+//! The `lint` subcommand, which checks Quest files for likely mistakes.
+
+use crate::error::Result;
+use crate::fmt::find_files;
+use clap::Clap;
+use std::io::Read;
+use std::path::PathBuf;
+
+/// Check Quest files for likely mistakes, such as misspelled variables
+#[derive(Clap, Debug)]
+pub struct LintOpts {
+	/// The files to lint; `.qs` files in directories are linted too. If none (or `-`) is supplied,
+	/// STDIN is linted.
+	files: Vec<PathBuf>
+}
+
+/// Prints what's found in `source`, returning whether nothing was.
+fn lint_source(source: &str, file: Option<PathBuf>) -> bool {
+	match quest_parser::lint::lint(source, file) {
+		Ok(findings) => {
+			for finding in &findings {
+				println!("{}\n", finding);
+			}
+
+			findings.is_empty()
+		},
+		Err(err) => {
+			println!("{}\n", err);
+			false
+		}
+	}
+}
+
+/// Lints the files in `opts`, returning whether nothing was found in any of them.
+pub fn run(LintOpts { files }: LintOpts) -> Result<bool> {
+	if files.is_empty() || files.iter().any(|file| file.to_str() == Some("-")) {
+		let mut source = String::new();
+		std::io::stdin().read_to_string(&mut source)?;
+
+		return Ok(lint_source(&source, None));
+	}
+
+	let mut paths = vec![];
+
+	for file in files {
+		if file.is_dir() {
+			find_files(&file, &mut paths)?;
+		} else {
+			paths.push(file);
+		}
+	}
+
+	let mut is_clean = true;
+
+	for path in paths {
+		let source = std::fs::read_to_string(&path)?;
+		is_clean &= lint_source(&source, Some(path));
+	}
+
+	Ok(is_clean)
+}
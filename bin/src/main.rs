@@ -1,5 +1,11 @@
 mod run;
 mod error;
+mod fmt;
+mod lint;
+mod debug;
+mod test;
+mod doc;
+mod package;
 
 use error::Result;
 use quest_core::Object;
@@ -10,15 +16,36 @@ use clap::Clap;
 #[clap(version = "0.1", author = "Sam Westerman <sam@sampersand.me>")]
 struct Opts {
 	/// Define the file to run. If `-` is supplied, STDIN is read.
-	#[clap(short='f', long, conflicts_with="eval")]
+	#[clap(short="f", long, conflicts_with="eval")]
 	file: Option<std::path::PathBuf>,
 
 	/// Evaluate a passed command as quest code. Omit `file`.
 	#[clap(short, long, conflicts_with="file")]
 	eval: Option<String>,
 
+	/// Freeze the builtin types, so their attributes can't be changed.
+	#[clap(long)]
+	freeze_builtins: bool,
+
+	/// After running, report objects that are still alive but can't be reached.
+	#[clap(long)]
+	report_leaks: bool,
+
+	/// Record how long each block and builtin function takes, and print a report of it to STDERR.
+	/// If a file's given (as `--profile=FILE`), the time spent in each stack of calls is also
+	/// written to it, in the folded format that flamegraph tools read.
+	#[clap(long, require_equals = true, value_name = "FILE")]
+	profile: Option<Option<std::path::PathBuf>>,
+
+	/// Record which lines of each file run, and write it to this file in the `lcov` format.
+	#[clap(long)]
+	coverage: Option<std::path::PathBuf>,
+
 	#[clap(last=true)]
-	args: Vec<String>
+	args: Vec<String>,
+
+	#[clap(subcommand)]
+	command: Option<Command>
 
 	/*
 	#[clap(short, long, env="QUEST_DEBUG")]
@@ -27,7 +54,35 @@ struct Opts {
 }
 
 
-fn run_options(Opts { file, eval, args, .. }: Opts) -> Result<Object> {
+#[derive(Clap, Debug)]
+enum Command {
+	Fmt(fmt::FmtOpts),
+	Lint(lint::LintOpts),
+	Debug(debug::DebugOpts),
+	Test(test::TestOpts),
+	Doc(doc::DocOpts),
+	Run(package::RunOpts),
+	Build(package::BuildOpts)
+}
+
+/// Runs `command`, returning whether it succeeded.
+fn run_command(command: Command) -> Result<bool> {
+	match command {
+		Command::Fmt(opts) => fmt::run(opts),
+		Command::Lint(opts) => lint::run(opts),
+		Command::Debug(opts) => debug::run(opts),
+		Command::Test(opts) => test::run(opts),
+		Command::Doc(opts) => doc::run(opts),
+		Command::Run(opts) => package::run(opts),
+		Command::Build(opts) => package::build(opts)
+	}
+}
+
+fn run_options(Opts { file, eval, freeze_builtins, args, .. }: Opts) -> Result<Object> {
+	if freeze_builtins {
+		quest_core::freeze_builtins()?;
+	}
+
 	let mut args: Vec<Object> = args.into_iter().map(Object::from).collect();
 
 	if let Some(file) = file.as_ref() {
@@ -91,9 +146,93 @@ fn main() {
 	// The following line is used by me internally for benchmarking.
 	// if true {run::run_file("code.ignore/fib.qs", Default::default()).unwrap(); return}
 
-	match run_options(Opts::parse()) {
+	let mut opts = Opts::parse();
+
+	if let Some(command) = opts.command.take() {
+		match run_command(command) {
+			Ok(true) => return,
+			Ok(false) => std::process::exit(1),
+			Err(err) => {
+				eprintln!("{}", err);
+				std::process::exit(1)
+			}
+		}
+	}
+
+	let report_leaks = opts.report_leaks;
+	let profile = opts.profile.clone();
+	let coverage = opts.coverage.clone();
+
+	if report_leaks {
+		quest_core::gc::track_objects();
+	}
+
+	if profile.is_some() {
+		quest_core::profile::start();
+	}
+
+	if coverage.is_some() {
+		quest_parser::coverage::start();
+	}
+
+	match run_options(opts) {
 		Ok(_) => {},
 		// Ok(x) => println!("{:?}", x),
 		Err(err) => eprintln!("uncaught error encountered:\n{}", err)
 	}
+
+	quest_core::gc::run_finalizers();
+
+	if let Some(stacks) = profile {
+		report_profile(stacks.as_deref());
+	}
+
+	if let Some(path) = coverage {
+		write_coverage(&path);
+	}
+
+	if report_leaks {
+		report_leaked_objects();
+	}
+}
+
+fn report_profile(stacks: Option<&std::path::Path>) {
+	let profile = quest_core::profile::stop().unwrap_or_default();
+
+	eprint!("{}", profile.flat_report());
+
+	let stacks =
+		match stacks {
+			Some(stacks) => stacks,
+			None => return
+		};
+
+	if let Err(err) = std::fs::write(stacks, profile.folded()) {
+		eprintln!("couldn't write the profile's stacks to {}: {}", stacks.display(), err);
+	}
+}
+
+fn write_coverage(path: &std::path::Path) {
+	let coverage = quest_parser::coverage::stop().unwrap_or_default();
+
+	if let Err(err) = std::fs::write(path, coverage.lcov()) {
+		eprintln!("couldn't write coverage to {}: {}", path.display(), err);
+	}
+}
+
+fn report_leaked_objects() {
+	use std::collections::BTreeMap;
+
+	let leaked = quest_core::gc::leaked_objects();
+	let mut counts = BTreeMap::<&str, usize>::new();
+
+	for obj in &leaked {
+		*counts.entry(obj.typename()).or_default() += 1;
+	}
+
+	eprintln!("{} object(s) leaked", leaked.len());
+
+	for (typename, count) in counts {
+		eprintln!("\t{:>6} {}", count, typename);
+	}
 }
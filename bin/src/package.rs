@@ -0,0 +1,450 @@
+//! Packages, which are described by a `quest.toml` manifest, and the `run` and `build` subcommands
+//! that use them.
+//!
+//! A manifest looks like:
+//!
+//! ```toml
+//! [package]
+//! name = "app"
+//! version = "0.1.0"
+//! entry = "src/main.qs"     # the default
+//! source-dirs = ["src"]     # the default
+//!
+//! [dependencies]
+//! shapes = { path = "vendor/shapes" }
+//! ```
+//!
+//! Dependencies are other packages on disk, which need their own manifests. They're resolved, along
+//! with their own dependencies, into a `quest.lock` next to the manifest, and their modules can be
+//! `import`ed by name (see [`quest_parser::import`]).
+
+use crate::error::{Error, Result};
+use crate::fmt::find_files;
+use clap::Clap;
+use quest_core::Object;
+use quest_parser::{import, Expression, Stream};
+use quest_parser::stream::BufStream;
+use std::collections::BTreeMap;
+use std::io::Cursor;
+use std::path::{Component, Path, PathBuf};
+
+/// The name of the file that describes a package.
+const MANIFEST: &str = "quest.toml";
+
+/// The name of the file that the resolved dependencies are written to.
+const LOCKFILE: &str = "quest.lock";
+
+/// Run a package's entry point, after resolving its dependencies
+#[derive(Clap, Debug)]
+pub struct RunOpts {
+	#[clap(flatten)]
+	manifest: ManifestOpts,
+
+	/// Arguments to pass to the entry point.
+	#[clap(last = true)]
+	args: Vec<String>
+}
+
+/// Resolve a package's dependencies into its lockfile, and check that all of its code parses
+#[derive(Clap, Debug)]
+pub struct BuildOpts {
+	#[clap(flatten)]
+	manifest: ManifestOpts
+}
+
+#[derive(Clap, Debug)]
+struct ManifestOpts {
+	/// The package's manifest. If it isn't supplied, the current directory and its parents are
+	/// searched for a `quest.toml`.
+	#[clap(long)]
+	manifest_path: Option<PathBuf>,
+
+	/// Fail instead of updating `quest.lock` if it's out of date.
+	#[clap(long)]
+	locked: bool
+}
+
+/// A package's `quest.toml`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Manifest {
+	/// The directory the manifest is in, which its paths are relative to.
+	pub dir: PathBuf,
+	pub name: String,
+	pub version: String,
+	/// The module that `quest run` runs, and that's imported when the package itself is.
+	pub entry: PathBuf,
+	/// The directories that the package's modules are in.
+	pub source_dirs: Vec<PathBuf>,
+	/// The path to each dependency, by name.
+	pub dependencies: BTreeMap<String, PathBuf>
+}
+
+fn package_error(file: &Path, message: impl std::fmt::Display) -> Error {
+	Error::Package(format!("{}: {}", file.display(), message))
+}
+
+/// Gets the text at `key` in `table`, or `None` if it's not there.
+fn get_str<'a>(table: &'a toml::value::Table, key: &str, file: &Path) -> Result<Option<&'a str>> {
+	match table.get(key) {
+		None => Ok(None),
+		Some(toml::Value::String(value)) => Ok(Some(value)),
+		Some(_) => Err(package_error(file, format_args!("`{}` should be a string", key)))
+	}
+}
+
+impl Manifest {
+	/// Parses `text`, which is the manifest at `file`.
+	pub fn parse(text: &str, file: &Path) -> Result<Self> {
+		let value = text.parse::<toml::Value>().map_err(|err| package_error(file, err))?;
+		let missing = |key: &str| package_error(file, format_args!("missing `{}`", key));
+
+		let package =
+			match value.get("package") {
+				Some(toml::Value::Table(package)) => package,
+				Some(_) => return Err(package_error(file, "`package` should be a table")),
+				None => return Err(missing("package"))
+			};
+
+		let name = get_str(package, "name", file)?.ok_or_else(|| missing("package.name"))?;
+
+		if name.is_empty() || !name.chars().all(|chr| chr.is_alphanumeric() || chr == '_' || chr == '-') {
+			return Err(package_error(file, format_args!(
+				"invalid package name {:?}: only letters, numbers, `_` and `-` are allowed", name)));
+		}
+
+		let version = get_str(package, "version", file)?.ok_or_else(|| missing("package.version"))?;
+		let entry = get_str(package, "entry", file)?.unwrap_or("src/main.qs");
+
+		let source_dirs =
+			match package.get("source-dirs") {
+				None => vec![PathBuf::from("src")],
+				Some(toml::Value::Array(dirs)) => dirs.iter()
+					.map(|dir| dir.as_str().map(PathBuf::from)
+						.ok_or_else(|| package_error(file, "`source-dirs` should be a list of strings")))
+					.collect::<Result<_>>()?,
+				Some(_) => return Err(package_error(file, "`source-dirs` should be a list of strings"))
+			};
+
+		let mut dependencies = BTreeMap::new();
+
+		match value.get("dependencies") {
+			None => {},
+			Some(toml::Value::Table(deps)) =>
+				for (dep, spec) in deps {
+					let path = spec.as_table()
+						.and_then(|spec| spec.get("path"))
+						.and_then(toml::Value::as_str)
+						.ok_or_else(|| package_error(file, format_args!(
+							"dependency `{}` should be a table with a `path`, eg `{{ path = \"../{0}\" }}`", dep)))?;
+
+					dependencies.insert(dep.clone(), PathBuf::from(path));
+				},
+			Some(_) => return Err(package_error(file, "`dependencies` should be a table"))
+		}
+
+		Ok(Self {
+			// a bare `quest.toml` has an empty parent, which isn't a directory we can join paths onto.
+			dir: file.parent()
+				.filter(|dir| !dir.as_os_str().is_empty())
+				.map_or_else(|| PathBuf::from("."), Path::to_path_buf),
+			name: name.to_string(),
+			version: version.to_string(),
+			entry: PathBuf::from(entry),
+			source_dirs,
+			dependencies
+		})
+	}
+
+	/// Reads the manifest in `dir`.
+	pub fn load(dir: &Path) -> Result<Self> {
+		let file = dir.join(MANIFEST);
+		let text = std::fs::read_to_string(&file).map_err(|err| package_error(&file, err))?;
+
+		Self::parse(&text, &file)
+	}
+
+	/// Finds the manifest that applies to the current directory, which is the first one in it or
+	/// its parents.
+	fn find() -> Result<Self> {
+		let cwd = std::env::current_dir()?;
+
+		cwd.ancestors()
+			.find(|dir| dir.join(MANIFEST).is_file())
+			.map(Self::load)
+			.unwrap_or_else(|| Err(Error::Package(format!(
+				"couldn't find a `{}` in {} or any parent directory", MANIFEST, cwd.display()))))
+	}
+
+	/// The package's modules, as they're given to [`import`].
+	fn to_import(&self) -> import::Package {
+		import::Package {
+			name: self.name.clone(),
+			entry: self.dir.join(&self.entry),
+			source_dirs: self.source_dirs.iter().map(|dir| self.dir.join(dir)).collect()
+		}
+	}
+}
+
+/// A package in a [`Lockfile`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Locked {
+	pub manifest: Manifest,
+	/// The package's directory, relative to the root package's.
+	pub path: PathBuf,
+	/// The names of the packages it directly depends on.
+	pub dependencies: Vec<String>
+}
+
+/// A package and all of the packages it depends on, directly or not.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lockfile {
+	pub root: Locked,
+	/// The dependencies, sorted by name.
+	pub packages: Vec<Locked>
+}
+
+/// Gets `path` relative to `base`; both should be absolute.
+fn relative_to(path: &Path, base: &Path) -> PathBuf {
+	let path = path.components().collect::<Vec<_>>();
+	let base = base.components().collect::<Vec<_>>();
+	let common = path.iter().zip(&base).take_while(|(lhs, rhs)| lhs == rhs).count();
+
+	let relative = std::iter::repeat_n(Component::ParentDir, base.len() - common)
+		.chain(path[common..].iter().copied())
+		.collect::<PathBuf>();
+
+	if relative.as_os_str().is_empty() { PathBuf::from(".") } else { relative }
+}
+
+/// Writes `path` with `/`s, so lockfiles are the same on every platform.
+fn portable(path: &Path) -> String {
+	path.components()
+		.map(|component| component.as_os_str().to_string_lossy().into_owned())
+		.collect::<Vec<_>>()
+		.join("/")
+}
+
+struct Resolver {
+	root_dir: PathBuf,
+	// the packages that have been resolved, by name.
+	resolved: BTreeMap<String, Locked>,
+	// the packages that are being resolved, outermost first, to find cycles.
+	resolving: Vec<String>
+}
+
+impl Resolver {
+	/// Resolves `manifest`'s dependencies, adding them and it to `resolved`.
+	fn resolve(&mut self, manifest: Manifest) -> Result<()> {
+		self.resolving.push(manifest.name.clone());
+		let mut dependencies = vec![];
+
+		for (name, path) in &manifest.dependencies {
+			let dir = manifest.dir.join(path).canonicalize().map_err(|err| Error::Package(format!(
+				"couldn't find dependency `{}` of `{}` at {}: {}", name, manifest.name, manifest.dir.join(path).display(), err)))?;
+
+			if let Some(start) = self.resolving.iter().position(|other| other == name) {
+				let mut cycle = self.resolving[start..].to_vec();
+				cycle.push(name.clone());
+
+				return Err(Error::Package(format!("dependency cycle: {}", cycle.join(" -> "))));
+			}
+
+			match self.resolved.get(name) {
+				Some(locked) if locked.manifest.dir == dir => {},
+				Some(locked) => return Err(Error::Package(format!(
+					"two packages named `{}` are depended on: {} and {}",
+					name, locked.manifest.dir.display(), dir.display()))),
+				None => {
+					let dep = Manifest::load(&dir)?;
+
+					if dep.name != *name {
+						return Err(Error::Package(format!(
+							"dependency `{}` of `{}` is at {}, but that package is named `{}`",
+							name, manifest.name, dir.display(), dep.name)));
+					}
+
+					self.resolve(dep)?;
+				}
+			}
+
+			dependencies.push(name.clone());
+		}
+
+		self.resolving.pop();
+
+		let path = relative_to(&manifest.dir, &self.root_dir);
+		self.resolved.insert(manifest.name.clone(), Locked { manifest, path, dependencies });
+
+		Ok(())
+	}
+}
+
+impl Lockfile {
+	/// Resolves the dependencies of `root`, and their dependencies, and so on.
+	pub fn resolve(mut root: Manifest) -> Result<Self> {
+		root.dir = root.dir.canonicalize()?;
+
+		let name = root.name.clone();
+		let mut resolver = Resolver { root_dir: root.dir.clone(), resolved: BTreeMap::new(), resolving: vec![] };
+		resolver.resolve(root)?;
+
+		let root = resolver.resolved.remove(&name).expect("the root is always resolved");
+
+		Ok(Self { root, packages: resolver.resolved.into_values().collect() })
+	}
+
+	/// Writes the lockfile in the `quest.lock` format, with the root package first.
+	#[must_use]
+	pub fn to_toml(&self) -> String {
+		let mut toml = String::from("# This file is generated by `quest build` and `quest run`; don't edit it.\n");
+
+		for locked in std::iter::once(&self.root).chain(&self.packages) {
+			let dependencies = locked.dependencies.iter()
+				.map(|dep| format!("{:?}", dep))
+				.collect::<Vec<_>>();
+
+			toml.push_str(&format!("\n[[package]]\nname = {:?}\nversion = {:?}\npath = {:?}\ndependencies = [{}]\n",
+				locked.manifest.name, locked.manifest.version, portable(&locked.path), dependencies.join(", ")));
+		}
+
+		toml
+	}
+
+	/// Writes the lockfile next to the root package's manifest if it's changed, returning an error
+	/// instead if `locked` is set.
+	fn write(&self, locked: bool) -> Result<()> {
+		let file = self.root.manifest.dir.join(LOCKFILE);
+		let toml = self.to_toml();
+
+		if std::fs::read_to_string(&file).ok().as_deref() == Some(toml.as_str()) {
+			return Ok(());
+		}
+
+		if locked {
+			return Err(package_error(&file, "the lockfile needs to be updated, but `--locked` was passed"));
+		}
+
+		std::fs::write(&file, toml).map_err(|err| package_error(&file, err))
+	}
+}
+
+impl ManifestOpts {
+	/// Loads the manifest and resolves its dependencies, updating the lockfile.
+	fn resolve(&self) -> Result<Lockfile> {
+		let manifest =
+			match &self.manifest_path {
+				Some(path) => {
+					let text = std::fs::read_to_string(path).map_err(|err| package_error(path, err))?;
+					Manifest::parse(&text, path)?
+				},
+				None => Manifest::find()?
+			};
+
+		let lockfile = Lockfile::resolve(manifest)?;
+		lockfile.write(self.locked)?;
+
+		Ok(lockfile)
+	}
+}
+
+/// Runs the entry point of the package in `opts`.
+pub fn run(RunOpts { manifest, args }: RunOpts) -> Result<bool> {
+	let lockfile = manifest.resolve()?;
+	let root = &lockfile.root.manifest;
+
+	import::set_packages(root.to_import(),
+		lockfile.packages.iter().map(|locked| locked.manifest.to_import()).collect());
+
+	let entry = root.dir.join(&root.entry);
+	let args = std::iter::once(entry.display().to_string())
+		.chain(args)
+		.map(Object::from)
+		.collect::<Vec<_>>();
+
+	crate::run::run_file(&entry, args.iter().collect())?;
+
+	Ok(true)
+}
+
+/// Resolves the package in `opts`, and checks that each of its packages' modules parse.
+pub fn build(BuildOpts { manifest }: BuildOpts) -> Result<bool> {
+	let lockfile = manifest.resolve()?;
+	let mut is_ok = true;
+
+	for locked in std::iter::once(&lockfile.root).chain(&lockfile.packages) {
+		let manifest = &locked.manifest;
+		let mut files = vec![manifest.dir.join(&manifest.entry)];
+
+		for dir in &manifest.source_dirs {
+			let dir = manifest.dir.join(dir);
+
+			if !dir.is_dir() {
+				return Err(Error::Package(format!("source directory {} of `{}` doesn't exist", dir.display(), manifest.name)));
+			}
+
+			find_files(&dir, &mut files)?;
+		}
+
+		files.sort();
+		files.dedup();
+
+		for file in &files {
+			let source = std::fs::read_to_string(file).map_err(|err| package_error(file, err))?;
+
+			let stream = BufStream::new(Cursor::new(source), Some(file.clone()));
+
+			if let Err(err) = Expression::parse_stream(stream.tokens()) {
+				eprintln!("{}\n", err);
+				is_ok = false;
+			}
+		}
+
+		eprintln!("checked {} v{} ({}, {} file{})", manifest.name, manifest.version, portable(&locked.path),
+			files.len(), if files.len() == 1 { "" } else { "s" });
+	}
+
+	Ok(is_ok)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parses_manifests() {
+		let manifest = Manifest::parse("\
+[package]
+name = \"app\"
+version = \"0.1.0\"
+source-dirs = [\"src\", \"lib\"]
+
+[dependencies]
+shapes = { path = \"vendor/shapes\" }
+", Path::new("app/quest.toml")).unwrap();
+
+		assert_eq!(manifest, Manifest {
+			dir: PathBuf::from("app"),
+			name: "app".to_string(),
+			version: "0.1.0".to_string(),
+			entry: PathBuf::from("src/main.qs"),
+			source_dirs: vec![PathBuf::from("src"), PathBuf::from("lib")],
+			dependencies: vec![("shapes".to_string(), PathBuf::from("vendor/shapes"))].into_iter().collect()
+		});
+
+		let err = Manifest::parse("[package]\nname = \"a/b\"\nversion = \"1\"", Path::new("quest.toml")).unwrap_err();
+		assert_eq!(err.to_string(), "quest.toml: invalid package name \"a/b\": only letters, numbers, `_` and `-` are allowed");
+
+		let err = Manifest::parse("[package]\nname = \"a\"\nversion = \"1\"\n[dependencies]\nb = \"1.0\"", Path::new("quest.toml")).unwrap_err();
+		assert_eq!(err.to_string(), "quest.toml: dependency `b` should be a table with a `path`, eg `{ path = \"../b\" }`");
+
+		let err = Manifest::parse("[package]\nname = \"a\"", Path::new("quest.toml")).unwrap_err();
+		assert_eq!(err.to_string(), "quest.toml: missing `package.version`");
+	}
+
+	#[test]
+	fn relative_paths() {
+		assert_eq!(relative_to(Path::new("/a/b/c"), Path::new("/a/b")), PathBuf::from("c"));
+		assert_eq!(relative_to(Path::new("/a/x"), Path::new("/a/b/c")), PathBuf::from("../../x"));
+		assert_eq!(relative_to(Path::new("/a/b"), Path::new("/a/b")), PathBuf::from("."));
+	}
+}
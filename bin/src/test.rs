@@ -0,0 +1,206 @@
+//! The `test` subcommand, which runs the tests in `*_test.qs` files.
+
+use crate::error::Result;
+use crate::fmt::find_files;
+use clap::Clap;
+use quest_parser::testing::{self, Options, Outcome};
+use std::fmt::{self, Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Run the tests in Quest files, which are written with `Test.describe` and `Test.it`
+#[derive(Clap, Debug)]
+pub struct TestOpts {
+	/// Only run tests whose names contain this.
+	#[clap(long)]
+	filter: Option<String>,
+
+	/// How many seconds each test may run for, unless it gives its own timeout. This is only
+	/// checked between expressions, so it can't stop builtin functions that block.
+	#[clap(long)]
+	timeout: Option<f64>,
+
+	/// How to print the results: `tap`, or `junit` for JUnit XML.
+	#[clap(long, default_value = "tap")]
+	format: Format,
+
+	/// The test files to run; directories are searched for `*_test.qs` files. If none are supplied,
+	/// the current directory is searched.
+	paths: Vec<PathBuf>
+}
+
+/// How the results are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+	Tap,
+	Junit
+}
+
+/// A format that isn't `tap` or `junit`.
+#[derive(Debug)]
+pub struct BadFormat(String);
+
+impl Display for BadFormat {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "unknown format `{}`: expected `tap` or `junit`", self.0)
+	}
+}
+
+impl std::error::Error for BadFormat {}
+
+impl FromStr for Format {
+	type Err = BadFormat;
+
+	fn from_str(input: &str) -> std::result::Result<Self, Self::Err> {
+		match input {
+			"tap" => Ok(Self::Tap),
+			"junit" => Ok(Self::Junit),
+			_ => Err(BadFormat(input.to_string()))
+		}
+	}
+}
+
+/// Adds the `*_test.qs` files in `path` to `files`, or `path` itself if it's a file.
+fn find_tests(path: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+	if !path.is_dir() {
+		files.push(path.to_path_buf());
+		return Ok(());
+	}
+
+	let mut quest_files = vec![];
+	find_files(path, &mut quest_files)?;
+
+	files.extend(quest_files.into_iter().filter(|file| {
+		file.file_name().and_then(|name| name.to_str()).is_some_and(|name| name.ends_with("_test.qs"))
+	}));
+
+	Ok(())
+}
+
+/// Prints a TAP line for `outcome`, which is test number `number`.
+fn print_tap(number: usize, outcome: &Outcome) {
+	let status = if outcome.passed() { "ok" } else { "not ok" };
+	println!("{} {} - {}", status, number, outcome.name);
+
+	if let Some(message) = outcome.message() {
+		println!("  ---\n  message: |");
+
+		for line in message.lines() {
+			println!("    {}", line);
+		}
+
+		if let Some(file) = &outcome.file {
+			println!("  file: {:?}", file.display().to_string());
+		}
+
+		println!("  ...");
+	}
+}
+
+/// Escapes `text` so it can be put in XML.
+fn escape_xml(text: &str) -> String {
+	text.chars().fold(String::with_capacity(text.len()), |mut escaped, chr| {
+		match chr {
+			'&' => escaped.push_str("&amp;"),
+			'<' => escaped.push_str("&lt;"),
+			'>' => escaped.push_str("&gt;"),
+			'"' => escaped.push_str("&quot;"),
+			'\'' => escaped.push_str("&apos;"),
+			other => escaped.push(other)
+		}
+
+		escaped
+	})
+}
+
+/// Prints the outcomes of each file's tests as JUnit XML, with a `<testsuite>` per file.
+fn print_junit(suites: &[(PathBuf, Vec<Outcome>)]) {
+	let count = |outcomes: &[Outcome]| (outcomes.len(), outcomes.iter().filter(|outcome| !outcome.passed()).count());
+	let time = |outcomes: &[Outcome]| outcomes.iter().map(|outcome| outcome.time).sum::<Duration>().as_secs_f64();
+	let all = suites.iter().flat_map(|(_, outcomes)| outcomes.iter().cloned()).collect::<Vec<_>>();
+	let (tests, failures) = count(&all);
+
+	println!(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+	println!(r#"<testsuites tests="{}" failures="{}" time="{:.3}">"#, tests, failures, time(&all));
+
+	for (file, outcomes) in suites {
+		let file = escape_xml(&file.display().to_string());
+		let (tests, failures) = count(outcomes);
+
+		println!(r#"  <testsuite name="{}" tests="{}" failures="{}" time="{:.3}">"#, file, tests, failures, time(outcomes));
+
+		for outcome in outcomes {
+			print!(r#"    <testcase name="{}" classname="{}" time="{:.3}""#,
+				escape_xml(&outcome.name), file, outcome.time.as_secs_f64());
+
+			match outcome.message() {
+				None => println!("/>"),
+				Some(message) => {
+					let summary = message.lines().next().unwrap_or_default();
+					println!(">");
+					println!(r#"      <failure message="{}">{}</failure>"#, escape_xml(summary), escape_xml(&message));
+					println!("    </testcase>");
+				}
+			}
+		}
+
+		println!("  </testsuite>");
+	}
+
+	println!("</testsuites>");
+}
+
+/// Runs the tests in `opts`, returning whether they all passed.
+pub fn run(TestOpts { filter, timeout, format, mut paths }: TestOpts) -> Result<bool> {
+	let timeout =
+		match timeout {
+			Some(secs) => Some(Duration::try_from_secs_f64(secs)
+				.map_err(|err| quest_core::Error::Messaged(format!("invalid timeout: {}", err)))?),
+			None => None
+		};
+
+	if paths.is_empty() {
+		paths.push(PathBuf::from("."));
+	}
+
+	let mut files = vec![];
+
+	for path in &paths {
+		find_tests(path, &mut files)?;
+	}
+
+	let options = Options { filter, timeout };
+	let mut suites = vec![];
+	let mut number = 0;
+
+	if format == Format::Tap {
+		println!("TAP version 13");
+	}
+
+	for file in files {
+		let outcomes = testing::run_file(&file, &options);
+
+		if format == Format::Tap {
+			for outcome in &outcomes {
+				number += 1;
+				print_tap(number, outcome);
+			}
+		}
+
+		suites.push((file, outcomes));
+	}
+
+	let outcomes = suites.iter().flat_map(|(_, outcomes)| outcomes).collect::<Vec<_>>();
+	let failed = outcomes.iter().filter(|outcome| !outcome.passed()).count();
+
+	match format {
+		Format::Tap => {
+			println!("1..{}", outcomes.len());
+			println!("# {} passed, {} failed", outcomes.len() - failed, failed);
+		},
+		Format::Junit => print_junit(&suites)
+	}
+
+	Ok(failed == 0)
+}
@@ -0,0 +1,40 @@
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+#[test]
+fn writes_lcov() {
+	let exe = env!("CARGO_BIN_EXE_quest-bin");
+	let file = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/debugging.qs");
+	let lcov = std::env::temp_dir().join(format!("quest-coverage-{}.lcov", std::process::id()));
+
+	let output = Command::new(exe)
+		.arg("--coverage")
+		.arg(&lcov)
+		.arg("-f")
+		.arg(&file)
+		.stdin(Stdio::null())
+		.output()
+		.unwrap_or_else(|err| panic!("couldn't run `{}`: {:?}", exe, err));
+
+	assert!(output.status.success(), "quest failed: {:?}", output);
+	assert_eq!(String::from_utf8(output.stdout).unwrap(), "12\n");
+
+	let report = std::fs::read_to_string(&lcov).unwrap();
+	std::fs::remove_file(&lcov).unwrap();
+
+	assert_eq!(report, format!("\
+TN:
+SF:{}
+DA:1,1
+DA:2,1
+DA:3,1
+DA:5,1
+DA:6,1
+DA:7,1
+DA:8,1
+DA:9,1
+LF:8
+LH:8
+end_of_record
+", file.display()));
+}
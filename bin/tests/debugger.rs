@@ -0,0 +1,78 @@
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// Runs `quest debug` on `tests/debugging.qs`, typing each of `commands`, and returns what's
+/// printed.
+fn debug(args: &[&str], commands: &[&str]) -> String {
+	let exe = env!("CARGO_BIN_EXE_quest-bin");
+	let file = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/debugging.qs");
+
+	let mut debugger = Command::new(exe)
+		.arg("debug")
+		.args(args)
+		.arg(&file)
+		.stdin(Stdio::piped())
+		.stdout(Stdio::piped())
+		.spawn()
+		.unwrap_or_else(|err| panic!("couldn't run `{} debug`: {:?}", exe, err));
+
+	let mut stdin = debugger.stdin.take().unwrap();
+
+	for command in commands {
+		writeln!(stdin, "{}", command).unwrap();
+	}
+
+	drop(stdin);
+
+	let output = debugger.wait_with_output().unwrap();
+	assert!(output.status.success(), "debugger failed: {:?}", output);
+
+	String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn pauses_at_the_first_line() {
+	let output = debug(&[], &["c"]);
+
+	assert!(output.contains("debugging.qs:1\n 1  | double = n -> {\n(qdb) "), "{}", output);
+	assert!(output.contains("`breakpoint()` called\npaused at"), "{}", output);
+
+	// once STDIN's closed, the rest of the program is run.
+	assert!(output.ends_with("(qdb) \n12\n"), "{}", output);
+}
+
+#[test]
+fn breakpoints_and_printing() {
+	let output = debug(&["--break", "debugging.qs:3"], &["c", "p", "p n * 10", "up", "p x", "c"]);
+
+	assert!(output.contains("breakpoint 1 at debugging.qs:3\npaused at"), "{}", output);
+	assert!(output.contains("\n  n = 6\n"), "{}", output);
+	assert!(output.contains("\n  result = 12\n"), "{}", output);
+	assert!(output.contains("(qdb) 60\n"), "{}", output);
+	assert!(output.contains("(qdb) 1\n"), "{}", output);
+}
+
+#[test]
+fn stepping_and_backtraces() {
+	let output = debug(&[], &["n", "s", "s", "bt", "finish", "n", "p x + y", "c"]);
+
+	let paused_at = output.lines()
+		.filter_map(|line| line.rsplit("paused at ").next().filter(|_| line.contains("paused at ")))
+		.map(|location| location.rsplit(':').next().unwrap())
+		.collect::<Vec<_>>();
+
+	// `finish` carries on from `double` until line 7, and `breakpoint()` pauses on line 8 again.
+	assert_eq!(paused_at, ["1", "5", "6", "2", "7", "8", "8"], "{}", output);
+	assert!(output.contains(">#0 "), "{}", output);
+	assert!(output.contains(" #1 "), "{}", output);
+	assert!(output.contains("(qdb) 13\n"), "{}", output);
+}
+
+#[test]
+fn kernel_breakpoint() {
+	let output = debug(&[], &["c", "p z", "c"]);
+
+	assert!(output.contains("`breakpoint()` called\npaused at"), "{}", output);
+	assert!(output.contains("(qdb) 13\n(qdb) 12\n"), "{}", output);
+}
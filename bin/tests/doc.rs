@@ -0,0 +1,62 @@
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+/// Runs `quest doc` on `tests/shapes.qs` with `args`, returning what it printed.
+fn quest_doc(args: &[&str]) -> String {
+	let exe = env!("CARGO_BIN_EXE_quest-bin");
+	let file = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/shapes.qs");
+
+	let Output { status, stdout, stderr } = Command::new(exe)
+		.arg("doc")
+		.args(args)
+		.arg(&file)
+		.stdin(Stdio::null())
+		.output()
+		.unwrap_or_else(|err| panic!("couldn't run `{} doc`: {:?}", exe, err));
+
+	assert!(status.success(), "{}", String::from_utf8_lossy(&stderr));
+	String::from_utf8(stdout).unwrap()
+}
+
+#[test]
+fn markdown() {
+	assert_eq!(quest_doc(&[]), "\
+# shapes
+
+## `circle_area(r)`
+
+The area of a circle with radius `r`.
+
+## `Rect`
+
+A rectangle.
+
+```
+Rect(2, 3).area() # => 6
+```
+
+## `Rect.()(class, width, height)`
+
+Makes a rectangle that's `width` by `height`.
+
+## `Rect.area(rect)`
+
+The rectangle's area.
+");
+}
+
+#[test]
+fn html() {
+	let output = std::env::temp_dir().join(format!("quest-doc-{}.html", std::process::id()));
+	let printed = quest_doc(&["--format", "html", "-o", output.to_str().unwrap()]);
+	assert_eq!(printed, "");
+
+	let html = std::fs::read_to_string(&output).unwrap();
+	std::fs::remove_file(&output).unwrap();
+
+	assert!(html.starts_with("<!DOCTYPE html>\n"), "{}", html);
+	assert!(html.contains("<title>shapes</title>"), "{}", html);
+	assert!(html.contains("<h2 id=\"Rect\"><code>Rect</code></h2>\n<p>A rectangle.</p>\n\
+		<pre><code>Rect(2, 3).area() # =&gt; 6</code></pre>\n"), "{}", html);
+	assert!(html.contains("<p>Makes a rectangle that's <code>width</code> by <code>height</code>.</p>"), "{}", html);
+}
@@ -0,0 +1,40 @@
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+/// Runs quest in `dir` with `args`, returning whether it succeeded, its STDOUT and its STDERR.
+fn quest(dir: &Path, args: &[&str]) -> (bool, String, String) {
+	let exe = env!("CARGO_BIN_EXE_quest-bin");
+
+	let Output { status, stdout, stderr } = Command::new(exe)
+		.args(args)
+		.current_dir(dir)
+		.stdin(Stdio::null())
+		.output()
+		.unwrap_or_else(|err| panic!("couldn't run `{}`: {:?}", exe, err));
+
+	(status.success(), String::from_utf8(stdout).unwrap(), String::from_utf8(stderr).unwrap())
+}
+
+#[test]
+fn check_and_format_files() {
+	let dir = std::env::temp_dir().join(format!("quest-fmt-{}", std::process::id()));
+	let _ = std::fs::remove_dir_all(&dir);
+	std::fs::create_dir_all(&dir).unwrap();
+	std::fs::write(dir.join("messy.qs"), "x=1  ;print( x )").unwrap();
+
+	let (success, stdout, _) = quest(&dir, &["fmt", "--check", "messy.qs"]);
+	assert!(!success);
+	assert_eq!(stdout, "messy.qs isn't formatted\n");
+
+	// STDIN can't be mixed with files, as it's written to STDOUT instead.
+	let (success, _, stderr) = quest(&dir, &["fmt", "-", "messy.qs"]);
+	assert!(!success);
+	assert!(stderr.contains("`-` (STDIN) can't be formatted along with other files"), "{}", stderr);
+
+	let (success, _, _) = quest(&dir, &["fmt", "messy.qs"]);
+	assert!(success);
+	let (success, stdout, _) = quest(&dir, &["fmt", "--check", "messy.qs"]);
+	assert!(success, "{}", stdout);
+
+	std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,135 @@
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+
+/// Copies the directory `from` into `to`.
+fn copy_dir(from: &Path, to: &Path) {
+	std::fs::create_dir_all(to).unwrap();
+
+	for entry in std::fs::read_dir(from).unwrap() {
+		let path = entry.unwrap().path();
+		let dest = to.join(path.file_name().unwrap());
+
+		if path.is_dir() {
+			copy_dir(&path, &dest);
+		} else {
+			std::fs::copy(&path, &dest).unwrap();
+		}
+	}
+}
+
+/// Copies the `tests/package/app` package into a new temporary directory, returning where it is.
+fn app(name: &str) -> PathBuf {
+	let dir = std::env::temp_dir().join(format!("quest-package-{}-{}", name, std::process::id()));
+	let _ = std::fs::remove_dir_all(&dir);
+
+	copy_dir(&Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/package/app"), &dir);
+	dir
+}
+
+/// Runs quest in `dir` with `args`, returning whether it succeeded, its STDOUT and its STDERR.
+fn quest(dir: &Path, args: &[&str]) -> (bool, String, String) {
+	let exe = env!("CARGO_BIN_EXE_quest-bin");
+
+	let Output { status, stdout, stderr } = Command::new(exe)
+		.args(args)
+		.current_dir(dir)
+		.stdin(Stdio::null())
+		.output()
+		.unwrap_or_else(|err| panic!("couldn't run `{}`: {:?}", exe, err));
+
+	(status.success(), String::from_utf8(stdout).unwrap(), String::from_utf8(stderr).unwrap())
+}
+
+const LOCKFILE: &str = "\
+# This file is generated by `quest build` and `quest run`; don't edit it.
+
+[[package]]
+name = \"app\"
+version = \"0.1.0\"
+path = \".\"
+dependencies = [\"shapes\", \"units\"]
+
+[[package]]
+name = \"shapes\"
+version = \"0.2.0\"
+path = \"vendor/shapes\"
+dependencies = [\"units\"]
+
+[[package]]
+name = \"units\"
+version = \"1.0.0\"
+path = \"vendor/units\"
+dependencies = []
+";
+
+#[test]
+fn run_resolves_dependencies() {
+	let dir = app("run");
+
+	let (success, stdout, stderr) = quest(&dir.join("src"), &["run", "--", "world"]);
+	assert!(success, "{}", stderr);
+	assert_eq!(stdout, "hello, world\narea: 9 cm²\n");
+
+	assert_eq!(std::fs::read_to_string(dir.join("quest.lock")).unwrap(), LOCKFILE);
+
+	std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn bare_manifest_path_is_relative_to_the_current_directory() {
+	let dir = app("manifest-path");
+
+	let (success, stdout, stderr) = quest(&dir, &["run", "--manifest-path", "quest.toml", "--", "world"]);
+	assert!(success, "{}", stderr);
+	assert_eq!(stdout, "hello, world\narea: 9 cm²\n");
+
+	let (success, _, stderr) = quest(&dir, &["build", "--manifest-path", "quest.toml"]);
+	assert!(success, "{}", stderr);
+	assert!(stderr.starts_with("checked app v0.1.0 (., 2 files)\n"), "{}", stderr);
+
+	std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn build_checks_every_package() {
+	let dir = app("build");
+	std::fs::write(dir.join("vendor/shapes/lib/broken.qs"), "x = (1 + ;").unwrap();
+
+	let (success, _, stderr) = quest(&dir, &["build"]);
+	assert!(!success);
+	assert!(stderr.contains("broken.qs:1:"), "{}", stderr);
+	assert!(stderr.contains("checked shapes v0.2.0 (vendor/shapes, 3 files)\n"), "{}", stderr);
+
+	std::fs::remove_file(dir.join("vendor/shapes/lib/broken.qs")).unwrap();
+	let (success, _, stderr) = quest(&dir, &["build", "--locked"]);
+	assert!(success, "{}", stderr);
+	assert_eq!(stderr, "\
+checked app v0.1.0 (., 2 files)
+checked shapes v0.2.0 (vendor/shapes, 2 files)
+checked units v1.0.0 (vendor/units, 1 file)
+");
+
+	// the lockfile's out of date once a dependency is removed.
+	let manifest = std::fs::read_to_string(dir.join("quest.toml")).unwrap();
+	std::fs::write(dir.join("quest.toml"), manifest.replace("units = { path = \"vendor/units\" }\n", "")).unwrap();
+
+	let (success, _, stderr) = quest(&dir, &["build", "--locked"]);
+	assert!(!success);
+	assert!(stderr.ends_with("quest.lock: the lockfile needs to be updated, but `--locked` was passed\n"), "{}", stderr);
+	assert_eq!(std::fs::read_to_string(dir.join("quest.lock")).unwrap(), LOCKFILE);
+
+	std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn dependency_cycles_are_errors() {
+	let dir = app("cycle");
+	std::fs::write(dir.join("vendor/units/quest.toml"),
+		"[package]\nname = \"units\"\nversion = \"1.0.0\"\n\n[dependencies]\nshapes = { path = \"../shapes\" }\n").unwrap();
+
+	let (success, _, stderr) = quest(&dir, &["build"]);
+	assert!(!success);
+	assert_eq!(stderr, "dependency cycle: shapes -> units -> shapes\n");
+
+	std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,67 @@
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+#[test]
+fn reports_blocks_and_builtins() {
+	let exe = env!("CARGO_BIN_EXE_quest-bin");
+	let file = Path::new(env!("CARGO_MANIFEST_DIR")).join("../examples/fibonacci.qs");
+	let stacks = std::env::temp_dir().join(format!("quest-profile-{}.folded", std::process::id()));
+
+	let output = Command::new(exe)
+		.arg(format!("--profile={}", stacks.display()))
+		.arg("-f")
+		.arg(&file)
+		.stdin(Stdio::null())
+		.output()
+		.unwrap_or_else(|err| panic!("couldn't run `{}`: {:?}", exe, err));
+
+	assert!(output.status.success(), "quest failed: {:?}", output);
+
+	// the program's output isn't changed.
+	let stdout = String::from_utf8(output.stdout).unwrap();
+	assert!(stdout.ends_with("\n55\n"), "{}", stdout);
+
+	let report = String::from_utf8(output.stderr).unwrap();
+	let mut lines = report.lines();
+	assert_eq!(lines.next().map(str::split_whitespace).map(Iterator::collect),
+		Some(vec!["own", "(ms)", "total", "(ms)", "calls", "name"]));
+
+	// the memoizing block, which runs once for each number that isn't memoized yet.
+	let memoizing = lines.clone().find(|line| line.ends_with("fibonacci.qs:23"))
+		.unwrap_or_else(|| panic!("no entry for the memoizing block:\n{}", report));
+	assert_eq!(memoizing.split_whitespace().nth(2), Some("9"), "{}", memoizing);
+
+	assert!(lines.any(|line| line.ends_with(" Basic::print")), "{}", report);
+
+	let folded = std::fs::read_to_string(&stacks).unwrap();
+	std::fs::remove_file(&stacks).unwrap();
+
+	for line in folded.lines() {
+		let (stack, micros) = line.split_at(line.rfind(' ').unwrap());
+		assert!(micros.trim().parse::<u128>().is_ok(), "{}", line);
+		assert!(!stack.is_empty(), "{}", line);
+	}
+
+	assert!(folded.lines().any(|line| line.contains("fibonacci.qs:23;") && line.contains("Basic::print")), "{}", folded);
+}
+
+#[test]
+fn only_writes_stacks_when_asked() {
+	let exe = env!("CARGO_BIN_EXE_quest-bin");
+	let dir = std::env::temp_dir().join(format!("quest-profile-cwd-{}", std::process::id()));
+	std::fs::create_dir_all(&dir).unwrap();
+
+	let output = Command::new(exe)
+		.args(["--profile", "-e", "1 + 2"])
+		.current_dir(&dir)
+		.stdin(Stdio::null())
+		.output()
+		.unwrap_or_else(|err| panic!("couldn't run `{}`: {:?}", exe, err));
+
+	let written = std::fs::read_dir(&dir).unwrap().count();
+	std::fs::remove_dir_all(&dir).unwrap();
+
+	assert!(output.status.success(), "quest failed: {:?}", output);
+	assert!(String::from_utf8(output.stderr).unwrap().trim_start().starts_with("own (ms)"));
+	assert_eq!(written, 0, "the stacks were written to the current directory");
+}
@@ -0,0 +1,64 @@
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+/// Runs `quest test` on `tests/testing` with `args`, returning whether it passed and what it printed.
+fn quest_test(args: &[&str]) -> (bool, String) {
+	let exe = env!("CARGO_BIN_EXE_quest-bin");
+	let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/testing");
+
+	let Output { status, stdout, .. } = Command::new(exe)
+		.arg("test")
+		.args(args)
+		.arg(&dir)
+		.stdin(Stdio::null())
+		.output()
+		.unwrap_or_else(|err| panic!("couldn't run `{} test`: {:?}", exe, err));
+
+	(status.success(), String::from_utf8(stdout).unwrap())
+}
+
+#[test]
+fn tap() {
+	let (passed, output) = quest_test(&[]);
+	assert!(!passed, "{}", output);
+
+	let results = output.lines()
+		.filter(|line| line.starts_with("ok") || line.starts_with("not ok"))
+		.collect::<Vec<_>>();
+
+	assert_eq!(results, [
+		"ok 1 - Number adds",
+		"not ok 2 - Number multiplies",
+		"ok 3 - Number can't be divided by text",
+		"ok 4 - List joins",
+		"not ok 5 - List never finishes",
+		"ok 6 - concatenates <text>",
+	]);
+
+	assert!(output.starts_with("TAP version 13\n"), "{}", output);
+	assert!(output.contains("    assertion failed: multiplication is off\n      expected: 7\n        actual: 6\n"), "{}", output);
+	assert!(output.contains("    timed out after 50ms\n"), "{}", output);
+	assert!(output.ends_with("1..6\n# 4 passed, 2 failed\n"), "{}", output);
+}
+
+#[test]
+fn filtering() {
+	let (passed, output) = quest_test(&["--filter", "Number a"]);
+	assert!(passed, "{}", output);
+	assert!(output.contains("ok 1 - Number adds\n1..1\n"), "{}", output);
+}
+
+#[test]
+fn junit() {
+	let (passed, output) = quest_test(&["--format", "junit", "--filter", "text"]);
+	assert!(passed, "{}", output);
+
+	assert!(output.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites tests=\"2\" failures=\"0\""), "{}", output);
+	assert!(output.contains("<testcase name=\"Number can&apos;t be divided by text\""), "{}", output);
+	assert!(output.contains("<testcase name=\"concatenates &lt;text&gt;\""), "{}", output);
+	assert_eq!(output.matches("<testsuite ").count(), 2, "{}", output);
+
+	let (passed, output) = quest_test(&["--format", "junit", "--filter", "multiplies"]);
+	assert!(!passed, "{}", output);
+	assert!(output.contains("<failure message=\"assertion failed: multiplication is off\">"), "{}", output);
+}
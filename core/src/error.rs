@@ -6,11 +6,15 @@ mod key_error;
 mod type_error;
 mod value_error;
 mod argument_error;
+mod frozen_error;
+mod budget_error;
 
 pub use type_error::TypeError;
 pub use key_error::KeyError;
 pub use value_error::ValueError;
 pub use argument_error::ArgumentError;
+pub use frozen_error::FrozenError;
+pub use budget_error::BudgetError;
 
 #[derive(Debug)]
 #[non_exhaustive]
@@ -33,6 +37,12 @@ pub enum Error {
 	/// An invalid value was supplied somewhere
 	ValueError(ValueError),
 
+	/// A frozen object was modified
+	FrozenError(FrozenError),
+
+	/// Sandboxed code used up its budget
+	BudgetError(BudgetError),
+
 	/// Some quest assertion failed.
 	AssertionFailed(Option<String>),
 
@@ -52,6 +62,7 @@ pub enum Error {
 }
 
 #[must_use]
+#[allow(unused_attributes)]
 /// An alias for results within quest.
 pub type Result<T> = ::std::result::Result<T, Error>;
 
@@ -65,6 +76,8 @@ impl Display for Error {
 			Self::IoError(err) => Display::fmt(&err, f),
 			Self::ValueError(err) => Display::fmt(&err, f),
 			Self::ArgumentError(err) => Display::fmt(&err, f),
+			Self::FrozenError(err) => Display::fmt(&err, f),
+			Self::BudgetError(err) => Display::fmt(&err, f),
 			Self::AssertionFailed(Some(err)) => write!(f, "assertion failed: {}", err),
 			Self::AssertionFailed(None) => write!(f, "assertion failed"),
 			Self::Boxed(err) => Display::fmt(&err, f),
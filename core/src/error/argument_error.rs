@@ -1,4 +1,5 @@
 use std::fmt::{self, Display, Formatter};
+use super::TypeError;
 
 /// The type was correct, but its value was incorrect.
 #[derive(Debug, Clone)]
@@ -6,6 +7,9 @@ pub enum ArgumentError {
 	/// When a more specific error isn't available
 	InvalidLength { given: usize, expected: usize },
 
+	/// The argument at `position` couldn't be converted to the type that was expected.
+	WrongType { position: usize, error: TypeError },
+
 	Messaged(String),
 }
 
@@ -22,6 +26,8 @@ impl Display for ArgumentError {
 		match self {
 			ArgumentError::InvalidLength { given, expected }
 				=> write!(f, "wrong number of arguments (given {}, expected {})", given, expected),
+			ArgumentError::WrongType { position, error }
+				=> write!(f, "argument {} has the wrong type ({})", position, error),
 			ArgumentError::Messaged(string) => write!(f, "{}", string)
 		}
 	}
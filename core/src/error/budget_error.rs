@@ -0,0 +1,30 @@
+use std::fmt::{self, Display, Formatter};
+use std::time::Duration;
+
+/// Sandboxed code used up its budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetError {
+	/// The code took more steps than it was allowed.
+	Steps(usize),
+
+	/// The code ran for longer than it was allowed.
+	Time(Duration)
+}
+
+impl From<BudgetError> for super::Error {
+	#[inline]
+	fn from(err: BudgetError) -> Self {
+		Self::BudgetError(err)
+	}
+}
+
+impl Display for BudgetError {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "budget error: ")?;
+
+		match self {
+			BudgetError::Steps(steps) => write!(f, "step budget of {} exceeded", steps),
+			BudgetError::Time(time) => write!(f, "time budget of {:?} exceeded", time),
+		}
+	}
+}
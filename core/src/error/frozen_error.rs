@@ -0,0 +1,60 @@
+use std::fmt::{self, Display, Formatter};
+use crate::Object;
+
+/// An attempt was made to modify a frozen object.
+#[derive(Debug, Clone)]
+pub enum FrozenError {
+	/// An attribute was assigned on a frozen object.
+	SetAttr {
+		/// The attribute that was being assigned.
+		attr: Object,
+		/// The frozen object.
+		obj: Object
+	},
+
+	/// An attribute was deleted from a frozen object.
+	DelAttr {
+		/// The attribute that was being deleted.
+		attr: Object,
+		/// The frozen object.
+		obj: Object
+	},
+
+	/// A parent was added to a frozen object.
+	AddParent {
+		/// The parent that was being added.
+		parent: Object,
+		/// The frozen object.
+		obj: Object
+	},
+
+	/// A frozen [`List`](crate::types::List) was modified.
+	Modify {
+		/// The frozen object.
+		obj: Object
+	}
+}
+
+impl From<FrozenError> for super::Error {
+	#[inline]
+	fn from(err: FrozenError) -> Self {
+		Self::FrozenError(err)
+	}
+}
+
+impl Display for FrozenError {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "frozen error: ")?;
+
+		match self {
+			FrozenError::SetAttr { attr, obj } =>
+				write!(f, "can't set attr {:?} on frozen object {:?}", attr, obj),
+			FrozenError::DelAttr { attr, obj } =>
+				write!(f, "can't delete attr {:?} from frozen object {:?}", attr, obj),
+			FrozenError::AddParent { parent, obj } =>
+				write!(f, "can't add parent {:?} to frozen object {:?}", parent, obj),
+			FrozenError::Modify { obj } =>
+				write!(f, "can't modify frozen object {:?}", obj),
+		}
+	}
+}
@@ -31,6 +31,14 @@ pub enum KeyError {
 		obj: Object
 	},
 
+	/// The attribute is a property without a setter.
+	ReadOnly {
+		/// The attribute that was being assigned.
+		attr: Object,
+		/// The object that we're trying to set the attribute of
+		obj: Object
+	},
+
 	// /// The attribute doesn't exist for the given object.
 	// DoesntExist1 {
 	// 	/// The attribute that doens't exist.
@@ -58,6 +66,8 @@ impl Display for KeyError {
 				write!(f, "range '{}' out of bounds (max: {})", range, len),
 			KeyError::DoesntExist { attr, obj } => 
 				write!(f, "attr {:?} doesn't exist for {:?}", attr, obj),
+			KeyError::ReadOnly { attr, obj } =>
+				write!(f, "attr {:?} is read-only for {:?}", attr, obj),
 			// KeyError::DoesntExist1 { attr, obj } => 
 			// 	write!(f, "attr {:?} doesn't exist for {:?}", attr, obj),
 		}
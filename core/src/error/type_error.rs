@@ -6,7 +6,12 @@ use std::fmt::{self, Display, Formatter};
 pub enum TypeError {
 	/// An invalid type was used.
 	// todo: remove this entirely.
-	WrongType { /** */ expected: &'static str, /** */ got: &'static str },
+	WrongType {
+		/// The type that was expected.
+		expected: &'static str,
+		/// The type that was given.
+		got: &'static str
+	},
 	/// A conversion returned a bad type.
 	ConversionReturnedBadType {
 		/// The conversion function.
@@ -7,24 +7,43 @@
 //! # See Also
 //! - [`quest-parser`](#TODO) for parsing quest
 //! - [`quest-bin`](#TODO) the quest executable
-#![allow(clippy::pub_enum_variant_names, clippy::tabs_in_doc_comments, clippy::needless_lifetimes)]
+#![allow(clippy::tabs_in_doc_comments, clippy::multiple_bound_locations, clippy::needless_lifetimes)]
 // #![warn(missing_docs)]
 #[cfg(feature = "mimalloc")]
 #[global_allocator]
 static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 
+// lets `#[quest::object]` and friends refer to `::quest_core` from within this crate too.
+extern crate self as quest_core;
+
 mod shared_cow;
 mod obj;
 mod literal;
 pub mod utils;
 pub mod error;
 pub mod types;
+pub mod sandbox;
+pub mod profile;
 
 use shared_cow::SharedCow;
 pub use literal::Literal;
-pub use obj::Object;
+pub use obj::{Object, gc};
 pub use error::{Error, Result};
 pub use types::{ObjectType, rustfn::{Args, Binding}};
+pub use quest_macros::{object, methods};
+
+/// Calls `$mac` with every builtin type.
+macro_rules! with_builtin_types {
+	($mac:ident) => {
+		$mac!(
+			Pristine, Basic, Boolean, BoundFunction, Comparable, Function, Kernel,
+			List, Null, Number, Property, Regex, RustFn, RustClosure, Scope, Text, Iterable, Iter, StopIteration,
+			Tcp, WeakRef, Reflect,
+			BoundRustFn, Io, types::io::File, // todo: remove it?
+			types::regex::Match
+		)
+	};
+}
 
 /// Start up Quest by initializing all the types.
 pub fn init() {
@@ -41,11 +60,76 @@ pub fn init() {
 
 	static INITIALIZE: Once = Once::new();
 
-	INITIALIZE.call_once(||
-		initialize!(
-			Pristine, Basic, Boolean, BoundFunction, Comparable, Function, Kernel,
-			List, Null, Number, Regex, RustFn, RustClosure, Scope, Text, Iterable, Tcp,
-			BoundRustFn, Io, types::io::File // todo: remove it?
-		)
-	)
+	INITIALIZE.call_once(|| with_builtin_types!(initialize))
+}
+
+/// Freezes the mappings of all the builtin types (e.g. [`Number::mapping()`](
+/// types::Number)), so scripts can't redefine their attributes.
+///
+/// This should be called after [`init`], before any Quest code is run.
+pub fn freeze_builtins() -> Result<()> {
+	use crate::types::*;
+
+	macro_rules! freeze {
+		($($ty:ty),*) => {{
+			$(
+				<$ty>::mapping().freeze()?;
+			)*
+		}};
+	}
+
+	with_builtin_types!(freeze);
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::obj::Value;
+	use crate::types::{RustFn, Text};
+	use crate::Object;
+
+	/// Gets the names of the builtin functions on `mapping` that don't have any docs.
+	fn undocumented(name: &str, mapping: &Object) -> Vec<String> {
+		let mut missing = vec![];
+
+		for key in mapping.mapping_keys(false).unwrap() {
+			let rustfn =
+				match mapping.get_own_definition(&key).unwrap() {
+					Some(Value::RustFn(rustfn)) => Some(rustfn),
+					Some(Value::Property(property)) => {
+						let mut getter = None;
+						property.for_each_reference(|obj| { getter.get_or_insert_with(|| obj.clone()); });
+						getter.and_then(|getter| getter.downcast::<RustFn>().map(|rustfn| *rustfn))
+					},
+					_ => None
+				};
+
+			if let Some(rustfn) = rustfn {
+				if rustfn.doc().is_none() {
+					missing.push(format!("{}.{}", name, key.downcast::<Text>().unwrap().as_ref()));
+				}
+			}
+		}
+
+		missing
+	}
+
+	#[test]
+	fn every_builtin_is_documented() {
+		use crate::types::{self, *};
+		crate::init();
+
+		let mut missing = vec![];
+
+		macro_rules! check {
+			($($ty:ty),*) => {
+				$(missing.extend(undocumented(&stringify!($ty).replace(" ", ""), <$ty>::mapping()));)*
+			};
+		}
+
+		with_builtin_types!(check);
+
+		assert!(missing.is_empty(), "builtins without docs:\n{}", missing.join("\n"));
+	}
 }
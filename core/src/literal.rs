@@ -55,7 +55,7 @@ impl From<&'static str> for Literal {
 impl AsRef<str> for Literal {
 	#[inline]
 	fn as_ref(&self) -> &str {
-		&self.0
+		self.0
 	}
 }
 
@@ -113,6 +113,7 @@ macro_rules! literals {
 literals! {
 	// stuff for mappings
 	__PARENTS__ "__parents__" __ID__ "__id__" __ATTR_MISSING__ "__attr_missing__"
+	__MRO__ "__mro__"
 
 	__KEYS__ "__keys__" __ARGS__ "__args__" __STACK__ "__stack__"
 
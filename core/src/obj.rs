@@ -1,6 +1,6 @@
 use crate::{Args, Literal};
-use crate::error::{TypeError, KeyError};
-use crate::types::{self, ObjectType, Boolean};
+use crate::error::{TypeError, KeyError, FrozenError};
+use crate::types::{self, ObjectType, Boolean, Property, Text};
 
 use std::sync::Arc;
 use std::fmt::{self, Debug, Formatter};
@@ -10,8 +10,10 @@ use std::borrow::Borrow;
 
 mod data;
 mod attributes;
+pub mod gc;
 
-use attributes::{Attributes, Value};
+use attributes::Attributes;
+pub(crate) use attributes::Value;
 use data::Data;
 
 /// The struct that represents any type within Quest.
@@ -95,7 +97,9 @@ impl Object {
 
 	#[inline]
 	fn from_parts(data: Data, attrs: Attributes) -> Self {
-		Self(Arc::new(Internal { data, attrs }))
+		let obj = Self(Arc::new(Internal { data, attrs }));
+		gc::track(&obj);
+		obj
 	}
 
 	/// Creates a new object with its default parents.
@@ -245,6 +249,26 @@ impl Internal {
 		self.attrs.get(attr)
 	}
 
+	#[inline]
+	fn get_own(&self, attr: &Object) -> crate::Result<Option<Value>> {
+		self.attrs.get_own(attr)
+	}
+
+	#[inline]
+	fn has_properties(&self) -> crate::Result<bool> {
+		self.attrs.has_properties()
+	}
+
+	#[inline]
+	fn parents(&self) -> crate::Result<Vec<Object>> {
+		self.attrs.parents()
+	}
+
+	#[inline]
+	fn mro(&self) -> crate::Result<Arc<[Object]>> {
+		self.attrs.mro()
+	}
+
 	#[inline]
 	fn set(&self, attr: Object, value: Value) -> crate::Result<()> {
 		self.attrs.set(attr, value)
@@ -260,6 +284,21 @@ impl Internal {
 		self.attrs.add_parent(val)
 	}
 
+	#[inline]
+	fn add_finalizer(&self, finalizer: Object) {
+		self.attrs.add_finalizer(finalizer)
+	}
+
+	#[inline]
+	fn is_frozen(&self) -> bool {
+		self.attrs.is_frozen()
+	}
+
+	#[inline]
+	fn freeze(&self) -> crate::Result<()> {
+		self.attrs.freeze()
+	}
+
 	#[inline]
 	fn keys(&self, include_parents: bool) -> crate::Result<Vec<Object>> {
 		self.attrs.keys(include_parents)
@@ -288,12 +327,22 @@ impl Object {
 	}
 
 	/// Fetches a value, returning `None` if it doesn't exist.
+	///
+	/// If the value is a [`Property`], its getter is called and the result is returned instead.
 	fn get_value_lit<L: ?Sized>(&self, attr: &L) -> crate::Result<Option<Value>>
 	where
 		Literal: Borrow<L>,
 		L: Hash + Eq
 	{
-		self.0.get_lit(attr)
+		self.0.get_lit(attr).and_then(|value| self.resolve_property(value))
+	}
+
+	/// Calls the getter of `value` if it's a [`Property`].
+	fn resolve_property(&self, value: Option<Value>) -> crate::Result<Option<Value>> {
+		match value {
+			Some(Value::Property(property)) => property.get(self).map(|value| Some(value.into())),
+			other => Ok(other)
+		}
 	}
 
 	/// Fetches the attribute `attr`, returning a [`KeyError`] if it doesn't exist.
@@ -314,6 +363,20 @@ impl Object {
 	pub fn set_value_lit(&self, attr: impl Into<Literal>, value: impl Into<Value>)
 		-> crate::Result<()>
 	{
+		let attr = attr.into();
+
+		if self.is_frozen() {
+			return Err(FrozenError::SetAttr { attr: attr.to_string().into(), obj: self.clone() }.into());
+		}
+
+		if self.0.has_properties()? {
+			if let Some(Value::Property(property)) = self.0.get_lit(&attr)? {
+				if !property.is_builtin() {
+					return property.set(self, attr.to_string().into(), value.into().into());
+				}
+			}
+		}
+
 		// TODO: this will just set a literal value even if the corresponding nonliteral works.
 		self.0.set_lit(attr, value)
 	}
@@ -330,6 +393,10 @@ impl Object {
 		Literal: Borrow<L>,
 		L: Hash + Eq + ToString
 	{
+		if self.is_frozen() {
+			return Err(FrozenError::DelAttr { attr: attr.to_string().into(), obj: self.clone() }.into());
+		}
+
 		self.0.del_lit(attr)?
 			.map(Self::from)
 			.ok_or_else(|| KeyError::DoesntExist {
@@ -362,7 +429,43 @@ impl Object {
 	/// Gets the attribute `attr`, returning `None` if it didn't exist
 	#[inline]
 	pub(crate) fn get_value(&self, attr: &Self) -> crate::Result<Option<Value>> {
-		self.0.get(attr)
+		self.0.get(attr).and_then(|value| self.resolve_property(value))
+	}
+
+	/// Gets the attribute `attr` defined directly on this object, ignoring its parents.
+	#[inline]
+	pub(crate) fn get_own_value(&self, attr: &Self) -> crate::Result<Option<Value>> {
+		self.0.get_own(attr).and_then(|value| self.resolve_property(value))
+	}
+
+	/// Like [`get_own_value`](Self::get_own_value), except properties are returned as-is instead of
+	/// being computed.
+	#[inline]
+	pub(crate) fn get_own_definition(&self, attr: &Self) -> crate::Result<Option<Value>> {
+		self.0.get_own(attr)
+	}
+
+	/// Finds the object that `attr` is defined on: either this object, or one of its ancestors.
+	pub(crate) fn attr_owner(&self, attr: &Self) -> crate::Result<Option<Self>> {
+		if let Some(text) = attr.downcast::<Text>() {
+			let text = text.as_ref();
+
+			if Literal::__ID__ == text || Literal::__PARENTS__ == text || Literal::__MRO__ == text {
+				return Ok(Some(self.clone()));
+			}
+		}
+
+		if self.get_own_definition(attr)?.is_some() {
+			return Ok(Some(self.clone()));
+		}
+
+		for ancestor in self.mro()?.iter() {
+			if ancestor.get_own_definition(attr)?.is_some() {
+				return Ok(Some(ancestor.clone()));
+			}
+		}
+
+		Ok(None)
 	}
 
 	/// Gets an attribute, returning a [`KeyError`] if it doesn't exist.
@@ -375,11 +478,36 @@ impl Object {
 	/// Sets the attribute `attr` to `value`.
 	#[inline]
 	pub fn set_attr(&self, attr: Self, value: Self) -> crate::Result<()> {
+		if self.is_frozen() {
+			return Err(FrozenError::SetAttr { attr, obj: self.clone() }.into());
+		}
+
+		if self.0.has_properties()? {
+			if let Some(Value::Property(property)) = self.0.get(&attr)? {
+				if !property.is_builtin() {
+					return property.set(self, attr, value);
+				}
+			}
+		}
+
 		self.0.set(attr, value.into())
 	}
 
+	/// Defines `attr` as the computed attribute `property`, replacing any existing value.
+	pub(crate) fn set_property(&self, attr: Self, property: Property) -> crate::Result<()> {
+		if self.is_frozen() {
+			return Err(FrozenError::SetAttr { attr, obj: self.clone() }.into());
+		}
+
+		self.0.set(attr, property.into())
+	}
+
 	/// Deletes the attribute `attr`, returning a [`KeyError`] if the attr didn't exist.
 	pub fn del_attr(&self, attr: &Self) -> crate::Result<Self> {
+		if self.is_frozen() {
+			return Err(FrozenError::DelAttr { attr: attr.clone(), obj: self.clone() }.into());
+		}
+
 		self.0.del(attr)?
 			.map(Self::from)
 			.ok_or_else(|| KeyError::DoesntExist { attr: attr.clone(), obj: self.clone() }.into())
@@ -425,9 +553,48 @@ impl Object {
 	/// parents.
 	#[inline]
 	pub fn add_parent(&self, val: Self) -> crate::Result<()> {
+		if self.is_frozen() {
+			return Err(FrozenError::AddParent { parent: val, obj: self.clone() }.into());
+		}
+
 		self.0.add_parent(val)
 	}
 
+	/// Adds a finalizer, which is called with this object's id after it's been dropped.
+	///
+	/// See [`gc::run_finalizers`] for when finalizers are run.
+	#[inline]
+	pub fn add_finalizer(&self, finalizer: Self) {
+		self.0.add_finalizer(finalizer)
+	}
+
+	/// Checks to see if this object has been frozen.
+	#[inline]
+	pub fn is_frozen(&self) -> bool {
+		self.0.is_frozen()
+	}
+
+	/// Freezes this object, so that its attributes and parents can no longer be changed.
+	///
+	/// Frozen [`List`](crate::types::List)s can't be modified either, but other data isn't affected
+	/// (e.g. a frozen `Text` can still be appended to), nor are any of the attributes' values.
+	#[inline]
+	pub fn freeze(&self) -> crate::Result<()> {
+		self.0.freeze()
+	}
+
+	/// Gets this object's parents, in the order attributes are looked up in them.
+	#[inline]
+	pub(crate) fn parents(&self) -> crate::Result<Vec<Self>> {
+		self.0.parents()
+	}
+
+	/// Gets every ancestor of this object, in the order attributes are looked up in them.
+	#[inline]
+	pub(crate) fn mro(&self) -> crate::Result<Arc<[Self]>> {
+		self.0.mro()
+	}
+
 	/// Gets the list of keys corresponding to this object.
 	#[inline]
 	pub(crate) fn mapping_keys(&self, include_parents: bool) -> crate::Result<Vec<Self>> {
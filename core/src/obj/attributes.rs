@@ -3,12 +3,18 @@ use crate::types::Text;
 use std::fmt::{self, Debug, Formatter};
 use std::borrow::Borrow;
 use std::hash::Hash;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::collections::HashSet;
+use parking_lot::Mutex;
 
 mod parents;
 mod attrmap;
 mod value;
+mod mro;
 
 use attrmap::AttrMap;
+use mro::Mro;
 pub use value::Value;
 pub use parents::Parents;
 
@@ -22,6 +28,11 @@ struct Inner {
 #[derive(Default)]
 pub struct Attributes {
 	data: SharedCow<Inner>,
+	mro: Mro,
+	frozen: AtomicBool,
+	/// Whether a property (other than a builtin one) has ever been set directly on these attributes.
+	properties: AtomicBool,
+	finalizers: Mutex<Vec<Object>>,
 	id: usize
 }
 
@@ -36,7 +47,19 @@ impl Debug for Attributes {
 
 impl Clone for Attributes {
 	fn clone(&self) -> Self {
-		Self::from_data(self.data.clone())
+		let clone = Self::from_data(self.data.clone());
+		clone.properties.store(self.has_own_properties(), Ordering::Relaxed);
+		clone
+	}
+}
+
+impl Drop for Attributes {
+	fn drop(&mut self) {
+		let finalizers = std::mem::take(self.finalizers.get_mut());
+
+		if !finalizers.is_empty() {
+			super::gc::schedule_finalizers(self.id, finalizers);
+		}
 	}
 }
 
@@ -52,10 +75,17 @@ impl Attributes {
 	}
 
 	fn from_data(data: SharedCow<Inner>) -> Self {
-		use std::sync::atomic::{AtomicUsize, Ordering};
+		use std::sync::atomic::AtomicUsize;
 		static ID_COUNTER: AtomicUsize = AtomicUsize::new(0);
 
-		Attributes { data, id: ID_COUNTER.fetch_add(1, Ordering::Relaxed) }
+		Attributes {
+			data,
+			mro: Mro::default(),
+			frozen: AtomicBool::new(false),
+			properties: AtomicBool::new(false),
+			finalizers: Mutex::new(vec![]),
+			id: ID_COUNTER.fetch_add(1, Ordering::Relaxed)
+		}
 	}
 
 	/// Gets the id associated with these attributes.
@@ -64,9 +94,98 @@ impl Attributes {
 		self.id
 	}
 
+	/// Checks to see if these attributes have been frozen.
+	#[inline]
+	pub fn is_frozen(&self) -> bool {
+		self.frozen.load(Ordering::Acquire)
+	}
+
+	/// Freezes these attributes.
+	///
+	/// If the parents are stored in a list, they're copied out of it so modifying the list in place
+	/// won't change them.
+	pub fn freeze(&self) -> Result<()> {
+		if !self.frozen.swap(true, Ordering::AcqRel) {
+			self.data.write().parents.detach()?;
+			self.mro.invalidate();
+		}
+
+		Ok(())
+	}
+
+	/// Adds a finalizer, which is called with our id after we're dropped.
+	pub fn add_finalizer(&self, finalizer: Object) {
+		self.finalizers.lock().push(finalizer);
+	}
+
+	/// Calls `f` with every object that's directly referenced by these attributes.
+	///
+	/// The attributes themselves may be shared with other objects (after a `clone`); if they're in
+	/// `seen_data`, then `f`'s second argument is `false` for objects referenced by them, as they've
+	/// already been visited. The ids of any shared attributes are added to `seen_data`.
+	pub fn for_each_reference<F: FnMut(&Object, bool)>(&self, seen_data: &mut HashSet<usize>, f: &mut F) {
+		let counted = self.data.shared_id().is_none_or(|id| seen_data.insert(id));
+
+		{
+			let inner = self.data.read();
+			inner.map.for_each_reference(|obj| f(obj, counted));
+			inner.parents.for_each_reference(|obj| f(obj, counted));
+		}
+
+		self.mro.for_each_reference(seen_data, |obj, counted| f(obj, counted));
+		self.finalizers.lock().iter().for_each(|obj| f(obj, true));
+	}
+
+	/// Checks to see if a property's been set on `self` or any of its ancestors, in which case
+	/// assignments need to check for setters.
+	pub fn has_properties(&self) -> Result<bool> {
+		if self.has_own_properties() {
+			return Ok(true);
+		}
+
+		self.mro.has_properties(self.id, || self.parents_and_list())
+	}
+
+	#[inline]
+	fn has_own_properties(&self) -> bool {
+		self.properties.load(Ordering::Acquire)
+	}
+
+	/// Notes that `value` is about to be set directly on `self`.
+	fn note_value(&self, value: &Value) {
+		if matches!(value, Value::Property(property) if !property.is_builtin())
+			&& !self.properties.swap(true, Ordering::AcqRel)
+		{
+			// descendants' cached linearizations say whether any ancestors have properties.
+			self.mro.invalidate();
+		}
+	}
+
 	/// Add a parent to the list of parents.
 	pub fn add_parent(&self, parent: Object) -> Result<()> {
-		self.data.write().parents.add_parent(parent)
+		self.data.write().parents.add_parent(parent)?;
+		self.mro.invalidate();
+		Ok(())
+	}
+
+	/// Gets every ancestor of `self`, in the order they're searched for attributes.
+	pub fn mro(&self) -> Result<Arc<[Object]>> {
+		self.mro.get_or_compute(self.id, || self.parents_and_list())
+	}
+
+	/// Finds the first ancestor of `self` that `func` returns `Some` for, without copying them.
+	/// `func` shouldn't run any quest code.
+	fn find_in_mro<F, T>(&self, func: F) -> Result<Option<T>>
+	where
+		F: FnMut(&Object) -> Option<T>
+	{
+		self.mro.find_map(self.id, || self.parents_and_list(), func)
+	}
+
+	/// Gets the parents, as well as the list they're stored in (if they are).
+	fn parents_and_list(&self) -> Result<(Vec<Object>, Option<Object>)> {
+		let inner = self.data.read();
+		Ok((inner.parents.keys()?, inner.parents.as_list()))
 	}
 
 	/// Get a list of keys for this class, optionally including all keys defined on parents as well.
@@ -75,6 +194,7 @@ impl Attributes {
 
 		keys.push(Literal::__PARENTS__.into());
 		keys.push(Literal::__ID__.into());
+		keys.push(Literal::__MRO__.into());
 
 		let inner = self.data.write();
 		keys.extend(inner.map.keys());
@@ -93,12 +213,14 @@ impl Attributes {
 		Literal: Borrow<L>,
 		L: Hash + Eq
 	{
-		if key == Literal::__ID__.borrow() || key == Literal::__PARENTS__.borrow() {
-			Ok(true)
-		} else {
-			let inner = self.data.read();
-			Ok(inner.map.has_lit(key) || inner.parents.has_lit(key)?)
+		if key == Literal::__ID__.borrow() || key == Literal::__PARENTS__.borrow()
+			|| key == Literal::__MRO__.borrow() || self.data.read().map.has_lit(key)
+		{
+			return Ok(true);
 		}
+
+		self.find_in_mro(|ancestor| ancestor.0.attrs.data.read().map.has_lit(key).then_some(()))
+			.map(|found| found.is_some())
 	}
 
 	/// Gets the associated value to `key` from `self` directly or its parents.
@@ -111,14 +233,36 @@ impl Attributes {
 			return Ok(Some(Object::from(self.id()).into()))
 		}
 
-		let inner = self.data.read();
+		if key == Literal::__MRO__.borrow() {
+			return Ok(Some(Object::from(self.mro()?.to_vec()).into()))
+		}
+
 		if key == Literal::__PARENTS__.borrow() {
-			Ok(Some(inner.parents.to_object().into()))
-		} else if let Some(lit) = inner.map.get_lit(key).cloned() {
-			Ok(Some(lit))
-		} else {
-			inner.parents.get_lit(key)
+			// frozen objects hand out frozen copies of their parents, so they can't be modified in place.
+			if self.is_frozen() {
+				let parents = Object::from(self.parents()?);
+				parents.freeze()?;
+				return Ok(Some(parents.into()))
+			}
+
+			let (was_object, parents) = {
+				let inner = self.data.read();
+				(inner.parents.is_object(), inner.parents.to_object())
+			};
+
+			// the parents may now be modified in place, so the mro needs to start tracking them.
+			if !was_object {
+				self.mro.track(&parents);
+			}
+
+			return Ok(Some(parents.into()))
+		}
+
+		if let Some(lit) = self.data.read().map.get_lit(key).cloned() {
+			return Ok(Some(lit))
 		}
+
+		self.find_in_mro(|ancestor| ancestor.0.attrs.data.read().map.get_lit(key).cloned())
 	}
 
 	/// Sets the associated `key` to `value` from `self` directly or its parents.
@@ -129,7 +273,9 @@ impl Attributes {
 
 		if key == Literal::__PARENTS__ {
 			inner.parents = Parents::from(Object::from(value));
+			self.mro.invalidate();
 		} else {
+			self.note_value(&value);
 			inner.map.set_lit(key, value);
 		}
 	}
@@ -143,6 +289,7 @@ impl Attributes {
 		let mut inner = self.data.write();
 
 		if key == Literal::__PARENTS__.borrow() {
+			self.mro.invalidate();
 			Some(std::mem::take(&mut inner.parents).into())
 		} else {
 			inner.map.del_lit(key)
@@ -155,8 +302,17 @@ impl Attributes {
 			return res
 		}
 
-		let inner = self.data.read();
-		Ok(inner.map.has_obj(key)? || inner.parents.has_obj(key)?)
+		if self.data.read().map.has_obj(key)? {
+			return Ok(true);
+		}
+
+		for ancestor in self.mro()?.iter() {
+			if ancestor.0.attrs.data.read().map.has_obj(key)? {
+				return Ok(true);
+			}
+		}
+
+		Ok(false)
 	}
 
 	/// Gets the associated value to `key` from `self` directly or its parents.
@@ -165,15 +321,35 @@ impl Attributes {
 			return res;
 		}
 
+		if let Some(obj) = self.get_own(key)? {
+			return Ok(Some(obj));
+		}
+
+		for ancestor in self.mro()?.iter() {
+			if let Some(obj) = ancestor.0.attrs.get_own(key)? {
+				return Ok(Some(obj));
+			}
+		}
+
+		Ok(None)
+	}
+
+	/// Gets the associated value to `key` from `self` directly, without checking its parents.
+	pub fn get_own(&self, key: &Object) -> Result<Option<Value>> {
 		let inner = self.data.read();
 
-		if let Some(obj) = inner.map.get_obj(key)? {
-			Ok(Some(obj.clone()))
+		if let Some(text) = key.downcast::<Text>() {
+			Ok(inner.map.get_lit(text.as_ref()).cloned())
 		} else {
-			inner.parents.get_obj(key)
+			Ok(inner.map.get_obj(key)?.cloned())
 		}
 	}
 
+	/// Gets the list of parents, in the order they're checked.
+	pub fn parents(&self) -> Result<Vec<Object>> {
+		self.data.read().parents.keys()
+	}
+
 	/// Sets the associated `key` to `value` from `self` directly or its parents.
 	pub fn set(&self, key: Object, value: Value) -> Result<()> {
 		if let Some(text) = key.downcast::<Text>() {
@@ -181,6 +357,7 @@ impl Attributes {
 			return Ok(());
 		}
 
+		self.note_value(&value);
 		self.data.write().map.set_obj(key, value)
 	}
 
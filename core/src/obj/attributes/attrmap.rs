@@ -31,6 +31,18 @@ impl AttrMap {
 			.chain(self.objects.iter().map(|(k, _)| k.clone()))
 	}
 
+	/// Calls `f` with every object that's referenced by this map.
+	pub fn for_each_reference<F: FnMut(&Object)>(&self, mut f: F) {
+		for value in self.literals.values() {
+			value.for_each_reference(&mut f);
+		}
+
+		for (key, value) in self.objects.iter() {
+			f(key);
+			value.for_each_reference(&mut f);
+		}
+	}
+
 	/// Checks to see if this map has `key`.
 	#[inline]
 	pub fn has_lit<L: ?Sized>(&self, key: &L) -> bool
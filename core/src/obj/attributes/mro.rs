@@ -0,0 +1,601 @@
+//! The method resolution order (MRO) of objects.
+//!
+//! Rather than walking each parent (and their parents) whenever an attribute's looked up, each
+//! object lazily computes a C3 linearization of all of its ancestors, which is then searched in
+//! order. This means diamond-shaped hierarchies resolve the same way they do in Python: a parent is
+//! only ever checked after all of the objects that inherit from it.
+//!
+//! Linearizations are cached per-object. Each cache also knows the caches of the objects whose
+//! linearizations were computed from it, so when an object's `__parents__` change, only its own
+//! cache and those of everything that inherits from it are cleared. Since `__parents__` can also be
+//! a list that's modified in place (e.g. `__parents__.push(...)`), caches also record the contents
+//! of any such lists they relied upon, and are discarded if those lists have since changed.
+//!
+//! Objects with a single parent (such as most instances) and inconsistent hierarchies (such as most
+//! stackframes) don't copy their parents' linearizations, but refer to them instead. Otherwise, each
+//! new stackframe would have to copy every frame below it on the stack.
+use crate::{Object, Result};
+use crate::types::List;
+use parking_lot::{Mutex, RwLock};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasherDefault, Hasher};
+use std::sync::{Arc, OnceLock, Weak};
+
+/// A map keyed by object ids, which are already unique and so don't need a strong hash.
+type IdMap<V> = HashMap<usize, V, BuildHasherDefault<IdHasher>>;
+
+#[derive(Default)]
+struct IdHasher(u64);
+
+impl Hasher for IdHasher {
+	fn finish(&self) -> u64 {
+		self.0
+	}
+
+	fn write(&mut self, bytes: &[u8]) {
+		bytes.iter().for_each(|&byte| self.write_u8(byte));
+	}
+
+	fn write_u8(&mut self, byte: u8) {
+		self.write_u64(u64::from(byte));
+	}
+
+	fn write_u64(&mut self, num: u64) {
+		self.0 = (self.0 ^ num).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+	}
+
+	fn write_usize(&mut self, num: usize) {
+		self.write_u64(num as u64);
+	}
+}
+
+thread_local! {
+	/// The ids of the objects whose MROs we're currently computing, to guard against cycles.
+	static IN_PROGRESS: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
+}
+
+/// The contents of a list used as `__parents__` when a linearization was computed.
+#[derive(Debug)]
+struct Snapshot {
+	list: Object,
+	ids: Vec<usize>
+}
+
+impl Snapshot {
+	fn new(list: Object, parents: &[Object]) -> Self {
+		Self { list, ids: parents.iter().map(Object::id).collect() }
+	}
+
+	fn is_current(&self) -> bool {
+		self.list.downcast::<List>()
+			.is_some_and(|list| list.as_ref().iter().map(Object::id).eq(self.ids.iter().copied()))
+	}
+}
+
+/// The order an object's ancestors are searched in.
+#[derive(Debug)]
+enum Order {
+	/// A C3 linearization.
+	Flat(Arc<[Object]>),
+
+	/// Each parent followed by its own order, searched depth-first. C3 gives this order whenever
+	/// there's only one parent, and it's also the fallback for inconsistent hierarchies. Parents'
+	/// orders are shared rather than copied, so objects may appear more than once; only the first
+	/// time counts.
+	Chain(Vec<(Object, Arc<Order>)>)
+}
+
+impl Order {
+	fn find_map<T, F>(&self, func: &mut F) -> Option<T>
+	where
+		F: FnMut(&Object) -> Option<T>
+	{
+		match self {
+			Self::Flat(mro) => mro.iter().find_map(func),
+			Self::Chain(links) => links.iter()
+				.find_map(|(parent, order)| func(parent).or_else(|| order.find_map(func)))
+		}
+	}
+
+	fn contains(&self, obj: &Object) -> bool {
+		self.find_map(&mut |ancestor| ancestor.is_identical(obj).then_some(())).is_some()
+	}
+
+	/// Adds every object that's not been seen yet to `mro`, in order.
+	fn flatten_into(
+		self: &Arc<Self>,
+		mro: &mut Vec<Object>,
+		seen: &mut IdMap<()>,
+		visited: &mut HashSet<*const Self>
+	) {
+		// everything in an order we've already visited has been seen.
+		if !visited.insert(Arc::as_ptr(self)) {
+			return;
+		}
+
+		match **self {
+			Self::Flat(ref flat) =>
+				mro.extend(flat.iter().filter(|obj| seen.insert(obj.id(), ()).is_none()).cloned()),
+			Self::Chain(ref links) =>
+				for (parent, order) in links {
+					if seen.insert(parent.id(), ()).is_none() {
+						mro.push(parent.clone());
+					}
+
+					order.flatten_into(mro, seen, visited);
+				}
+		}
+	}
+
+	fn to_flat(self: &Arc<Self>) -> Arc<[Object]> {
+		match **self {
+			Self::Flat(ref flat) => flat.clone(),
+			Self::Chain(_) => {
+				let mut mro = vec![];
+				self.flatten_into(&mut mro, &mut IdMap::default(), &mut HashSet::new());
+				mro.into()
+			}
+		}
+	}
+
+	/// Calls `f` with every object in `self`. Orders are shared, so whether their references are
+	/// counted depends on whether they've been seen before.
+	fn for_each_reference<F: FnMut(&Object, bool)>(self: &Arc<Self>, seen: &mut HashSet<usize>, f: &mut F) {
+		let counted = seen.insert(Arc::as_ptr(self) as *const () as usize);
+
+		match **self {
+			Self::Flat(ref mro) => mro.iter().for_each(|obj| f(obj, counted)),
+			Self::Chain(ref links) =>
+				for (parent, order) in links {
+					f(parent, counted);
+					order.for_each_reference(seen, f);
+				}
+		}
+	}
+}
+
+/// A linearization, along with the lists it depends on.
+#[derive(Debug)]
+struct Linearization {
+	order: Arc<Order>,
+	/// `order` without any duplicates, which is only computed when it's needed.
+	flat: OnceLock<Arc<[Object]>>,
+	snapshots: Vec<Arc<Snapshot>>,
+	complete: bool,
+	/// Whether any of the ancestors have properties.
+	properties: bool
+}
+
+impl Linearization {
+	fn new(order: Order, snapshots: Vec<Arc<Snapshot>>, complete: bool, properties: bool) -> Self {
+		Self { order: Arc::new(order), flat: OnceLock::new(), snapshots, complete, properties }
+	}
+
+	fn is_current(&self) -> bool {
+		self.snapshots.iter().all(|snapshot| snapshot.is_current())
+	}
+
+	fn flat(&self) -> &Arc<[Object]> {
+		self.flat.get_or_init(|| self.order.to_flat())
+	}
+}
+
+#[derive(Debug, Default)]
+struct Cache {
+	linearization: RwLock<Option<Linearization>>,
+	/// The caches of objects whose linearizations were computed from this one.
+	dependents: Mutex<Vec<Weak<Cache>>>
+}
+
+impl Cache {
+	fn invalidate(&self) {
+		self.linearization.write().take();
+
+		let dependents = std::mem::take(&mut *self.dependents.lock());
+
+		for dependent in dependents.iter().filter_map(Weak::upgrade) {
+			dependent.invalidate();
+		}
+	}
+
+	fn track(&self, snapshot: &Arc<Snapshot>) {
+		if let Some(ref mut linearization) = *self.linearization.write() {
+			if linearization.snapshots.iter().any(|seen| seen.list.is_identical(&snapshot.list)) {
+				return;
+			}
+
+			linearization.snapshots.push(snapshot.clone());
+		}
+
+		let dependents = self.dependents.lock().clone();
+
+		for dependent in dependents.iter().filter_map(Weak::upgrade) {
+			dependent.track(snapshot);
+		}
+	}
+
+	fn add_dependent(&self, dependent: &Arc<Cache>) {
+		let mut dependents = self.dependents.lock();
+
+		// dependents are never removed individually, so clean up when we'd otherwise reallocate.
+		if dependents.len() == dependents.capacity() {
+			let mut seen = IdMap::default();
+			dependents.retain(|dep| dep.strong_count() != 0 && seen.insert(dep.as_ptr() as usize, ()).is_none());
+		}
+
+		dependents.push(Arc::downgrade(dependent));
+	}
+}
+
+/// The cached linearization of an object's ancestors.
+///
+/// Plenty of objects never have anything looked up on their ancestors, so the cache is only
+/// allocated once it's first used.
+#[derive(Debug, Default)]
+pub struct Mro(OnceLock<Arc<Cache>>);
+
+impl Mro {
+	/// Notes that the parents of the object this belongs to have changed. This also clears the
+	/// caches of everything that inherits from it.
+	pub fn invalidate(&self) {
+		if let Some(cache) = self.0.get() {
+			cache.invalidate();
+		}
+	}
+
+	fn cache(&self) -> &Arc<Cache> {
+		self.0.get_or_init(Arc::default)
+	}
+
+	/// Notes that the parents of the object this belongs to are now stored in `list`, so that
+	/// changes made to it in place are noticed. The linearization itself isn't changed.
+	pub fn track(&self, list: &Object) {
+		let snapshot =
+			match list.downcast::<List>() {
+				Some(parents) => Arc::new(Snapshot::new(list.clone(), parents.as_ref())),
+				None => return
+			};
+
+		if let Some(cache) = self.0.get() {
+			cache.track(&snapshot);
+		}
+	}
+
+	/// Calls `f` with every object referenced by the cached linearization, and whether that
+	/// reference should be counted. Linearizations share parts of each other, so the ids of any
+	/// shared parts are added to `seen`.
+	pub fn for_each_reference<F: FnMut(&Object, bool)>(&self, seen: &mut HashSet<usize>, mut f: F) {
+		let linearization = self.0.get().map(|cache| cache.linearization.read());
+
+		if let Some(Some(ref linearization)) = linearization.as_deref() {
+			linearization.order.for_each_reference(seen, &mut f);
+
+			for snapshot in &linearization.snapshots {
+				f(&snapshot.list, seen.insert(Arc::as_ptr(snapshot) as usize));
+			}
+		}
+	}
+
+	/// Gets the ancestors of the object with the given `id`, in the order they should be searched.
+	/// The object itself isn't included.
+	///
+	/// `parents` should return the object's parents, as well as the list they came from if they're
+	/// stored as one.
+	pub fn get_or_compute<F>(&self, id: usize, parents: F) -> Result<Arc<[Object]>>
+	where
+		F: FnOnce() -> Result<(Vec<Object>, Option<Object>)>
+	{
+		self.with_linearization(id, parents, |linearization| linearization.flat().clone())
+	}
+
+	/// Finds the first ancestor of the object with the given `id` that `func` returns `Some` for,
+	/// without copying them if they're already cached. See [`get_or_compute`](Self::get_or_compute)
+	/// for `parents`.
+	///
+	/// `func` is called while the cache is locked, so it shouldn't run any quest code.
+	pub fn find_map<F, T, P>(&self, id: usize, parents: P, mut func: F) -> Result<Option<T>>
+	where
+		F: FnMut(&Object) -> Option<T>,
+		P: FnOnce() -> Result<(Vec<Object>, Option<Object>)>
+	{
+		self.with_linearization(id, parents, |linearization| linearization.order.find_map(&mut func))
+	}
+
+	/// Checks to see if any ancestors of the object with the given `id` have properties. See
+	/// [`get_or_compute`](Self::get_or_compute) for `parents`.
+	pub fn has_properties<P>(&self, id: usize, parents: P) -> Result<bool>
+	where
+		P: FnOnce() -> Result<(Vec<Object>, Option<Object>)>
+	{
+		self.with_linearization(id, parents, |linearization| linearization.properties)
+	}
+
+	fn with_linearization<F, T, P>(&self, id: usize, parents: P, func: F) -> Result<T>
+	where
+		F: FnOnce(&Linearization) -> T,
+		P: FnOnce() -> Result<(Vec<Object>, Option<Object>)>
+	{
+		let cache = self.cache();
+		let is_stale =
+			match *cache.linearization.read() {
+				Some(ref linearization) if linearization.is_current() => return Ok(func(linearization)),
+				Some(_) => true,
+				None => false
+			};
+
+		if is_stale {
+			cache.invalidate();
+		}
+
+		if IN_PROGRESS.with(|ids| ids.borrow().contains(&id)) {
+			return Ok(func(&Linearization::new(Order::Flat(Arc::new([])), vec![], false, false)));
+		}
+
+		IN_PROGRESS.with(|ids| ids.borrow_mut().push(id));
+		let result = parents().and_then(|(parents, list)| linearize(&parents, list).map(|lin| (lin, parents)));
+		IN_PROGRESS.with(|ids| ids.borrow_mut().pop());
+
+		let (linearization, parents) = result?;
+		let result = func(&linearization);
+
+		if linearization.complete {
+			*cache.linearization.write() = Some(linearization);
+
+			for parent in parents {
+				parent.0.attrs.mro.cache().add_dependent(cache);
+			}
+		}
+
+		Ok(result)
+	}
+}
+
+/// An object followed by the ones after it, such as a parent and its linearization.
+#[derive(Clone, Copy)]
+struct Sequence<'a> {
+	first: &'a Object,
+	rest: &'a [Object]
+}
+
+impl<'a> Sequence<'a> {
+	fn len(self) -> usize {
+		self.rest.len() + 1
+	}
+
+	fn get(self, idx: usize) -> Option<&'a Object> {
+		match idx {
+			0 => Some(self.first),
+			_ => self.rest.get(idx - 1)
+		}
+	}
+
+	fn iter(self) -> impl Iterator<Item=&'a Object> {
+		std::iter::once(self.first).chain(self.rest)
+	}
+}
+
+/// Computes the C3 linearization of `parents`, which came from `list` if it's given.
+///
+/// If the hierarchy is inconsistent (e.g. one parent says `A` comes before `B` but another says
+/// the opposite), this falls back to a depth-first search, ignoring objects that've been seen.
+fn linearize(parents: &[Object], list: Option<Object>) -> Result<Linearization> {
+	let mut complete = true;
+	let mut properties = false;
+	let mut snapshots = vec![];
+	let mut links = Vec::with_capacity(parents.len());
+
+	if let Some(list) = list {
+		snapshots.push(Arc::new(Snapshot::new(list, parents)));
+	}
+
+	for parent in parents {
+		let attrs = &parent.0.attrs;
+		let order = attrs.mro.with_linearization(attrs.id(), || attrs.parents_and_list(), |linearization| {
+			complete &= linearization.complete;
+			properties |= attrs.has_own_properties() || linearization.properties;
+
+			for snapshot in &linearization.snapshots {
+				if !snapshots.iter().any(|seen: &Arc<Snapshot>| seen.list.is_identical(&snapshot.list)) {
+					snapshots.push(snapshot.clone());
+				}
+			}
+
+			linearization.order.clone()
+		})?;
+
+		links.push((parent.clone(), order));
+	}
+
+	let order =
+		match parents {
+			[] => Order::Flat(Arc::new([])),
+			// with only one parent (which is common for stackframes, as the block's parent is usually
+			// also the caller), its linearization's already in the right order.
+			[first, rest @ ..] if rest.iter().all(|parent| parent.is_identical(first)) => {
+				links.truncate(1);
+				Order::Chain(links)
+			},
+			_ if is_inconsistent(&links) => Order::Chain(links),
+			_ => {
+				let flat = links.iter().map(|(_, order)| order.to_flat()).collect::<Vec<_>>();
+				let mut sequences = parents.iter()
+					.zip(&flat)
+					.map(|(first, rest)| Sequence { first, rest })
+					.collect::<Vec<_>>();
+
+				// the parents themselves have to stay in order too.
+				if let [first, rest @ ..] = parents {
+					sequences.push(Sequence { first, rest });
+				}
+
+				match merge(&sequences) {
+					Some(mro) => Order::Flat(mro.into()),
+					None => Order::Chain(links)
+				}
+			}
+		};
+
+	Ok(Linearization::new(order, snapshots, complete, properties))
+}
+
+/// Checks whether a parent inherits from one that's listed before it, which C3 can't order. This
+/// is common for stackframes, as the caller usually inherits from the frame's lexical parent.
+fn is_inconsistent(links: &[(Object, Arc<Order>)]) -> bool {
+	links.iter().enumerate().any(|(idx, (earlier, _))| {
+		links[idx + 1..].iter().any(|(later, order)| !later.is_identical(earlier) && order.contains(earlier))
+	})
+}
+
+/// Merges `sequences` according to C3, returning `None` if there's no consistent ordering.
+fn merge(sequences: &[Sequence]) -> Option<Vec<Object>> {
+	// how many times each object appears anywhere other than the front of a sequence.
+	let len = sequences.iter().map(|seq| seq.len()).sum();
+	let mut in_tails = IdMap::<usize>::with_capacity_and_hasher(len, Default::default());
+
+	for seq in sequences {
+		for obj in seq.iter().skip(1) {
+			*in_tails.entry(obj.id()).or_default() += 1;
+		}
+	}
+
+	let mut heads = vec![0; sequences.len()];
+	let mut mro = vec![];
+
+	loop {
+		let candidate = sequences.iter()
+			.zip(&heads)
+			.filter_map(|(seq, &head)| seq.get(head))
+			.find(|obj| in_tails.get(&obj.id()).is_none_or(|&count| count == 0));
+
+		let candidate =
+			match candidate {
+				Some(candidate) => candidate,
+				None if sequences.iter().zip(&heads).all(|(seq, &head)| head == seq.len()) => return Some(mro),
+				None => return None
+			};
+
+		for (seq, head) in sequences.iter().zip(heads.iter_mut()) {
+			if seq.get(*head).is_some_and(|obj| obj.is_identical(candidate)) {
+				*head += 1;
+
+				if let Some(next) = seq.get(*head) {
+					*in_tails.get_mut(&next.id()).expect("all tails were counted") -= 1;
+				}
+			}
+		}
+
+		mro.push(candidate.clone());
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::Object;
+
+	fn class(parents: Vec<Object>) -> Object {
+		Object::new_with_parent((), parents)
+	}
+
+	fn ids(obj: &Object) -> Vec<usize> {
+		obj.mro().unwrap().iter().map(Object::id).collect()
+	}
+
+	#[test]
+	fn c3_order() {
+		crate::init();
+
+		let named = class(vec![]);
+		let greeter = class(vec![named.clone()]);
+		let shouter = class(vec![named.clone()]);
+		let robot = class(vec![greeter.clone(), shouter.clone()]);
+
+		assert_eq!(ids(&robot), [greeter.id(), shouter.id(), named.id()]);
+		assert_eq!(ids(&greeter), [named.id()]);
+
+		// an inconsistent hierarchy falls back to depth first.
+		let a = class(vec![]);
+		let b = class(vec![a.clone()]);
+		let inconsistent = class(vec![a.clone(), b.clone()]);
+		assert_eq!(ids(&inconsistent), [a.id(), b.id()]);
+
+		// which is shared with its children, but still only lists each ancestor once.
+		let child = class(vec![inconsistent.clone()]);
+		assert_eq!(ids(&child), [inconsistent.id(), a.id(), b.id()]);
+	}
+
+	#[test]
+	fn invalidated_when_parents_change() {
+		crate::init();
+
+		let named = class(vec![]);
+		let greeter = class(vec![named.clone()]);
+		let shouter = class(vec![]);
+		let robot = class(vec![greeter.clone()]);
+		assert_eq!(ids(&robot), [greeter.id(), named.id()]);
+
+		// changing an ancestor's parents changes the mro of everything that inherits from it.
+		greeter.set_attr_lit("__parents__", Object::from(vec![shouter.clone()])).unwrap();
+		assert_eq!(ids(&robot), [greeter.id(), shouter.id()]);
+
+		// as does modifying a `__parents__` list in place.
+		let parents = robot.get_attr_lit("__parents__").unwrap();
+		parents.call_attr_lit("push", &[&named]).unwrap();
+		assert_eq!(ids(&robot), [greeter.id(), shouter.id(), named.id()]);
+
+		parents.call_attr_lit("shift", &[]).unwrap();
+		assert_eq!(ids(&robot), [named.id()]);
+	}
+
+	#[test]
+	fn reading_parents_keeps_the_cache() {
+		crate::init();
+
+		let named = class(vec![]);
+		let greeter = class(vec![named.clone()]);
+		let robot = class(vec![greeter.clone()]);
+		let mro = robot.mro().unwrap();
+
+		// reading `__parents__` doesn't change the mro, so it's not recomputed...
+		let parents = greeter.get_attr_lit("__parents__").unwrap();
+		assert!(std::sync::Arc::ptr_eq(&mro, &robot.mro().unwrap()));
+
+		// ...but modifying what was read still is noticed.
+		parents.call_attr_lit("pop", &[]).unwrap();
+		assert_eq!(ids(&robot), [greeter.id()]);
+	}
+
+	#[test]
+	fn only_descendants_are_invalidated() {
+		crate::init();
+
+		let named = class(vec![]);
+		let greeter = class(vec![named.clone()]);
+		let robot = class(vec![greeter.clone()]);
+		let unrelated = class(vec![named.clone()]);
+		let robot_mro = robot.mro().unwrap();
+		let unrelated_mro = unrelated.mro().unwrap();
+
+		greeter.set_attr_lit("__parents__", Object::from(Vec::<Object>::new())).unwrap();
+		assert!(std::sync::Arc::ptr_eq(&unrelated_mro, &unrelated.mro().unwrap()));
+		assert!(!std::sync::Arc::ptr_eq(&robot_mro, &robot.mro().unwrap()));
+	}
+
+	#[test]
+	fn releases_ancestors_once_recomputed() {
+		crate::init();
+
+		let grandparent = class(vec![]);
+		let parent = class(vec![grandparent.clone()]);
+		let child = class(vec![parent.clone()]);
+		assert_eq!(ids(&child), [parent.id(), grandparent.id()]);
+
+		let weak = grandparent.downgrade();
+		drop(grandparent);
+		parent.set_attr_lit("__parents__", Object::from(Vec::<Object>::new())).unwrap();
+
+		// `child`'s cache isn't recomputed until it's next used.
+		assert_eq!(ids(&child), [parent.id()]);
+		assert!(weak.upgrade().is_none());
+	}
+}
@@ -1,10 +1,8 @@
-use crate::{Object, Result, Literal};
+use crate::{Object, Result};
 use crate::types::List;
 use super::Value;
 use std::iter::FromIterator;
 use parking_lot::RwLock;
-use std::hash::Hash;
-use std::borrow::Borrow;
 
 /// A list of parents associated with an object.
 #[derive(Debug)]
@@ -72,7 +70,7 @@ impl From<Vec<Object>> for Parents {
 impl From<Vec<&'static Object>> for Parents {
 	#[inline]
 	fn from(vec: Vec<&'static Object>) -> Self {
-		Self::from_inner(Inner::Builtin(vec.into_iter().map(Object::clone).collect()))
+		Self::from_inner(Inner::Builtin(vec.into_iter().cloned().collect()))
 	}
 }
 
@@ -126,7 +124,7 @@ impl Parents {
 				obj
 			},
 			Inner::Builtin(ref mut vec) => {
-				let obj = Object::from(std::mem::replace(vec, vec![]));
+				let obj = Object::from(std::mem::take(vec));
 				*inner = Inner::Object(obj.clone());
 				obj
 			},
@@ -134,6 +132,40 @@ impl Parents {
 		}
 	}
 
+	/// Copies the parents out of the object they're stored in, if they are.
+	pub fn detach(&self) -> Result<()> {
+		let keys = self.keys()?;
+		let mut inner = self.0.write();
+
+		if let Inner::Object(_) = *inner {
+			*inner = Inner::Builtin(keys);
+		}
+
+		Ok(())
+	}
+
+	/// Calls `f` with every object that's directly referenced by the parents.
+	pub fn for_each_reference<F: FnMut(&Object)>(&self, mut f: F) {
+		match *self.0.read() {
+			Inner::None => {},
+			Inner::Builtin(ref parents) => parents.iter().for_each(f),
+			Inner::Object(ref object) => f(object)
+		}
+	}
+
+	/// Checks to see if the parents are stored as an object (rather than a builtin list).
+	pub fn is_object(&self) -> bool {
+		matches!(*self.0.read(), Inner::Object(_))
+	}
+
+	/// Gets the `List` the parents are stored in, if they are.
+	pub fn as_list(&self) -> Option<Object> {
+		match *self.0.read() {
+			Inner::Object(ref object) if object.is_a::<List>() => Some(object.clone()),
+			_ => None
+		}
+	}
+
 	fn with_iter<F: FnOnce(std::slice::Iter<Object>) -> Result<R>, R>(&self, f: F) -> Result<R> {
 		match *self.0.read() {
 			Inner::None => f([].iter()),
@@ -147,62 +179,6 @@ impl Parents {
 	pub fn keys(&self) -> Result<Vec<Object>> {
 		self.with_iter(|iter| Ok(iter.cloned().collect()))
 	}
-
-	/// Checks to see if any of the parents responds to `Key`.
-	pub fn has_lit<L: ?Sized>(&self, key: &L) -> Result<bool>
-	where
-		Literal: Borrow<L>,
-		L: Hash + Eq
-	{
-		self.with_iter(|iter| {
-			for parent in iter {
-				if parent.has_attr_lit(key)? {
-					return Ok(true)
-				}
-			}
-			Ok(false)
-		})
-	}
-
-	/// Gets a value associated with `key` from the parents.
-	pub fn get_lit<L: ?Sized>(&self, key: &L) -> Result<Option<Value>>
-	where
-		Literal: Borrow<L>,
-		L: Hash + Eq
-	{
-		self.with_iter(|iter| {
-			for parent in iter {
-				if let Some(value) = parent.get_value_lit(key)? {
-					return Ok(Some(value))
-				}
-			}
-			Ok(None)
-		})
-	}
-
-	/// Checks to see if any of the parents responds to `Key`.
-	pub fn has_obj(&self, key: &Object) -> Result<bool> {
-		self.with_iter(|iter| {
-			for parent in iter {
-				if parent.has_attr(key)? {
-					return Ok(true)
-				}
-			}
-			Ok(false)
-		})
-	}
-
-	/// Gets a value associated with `key` from the parents.
-	pub fn get_obj(&self, key: &Object) -> Result<Option<Value>> {
-		self.with_iter(|iter| {
-			for parent in iter {
-				if let Some(value) = parent.get_value(key)? {
-					return Ok(Some(value))
-				}
-			}
-			Ok(None)
-		})
-	}
 }
 
 // impl IntoIterator for Parents {
@@ -1,4 +1,4 @@
-use crate::types::RustFn;
+use crate::types::{RustFn, Property};
 use crate::{Object, Args, Result, Literal};
 
 /// A value.
@@ -7,7 +7,8 @@ use crate::{Object, Args, Result, Literal};
 #[derive(Debug, Clone)]
 pub enum Value {
 	RustFn(RustFn),
-	Object(Object)
+	Object(Object),
+	Property(Property)
 }
 
 impl Value {
@@ -32,7 +33,19 @@ impl Value {
 					};
 
 				object.call_attr_lit(&Literal::CALL, args)
-			}
+			},
+			Value::Property(property) => Value::Object(property.get(owner)?).call(owner, args)
+		}
+	}
+}
+
+impl Value {
+	/// Calls `f` with every object that's referenced by this value.
+	pub fn for_each_reference<F: FnMut(&Object)>(&self, mut f: F) {
+		match self {
+			Value::RustFn(_) => {},
+			Value::Object(object) => f(object),
+			Value::Property(property) => property.for_each_reference(f)
 		}
 	}
 }
@@ -43,6 +56,7 @@ impl From<Value> for Object {
 		match val {
 			Value::RustFn(rustfn) => rustfn.into(),
 			Value::Object(obj) => obj,
+			Value::Property(property) => property.into()
 		}
 	}
 }
@@ -55,6 +69,13 @@ impl From<RustFn> for Value {
 	}
 }
 
+impl From<Property> for Value {
+	#[inline]
+	fn from(property: Property) -> Self {
+		Value::Property(property)
+	}
+}
+
 impl From<Object> for Value {
 	#[inline]
 	fn from(obj: Object) -> Self {
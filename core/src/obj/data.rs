@@ -115,6 +115,12 @@ impl Data {
 		self.typename
 	}
 
+	/// Gets an identifier for the contained data, or `None` if it isn't shared with another object.
+	#[inline]
+	pub fn shared_id(&self) -> Option<usize> {
+		self.data.shared_id()
+	}
+
 	/// Checks to see if the contained data is a `T`.
 	#[inline]
 	pub fn is_a<T: Any>(&self) -> bool {
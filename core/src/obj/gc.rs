@@ -0,0 +1,284 @@
+//! Weak references, finalizers, and finding leaked objects.
+//!
+//! Objects are reference counted, so any cycle between them (e.g. a block that has the scope it
+//! was defined in as a parent, while that scope has the block as an attribute) is never freed.
+//! Rather than collecting these cycles, Quest can report them: when tracking is enabled (via
+//! [`track_objects`]), every object that's created afterwards is recorded, and [`leaked_objects`]
+//! finds the ones that are only kept alive by other tracked objects.
+//!
+//! This is done by counting how many of each object's references come from the attributes,
+//! parents, finalizers, `List` elements, and [`captures`](crate::types::RustClosure::capturing) of
+//! other tracked objects. If that's every reference to it, nothing outside of the tracked objects
+//! refers to it; anything that isn't reachable from an object that _is_ referred to from outside
+//! has leaked. Other references held within an object's data (such as the variables moved into a
+//! `RustClosure`) can't be seen, so objects they refer to are always considered reachable.
+use super::{Internal, Object};
+use crate::types::{List, RustClosure};
+use parking_lot::Mutex;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Weak};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// A weak reference to an [`Object`], which doesn't keep it alive.
+#[derive(Debug, Clone)]
+pub struct WeakObject(Weak<Internal>);
+
+impl WeakObject {
+	/// Gets the object, if it's still alive.
+	#[inline]
+	pub fn upgrade(&self) -> Option<Object> {
+		self.0.upgrade().map(Object)
+	}
+}
+
+impl Object {
+	/// Creates a weak reference to this object.
+	#[inline]
+	pub fn downgrade(&self) -> WeakObject {
+		WeakObject(Arc::downgrade(&self.0))
+	}
+}
+
+static TRACKING: AtomicBool = AtomicBool::new(false);
+
+lazy_static::lazy_static! {
+	static ref TRACKED: Mutex<Vec<Weak<Internal>>> = Mutex::new(Vec::new());
+
+	/// Finalizers of objects that've been dropped, which haven't been run yet.
+	static ref PENDING: Mutex<Vec<(usize, Vec<Object>)>> = Mutex::new(Vec::new());
+}
+
+/// Starts recording every object that's created, so that [`leaked_objects`] can find them.
+///
+/// Objects created before this is called are never reported.
+pub fn track_objects() {
+	TRACKING.store(true, Ordering::Release);
+}
+
+/// Records `obj` if tracking's enabled.
+pub(super) fn track(obj: &Object) {
+	if TRACKING.load(Ordering::Acquire) {
+		TRACKED.lock().push(Arc::downgrade(&obj.0));
+	}
+}
+
+/// Gets every tracked object that's still alive.
+pub fn live_objects() -> Vec<Object> {
+	let mut tracked = TRACKED.lock();
+	tracked.retain(|weak| weak.strong_count() != 0);
+	tracked.iter().filter_map(Weak::upgrade).map(Object).collect()
+}
+
+/// Calls `f` with every object referenced by `obj`, and whether that reference should be counted.
+fn for_each_reference<F: FnMut(&Object, bool)>(obj: &Object, seen_data: &mut HashSet<usize>, mut f: F) {
+	obj.0.attrs.for_each_reference(seen_data, &mut f);
+
+	if let Some(list) = obj.downcast::<List>() {
+		let counted = obj.0.data.shared_id().is_none_or(|id| seen_data.insert(id));
+		list.as_ref().iter().for_each(|elem| f(elem, counted));
+	}
+
+	if let Some(closure) = obj.downcast::<RustClosure>() {
+		let captures = closure.captures();
+		let counted = seen_data.insert(captures.as_ptr() as *const () as usize);
+		captures.iter().for_each(|capture| f(capture, counted));
+	}
+}
+
+/// Counts how many references to each of `objects` come from outside of them, and which of them
+/// each one refers to.
+fn count_references(objects: &[Object]) -> (Vec<usize>, Vec<Vec<usize>>) {
+	let index = objects.iter()
+		.enumerate()
+		.map(|(idx, obj)| (obj.id(), idx))
+		.collect::<HashMap<_, _>>();
+
+	// we have to read the counts before looking at references, as that clones them. We also
+	// subtract one for the reference in `objects`.
+	let mut external = objects.iter()
+		.map(|obj| Arc::strong_count(&obj.0) - 1)
+		.collect::<Vec<_>>();
+
+	let mut edges = vec![vec![]; objects.len()];
+	let mut seen_data = HashSet::new();
+
+	for (idx, obj) in objects.iter().enumerate() {
+		for_each_reference(obj, &mut seen_data, |child, counted| {
+			if let Some(&child_idx) = index.get(&child.id()) {
+				edges[idx].push(child_idx);
+
+				if counted {
+					external[child_idx] = external[child_idx].saturating_sub(1);
+				}
+			}
+		});
+	}
+
+	(external, edges)
+}
+
+/// Finds every tracked object that's still alive but can no longer be reached.
+pub fn leaked_objects() -> Vec<Object> {
+	let objects = live_objects();
+	let (external, edges) = count_references(&objects);
+
+	let mut reachable = vec![false; objects.len()];
+	let mut queue = (0..objects.len()).filter(|&idx| external[idx] != 0).collect::<Vec<_>>();
+
+	while let Some(idx) = queue.pop() {
+		if !std::mem::replace(&mut reachable[idx], true) {
+			queue.extend(edges[idx].iter().copied());
+		}
+	}
+
+	objects.into_iter()
+		.zip(reachable)
+		.filter_map(|(obj, reachable)| if reachable { None } else { Some(obj) })
+		.collect()
+}
+
+/// Finds the objects in `candidates` that belong to `owner`: the ones it refers to (directly or
+/// through others that belong to it) that aren't referred to by anything else.
+///
+/// References are counted the same way as [`leaked_objects`], so anything referred to in a way
+/// that can't be seen (such as by a running stackframe) never belongs to `owner`.
+pub(crate) fn owned_by(owner: &Object, candidates: Vec<Object>) -> Vec<Object> {
+	let mut objects = candidates;
+	objects.insert(0, owner.clone());
+
+	let (external, edges) = count_references(&objects);
+	let mut owned = vec![false; objects.len()];
+	let mut queue = vec![0];
+
+	while let Some(idx) = queue.pop() {
+		for &child in &edges[idx] {
+			if child != 0 && external[child] == 0 && !std::mem::replace(&mut owned[child], true) {
+				queue.push(child);
+			}
+		}
+	}
+
+	objects.into_iter()
+		.zip(owned)
+		.filter_map(|(obj, owned)| if owned { Some(obj) } else { None })
+		.collect()
+}
+
+static ANY_PENDING: AtomicBool = AtomicBool::new(false);
+
+/// Schedules `finalizers` to be run for the object with the id `id`, which was just dropped.
+///
+/// They aren't run immediately, as the object may have been dropped while locks that they'd need
+/// were held.
+pub(super) fn schedule_finalizers(id: usize, finalizers: Vec<Object>) {
+	PENDING.lock().push((id, finalizers));
+	ANY_PENDING.store(true, Ordering::Release);
+}
+
+/// Runs the finalizers of every object that's been dropped so far.
+///
+/// This is called whenever a stackframe is exited, but should also be called before exiting. Since
+/// finalizers aren't able to raise errors, any that do are printed to stderr.
+pub fn run_finalizers() {
+	// this is called a lot, so we check the flag before writing to it.
+	while ANY_PENDING.load(Ordering::Acquire) {
+		ANY_PENDING.store(false, Ordering::Release);
+		let pending = std::mem::take(&mut *PENDING.lock());
+
+		for (id, finalizers) in pending {
+			let id_obj = Object::from(id);
+
+			for finalizer in finalizers {
+				if let Err(err) = finalizer.call_attr_lit(&crate::Literal::CALL, &[&id_obj]) {
+					eprintln!("error in finalizer for object {}: {}", id, err);
+				}
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::types::{List, Number, RustFn, WeakRef};
+
+	fn ids(objects: &[Object]) -> HashSet<usize> {
+		objects.iter().map(Object::id).collect()
+	}
+
+	/// A block that returns `1` when it's called.
+	fn block(parent: &Object) -> Object {
+		let block = Object::new_with_parent((), vec![parent.clone()]);
+		block.set_attr_lit("call_noscope", RustFn::method("call_noscope", |_, _| Ok(1.into())).into()).unwrap();
+		block
+	}
+
+	#[test]
+	fn weak_references() {
+		crate::init();
+
+		let obj = Object::new_with_parent((), ());
+		let weak = obj.downgrade();
+		let weak_ref = Object::from(WeakRef::new(&obj));
+
+		assert!(weak.upgrade().unwrap().is_identical(&obj));
+		assert!(weak_ref.call_attr_lit("get", &[]).unwrap().is_identical(&obj));
+
+		drop(obj);
+		assert!(weak.upgrade().is_none());
+		assert!(weak_ref.call_attr_lit("get", &[]).unwrap().is_a::<crate::types::Null>());
+		assert_eq!(*weak_ref.call_attr_lit("alive?", &[]).unwrap().downcast::<crate::types::Boolean>().unwrap(),
+			crate::types::Boolean::FALSE);
+	}
+
+	#[test]
+	fn finalizers() {
+		crate::init();
+
+		let dropped = Object::from(List::from(vec![]));
+		let dropped2 = dropped.clone();
+		let finalizer = Object::from(RustClosure::new(move |args| dropped2.call_attr_lit("push", &[args.try_arg(0)?])));
+
+		let obj = Object::new_with_parent((), ());
+		let id = obj.id();
+		obj.add_finalizer(finalizer);
+
+		drop(obj);
+		run_finalizers();
+
+		let dropped = dropped.downcast::<List>().unwrap();
+		assert_eq!(dropped.as_ref().len(), 1);
+		assert_eq!(*dropped.as_ref()[0].downcast::<Number>().unwrap(), Number::from(id));
+	}
+
+	#[test]
+	fn functions_keep_their_blocks_alive() {
+		crate::init();
+
+		let scope = Object::new_with_parent((), ());
+		let function = List::qs_arrow(&Object::from(List::from(vec![])), crate::Args::new(vec![&block(&scope)])).unwrap();
+
+		assert_eq!(*function.call_attr_lit("()", &[]).unwrap().downcast::<Number>().unwrap(), Number::from(1));
+	}
+
+	#[test]
+	fn reports_cycles_through_functions() {
+		crate::init();
+		track_objects();
+
+		// a scope that has a function defined within it as an attribute.
+		let scope = Object::new_with_parent((), ());
+		let block = block(&scope);
+		let function = List::qs_arrow(&Object::from(List::from(vec![])), crate::Args::new(vec![&block])).unwrap();
+		scope.set_attr_lit("function", function.clone()).unwrap();
+
+		let cycle = ids(&[scope.clone(), block.clone(), function.clone()]);
+		drop((block, function));
+		assert!(leaked_objects().iter().all(|obj| !cycle.contains(&obj.id())), "the scope is still referenced");
+
+		let weak = scope.downgrade();
+		drop(scope);
+		assert!(weak.upgrade().is_some(), "the cycle is never freed");
+		assert!(cycle.is_subset(&ids(&leaked_objects())));
+	}
+}
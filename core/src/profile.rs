@@ -0,0 +1,232 @@
+//! Measuring where the time is spent while running code.
+//!
+//! Once profiling is [started](start), interpreters call [`enter`] whenever something is called,
+//! such as a block (named by where it is, eg `fib.qs:3`) or a [`RustFn`](crate::types::RustFn)
+//! (named by its name, eg `Kernel::disp`), and keep the returned [`Call`] until it returns.
+//!
+//! For each name, the [`Profile`] records how many times it was called, the time spent in it
+//! (including what it called), and the time spent in it alone. It also records the time spent in
+//! each distinct stack of calls, which [`Profile::folded`] writes in the "folded stacks" format
+//! that flamegraph tools read.
+
+use parking_lot::{const_mutex, Mutex};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// The measurements for one name.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Entry {
+	/// How many times it was called.
+	pub calls: usize,
+	/// The time spent in it, including what it called. Recursive calls aren't counted twice.
+	pub total: Duration,
+	/// The time spent in it, excluding what it called.
+	pub own: Duration
+}
+
+/// What was measured while profiling.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Profile {
+	/// The measurements for each name.
+	pub entries: HashMap<String, Entry>,
+	/// The time spent in each stack of calls (outermost first), excluding what the last one called.
+	pub stacks: HashMap<Vec<String>, Duration>
+}
+
+impl Profile {
+	/// A table of each name's measurements, with the ones that took the most time themselves first.
+	#[must_use]
+	pub fn flat_report(&self) -> String {
+		let mut entries = self.entries.iter().collect::<Vec<_>>();
+		entries.sort_by(|(lname, lhs), (rname, rhs)| rhs.own.cmp(&lhs.own).then_with(|| lname.cmp(rname)));
+
+		let ms = |duration: Duration| duration.as_secs_f64() * 1000.0;
+		let mut report = format!("{:>12} {:>12} {:>10}  {}\n", "own (ms)", "total (ms)", "calls", "name");
+
+		for (name, entry) in entries {
+			writeln!(report, "{:>12.3} {:>12.3} {:>10}  {}", ms(entry.own), ms(entry.total), entry.calls, name)
+				.expect("writing to a string can't fail");
+		}
+
+		report
+	}
+
+	/// Each stack of calls, and how many microseconds were spent in it, in the "folded stacks"
+	/// format that tools such as `flamegraph.pl` and `inferno` read.
+	///
+	/// Each line is the names in the stack, outermost first and separated by `;`, followed by a
+	/// space and the time. Stacks that took less than a microsecond are left out.
+	#[must_use]
+	pub fn folded(&self) -> String {
+		let mut stacks = self.stacks.iter()
+			.map(|(stack, time)| {
+				let names = stack.iter().map(|name| name.replace(';', ",")).collect::<Vec<_>>();
+				(names.join(";"), time.as_micros())
+			})
+			.filter(|&(_, micros)| micros != 0)
+			.collect::<Vec<_>>();
+
+		stacks.sort();
+
+		stacks.into_iter()
+			.map(|(stack, micros)| format!("{} {}\n", stack, micros))
+			.collect()
+	}
+}
+
+/// A call that's running.
+#[derive(Debug)]
+struct Frame {
+	name: String,
+	start: Instant,
+	/// The time spent in calls it made.
+	children: Duration
+}
+
+// checked before anything else, so calls are only slowed down a little when we aren't profiling.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+static PROFILE: Mutex<Option<Profile>> = const_mutex(None);
+
+thread_local!(
+	// Each thread has its own calls, but they're all recorded in the same profile.
+	static FRAMES: RefCell<Vec<Frame>> = const { RefCell::new(Vec::new()) };
+);
+
+/// Starts profiling, discarding anything that was recorded before.
+pub fn start() {
+	*PROFILE.lock() = Some(Profile::default());
+	ENABLED.store(true, Ordering::SeqCst);
+}
+
+/// Stops profiling, returning what was recorded since it was [started](start).
+pub fn stop() -> Option<Profile> {
+	ENABLED.store(false, Ordering::SeqCst);
+	PROFILE.lock().take()
+}
+
+/// Whether calls are being profiled.
+#[inline]
+pub fn is_enabled() -> bool {
+	ENABLED.load(Ordering::Relaxed)
+}
+
+/// A call that's being profiled; it's recorded when this is dropped.
+#[derive(Debug)]
+#[must_use = "the call is recorded when this is dropped"]
+pub struct Call(());
+
+/// Records a call to whatever `name` returns, if profiling is enabled.
+///
+/// `name` is only called when profiling, so that it doesn't slow anything down otherwise.
+#[inline]
+pub fn enter(name: impl FnOnce() -> String) -> Option<Call> {
+	if !is_enabled() {
+		return None;
+	}
+
+	let frame = Frame { name: name(), start: Instant::now(), children: Duration::default() };
+	FRAMES.with(|frames| frames.borrow_mut().push(frame));
+
+	Some(Call(()))
+}
+
+impl Drop for Call {
+	fn drop(&mut self) {
+		FRAMES.with(|frames| {
+			let mut frames = frames.borrow_mut();
+			let frame = match frames.pop() {
+				Some(frame) => frame,
+				None => return
+			};
+
+			let elapsed = frame.start.elapsed();
+			let own = elapsed.checked_sub(frame.children).unwrap_or_default();
+			let is_recursive = frames.iter().any(|outer| outer.name == frame.name);
+
+			if let Some(caller) = frames.last_mut() {
+				caller.children += elapsed;
+			}
+
+			let mut profile = PROFILE.lock();
+			let profile =
+				match profile.as_mut() {
+					Some(profile) => profile,
+					None => return
+				};
+
+			let stack = frames.iter().map(|frame| frame.name.clone())
+				.chain(std::iter::once(frame.name.clone()))
+				.collect();
+			*profile.stacks.entry(stack).or_default() += own;
+
+			let entry = profile.entries.entry(frame.name).or_default();
+			entry.calls += 1;
+			entry.own += own;
+
+			if !is_recursive {
+				entry.total += elapsed;
+			}
+		});
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// profiles are shared between threads, so tests that start them can't run at the same time.
+	static SERIAL: Mutex<()> = const_mutex(());
+
+	fn call(name: &str, inner: impl FnOnce()) {
+		let _call = enter(|| name.to_string());
+		std::thread::sleep(Duration::from_millis(2));
+		inner();
+	}
+
+	#[test]
+	fn records_calls_and_stacks() {
+		let _serial = SERIAL.lock();
+		assert!(enter(|| unreachable!("not profiling")).is_none());
+
+		start();
+		call("main.qs:1", || {
+			call("Kernel::disp", || {});
+			call("main.qs:3", || call("main.qs:3", || call("Kernel::disp", || {})));
+		});
+		let profile = stop().unwrap();
+
+		let disp = profile.entries["Kernel::disp"];
+		assert_eq!(disp.calls, 2);
+		assert_eq!(disp.own, disp.total);
+
+		let main = profile.entries["main.qs:1"];
+		assert_eq!(main.calls, 1);
+		assert!(main.total >= main.own + disp.total);
+
+		// recursive calls only count towards the total once.
+		let recursive = profile.entries["main.qs:3"];
+		assert_eq!(recursive.calls, 2);
+		assert!(recursive.total < recursive.own + disp.total + main.own);
+
+		let mut stacks = profile.folded().lines()
+			.map(|line| line.rsplit_once(' ').unwrap().0.to_string())
+			.collect::<Vec<_>>();
+		stacks.sort();
+
+		assert_eq!(stacks, [
+			"main.qs:1",
+			"main.qs:1;Kernel::disp",
+			"main.qs:1;main.qs:3",
+			"main.qs:1;main.qs:3;main.qs:3",
+			"main.qs:1;main.qs:3;main.qs:3;Kernel::disp"
+		]);
+
+		let report = profile.flat_report();
+		assert!(report.lines().next().unwrap().contains("calls"));
+		assert_eq!(report.lines().count(), 4);
+	}
+}
@@ -0,0 +1,218 @@
+//! Running code with a restricted kernel and a limited budget.
+//!
+//! Sandboxed code runs on its own stack, so it can't reach the stackframes that started it (e.g.
+//! through `:1` or `__stack__`). Every stackframe it creates has the sandbox's kernel as its parent
+//! instead of [`Kernel`], so only the attributes copied into that kernel can be looked up.
+//!
+//! Interpreters call [`step`] as they run code, which fails with a [`BudgetError`] once a
+//! sandbox's step or time budget is used up. Code can also be given a budget without being
+//! sandboxed, with [`limit`].
+//!
+//! Sandboxes are tracked per-thread, so anything that runs Quest code on another thread (such as
+//! generators) must [`capture`] the sandboxes it was started in and [`enter`] them on that thread.
+//!
+//! Builtin types such as `Number` are shared with the code outside the sandbox, so sandboxed code
+//! can modify them unless they've been frozen with [`freeze_builtins`](crate::freeze_builtins).
+
+use crate::{Object, Binding, Result};
+use crate::error::{BudgetError, KeyError};
+use crate::types::{ObjectType, Basic, Class, Kernel, Scope};
+use std::cell::RefCell;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// The attributes of [`Kernel`] that every sandbox's kernel has.
+///
+/// These can't affect anything outside of the sandbox: there's no IO, threads, or `quit`.
+pub const DEFAULT_ALLOWED: &[&str] = &[
+	"true", "false", "null",
+	"if", "ifl", "unless", "unlessl", "while", "until", "loop", "return", "assert", "yield",
+	"Basic", "Boolean", "BoundFunction", "Function", "List", "Null", "Number", "Pristine",
+	"RustFn", "Text", "Regex", "Match", "Comparable", "Iterable", "Iter", "StopIteration",
+	"WeakRef", "Block",
+];
+
+/// The limits placed on sandboxed code. `None` means unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Budget {
+	/// How many steps the code may take.
+	pub steps: Option<usize>,
+
+	/// How long the code may run for.
+	pub time: Option<Duration>
+}
+
+#[derive(Debug, Clone)]
+struct Sandbox {
+	/// The kernel its stackframes have as their parent; `None` if it only limits the budget.
+	kernel: Option<Object>,
+	budget: Budget,
+	/// Shared with every thread the sandbox has been entered on, so they all use the same budget.
+	steps: Arc<AtomicUsize>,
+	deadline: Option<Instant>
+}
+
+impl Sandbox {
+	fn new(kernel: Option<Object>, budget: Budget) -> Self {
+		let deadline = budget.time.map(|time| Instant::now() + time);
+
+		Self { kernel, budget, steps: Arc::new(AtomicUsize::new(0)), deadline }
+	}
+
+	fn is_same(&self, other: &Self) -> bool {
+		Arc::ptr_eq(&self.steps, &other.steps)
+	}
+
+	fn step(&self) -> Result<()> {
+		let steps = self.steps.fetch_add(1, Ordering::Relaxed) + 1;
+
+		if let Some(max) = self.budget.steps {
+			if steps > max {
+				return Err(BudgetError::Steps(max).into());
+			}
+		}
+
+		match (self.deadline, self.budget.time) {
+			(Some(deadline), Some(time)) if Instant::now() >= deadline =>
+				Err(BudgetError::Time(time).into()),
+			_ => Ok(())
+		}
+	}
+}
+
+thread_local!(
+	// Sandboxes can be nested, so we keep a stack of them; every one of them is charged for a step.
+	static SANDBOXES: RefCell<Vec<Sandbox>> = const { RefCell::new(Vec::new()) };
+);
+
+/// Creates a kernel containing the [`DEFAULT_ALLOWED`] attributes of [`Kernel`], as well as the
+/// ones named in `allow`.
+pub fn kernel_with(allow: &[Object]) -> Result<Object> {
+	let kernel = Object::new_with_parent(Class::new("Kernel"), vec![Basic::mapping().clone()]);
+	let default = DEFAULT_ALLOWED.iter().map(|&name| Object::from(name));
+
+	for name in default.chain(allow.iter().cloned()) {
+		let value = Kernel::mapping().get_own_definition(&name)?
+			.ok_or_else(|| KeyError::DoesntExist { attr: name.clone(), obj: Kernel::mapping().clone() })?;
+
+		kernel.set_attr(name, value.into())?;
+	}
+
+	// stackframes usually get `super` from `Scope`, which they don't have in a sandbox.
+	if let Some(super_) = Scope::mapping().get_own_definition(&Object::from("super"))? {
+		kernel.set_attr_lit("super", super_.into())?;
+	}
+
+	Ok(kernel)
+}
+
+/// Gets the kernel of the innermost sandbox, if we're in one.
+pub fn current_kernel() -> Option<Object> {
+	SANDBOXES.with(|sandboxes| sandboxes.borrow().iter().rev().find_map(|sandbox| sandbox.kernel.clone()))
+}
+
+/// Charges every sandbox we're in for one step, returning an error if any of them are over budget.
+///
+/// This does nothing outside of a sandbox.
+pub fn step() -> Result<()> {
+	SANDBOXES.with(|sandboxes| {
+		sandboxes.borrow()
+			.iter()
+			.try_for_each(Sandbox::step)
+	})
+}
+
+/// Sleeps for `duration`, or until the first of the deadlines of the sandboxes we're in, in which
+/// case a [`BudgetError`] is returned.
+///
+/// Budgets are only checked by [`step`], so builtins that block should use this (or check
+/// [`step`] themselves) to be stopped when they run out of time.
+pub fn sleep(duration: Duration) -> Result<()> {
+	let deadline = SANDBOXES.with(|sandboxes| sandboxes.borrow().iter().filter_map(|sandbox| sandbox.deadline).min());
+
+	match deadline.map(|deadline| deadline.saturating_duration_since(Instant::now())) {
+		Some(left) if left < duration => {
+			std::thread::sleep(left);
+			step()
+		},
+		_ => { std::thread::sleep(duration); Ok(()) }
+	}
+}
+
+/// The sandboxes a thread was in when [`capture`] was called.
+#[derive(Debug, Clone, Default)]
+pub struct Captured(Vec<Sandbox>);
+
+impl Captured {
+	/// Adds the sandboxes in `other` that aren't already in `self`, which will be nested within
+	/// `self`'s.
+	#[must_use]
+	pub fn merge(mut self, other: Self) -> Self {
+		for sandbox in other.0 {
+			if !self.0.iter().any(|seen| seen.is_same(&sandbox)) {
+				self.0.push(sandbox);
+			}
+		}
+
+		self
+	}
+}
+
+/// Gets the sandboxes the current thread is in, so they can be [`enter`]ed on another thread.
+///
+/// The budgets are shared, not copied: steps taken on either thread are charged to both.
+pub fn capture() -> Captured {
+	SANDBOXES.with(|sandboxes| Captured(sandboxes.borrow().clone()))
+}
+
+/// Puts the current thread in `captured`'s sandboxes, in addition to any it's already in.
+///
+/// This is meant for new threads, before they run any Quest code; the sandboxes are never left.
+pub fn enter(captured: Captured) {
+	SANDBOXES.with(|sandboxes| sandboxes.borrow_mut().extend(captured.0));
+}
+
+/// Runs `func` in a new sandbox, whose stackframes have `kernel` as their parent.
+///
+/// `func` is run in a stackframe that's the only one on the stack; the caller's stack is restored
+/// afterwards, even if `func` fails.
+pub fn run<F>(kernel: Object, budget: Budget, func: F) -> Result<Object>
+where
+	F: FnOnce(&Binding) -> Result<Object>
+{
+	struct SandboxGuard(Vec<Binding>);
+	impl Drop for SandboxGuard {
+		fn drop(&mut self) {
+			Binding::replace_stack(std::mem::take(&mut self.0));
+			SANDBOXES.with(|sandboxes| sandboxes.borrow_mut().pop());
+		}
+	}
+
+	SANDBOXES.with(|sandboxes| sandboxes.borrow_mut().push(Sandbox::new(Some(kernel), budget)));
+
+	// now that the sandbox is active, this has its kernel as a parent.
+	let binding = Binding::from(Object::from(Scope));
+	let _guard = SandboxGuard(Binding::replace_stack(vec![binding.clone()]));
+
+	func(&binding)
+}
+
+/// Runs `func` with `budget`, without otherwise sandboxing it: it has the same kernel and stack as
+/// the code around it.
+pub fn limit<F>(budget: Budget, func: F) -> Result<Object>
+where
+	F: FnOnce() -> Result<Object>
+{
+	struct LimitGuard;
+	impl Drop for LimitGuard {
+		fn drop(&mut self) {
+			SANDBOXES.with(|sandboxes| sandboxes.borrow_mut().pop());
+		}
+	}
+
+	SANDBOXES.with(|sandboxes| sandboxes.borrow_mut().push(Sandbox::new(None, budget)));
+
+	let _guard = LimitGuard;
+	func()
+}
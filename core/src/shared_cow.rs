@@ -94,6 +94,14 @@ impl<T: Sharable + ?Sized> SharedCow<T> {
 		Self(RwLock::new(Data::Owned(data)))
 	}
 
+	/// Gets an identifier for the shared data, or `None` if the data isn't shared.
+	pub fn shared_id(&self) -> Option<usize> {
+		match *self.0.read() {
+			Data::Shared(ref shared) => Some(shared.borrow() as *const T as *const () as usize),
+			Data::Owned(_) => None
+		}
+	}
+
 	pub fn read<'a>(&'a self) -> impl Deref<Target=T> + 'a {
 		struct Reader<'a, T: Sharable + ?Sized>(RwLockReadGuard<'a, Data<T>>);
 
@@ -215,32 +215,56 @@ impl Basic {
 
 impl_object_type!{
 for Basic [(parents super::Pristine)]:
+	/// Converts `this` to a `Boolean`, which is `true` by default.
 	"@bool" => method Self::qs_at_bool,
+	/// Converts `this` to a `Text`, by calling `inspect`.
 	"@text" => method Self::qs_at_text,
+	/// Checks to see if `this` is the exact same object as the first argument.
 	"==" => method Self::qs_eql,
+	/// Checks to see if `this` isn't equal (`==`) to the first argument.
 	"!=" => method Self::qs_neq,
+	/// Gets the logical inverse of `this`'s `@bool`.
 	"!" => method Self::qs_not,
+	/// Creates a copy of `this`, which is only actually copied when it's changed.
 	"clone" => method Self::qs_clone,
+	/// Gets a hash of `this`, which is unique to the object.
 	"hash" => method Self::qs_hash,
+	/// Returns `this`, which is useful for passing methods around.
 	"itself" => method Self::qs_itself,
+	/// Calls the first argument with `this`, and then returns `this`.
 	"tap" => method Self::qs_tap,
+	/// Calls the first argument with `this`, and returns its result.
 	"tap_into" => method Self::qs_tap_into,
 
 	// TODO: move these out of kernel
+	/// Calls the first argument if `this` is truthy, and the second (if given) otherwise.
+	///
+	/// If `this` is falsey and there's no second argument, `this` is returned.
 	"if" => method super::Kernel::qs_if, 
+	/// Prints each argument's `@text`, followed by a newline.
 	"disp" => function super::Kernel::qs_disp,
+	/// Prints each argument's `@text`, without a newline.
 	"dispn" => function super::Kernel::qs_dispn,
+	/// Prints each argument's `@text`, followed by a newline.
 	"print" => function super::Kernel::qs_disp,
+	/// Prints each argument's `@text`, without a newline.
 	"printn" => function super::Kernel::qs_dispn,
-	"print" => function super::Kernel::qs_disp,
-	"printn" => function super::Kernel::qs_dispn,
+	/// Calls the first argument for as long as calling `this` returns something truthy, returning
+	/// the last result.
 	"while" => method super::Kernel::qs_while,
+	/// Calls `this` forever, until it returns or there's an error.
 	"loop" => method super::Kernel::qs_loop,
+	/// Returns the first argument (or `null`) from the stackframe given as the second argument,
+	/// which is the current one by default.
 	"return" => function super::Kernel::qs_return,
+	/// Raises an error, with the first argument as its message, if `this` is falsey.
 	"assert" => method super::Kernel::qs_assert,
 
+	/// Calls the first argument if `this` is truthy, and the second (if given) otherwise.
 	"then" => method super::Kernel::qs_if, 
+	/// Calls the first argument if `this` is falsey, and the second (if given) otherwise.
 	"else" => method super::Kernel::qs_unless, 
+	/// Calls the first argument with `this` if `this` is truthy, returning `this` otherwise.
 	"then_into" => method |this, args| {
 		let func = args.try_arg(0)?;
 		if this.call_downcast::<super::Boolean>()?.into_inner() {
@@ -249,6 +273,7 @@ for Basic [(parents super::Pristine)]:
 			Ok(this.clone())
 		}
 	},
+	/// Calls the first argument with `this` if `this` is falsey, returning `this` otherwise.
 	"else_into" => method |this, args| {
 		let func = args.try_arg(0)?;
 		if !this.call_downcast::<super::Boolean>()?.into_inner() {
@@ -257,6 +282,7 @@ for Basic [(parents super::Pristine)]:
 			Ok(this.clone())
 		}
 	},
+	/// Returns `this` if it's truthy, and the first argument otherwise.
 	"or" => method |this, args| {
 		let if_false = args.try_arg(0)?;
 		let is_truthy = this.call_downcast::<super::Boolean>()?.into_inner();
@@ -267,6 +293,7 @@ for Basic [(parents super::Pristine)]:
 			Ok(if_false.clone())
 		}
 	},
+	/// Returns the first argument if `this` is truthy, and `this` otherwise.
 	"and" => method |this, args| {
 		let if_true = args.try_arg(0)?;
 		let is_truthy = this.call_downcast::<super::Boolean>()?.into_inner();
@@ -281,6 +308,7 @@ for Basic [(parents super::Pristine)]:
 
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
 	use super::*;
 
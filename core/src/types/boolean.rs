@@ -423,7 +423,7 @@ impl Boolean {
 		let rhs = args.try_arg(0)?.downcast::<Self>();
 		let this = this.try_downcast::<Self>()?;
 
-		Ok(rhs.map_or(false, |rhs| *this == *rhs).into())
+		Ok(rhs.is_some_and(|rhs| *this == *rhs).into())
 	}
 
 	/// Compares `this` to the first argument, returning [`Null`](crate::types::Null) if the argument isn't a
@@ -725,23 +725,39 @@ for Boolean {
 	}
 }
 [(parents super::Basic) (no_convert)]:
+	/// Convert `this` into a [`Text`].
 	"@text"   => method Self::qs_at_text,
+	/// Inspects `this`.
 	"inspect" => method Self::qs_inspect,
+	/// Convert `this` into a [`Number`].
 	"@num"    => method Self::qs_at_num,
+	/// Converts `this` into a [`Boolean`].
 	"@bool"   => method Self::qs_at_bool,
+	/// See if a `this` is equal to the first argument.
 	"=="      => method Self::qs_eql,
+	/// Logical NOT of `this`.
 	"!"       => method Self::qs_not,
+	/// Logical AND of `this` and the first argument.
 	"&"       => method Self::qs_bitand,
+	/// In-place logical AND of `this` and the first argument.
 	"&="      => method Self::qs_bitand_assign,
+	/// Logical OR of `this` and the first argument.
 	"|"       => method Self::qs_bitor,
+	/// In-place logical OR of `this` and the first argument.
 	"|="      => method Self::qs_bitor_assign,
+	/// Logical XOR of `this` and the first argument.
 	"^"       => method Self::qs_bitxor,
+	/// In-place logical XOR of this and the first argument.
 	"^="      => method Self::qs_bitxor_assign,
+	/// Compares `this` to the first argument, returning [`Null`](crate::types::Null) if the argument isn't a
+	/// [`Boolean`].
 	"<=>"     => method Self::qs_cmp,
+	/// Hashes `this`.
 	"hash"    => method Self::qs_hash,
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
 	use super::*;
 	#[test]
@@ -16,10 +16,21 @@ impl BoundFunction {
 		let args: Args = std::iter::once(bound_owner).chain(args.into_iter()).collect();
 		bound_object.call_attr_lit(&Literal::CALL, args)
 	}
+
+	/// Gets the unbound object's documentation, or `null` if it doesn't have any.
+	#[instrument(name="BoundFunction::doc", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_doc(this: &Object, _: Args) -> Result<Object> {
+		Ok(this.get_attr_lit("__bound_object__")
+			.and_then(|bound_object| bound_object.get_attr_lit("doc"))
+			.unwrap_or_default())
+	}
 }
 
 impl_object_type!{
 for BoundFunction [(parents super::Function)]:
 // for BoundFunction [(parents super::Basic)]:
+	/// Call this function with the specified args, passing them on to the unbound object.
 	"()" => method Self::qs_call,
+	/// Gets the unbound object's documentation, or `null` if it doesn't have any.
+	"doc" => property Self::qs_doc,
 }
@@ -32,5 +32,6 @@ impl Class {
 
 impl_object_type!{
 for Class [(parents super::Basic) (no_convert)]:
+	/// Gets the name of this Class.
 	"name" => method Class::qs_name
 }
@@ -36,7 +36,7 @@ impl Comparable {
 		let rhs = args.try_arg(0)?;
 		let cmp = compare(this, rhs)?;
 
-		Ok(cmp.map_or(false, |c| c == Ordering::Less).into())
+		Ok((cmp == Some(Ordering::Less)).into())
 	}
 
 	/// Check to see if `this` is greater than the first argument in `args`.
@@ -47,7 +47,7 @@ impl Comparable {
 		let rhs = args.try_arg(0)?;
 		let cmp = compare(this, rhs)?;
 
-		Ok(cmp.map_or(false, |c| c == Ordering::Greater).into())
+		Ok((cmp == Some(Ordering::Greater)).into())
 	}
 
 	/// Check to see if `this` is less than or equal to the first argument in `args`.
@@ -58,7 +58,7 @@ impl Comparable {
 		let rhs = args.try_arg(0)?;
 		let cmp = compare(this, rhs)?;
 
-		Ok(cmp.map_or(false, |c| c != Ordering::Greater).into())
+		Ok(cmp.is_some_and(|c| c != Ordering::Greater).into())
 	}
 
 	/// Check to see if `this` is less than or equal to the first argument in `args`.
@@ -69,15 +69,19 @@ impl Comparable {
 		let rhs = args.try_arg(0)?;
 		let cmp = compare(this, rhs)?;
 
-		Ok(cmp.map_or(false, |c| c != Ordering::Less).into())
+		Ok(cmp.is_some_and(|c| c != Ordering::Less).into())
 	}
 }
 
 impl_object_type!{
 for Comparable [(parents super::Basic)]:
+	/// Check to see if `this` is less than the first argument in `args`.
 	"<" => method Self::qs_lth,
+	/// Check to see if `this` is greater than the first argument in `args`.
 	">" => method Self::qs_gth,
+	/// Check to see if `this` is less than or equal to the first argument in `args`.
 	"<=" => method Self::qs_leq,
+	/// Check to see if `this` is less than or equal to the first argument in `args`.
 	">=" => method Self::qs_geq,
 }
 
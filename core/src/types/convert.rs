@@ -1,4 +1,6 @@
-use crate::{Object, Literal, types::ObjectType, error::TypeError};
+use crate::{Object, Literal, types::ObjectType, error::{ArgumentError, TypeError}};
+use crate::types::{Boolean, List, Null, Number, Text};
+use std::convert::TryFrom;
 use std::any::{Any, type_name};
 use std::ops::Deref;
 use std::marker::PhantomData;
@@ -21,7 +23,8 @@ impl Object {
 		// We either have an `Original` reference or a `Converted` Object.
 		enum CalledReader<T, D> {
 			Original(D, PhantomData<T>),
-			Converted(Object, D)
+			// the object is kept so that `D`, which borrows from it, stays valid.
+			Converted(#[allow(dead_code)] Object, D)
 		}
 
 		impl<T, D> Deref for CalledReader<T, D>
@@ -32,8 +35,8 @@ impl Object {
 			type Target = T;
 			fn deref(&self) -> &Self::Target {
 				match self {
-					Self::Original(orig, _) => &orig,
-					Self::Converted(_, data) => &data
+					Self::Original(orig, _) => orig,
+					Self::Converted(_, data) => data
 				}
 			}
 		}
@@ -66,6 +69,92 @@ impl Object {
 	}
 }
 
+/// Rust types that can be extracted from objects, such as the parameters of functions made with
+/// [`#[quest::methods]`](crate::methods).
+///
+/// Builtin types are converted the same way Quest converts them (e.g. `@num` for numbers), so any
+/// object that defines the conversion can be used. Other [`ObjectType`]s are cloned out of objects
+/// of that type. To convert Rust values into objects, use `Object::from`.
+pub trait FromObject: Sized {
+	/// Converts `obj` into `Self`.
+	fn from_object(obj: &Object) -> crate::Result<Self>;
+
+	/// Converts the argument at `position` into `Self`, raising an [`ArgumentError`] if it's the
+	/// wrong type.
+	fn from_argument(obj: &Object, position: usize) -> crate::Result<Self> {
+		Self::from_object(obj).map_err(|err| match err {
+			crate::Error::TypeError(error) => ArgumentError::WrongType { position, error }.into(),
+			other => other
+		})
+	}
+}
+
+impl<T: ObjectType> FromObject for T {
+	fn from_object(obj: &Object) -> crate::Result<Self> {
+		Ok(obj.try_downcast::<T>()?.clone())
+	}
+}
+
+impl FromObject for Object {
+	#[inline]
+	fn from_object(obj: &Object) -> crate::Result<Self> {
+		Ok(obj.clone())
+	}
+}
+
+impl FromObject for bool {
+	fn from_object(obj: &Object) -> crate::Result<Self> {
+		Ok(obj.call_downcast::<Boolean>()?.into_inner())
+	}
+}
+
+impl FromObject for String {
+	fn from_object(obj: &Object) -> crate::Result<Self> {
+		Ok(obj.call_downcast::<Text>()?.to_string())
+	}
+}
+
+impl FromObject for f64 {
+	fn from_object(obj: &Object) -> crate::Result<Self> {
+		Ok(f64::from(*obj.call_downcast::<Number>()?))
+	}
+}
+
+macro_rules! impl_from_object_for_int {
+	($($int:ty)*) => {
+		$(
+			impl FromObject for $int {
+				fn from_object(obj: &Object) -> crate::Result<Self> {
+					Ok(<$int>::try_from(*obj.call_downcast::<Number>()?)?)
+				}
+			}
+		)*
+	};
+}
+
+impl_from_object_for_int!(i8 i16 i32 i64 isize u8 u16 u32 u64 usize);
+
+impl<T: FromObject> FromObject for Vec<T> {
+	fn from_object(obj: &Object) -> crate::Result<Self> {
+		obj.call_downcast::<List>()?
+			.as_ref()
+			.iter()
+			.map(T::from_object)
+			.collect()
+	}
+}
+
+/// `null` is converted to `None`, and everything else to `Some`.
+impl<T: FromObject> FromObject for Option<T> {
+	fn from_object(obj: &Object) -> crate::Result<Self> {
+		if obj.is_a::<Null>() {
+			Ok(None)
+		} else {
+			T::from_object(obj).map(Some)
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -19,6 +19,7 @@ impl Debug for BoundRustFn {
 
 impl_object_type!{
 for BoundRustFn [(parents super::Function)]:
+	/// Calls the function with the arguments.
 	"()" => method |this: &Object, args: Args| {
 		let this = this.try_downcast::<Self>()?;
 		(this.0)(args)
@@ -45,6 +46,18 @@ impl Function {
 }
 
 impl Function {
+	/// Returns a function that calls `this` with the first argument before the arguments it's given.
+	///
+	/// # Arguments
+	///
+	/// 1. (required) The argument to bind.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// sub = (a, b) -> { a - b };
+	///
+	/// assert((sub << 10)(3) == 7);
+	/// ```
 	#[instrument(name="Function::<<", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_lsh(this: &Object, args: Args) -> Result<Object> {
 		let this = this.clone();
@@ -53,6 +66,19 @@ impl Function {
 		Ok(Self::curry(this, rhs))
 	}
 
+	/// Returns a function that calls the first argument with `this` before the arguments it's given.
+	///
+	/// # Arguments
+	///
+	/// 1. (required) The function to call.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// call_with = (func, arg) -> { func(arg) };
+	/// double = n -> { n * 2 };
+	///
+	/// assert((double >> call_with)(3) == 6);
+	/// ```
 	#[instrument(name="Function::>>", level="trace", skip(this, args), fields(self=?this, args=?args))]
 	pub fn qs_rsh(this: &Object, args: Args) -> Result<Object> {
 		let this = this.clone();
@@ -61,6 +87,18 @@ impl Function {
 		Ok(Self::curry(rhs, this))
 	}
 
+	/// Calls `this` with the elements of the first argument as its arguments.
+	///
+	/// # Arguments
+	///
+	/// 1. (required, `@list`) The arguments to call `this` with.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// sub = (a, b) -> { a - b };
+	///
+	/// assert(sub.apply([5, 1]) == 4);
+	/// ```
 	#[instrument(name="Function::apply", level="trace", skip(this, args), fields(self=?this, args=?args))]
 	pub fn qs_apply(this: &Object, args: Args) -> Result<Object> {
 		let this = this.clone();
@@ -72,8 +110,11 @@ impl Function {
 
 impl_object_type!{
 for Function [(parents super::Basic)]:
+	/// Returns a function that calls `this` with the first argument before the arguments it's given.
 	"<<" => method Self::qs_lsh,
+	/// Returns a function that calls the first argument with `this` before the arguments it's given.
 	">>" => method Self::qs_rsh,
+	/// Calls `this` with the elements of the first argument as its arguments.
 	"apply" => method Self::qs_apply,
 	"__should_be_bound__" => const true
 }
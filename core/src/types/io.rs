@@ -39,5 +39,7 @@ for Io [(parents super::Iterable)]:
 	"Stdin" => const file::File::from_fd(0, true, false).expect("cant create stdin"),
 	"Stdout" => const file::File::from_fd(1, false, true).expect("cant create stdout"),
 	"Stderr" => const file::File::from_fd(2, false, true).expect("cant create stdout"),
+	/// Calls the last argument with each chunk `read` returns, using the first argument (or a
+	/// newline) as the delimiter, until it returns `null`.
 	"each" => method Self::qs_each,
 }
@@ -40,6 +40,7 @@ impl File {
 		self.file.take();
 	}
 
+	#[allow(unused_must_use, clippy::unused_io_amount)]
 	pub fn write(&mut self, what: &[u8]) -> io::Result<()> {
 		if let Some(ref mut file) = self.file {
 			file.get_mut().write(what);
@@ -100,7 +101,7 @@ impl File {
 				break;
 			}
 
-			if std::str::from_utf8(&file_buf).is_err() {
+			if std::str::from_utf8(file_buf).is_err() {
 				return Err(io::Error::new(io::ErrorKind::InvalidData, "stream did not contain valid UTF-8"));
 			}
 
@@ -141,7 +142,7 @@ impl File {
 				break;
 			}
 
-			let contents = std::str::from_utf8(&file_buf).map_err(|_| 
+			let contents = std::str::from_utf8(file_buf).map_err(|_| 
 					io::Error::new(io::ErrorKind::InvalidData, "stream did not contain valid UTF-8"))?;
 
 			if let Some(end) = func(contents)? {
@@ -284,6 +285,7 @@ impl TryFrom<&str> for OpenOptions {
 	/// - `a` Sets [`append`](OpenOptions::append).
 	/// - `t` Sets [`truncate`](OpenOptions::truncate).
 	/// - `n` Sets [`create_new`](OpenOptions::create_new).
+	///
 	/// Any other character will yield an [`InvalidOptionChar`].
 	///
 	/// If an empty string is supplied, [the default](OpenOptions::default) is returned.
@@ -357,7 +359,7 @@ impl File {
 				return Err(crate::error::TypeError::Messaged("wrong type given to read".into()).into());
 			};
 
-		Ok(if read.as_ref().map_or(false, |s| s.is_empty()) {
+		Ok(if read.as_ref().is_some_and(|s| s.is_empty()) {
 			Object::default()
 		} else {
 			read.map(Object::from).unwrap_or_default()
@@ -385,14 +387,22 @@ impl File {
 
 impl_object_type!{
 for File [(parents super::Io)]:
+	/// Opens the file named by (or with the descriptor of) the first argument, with the second
+	/// argument's options (`"r"` by default).
 	"()" => method Self::qs_call,
+	/// Not yet implemented.
 	"@text" => method |_, _| panic!(),
+	/// Reads everything, an amount of bytes, or up to a [`Text`], [`Regex`] or function's match,
+	/// returning `null` if nothing's left.
 	"read" => method Self::qs_read,
+	/// Reads the rest of the file and splits it on newlines.
 	"lines" => method |this, args| {
 		Self::qs_read(this, args)?
 			.call_attr_lit("split", &[&Text::from("\n").into()])
 	},
+	/// Writes the first argument's `@text`, returning the file.
 	"write" => method Self::qs_write,
+	/// Closes the file, returning it.
 	"close" => method Self::qs_close,
 	// "close" => method Self::qs_close
 }
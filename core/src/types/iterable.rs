@@ -1,5 +1,7 @@
 mod iter;
+mod generator;
 pub use iter::{Iter, StopIteration};
+pub use generator::Generator;
 
 use tracing::instrument;
 use crate::error::ArgumentError;
@@ -21,8 +23,8 @@ impl Iterable {
 			.map(Object::from)
 	}
 
-	/// Finishes the iterable and converts it to a [`List`].
-	#[instrument(name="Iterable::@list", level="trace", skip(this), fields(self=?this))]
+	/// Runs the iterable until it's finished, discarding its elements.
+	#[instrument(name="Iterable::run", level="trace", skip(this), fields(self=?this))]
 	pub fn qs_run(this: &Object, _: Args) -> crate::Result<Object> {
 		this.call_downcast::<Iter>()?
 			.clone()
@@ -140,7 +142,7 @@ impl Iterable {
 		}
 	}
 
-	/// Only take elements while the give block (ie first arg) evaluates to true.
+	/// Only take elements while the given block (ie first arg) evaluates to true.
 	#[instrument(name="Iterable::take_while", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_take_while(this: &Object, args: Args) -> crate::Result<Object> {
 		let this = this.call_downcast::<Iter>()?.clone();
@@ -153,7 +155,7 @@ impl Iterable {
 		}).into())
 	}
 
-	/// Only take elements while the give block (ie first arg) evaluates to false.
+	/// Only take elements while the given block (ie first arg) evaluates to false.
 	#[instrument(name="Iterable::take_until", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_take_until(this: &Object, args: Args) -> crate::Result<Object> {
 		let this = this.call_downcast::<Iter>()?.clone();
@@ -179,7 +181,7 @@ impl Iterable {
 		}
 	}
 
-	/// Ignore elements while the give block (ie first arg) evaluates to true.
+	/// Ignore elements while the given block (ie first arg) evaluates to true.
 	#[instrument(name="Iterable::drop_while", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_drop_while(this: &Object, args: Args) -> crate::Result<Object> {
 		let this = this.call_downcast::<Iter>()?.clone();
@@ -218,7 +220,7 @@ impl Iterable {
 		}
 	}
 
-	/// Chunk elements while the given block (ie first argument) evaluates to truncate.
+	/// Chunk elements while the given block (ie first argument) evaluates to true.
 	#[instrument(name="Iterable::chunk_while", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_chunk_while(this: &Object, args: Args) -> crate::Result<Object> {
 		let this = this.call_downcast::<Iter>()?.clone();
@@ -311,7 +313,7 @@ impl Iterable {
 	/// TODO: unique, this is a stopgap
 	#[instrument(name="Iterable::unique", level="trace", skip(this), fields(self=?this, ?args))]
 	pub fn qs_unique(this: &Object, args: Args) -> crate::Result<Object> {
-		if args.len() != 0 { panic!("todo: nonzero args."); }
+		if !args.is_empty() { panic!("todo: nonzero args."); }
 
 		let this = this.call_downcast::<Iter>()?.clone();
 
@@ -452,7 +454,7 @@ impl Iterable {
 						.call_downcast::<Boolean>()
 						.map(|x| x.into_inner()))
 		} else {
-			this.all(move |ele| ele.call_downcast::<Boolean>().map(|x| x.into_inner()))
+			this.any(move |ele| ele.call_downcast::<Boolean>().map(|x| x.into_inner()))
 		}.map(Object::from)
 	}
 
@@ -467,7 +469,7 @@ impl Iterable {
 						.call_downcast::<Boolean>()
 						.map(|x| x.into_inner()))
 		} else {
-			this.all(move |ele| ele.call_downcast::<Boolean>().map(|x| x.into_inner()))
+			this.one(move |ele| ele.call_downcast::<Boolean>().map(|x| x.into_inner()))
 		}.map(Object::from)
 	}
 
@@ -477,13 +479,13 @@ impl Iterable {
 
 		if let Some(block) = args.arg(0) {
 			let block = block.clone();
-			this.one(move |ele|
+			this.any(move |ele|
 					block.call_attr_lit(&Literal::CALL, &[&ele])?
 						.call_downcast::<Boolean>()
 						.map(|x| x.into_inner()))
 		} else {
-			this.all(move |ele| ele.call_downcast::<Boolean>().map(|x| x.into_inner()))
-		}.map(Object::from)
+			this.any(move |ele| ele.call_downcast::<Boolean>().map(|x| x.into_inner()))
+		}.map(|any| Object::from(!any))
 	}
 
 	#[instrument(name="Iterable::include?", level="trace", skip(this, args), fields(self=?this, ?args))]
@@ -500,44 +502,89 @@ impl Iterable {
 }
 
 impl_object_type! { for Iterable [(parents super::Class)]:
+	/// Finishes the iterable and converts it to a [`List`].
 	"@list"       => method Self::qs_at_list,
+	/// Runs the iterable until it's finished, discarding its elements.
 	"run"         => method Self::qs_run,
+	/// Enumerates the iterable by returning `[ele, idx]`
 	"enumerate"   => method Self::qs_enumerate,
+	/// Lazily calls the first argument with each element, producing its results.
 	"map"         => method Self::qs_map,
+	/// Calls the first argument with each element, running the iterable until it's finished.
 	"each"        => method Self::qs_each,
+	/// Lazily calls the first argument with each element, producing the elements.
 	"eachl"       => method Self::qs_eachl,
+	/// Only keeps elements for which the first argument returns something truthy.
 	"select"      => method Self::qs_select,
+	/// Only keeps elements for which the first argument returns something falsey.
 	"reject"      => method Self::qs_reject,
+	/// Combines the elements with a block, starting from the first of two arguments if given, and
+	/// from the first element otherwise.
 	"reduce"      => method Self::qs_reduce,
+	/// Zip as many arguments as given in `args` into one array.
 	"zip"         => method Self::qs_zip,
+	/// Repeats the iterable forever, or as many times as the first argument if it's given.
 	"cycle"       => method Self::qs_cycle,
+	/// Only return a maximum of `n` elements, where `n` is the first argument.
 	"take"        => method Self::qs_take,
+	/// Only take elements while the given block (ie first arg) evaluates to true.
 	"take_while"  => method Self::qs_take_while,
+	/// Only take elements while the given block (ie first arg) evaluates to false.
 	"take_until"  => method Self::qs_take_until,
+	/// Ignore the first `n` elements (ie the first argument)
 	"drop"        => method Self::qs_drop,
+	/// Ignore elements while the given block (ie first arg) evaluates to true.
 	"drop_while"  => method Self::qs_drop_while,
+	/// Ignore elements while the given block (ie first arg) evaluates to false
 	"drop_until"  => method Self::qs_drop_until,
+	/// Group every `n` (ie the first argument) into an array.
 	"chunk"       => method Self::qs_chunk,
+	/// Chunk elements while the given block (ie first argument) evaluates to true.
 	"chunk_while" => method Self::qs_chunk_while,
+	/// Chunk elements while the given block (ie first argument) evaluates to false.
 	"chunk_until" => method Self::qs_chunk_until,
+	/// Adds the elements together with `+`, returning `0` if there aren't any.
 	"sum"         => method Self::qs_sum,
+	/// Multiplies the elements together with `*`, returning `1` if there aren't any.
 	"prod"        => method Self::qs_prod,
+	/// Removes elements that are equal to an earlier one.
 	"unique"      => method Self::qs_unique,
+	/// Not yet implemented.
 	"chain"       => method Self::qs_chain,
+	/// Not yet implemented.
 	"find"        => method Self::qs_find_first,
+	/// Not yet implemented.
 	"sort"        => method Self::qs_sort,
+	/// Not yet implemented.
 	"flatten"     => method Self::qs_flatten,
+	/// Not yet implemented.
 	"group_by"    => method Self::qs_group_by,
 
+	/// Not yet implemented.
 	"min"       => method Self::qs_min,
+	/// Not yet implemented.
 	"max"       => method Self::qs_max,
+	/// Gets the amount of elements in this iterator.
 	"len"       => method Self::qs_len,
+	/// The same as [`qs_len`], except it accepts an optional block which will be used to filter beforehand.
 	"count"     => method Self::qs_count,
+	/// Gets the first element, or the first `n` elements if a value's given.
+	/// This is the same as `take`, except it has a default value of one.
 	"first"     => method Self::qs_first,
+	/// Not yet implemented.
 	"last"      => method Self::qs_last,
+	/// Checks to see if the first argument returns something truthy for every element, or if every
+	/// element is truthy if it's not given.
 	"all?"      => method Self::qs_all_q,
+	/// Checks to see if the first argument returns something truthy for any element, or if any
+	/// element is truthy if it's not given.
 	"any?"      => method Self::qs_any_q,
+	/// Checks to see if the first argument returns something truthy for exactly one element, or if
+	/// exactly one element is truthy if it's not given.
 	"one?"      => method Self::qs_one_q,
+	/// Checks to see if the first argument returns something falsey for every element, or if every
+	/// element is falsey if it's not given.
 	"none?"     => method Self::qs_none_q,
+	/// Checks to see if any element is equal to the first argument.
 	"include?"  => method Self::qs_include_q,
 }
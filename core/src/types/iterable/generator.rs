@@ -0,0 +1,194 @@
+use crate::{Object, Args, Binding, Literal};
+use crate::sandbox::{self, Captured};
+use parking_lot::Mutex;
+use std::cell::RefCell;
+use std::fmt::{self, Debug, Formatter};
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use tracing::instrument;
+
+/// What the generator's thread sends back each time it's resumed: either a yielded value, `None`
+/// when the block finishes, or an error that occurred within the block.
+type Yielded = crate::Result<Option<Object>>;
+
+thread_local! {
+	/// The channels for the generator that's running on the current thread, if any.
+	static YIELDER: RefCell<Option<(SyncSender<Yielded>, Receiver<()>)>> = const { RefCell::new(None) };
+}
+
+/// A running generator's channels.
+struct Running {
+	resume: SyncSender<()>,
+	values: Receiver<Yielded>,
+	done: bool
+}
+
+/// An iterator that lazily runs a Quest block, suspending each time it calls `yield`.
+///
+/// Each generator runs its block on a separate thread, handing control back and forth so that
+/// only one side is ever running at a time. The thread is only spawned upon the first call to
+/// `next`, and will be unwound if the generator is dropped before the block finishes.
+///
+/// The block runs in the sandboxes the generator was created in, as well as the ones it's first
+/// resumed in, sharing their budgets.
+///
+/// Cloning a generator creates a new one that starts from the beginning of the block.
+pub struct Generator {
+	block: Object,
+	binding: Binding,
+	sandboxes: Captured,
+	running: Option<Mutex<Running>>
+}
+
+impl Debug for Generator {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		f.debug_struct("Generator")
+			.field("block", &self.block)
+			.field("started", &self.running.is_some())
+			.finish()
+	}
+}
+
+impl Clone for Generator {
+	fn clone(&self) -> Self {
+		Self {
+			block: self.block.clone(),
+			binding: self.binding.clone(),
+			sandboxes: self.sandboxes.clone(),
+			running: None
+		}
+	}
+}
+
+fn generator_dropped() -> crate::Error {
+	crate::Error::Messaged("generator was dropped".to_string())
+}
+
+impl Generator {
+	/// Creates a new generator that will call `block` within `binding`.
+	pub fn new(block: Object, binding: Binding) -> Self {
+		Self { block, binding, sandboxes: sandbox::capture(), running: None }
+	}
+
+	fn start(&self) -> Running {
+		let (resume, resume_rx) = mpsc::sync_channel::<()>(0);
+		let (values_tx, values) = mpsc::sync_channel::<Yielded>(0);
+		let block = self.block.clone();
+		let binding = self.binding.clone();
+		let sandboxes = self.sandboxes.clone().merge(sandbox::capture());
+
+		std::thread::spawn(move || {
+			// wait until we're first resumed before running anything.
+			if resume_rx.recv().is_err() {
+				return;
+			}
+
+			// sandboxes are per-thread, so without this the block could reach all of `Kernel`.
+			sandbox::enter(sandboxes);
+			Binding::set_binding(binding.into());
+			YIELDER.with(|yielder| *yielder.borrow_mut() = Some((values_tx.clone(), resume_rx)));
+
+			let result = block.call_attr_lit(&Literal::CALL, &[]).map(|_| None);
+
+			// if the receiver's gone, there's no one to tell we're done.
+			let _ = values_tx.send(result);
+		});
+
+		Running { resume, values, done: false }
+	}
+
+	/// Yields `value` to the consumer of the generator that's running on this thread, and waits
+	/// until it's resumed.
+	pub fn yield_value(value: Object) -> crate::Result<()> {
+		YIELDER.with(|yielder| {
+			let yielder = yielder.borrow();
+			let (values, resume) = yielder.as_ref()
+				.ok_or_else(|| crate::Error::Messaged("`yield` called outside of a generator".to_string()))?;
+
+			values.send(Ok(Some(value))).map_err(|_| generator_dropped())?;
+			resume.recv().map_err(|_| generator_dropped())
+		})
+	}
+
+	/// Yields the first argument (or `null` if none is given) to the current generator.
+	#[instrument(name="Kernel::yield", level="trace")]
+	pub fn qs_yield(args: Args) -> crate::Result<Object> {
+		Self::yield_value(args.arg(0).cloned().unwrap_or_default())?;
+
+		Ok(Object::default())
+	}
+}
+
+impl Iterator for Generator {
+	type Item = crate::Result<Object>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.running.is_none() {
+			self.running = Some(Mutex::new(self.start()));
+		}
+
+		let mut running = self.running.as_ref().expect("we just set it").lock();
+
+		if running.done {
+			return None;
+		}
+
+		let result =
+			if running.resume.send(()).is_err() {
+				None
+			} else {
+				// if the thread panicked, there's nothing more to yield.
+				running.values.recv().ok().and_then(Result::transpose)
+			};
+
+		running.done = !matches!(result, Some(Ok(_)));
+		result
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::types::{Number, RustClosure};
+	use std::convert::TryFrom;
+
+	fn counter(upto: i64) -> Generator {
+		crate::init();
+
+		let block = RustClosure::new(move |_| {
+			for i in 0..upto {
+				Generator::yield_value(i.into())?;
+			}
+
+			Ok(Object::default())
+		});
+
+		Generator::new(block.into(), Binding::instance())
+	}
+
+	fn collect(gen: impl Iterator<Item=crate::Result<Object>>) -> Vec<i64> {
+		gen.map(|obj| i64::try_from(*obj.unwrap().downcast::<Number>().unwrap()).unwrap()).collect()
+	}
+
+	#[test]
+	fn yields_lazily() {
+		let mut gen = counter(3);
+		assert_eq!(collect(gen.by_ref().take(2)), vec![0, 1]);
+		assert_eq!(collect(gen.by_ref()), vec![2]);
+		assert!(gen.next().is_none());
+	}
+
+	#[test]
+	fn clones_restart() {
+		let mut gen = counter(2);
+		gen.next();
+
+		assert_eq!(collect(gen.clone()), vec![0, 1]);
+		assert_eq!(collect(gen), vec![1]);
+	}
+
+	#[test]
+	fn yield_outside_generator() {
+		crate::init();
+		assert!(Generator::yield_value(Object::default()).is_err());
+	}
+}
@@ -81,6 +81,14 @@ impl Iter {
 		Self::new(iter.into_iter().map(Ok))
 	}
 
+	/// Creates an iterator that lazily runs `block` within `binding`, producing each value that
+	/// it `yield`s.
+	///
+	/// See [`Generator`](super::Generator) for more details.
+	pub fn generate(block: Object, binding: crate::Binding) -> Self {
+		Self::new(super::Generator::new(block, binding))
+	}
+
 	/// Creates an iterator from an object. The object will be repeatedly called until it returns
 	/// [`StopIteration`].
 	pub fn from_callable(obj: Object) -> Self {
@@ -153,7 +161,7 @@ impl Iter {
 		F: FnMut(&Object) -> Result<bool> + Send + Sync + Clone + 'static
 	{
 		Self::from_fn(move || {
-			while let Some(value) = self.next() {
+			for value in self.by_ref() {
 				match value.and_then(|value| Ok((func(&value)?, value))) {
 					Ok((true, value)) => return Some(Ok(value)),
 					Ok((false, _)) => { /* continue onwards */ },
@@ -241,7 +249,7 @@ impl Iter {
 				return self.next();
 			}
 
-			while let Some(result) = self.next() {
+			for result in self.by_ref() {
 				match result.and_then(|object| Ok((func(&object)?, object))) {
 					Ok((true, _)) => { /* go to the next one */},
 					Ok((false, object)) => { started = true; return Some(Ok(object)) },
@@ -396,6 +404,14 @@ impl Iter {
 		Ok(Clone::clone(&*this.try_downcast::<Self>()?).into())
 	}
 
+	/// Creates a new [`Iter`] from the block, which lazily produces each value passed to `yield`.
+	#[tracing::instrument(name="Iter::generate", level="trace", skip(_this, args), fields(?args))]
+	pub fn qs_generate(_this: &Object, args: Args) -> Result<Object> {
+		let block = args.try_arg(0)?.clone();
+
+		Ok(Self::generate(block, crate::Binding::instance()).into())
+	}
+
 	#[tracing::instrument(name="Iter::run", level="trace", skip(this), fields(self = ?this))]
 	pub fn qs_run(this: &Object, _: Args) -> Result<Object> {
 		this.try_downcast_mut::<Self>()
@@ -407,9 +423,14 @@ impl Iter {
 }
 
 impl_object_type! { for Iter [(parents super::Iterable) (convert "@iter")]:
+	/// Gets the next element, or [`StopIteration`] if there aren't any left.
 	"()" => method Self::qs_call,
+	/// Gets a copy of the iterator, which starts from where it currently is.
 	"@iter" => method Self::qs_at_iter,
+	/// Runs the iterator until it's finished, discarding its elements.
 	"run" => method Self::qs_run,
+	/// Creates a new [`Iter`] from the block, which lazily produces each value passed to `yield`.
+	"generate" => method Self::qs_generate,
 }
 
 
@@ -8,7 +8,7 @@ pub struct Kernel;
 fn display(args: &[&Object], newline: bool) -> crate::Result<()> {
 	print!("{}",
 		args.iter()
-			.map(|x| object_to_string(*x))
+			.map(|x| object_to_string(x))
 			.collect::<crate::Result<Vec<_>>>()?
 			.join("")
 	);
@@ -119,6 +119,7 @@ impl Kernel {
 		let mut result = None;
 
 		while is_object_truthy(&cond.call_attr_lit(&Literal::CALL, &[])?)? {
+			crate::sandbox::step()?;
 			result = Some(body.call_attr_lit(&Literal::CALL, &[])?);
 		}
 
@@ -131,6 +132,7 @@ impl Kernel {
 		let mut result = None;
 
 		while !is_object_truthy(&cond.call_attr_lit(&Literal::CALL, &[])?)? {
+			crate::sandbox::step()?;
 			result = Some(body.call_attr_lit(&Literal::CALL, &[])?);
 		}
 
@@ -140,6 +142,7 @@ impl Kernel {
 	#[instrument(name="Kernel::loop", level="trace")]
 	pub fn qs_loop(body: &Object, _: Args) -> crate::Result<Object> {
 		loop {
+			crate::sandbox::step()?;
 			body.call_attr_lit(&Literal::CALL, &[])?;
 		}
 	}
@@ -159,7 +162,7 @@ impl Kernel {
 			display(&[msg], true)?;
 		}
 
-		std::process::exit(code as i32)
+		std::process::exit(code)
 	}
 
 	#[instrument(name="Kernel::system", level="trace")]
@@ -198,6 +201,7 @@ impl Kernel {
 		Ok((rand::random::<FloatType>() * (end - start) + start).into())
 	}
 
+	#[allow(unexpected_cfgs)]
 	#[instrument(name="Kernel::prompt", level="trace")]
 	pub fn qs_prompt(args: Args) -> crate::Result<Object> {
 		use std::io;
@@ -228,7 +232,7 @@ impl Kernel {
 
 	#[instrument(name="Kernel::return", level="trace")]
 	pub fn qs_return(args: Args) -> crate::Result<Object> {
-		let obj = args.arg(0).map(Object::clone).unwrap_or_default();
+		let obj = args.arg(0).cloned().unwrap_or_default();
 		let to = args.arg(1).cloned()
 			.map(Binding::from)
 			.unwrap_or_else(Binding::instance);
@@ -249,12 +253,13 @@ impl Kernel {
 
 	#[instrument(name="Kernel::sleep", level="trace")]
 	pub fn qs_sleep(args: Args) -> crate::Result<Object> {
+		// sleeping is cut short if it'd take us over a time budget.
 		if let Some(arg) = args.arg(0) {
 			let dur: f64 = (*arg.call_downcast::<Number>()?).into();
-			std::thread::sleep(std::time::Duration::from_secs_f64(dur));
+			crate::sandbox::sleep(std::time::Duration::from_secs_f64(dur))?;
 		} else {
 			// technically doesn't sleep forever lol.
-			std::thread::sleep(std::time::Duration::from_secs_f64(f64::MAX));
+			crate::sandbox::sleep(std::time::Duration::MAX)?;
 		}
 
 		Ok(Object::default())
@@ -288,29 +293,62 @@ for Kernel [(parents super::Basic)]: // todo: do i want its parent to be pristin
 	"Scope" => const super::Scope::mapping().clone(),
 	"Text" => const super::Text::mapping().clone(),
 	"Regex" => const super::Regex::mapping().clone(),
+	"Match" => const super::regex::Match::mapping().clone(),
 	"Comparable" => const super::Comparable::mapping().clone(),
 	"Iterable" => const super::Iterable::mapping().clone(),
 	"Iter" => const super::Iter::mapping().clone(),
 	"StopIteration" => const super::StopIteration::mapping().clone(),
+	"WeakRef" => const super::WeakRef::mapping().clone(),
+	"Reflect" => const super::Reflect::mapping().clone(),
 
+	/// Calls the first argument if `this` is truthy, and the second (if given) otherwise.
+	///
+	/// If `this` is falsey and there's no second argument, `this` is returned.
 	"if" => method Self::qs_if,
+	/// Like `if`, but returns the arguments instead of calling them.
 	"ifl" => method Self::qs_ifl,
+	/// Calls the first argument if `this` is falsey, and the second (if given) otherwise.
 	"unless" => method Self::qs_unless,
+	/// Like `unless`, but returns the arguments instead of calling them.
 	"unlessl" => method Self::qs_unlessl,
+	/// Prints each argument's `@text`, followed by a newline.
 	"disp" => function Self::qs_disp,
+	/// Produces the first argument from the generator that's running, pausing it until the next
+	/// value is asked for.
+	"yield" => function super::iterable::Generator::qs_yield,
+	/// Prints each argument's `@text`, without a newline.
 	"dispn" => function Self::qs_dispn,
+	/// Exits with the first argument as the status code (`0` by default), printing the second
+	/// argument first if it's given.
 	"quit" => function Self::qs_quit,
+	/// Runs `this` as a command, with the arguments as its arguments, and returns its output.
 	"system" => method Self::qs_system,
+	/// Returns a random number: from `0` to `1` with no arguments, from `0` to the first argument
+	/// with one, and between the two arguments with two.
 	"rand" => function Self::qs_rand,
+	/// Prints the first argument (if given), and then returns a line read from stdin, or `null` at
+	/// the end of the input.
 	"prompt" => function Self::qs_prompt,
+	/// Calls the first argument for as long as calling `this` returns something truthy, returning
+	/// the last result.
 	"while" => method Self::qs_while,
+	/// Calls the first argument for as long as calling `this` returns something falsey, returning
+	/// the last result.
 	"until" => method Self::qs_until,
+	/// Calls `this` forever, until it returns or there's an error.
 	"loop" => method Self::qs_loop,
+	/// Pauses for the first argument's number of seconds, or forever if it's not given. This is cut short,
+	/// with an error, if it'd take longer than the time budget.
 	"sleep" => function Self::qs_sleep,
+	/// Not yet implemented; use `Io.File` instead.
 	"open" => method Self::qs_open,
+	/// Returns the first argument (or `null`) from the stackframe given as the second argument,
+	/// which is the current one by default.
 	"return" => function Self::qs_return,
+	/// Raises an error, with the first argument as its message, if `this` is falsey.
 	"assert" => method Self::qs_assert,
 
+	/// Calls `this` in a new thread, returning a `Thread` whose `join` waits for its result.
 	"spawn" => method |block, _| {
 		use std::thread::{self, JoinHandle};
 		use std::sync::Arc;
@@ -1,9 +1,10 @@
-use crate::{Object, Args, Literal, error::KeyError};
+use crate::{Object, Args, Literal, error::{KeyError, FrozenError}};
 use crate::utils::{correct_index, IndexError};
 use crate::types::{Convertible, Text, Boolean, Number};
 use std::convert::{TryFrom, TryInto};
 use std::iter::FromIterator;
 use std::fmt::{self, Debug, Formatter};
+use std::ops::DerefMut;
 use tracing::instrument;
 
 /// A List in Quest.
@@ -411,6 +412,15 @@ impl List {
 
 /// Quest methods
 impl List {
+	/// Mutably downcasts `this`, returning a [`FrozenError`] instead if it's been frozen.
+	fn try_modify(this: &Object) -> crate::Result<impl DerefMut<Target=Self> + '_> {
+		if this.is_frozen() {
+			return Err(FrozenError::Modify { obj: this.clone() }.into());
+		}
+
+		this.try_downcast_mut::<Self>()
+	}
+
 	/// Simply returns the list.
 	///
 	/// # Quest Examples
@@ -430,6 +440,12 @@ impl List {
 		Ok(this.clone())
 	}
 
+	/// Gets an [`Iter`](crate::types::Iter) over the elements of the list.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert([1, 2, 3].@iter().map({ _1 * 2 }) == [2, 4, 6]);
+	/// ```
 	#[instrument(name="List::@iter", level="trace", skip(this), fields(self=?this))]
 	pub fn qs_at_iter(this: &Object, _: Args) -> crate::Result<Object> {
 		let this = this.try_downcast::<Self>()?.clone();
@@ -484,6 +500,13 @@ impl List {
 		Ok(Boolean::from(&*this).into())
 	}
 
+	/// Checks to see if the list has no elements.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert([].empty?());
+	/// assert(![1].empty?());
+	/// ```
 	#[instrument(name="List::empty?", level="trace", skip(this), fields(self=?this))]
 	pub fn qs_empty_q(this: &Object, _: Args) -> crate::Result<Object> {
 		let this = this.try_downcast::<Self>()?;
@@ -491,14 +514,31 @@ impl List {
 		Ok(Boolean::from(this.as_ref().is_empty()).into())
 	}
 
+	/// Creates a function that binds its arguments to the names in the list, then runs a block.
+	///
+	/// Missing arguments are bound to [`Null`](crate::types::Null). The block's docs are copied
+	/// onto the function.
+	///
+	/// # Arguments
+	///
+	/// 1. (required, `@block`) The body of the function.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// add = [:a, :b] -> { a + b };
+	/// assert(add(1, 2) == 3);
+	/// ```
 	#[instrument(name="List::->", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_arrow(this: &Object, args: Args) -> crate::Result<Object> {
 		let this_list = this.try_downcast::<Self>()?.clone();
 		let this_list_dup = this_list.clone();
-		let block = args.try_arg(0)?.clone();
-		let block_dup = block.clone();
+		let block_dup = args.try_arg(0)?.clone();
+
+		// the block is given as a capture, rather than moved into the closures, so that
+		// `gc::leaked_objects` can see the references to it.
+		let closure = Object::from(crate::types::RustClosure::capturing(vec![block_dup.clone()], move |captures, args| {
+			let block = &captures[0];
 
-		let closure = Object::from(crate::types::RustClosure::new(move |args| {
 			crate::Binding::new_stackframe(Some(block.clone()), args.clone(), |binding| {
 				for (i, arg) in this_list.iter().enumerate() {
 					arg.call_attr_lit("=", 
@@ -510,9 +550,9 @@ impl List {
 			})
 		}));
 
-		let block = block_dup.clone();
+		closure.set_attr_lit("call_noscope", crate::types::RustClosure::capturing(vec![block_dup.clone()], move |captures, args| {
+			let block = &captures[0];
 
-		closure.set_attr_lit("call_noscope", crate::types::RustClosure::new(move |args| {
 			for (i, arg) in this_list_dup.iter().enumerate() {
 				crate::Binding::instance()
 					.set_attr(arg.clone(), args.arg(i).cloned().unwrap_or_default())?;
@@ -522,6 +562,12 @@ impl List {
 		}).into())?;
 
 		closure.set_attr_lit("args", this.clone())?;
+
+		// the closure is what's usually assigned, so it's given the block's docs.
+		if let Ok(doc) = block_dup.get_attr_lit("doc") {
+			closure.set_attr_lit("doc", doc)?;
+		}
+
 		closure.set_attr_lit("block", block_dup)?;
 		Ok(closure)
 	}
@@ -563,7 +609,7 @@ impl List {
 	/// ```
 	#[instrument(name="List::clear", level="trace", skip(this), fields(self=?this))]
 	pub fn qs_clear(this: &Object, _: Args) -> crate::Result<Object> {
-		this.try_downcast_mut::<Self>()?.clear();
+		Self::try_modify(this)?.clear();
 
 		Ok(this.clone())
 	}
@@ -585,9 +631,9 @@ impl List {
 
 	/// Gets an element or range from the list
 	///
-	/// If the element is out of range, [`Null`](crate::types::Null) is returned.
-	/// When using the range form, out-of-bounds `start` and `stop` values are converted to the
-	/// min/max of the list (respectively).
+	/// If the index is out of range, [`Null`](crate::types::Null) is returned. When using the
+	/// range form, the end is inclusive and is capped to the last element, but an out-of-range
+	/// start (or an end before the start) still returns [`Null`](crate::types::Null).
 	///
 	/// Quest supports negative indexing, which allows you to index from the end of the list.
 	/// 
@@ -669,7 +715,7 @@ impl List {
 
 		if args.len() == 2 {
 			let ele = args.arg(1).unwrap().clone();
-			let mut this = this.try_downcast_mut::<Self>()?;
+			let mut this = Self::try_modify(this)?;
 
 			if this.set(pos, ele.clone()).is_some() {
 				Err(KeyError::OutOfBounds { idx: pos, len: this.len() }.into())
@@ -679,7 +725,7 @@ impl List {
 		} else {
 			let end: isize = args.try_arg(1)?.call_downcast::<Number>().map(|n| *n)?.try_into()?;
 			let ele = args.try_arg(2)?.call_downcast::<Self>()?.clone();
-			let mut this = this.try_downcast_mut::<Self>()?;
+			let mut this = Self::try_modify(this)?;
 
 			if this.set_rng(pos, end, ele.0).is_some() {
 				Err(KeyError::OutOfBounds { idx: pos, len: this.len() }.into())
@@ -691,11 +737,27 @@ impl List {
 		}
 	}
 
+	/// Removes the element at an index, returning it.
+	///
+	/// If the index is out of range, [`Null`](crate::types::Null) is returned.
+	///
+	/// # Arguments
+	///
+	/// 1. (required, `@num`) The index to remove.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// list = [1, 2, 3];
+	///
+	/// assert(list.delete(-1) == 3);
+	/// assert(list.delete(5) == null);
+	/// assert(list == [1, 2]);
+	/// ```
 	#[instrument(name="List::delete", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_delete(this: &Object, args: Args) -> crate::Result<Object> {
 		let idx: isize = args.try_arg(0)?.call_downcast::<Number>().map(|n| *n)?.try_into()?;
 
-		let mut this = this.try_downcast_mut::<Self>()?;
+		let mut this = Self::try_modify(this)?;
 
 		Ok(this.delete(idx).unwrap_or_default())
 	}
@@ -720,6 +782,16 @@ impl List {
 		this.join(delim.as_ref().map(|delim| delim.as_ref())).map(Object::from)
 	}
 
+	/// Repeats the list a number of times.
+	///
+	/// # Arguments
+	///
+	/// 1. (required, `@num`) The number of times to repeat the list.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert([1, 2] * 3 == [1, 2, 1, 2, 1, 2]);
+	/// ```
 	#[instrument(name="List::*", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_mul(this: &Object, args: Args) -> crate::Result<Object> {
 		let this = this.try_downcast::<Self>()?;
@@ -728,11 +800,24 @@ impl List {
 		Ok((&*this * amnt).into())
 	}
 
+	/// Repeats the list a number of times, in place, returning the list.
+	///
+	/// # Arguments
+	///
+	/// 1. (required, `@num`) The number of times to repeat the list.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// list = [1, 2];
+	///
+	/// list *= 2;
+	/// assert(list == [1, 2, 1, 2]);
+	/// ```
 	#[instrument(name="List::*=", level="trace", skip(this, args), fields(self=?this, args=?args))]
 	pub fn qs_mul_assign(this: &Object, args: Args) -> crate::Result<Object> {
 		let amnt = usize::try_from(*args.try_arg(0)?.call_downcast::<Number>()?)?;
 
-		*this.try_downcast_mut::<Self>()? *= amnt;
+		*Self::try_modify(this)? *= amnt;
 
 		Ok(this.clone())
 	}
@@ -783,7 +868,7 @@ impl List {
 	#[instrument(name="List::push", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_push(this: &Object, args: Args) -> crate::Result<Object> {
 		let rhs = args.try_arg(0)?.clone();
-		this.try_downcast_mut::<Self>()?.push(rhs);
+		Self::try_modify(this)?.push(rhs);
 
 		Ok(this.clone())
 	}
@@ -801,7 +886,7 @@ impl List {
 	/// ```
 	#[instrument(name="List::pop", level="trace", skip(this), fields(self=?this))]
 	pub fn qs_pop(this: &Object, _: Args) -> crate::Result<Object> {
-		Ok(this.try_downcast_mut::<Self>()?
+		Ok(Self::try_modify(this)?
 			.pop()
 			.unwrap_or_default())
 	}
@@ -824,7 +909,7 @@ impl List {
 	pub fn qs_unshift(this: &Object, args: Args) -> crate::Result<Object> {
 		let rhs = args.try_arg(0)?.clone();
 		
-		this.try_downcast_mut::<Self>()?.unshift(rhs);
+		Self::try_modify(this)?.unshift(rhs);
 		Ok(this.clone())
 	}
 
@@ -841,7 +926,7 @@ impl List {
 	/// ```
 	#[instrument(name="List::shift", level="trace", skip(this), fields(self=?this))]
 	pub fn qs_shift(this: &Object, _: Args) -> crate::Result<Object> {
-		Ok(this.try_downcast_mut::<Self>()?
+		Ok(Self::try_modify(this)?
 			.shift()
 			.unwrap_or_default())
 	}
@@ -878,7 +963,7 @@ impl List {
 	#[instrument(name="List::+=", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_add_assign(this: &Object, args: Args) -> crate::Result<Object> {
 		let rhs = args.try_arg(0)?;
-		let mut this_mut = this.try_downcast_mut::<Self>()?;
+		let mut this_mut = Self::try_modify(this)?;
 
 		if this.is_identical(rhs) {
 			let dup = this_mut.clone();
@@ -929,7 +1014,7 @@ impl List {
 			return Self::qs_clear(rhs, Args::default());
 		}
 
-		this.try_downcast_mut::<Self>()?.try_sub_assign(&*rhs.call_downcast::<Self>()?)?;
+		Self::try_modify(this)?.try_sub_assign(&*rhs.call_downcast::<Self>()?)?;
 
 		Ok(this.clone())
 	}
@@ -978,7 +1063,7 @@ impl List {
 			return Ok(this.clone());
 		}
 
-		this.try_downcast_mut::<Self>()?.try_bitand_assign(&*rhs.call_downcast::<Self>()?)?;
+		Self::try_modify(this)?.try_bitand_assign(&*rhs.call_downcast::<Self>()?)?;
 
 		Ok(this.clone())
 	}
@@ -1026,7 +1111,7 @@ impl List {
 			return Ok(this.clone());
 		}
 
-		this.try_downcast_mut::<Self>()?.try_bitor_assign(&*rhs.call_downcast::<Self>()?)?;
+		Self::try_modify(this)?.try_bitor_assign(&*rhs.call_downcast::<Self>()?)?;
 
 		Ok(this.clone())
 	}
@@ -1051,7 +1136,10 @@ impl List {
 		this.try_bitxor(&rhs).map(Object::from)
 	}
 
-	/// Deletes all elements in the current list not common to both lists.
+	/// Keeps only the elements that are in exactly one of the lists, in place.
+	///
+	/// Elements of the current list that are also in the other one are removed, and the elements
+	/// only in the other list are appended.
 	///
 	/// # Arguments
 	///
@@ -1075,7 +1163,7 @@ impl List {
 			return Self::qs_clear(this, Args::default());
 		}
 
-		this.try_downcast_mut::<Self>()?.try_bitxor_assign(&*rhs.call_downcast::<Self>()?)?;
+		Self::try_modify(this)?.try_bitxor_assign(&*rhs.call_downcast::<Self>()?)?;
 
 		Ok(this.clone())
 	}
@@ -1086,53 +1174,88 @@ impl Convertible for List {
 }
 impl_object_type!{
 for List [(init_parent super::Basic super::Iterable) (parents super::Basic)]:
+	/// Attempts to get an internal representation of the list.
 	"inspect" => method Self::qs_inspect,
+	/// Attempts to convert this into a [`Text`].
 	"@text" => method Self::qs_at_text,
+	/// Converts this into a [`Boolean`].
 	"@bool" => method Self::qs_at_bool,
+	/// Simply returns the list.
 	"@list" => method Self::qs_at_list,
+	/// Gets an [`Iter`](crate::types::Iter) over the elements of the list.
 	"@iter" => method Self::qs_at_iter,
 
+	/// Checks to see if the list has no elements.
 	"empty?" => method Self::qs_empty_q,
 
+	/// Creates a function that binds its arguments to the names in the list, then runs a block.
 	"->"   => method Self::qs_arrow,
 
+	/// Remove all elements from the list and returns the list.
 	"clear" => method Self::qs_clear,
+	/// Finds an object within the list, returning its index.
 	"index" => method Self::qs_index,
+	/// Get the length of the list
 	"len"   => method Self::qs_len,
 
+	/// Gets an element or range from the list
 	"get"  => method Self::qs_get,
+	/// Sets an element or range of the list to an element or list.
 	"set"  => method Self::qs_set,
+	/// Gets the element at an index, or the elements from a start to an inclusive end, returning
+	/// [`Null`](crate::types::Null) if out of range.
 	"[]"  => method Self::qs_get,
+	/// Sets the element at an index, or replaces the elements from a start to an inclusive end.
 	"[]="  => method |this, args| {
 		let mut arg = args.try_arg(0)?.downcast_mut::<Self>().expect("`[]=` called without List.");
 		arg.push(args.try_arg(1)?.clone());
 
 		Self::qs_set(this, arg.as_ref().iter().collect())
 	},
+	/// Removes the element at an index, returning it or [`Null`](crate::types::Null).
 	"delete" => method Self::qs_delete,
 	// "first" => method Self::qs_first,
 	// "second" => method Self::qs_second,
 	// "last" => method Self::qs_last,
 	// "penult" => method Self::qs_penult,
 
+	/// Combine all elements into a [`Text`], optionally separated by a deliminator.
 	"join" => method Self::qs_join,
+	/// Repeats the list a number of times.
 	"*"    => method Self::qs_mul,
+	/// Repeats the list a number of times, in place, returning the list.
 	"*="   => method Self::qs_mul_assign,
+	/// Add an element to the back of the list, returning the list.
 	"<<"      => method Self::qs_push,
+	/// Add an element to the back of the list, returning the list.
 	"push"    => method Self::qs_push,
+	/// Remove an element from the end of the list, returning [`Null`](crate::types::Null) if empty.
 	"pop"     => method Self::qs_pop,
+	/// Add an element at the front of the list, returning the list.
 	"unshift" => method Self::qs_unshift,
+	/// Remove an element from the front of the list, returning [`Null`](crate::types::Null) if empty.
 	"shift"   => method Self::qs_shift,
 
+	/// Compares two [`List`]s
 	"==" => method Self::qs_eql,
+	/// Adds two lists together.
 	"+"  => method Self::qs_add,
+	/// Adds a list to the end of this one, in place, returning the first list.
 	"+=" => method Self::qs_add_assign,
+	/// Returns a new list of elements in the first list but not the second.
 	"-"  => method Self::qs_sub,
+	/// Delete all elements in the first list that are also in the second.
 	"-=" => method Self::qs_sub_assign,
+	/// Get the intersection of two lists, i.e. the common elements
 	"&"  => method Self::qs_bitand,
+	/// Deletes all elements in the current list not common to both lists.
 	"&=" => method Self::qs_bitand_assign,
+	/// Get the union of two lists, i.e. the combination of all elements
 	"|"  => method Self::qs_bitor,
+	/// Adds all unique elements in the second list to the original one.
 	"|=" => method Self::qs_bitor_assign,
+	/// Get the list of elements in only one list.
 	"^"  => method Self::qs_bitxor,
+	/// Keeps only the elements in exactly one of the lists, in place, returning the first list.
 	"^=" => method Self::qs_bitxor_assign,
 }
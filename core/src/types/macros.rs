@@ -119,7 +119,8 @@ macro_rules! args {
 #[macro_export]
 /// Create a new object type.
 ///
-/// This is soft-deprecated.
+/// This is soft-deprecated; use [`#[quest::object]`](crate::object) and
+/// [`#[quest::methods]`](crate::methods) instead.
 macro_rules! impl_object_type {
 	(@CONVERTIBLE $obj:ty;) => { /* TODO */ };
 
@@ -147,7 +148,7 @@ macro_rules! impl_object_type {
 		vec![$(<$init_parent as $crate::types::ObjectType>::mapping().clone()),+]
 	};
 	(@SET_PARENT $class:ident (parents $parent:path) $($_rest:tt)*) => {
-		impl_object_type!(@SET_PARENT $class (init_parent $parent));
+		impl_object_type!(@SET_PARENT $class (init_parent $parent))
 	};
 
 	(@SET_PARENT $class:ident $_b:tt $($rest:tt)*) => {
@@ -155,26 +156,58 @@ macro_rules! impl_object_type {
 	};
 
 	(@SET_ATTRS $class:ident $obj:ty;) => {};
+	(@SET_ATTRS $class:ident $obj:ty; #[doc = $doc:literal] $($rest:tt)*) => {
+		impl_object_type!(@DOCS $class $obj; [$doc] $($rest)*)
+	};
 	(@SET_ATTRS $class:ident $obj:ty; $attr:expr => const $val:expr $(, $($args:tt)*)?) => {{
 		$class.set_attr_lit($attr, Object::from($val))?;
 
 		impl_object_type!(@SET_ATTRS $class $obj; $($($args)*)?);
 	}};
 
-	(@SET_ATTRS $class:ident $obj:ty; $attr:expr => function $val:expr $(, $($args:tt)*)?) => {{
+	(@SET_ATTRS $class:ident $obj:ty; $attr:expr => function $val:expr $(, $($args:tt)*)?) => {
+		impl_object_type!(@DOCS $class $obj; [] $attr => function $val $(, $($args)*)?)
+	};
+
+	(@SET_ATTRS $class:ident $obj:ty; $attr:expr => method $val:expr $(, $($args:tt)*)?) => {
+		impl_object_type!(@DOCS $class $obj; [] $attr => method $val $(, $($args)*)?)
+	};
+
+	(@SET_ATTRS $class:ident $obj:ty; $attr:expr => property $val:expr $(, $($args:tt)*)?) => {
+		impl_object_type!(@DOCS $class $obj; [] $attr => property $val $(, $($args)*)?)
+	};
+
+	// `///` comments before an attribute are collected, and become its `RustFn`'s docs.
+	(@DOCS $class:ident $obj:ty; [$($doc:literal)*] #[doc = $next:literal] $($rest:tt)*) => {
+		impl_object_type!(@DOCS $class $obj; [$($doc)* $next] $($rest)*)
+	};
+
+	(@DOCS $class:ident $obj:ty; [$($doc:literal)*] $attr:expr => const $val:expr $(, $($args:tt)*)?) => {
+		impl_object_type!(@SET_ATTRS $class $obj; $attr => const $val $(, $($args)*)?)
+	};
+
+	(@DOCS $class:ident $obj:ty; [$($doc:literal)*] $attr:expr => function $val:expr $(, $($args:tt)*)?) => {{
 		$class.set_value_lit($attr, $crate::types::RustFn::function(
-			concat!(stringify!($obj), "::", $attr), $val)
+			concat!(stringify!($obj), "::", $attr), $val).with_doc(concat!($($doc, "\n",)*))
 		)?;
 		impl_object_type!(@SET_ATTRS $class $obj; $($($args)*)?);
 	}};
 
-	(@SET_ATTRS $class:ident $obj:ty; $attr:expr => method $val:expr $(, $($args:tt)*)?) => {{
+	(@DOCS $class:ident $obj:ty; [$($doc:literal)*] $attr:expr => method $val:expr $(, $($args:tt)*)?) => {{
 		$class.set_value_lit($attr, $crate::types::RustFn::method(
-			concat!(stringify!($obj), "::", $attr), $val)
+			concat!(stringify!($obj), "::", $attr), $val).with_doc(concat!($($doc, "\n",)*))
 		)?;
 		impl_object_type!(@SET_ATTRS $class $obj; $($($args)*)?);
 	}};
 
+	// properties are read-only, and their getter is called like a method.
+	(@DOCS $class:ident $obj:ty; [$($doc:literal)*] $attr:expr => property $val:expr $(, $($args:tt)*)?) => {{
+		$class.set_value_lit($attr, $crate::types::Property::builtin($crate::types::RustFn::method(
+			concat!(stringify!($obj), "::", $attr), $val).with_doc(concat!($($doc, "\n",)*)).into()
+		))?;
+		impl_object_type!(@SET_ATTRS $class $obj; $($($args)*)?);
+	}};
+
 	(@SET_ATTRS $_class:ident $_obj:ty; $($tt:tt)*) => {
 		compile_error!(concat!("Bad attrs given:", stringify!($($tt)*)));
 	};
@@ -192,6 +225,7 @@ macro_rules! impl_object_type {
 				const INIT: bool = true;
 				static INITIALIZE: AtomicBool = AtomicBool::new(UNINIT);
 
+				#[allow(deprecated)]
 				if INITIALIZE.compare_and_swap(UNINIT, INIT, Ordering::SeqCst) == INIT {
 					return Ok(());
 				}
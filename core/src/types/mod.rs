@@ -43,6 +43,9 @@ pub mod number;
 pub mod regex;
 pub mod io;
 mod list;
+mod property;
+mod weak_ref;
+mod reflect;
 
 mod tcp;
 
@@ -58,7 +61,7 @@ pub use tcp::Tcp;
 pub use class::Class;
 
 #[doc(inline)]
-pub use convert::Convertible;
+pub use convert::{Convertible, FromObject};
 
 #[doc(inline)]
 pub use comparable::Comparable;
@@ -102,6 +105,15 @@ pub use text::Text;
 #[doc(inline)]
 pub use list::List;
 
+#[doc(inline)]
+pub use property::Property;
+
+#[doc(inline)]
+pub use weak_ref::WeakRef;
+
+#[doc(inline)]
+pub use reflect::Reflect;
+
 #[doc(inline)]
 pub use self::regex::Regex;
 
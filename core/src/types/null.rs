@@ -313,12 +313,19 @@ for Null {
 	}
 }
 [(parents super::Basic) (no_convert)]:
+	/// Converts this to a [`Text`].
 	"@text" => method Self::qs_at_text,
+	/// Inspects `this`.
 	"inspect" => method Self::qs_inspect,
+	/// Converts this to a [`Boolean`].
 	"@bool" => method Self::qs_at_bool,
+	/// Converts this to a [`List`].
 	"@list" => method Self::qs_at_list,
+	/// Converts this to a [`Number`].
 	"@num" => method Self::qs_at_num,
+	/// Calls [`Null`], returning [`Null`] regardless of the provided arguments.
 	"()" => method Self::qs_call,
+	/// Checks to see if the right-hand-side is a [`Null`].
 	"==" => method Self::qs_eql,
 }
 
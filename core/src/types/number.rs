@@ -148,7 +148,7 @@ impl Number {
 
 	/// Try to parse a [`Number`] from the input with the given radix.
 	pub fn from_str_radix(inp: &str, radix: u32) -> Result<Self, FromStrError> {
-		if radix < 2 || radix > 36 {
+		if !(2..=36).contains(&radix) {
 			return Err(FromStrError::BadRadix(radix))
 		}
 
@@ -159,7 +159,7 @@ impl Number {
 
 	/// Converts a [`Number`] into a string with the given radix.
 	pub fn to_string_radix(&self, radix: u32) -> Result<String, ToStringRadixError> {
-		if radix < 2 || radix > 36 {
+		if !(2..=36).contains(&radix) {
 			return Err(ToStringRadixError::InvalidRadix(radix))
 		}
 
@@ -243,7 +243,7 @@ impl TryFrom<&str> for Number {
 
 		// if we have underscores, delete them and try again. We don't want to have to convert
 		// everything to a string in case a `_` doesn't exist, so we check for `_`'s existance first.
-		if inp.find('_') != None {
+		if inp.find('_').is_some() {
 			let mut inp = inp.to_string();
 
 			while let Some(idx) = inp.rfind('_') {
@@ -767,7 +767,7 @@ impl Number {
 		Ok(this.clone())
 	}
 
-	/// Subtract the the first argument from `this`.
+	/// Subtract the first argument from `this`.
 	///
 	/// # Arguments
 	/// 1. (required, `@num`) The subtrahend.
@@ -779,7 +779,7 @@ impl Number {
 		Ok((*this - *subtrahend).into())
 	}
 
-	/// Subtract the the first argument from `this`, in place.
+	/// Subtract the first argument from `this`, in place.
 	///
 	/// # Arguments
 	/// 1. (required, `@num`) The subtrahend.
@@ -1140,7 +1140,7 @@ impl Number {
 		Ok((this == Self::ONE).into())
 	}
 
-	/// Checks to see if `this` is one.
+	/// Checks to see if `this` is positive.
 	#[instrument(name="Number::positive?", level="trace", skip(this), fields(self=?this))]
 	pub fn qs_positive_q(this: &Object, _: Args) -> crate::Result<Object> {
 		let this = *this.try_downcast::<Self>()?;
@@ -1148,7 +1148,7 @@ impl Number {
 		Ok((this > Self::ZERO).into())
 	}
 
-	/// Checks to see if `this` is one.
+	/// Checks to see if `this` is negative.
 	#[instrument(name="Number::negative?", level="trace", skip(this), fields(self=?this))]
 	pub fn qs_negative_q(this: &Object, _: Args) -> crate::Result<Object> {
 		let this = *this.try_downcast::<Self>()?;
@@ -1161,7 +1161,7 @@ impl Number {
 	pub fn qs_even_q(this: &Object, _: Args) -> crate::Result<Object> {
 		let this = *this.try_downcast::<Self>()?;
 
-		Ok((IntegerType::try_from(this).map_or(false, |x| x % 2 == 0)).into())
+		Ok((IntegerType::try_from(this).is_ok_and(|x| x % 2 == 0)).into())
 	}
 
 	/// Checks to see if `this` is odd.
@@ -1169,7 +1169,7 @@ impl Number {
 	pub fn qs_odd_q(this: &Object, _: Args) -> crate::Result<Object> {
 		let this = *this.try_downcast::<Self>()?;
 
-		Ok((IntegerType::try_from(this).map_or(false, |x| x % 2 != 0)).into())
+		Ok((IntegerType::try_from(this).is_ok_and(|x| x % 2 != 0)).into())
 	}
 
 	/// Returns an array starting at `this` and ending at the first argument, with an optional step.
@@ -1247,47 +1247,106 @@ impl_object_type!{
 	"NAN" => const Self::NAN,
 	"INF" => const Self::INF,
 
+	/// Converts `this` to a [`Text`], with an optional base parameter.
 	"@text" => method Self::qs_at_text,
+	/// Inspects `this`.
 	"inspect" => method Self::qs_inspect,
+	/// Convert `this` to a [`Number`].
 	"@num" => method Self::qs_at_num,
+	/// Converts `this` to a [`Boolean`].
 	"@bool" => method Self::qs_at_bool,
+	/// Hash a number.
 	"hash" => method Self::qs_hash,
 
-	"+"  => method Self::qs_add,    "+="  => method Self::qs_add_assign,
-	"-"  => method Self::qs_sub,    "-="  => method Self::qs_sub_assign,
-	"*"  => method Self::qs_mul,    "*="  => method Self::qs_mul_assign,
-	"/"  => method Self::qs_div,    "/="  => method Self::qs_div_assign,
-	"%"  => method Self::qs_mod,    "%="  => method Self::qs_mod_assign,
-	"**" => method Self::qs_pow,    "**=" => method Self::qs_pow_assign,
-	"&"  => method Self::qs_bitand, "&="  => method Self::qs_bitand_assign,
-	"|"  => method Self::qs_bitor,  "|="  => method Self::qs_bitor_assign,
-	"^"  => method Self::qs_bitxor, "^="  => method Self::qs_bitxor_assign,
-	"<<" => method Self::qs_shl,    "<<=" => method Self::qs_shl_assign,
-	">>" => method Self::qs_shr,    ">>=" => method Self::qs_shr_assign,
+	/// Add `this` and the first argument.
+	"+"   => method Self::qs_add,
+	/// Add `this` and the first argument, in place.
+	"+="  => method Self::qs_add_assign,
+	/// Subtract the first argument from `this`.
+	"-"   => method Self::qs_sub,
+	/// Subtract the first argument from `this`, in place.
+	"-="  => method Self::qs_sub_assign,
+	/// Multiply `this` and the first argument.
+	"*"   => method Self::qs_mul,
+	/// Multiply `this` and the first argument, in place.
+	"*="  => method Self::qs_mul_assign,
+	/// Divide `this` by the first argument.
+	"/"   => method Self::qs_div,
+	/// Divide `this` by the first argument, in place.
+	"/="  => method Self::qs_div_assign,
+	/// Modulo `this` by the first argument.
+	"%"   => method Self::qs_mod,
+	/// Modulo `this` by the first argument, in place.
+	"%="  => method Self::qs_mod_assign,
+	/// Raises `this` to the power of the first argument.
+	"**"  => method Self::qs_pow,
+	/// Raises `this` to the power of the first argument, in place.
+	"**=" => method Self::qs_pow_assign,
+	/// Bitwise AND of `this` and the first argument.
+	"&"   => method Self::qs_bitand,
+	/// Bitwise AND of `this` and the first argument, in place.
+	"&="  => method Self::qs_bitand_assign,
+	/// Bitwise OR of `this` and the first argument.
+	"|"   => method Self::qs_bitor,
+	/// Bitwise OR of `this` and the first argument, in place.
+	"|="  => method Self::qs_bitor_assign,
+	/// Bitwise XOR of `this` and the first argument.
+	"^"   => method Self::qs_bitxor,
+	/// Bitwise XOR of `this` and the first argument, in place.
+	"^="  => method Self::qs_bitxor_assign,
+	/// Shift `this` left by the first argument.
+	"<<"  => method Self::qs_shl,
+	/// Shift `this` left by the first argument, in place.
+	"<<=" => method Self::qs_shl_assign,
+	/// Shift `this` right by the first argument.
+	">>"  => method Self::qs_shr,
+	/// Shift `this` right by the first argument, in place.
+	">>=" => method Self::qs_shr_assign,
 
+	/// Invert `this`'s sign.
 	"-@"  => method Self::qs_neg,
+	/// Get the absolute value of `this`.
 	"+@"  => method Self::qs_pos,
+	/// Bitwise NOT of `this`.
 	"~"   => method Self::qs_bitnot,
+	/// Get the absolute value of `this`.
 	"abs" => method Self::qs_abs,
+	/// Compares `this` to the first argument.
 	"<=>" => method Self::qs_cmp,
+	/// Calling a number is simply an alias for multiplication.
 	"()"  => method Self::qs_call,
+	/// See if a `this` is equal to the first argument.
 	"=="  => method Self::qs_eql,
 
+	/// Returns `this`, rounded towards the nearest integer. (`##.5` rounds away from zero.)
 	"round" => method Self::qs_round,
+	/// Returns `this`, rounded up.
 	"ceil"  => method Self::qs_ceil,
+	/// Returns `this`, rounded down.
 	"floor" => method Self::qs_floor,
+	/// Gets the square root of `this`
 	"sqrt"  => method Self::qs_sqrt,
+	/// Returns an array starting at `this` and ending at the first argument, with an optional step.
 	"upto"  => method Self::qs_upto,
+	/// Returns an array starting at `this` and ending at the first argument, with an optional step.
 	"downto"  => method Self::qs_downto,
 
+	/// Checks to see if `this` is between the first and second arguments, inclusive.
 	"between?" => method Self::qs_between_q,
+	/// Checks to see if `this` is even.
 	"even?" => method Self::qs_even_q,
+	/// Checks to see if `this` is odd.
 	"odd?" => method Self::qs_odd_q,
+	/// Checks to see if `this` is one.
 	"one?" => method Self::qs_one_q,
-	"zero?" => method Self::qs_one_q,
+	/// Checks to see if `this` is zero.
+	"zero?" => method Self::qs_zero_q,
+	/// Checks to see if `this` is positive.
 	"positive?" => method Self::qs_positive_q,
+	/// Checks to see if `this` is negative.
 	"negative?" => method Self::qs_negative_q,
 
+	/// Gets the character whose code is `this`, rounded down and wrapped around at `256`.
 	"chr" => method |this, _| {
 		Ok((u8::try_from(this.try_downcast::<Self>()?.floor()).unwrap() as char)
 			.to_string().into())
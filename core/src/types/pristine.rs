@@ -1,5 +1,7 @@
 use crate::{Object, Args};
-use crate::types::Boolean;
+use crate::obj::Value;
+use crate::types::{Boolean, Property};
+use std::collections::HashSet;
 use tracing::instrument;
 
 /// The base type that all other Quest types inherit from.
@@ -12,8 +14,8 @@ use tracing::instrument;
 ///
 /// # Additional Attributes
 /// 
-/// In addition to those detailed in this class, there are two additional keys that are always
-/// defined: `__id__` and `__parents__`.
+/// In addition to those detailed in this class, there are three additional keys that are always
+/// defined: `__id__`, `__parents__`, and `__mro__`.
 ///
 /// ## `__id__`
 ///
@@ -27,9 +29,9 @@ use tracing::instrument;
 /// attribute, the following places are looked, in order: (Note that this only applies to 
 /// fetching attributes; setting and deleting attributes only work on the base object.)
 ///
-/// 1. Builtin attributes (i.e. `__id__`, `__parents__`). Additionally, there are two "special"
-/// attributes that aren't considered to be a part of any particular object: `__this__` and
-/// `__stack__`
+/// 1. Builtin attributes (i.e. `__id__`, `__parents__`, `__mro__`). Additionally, there are two
+///    "special" attributes that aren't considered to be a part of any particular object:
+///    `__this__` and `__stack__`
 ///    - `__stack__` returns a list of all the stackframes so far, with `0` being the current one.
 ///    - `__this__` is the same as `__stack__.get(0)`. Currently, it's only defined for scopes, but
 ///      this may be changed in the future.
@@ -37,13 +39,26 @@ use tracing::instrument;
 /// 3. If `__attr_missing__` is defined, it is called; if a non-[`Null`] response is given, then
 ///    that value is returned. (In the future there may be a way to mark `null` as a valid response,
 ///    possibly with something like the `undefined` of javascript?)
-/// 4. Each parent, in order, is asked if they (Or any of their parents) have the attribute.
-///    the first parental chain that has one is returned.
+/// 4. Each ancestor in the object's `__mro__` is checked, in order, for the attribute. The first
+///    one that directly defines it is returned.
 /// 5. If nothing succeeds, (either an error or [`Null`] is returned. I haven't figured out which
 ///    is the best yet.)
 ///
+/// If the attribute that's found is a property (see [`property`](Pristine::qs_property)), its getter
+/// is called, and the result is used instead.
+///
 /// [`Null`]: crate::types::Null;
 ///
+/// ## `__mro__`
+///
+/// The method resolution order: a `List` of every ancestor of the object (not including the object
+/// itself), in the order they're searched for attributes. It's the [C3 linearization](
+/// https://en.wikipedia.org/wiki/C3_linearization) of `__parents__`, which means that an ancestor is
+/// always checked after every object that inherits from it, and parents are checked in the order
+/// they're listed. If no such order exists, the ancestors are instead ordered depth-first.
+///
+/// It's recomputed whenever `__parents__` (or any ancestor's `__parents__`) changes.
+///
 /// ## `:#`
 /// 
 /// Stack frame literal references have bene added to Quest: `:#` is identical to
@@ -138,6 +153,20 @@ impl Pristine {
 		this.set_attr(attr.clone(), val.clone()).map(|_| val.clone())
 	}
 
+	/// Checks to see if the object or one of its parents has an attribute.
+	///
+	/// # Arguments
+	///
+	/// 1. (required) The attribute to look for.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// foo = { bar = 3; :0 }();
+	///
+	/// assert(foo.__has_attr__('bar'));
+	/// assert(foo.__has_attr__('inspect'));
+	/// assert(!foo.__has_attr__('baz'));
+	/// ```
 	#[instrument(name="Pristine::__has_attr__", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs___has_attr__(this: &Object, args: Args) -> crate::Result<Object> {
 		let attr = args.try_arg(0)?;
@@ -145,6 +174,22 @@ impl Pristine {
 		this.has_attr(attr).map(Object::from)
 	}
 
+	/// Deletes an attribute from the object, returning its value.
+	///
+	/// Only attributes defined directly on the object can be deleted; if it doesn't have the
+	/// attribute, a [`KeyError`](crate::error::KeyError) is raised.
+	///
+	/// # Arguments
+	///
+	/// 1. (required) The attribute to delete.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// foo = { bar = 3; :0 }();
+	///
+	/// assert(foo.__del_attr__('bar') == 3);
+	/// assert(!foo.__has_attr__('bar'));
+	/// ```
 	#[instrument(name="Pristine::__del_attr__", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs___del_attr__(this: &Object, args: Args) -> crate::Result<Object> {
 		let attr = args.try_arg(0)?;
@@ -152,6 +197,21 @@ impl Pristine {
 		this.del_attr(attr)
 	}
 
+	/// Retrieves an attribute from the object or one of its parents, binding functions to it.
+	///
+	/// This is what `foo.bar` does: unlike [`__get_attr__`](#qs___get_attr__), if the attribute is a
+	/// function, a [`BoundFunction`](crate::types::BoundFunction) is returned, which passes along
+	/// the object whenever it's called.
+	///
+	/// # Arguments
+	///
+	/// 1. (required) The attribute to look up.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// len = "foo".len;
+	/// assert(len() == 3);
+	/// ```
 	#[instrument(name="Pristine::.", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_dot_get_attr(this: &Object, args: Args) -> crate::Result<Object> {
 		let attr = args.try_arg(0)?;
@@ -159,6 +219,20 @@ impl Pristine {
 		this.dot_get_attr(attr)
 	}
 
+	/// Retrieves an attribute from the object or one of its parents, returning
+	/// [`Null`](crate::types::Null) if it doesn't exist.
+	///
+	/// # Arguments
+	///
+	/// 1. (required) The attribute to look up.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// foo = { bar = 3; :0 }();
+	///
+	/// assert(foo.?bar == 3);
+	/// assert(foo.?baz == null);
+	/// ```
 	#[instrument(name="Pristine::.?", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_dot_get_attr_q(this: &Object, args: Args) -> crate::Result<Object> {
 		let attr = args.try_arg(0)?;
@@ -170,6 +244,19 @@ impl Pristine {
 		}
 	}
 
+	/// Gets a [`List`](crate::types::List) of the attributes defined on the object.
+	///
+	/// # Arguments
+	///
+	/// 1. (optional, `@bool`) Whether to also include the object's parents; defaults to `false`.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// foo = { bar = 3; :0 }();
+	///
+	/// assert(foo.__keys__().include?('bar'));
+	/// assert(!foo.__keys__().include?('inspect'));
+	/// ```
 	#[instrument(name="Pristine::__keys__", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs___keys__(this: &Object, args: Args) -> crate::Result<Object> {
 		let include_parents =
@@ -179,13 +266,158 @@ impl Pristine {
 				.map(|x| x.into_inner())
 				.unwrap_or(false);
 
-		Ok(this.mapping_keys(include_parents)?
-			.into_iter()
-			.map(Object::from)
-			.collect::<Vec<_>>()
-			.into())
+		Ok(this.mapping_keys(include_parents)?.into())
+	}
+
+	/// Defines a computed attribute, returning the [`Property`] that was created.
+	///
+	/// Whenever the attribute is fetched from the object (or anything that inherits from it), the
+	/// getter is called with that object, and its return value is used instead. Likewise, assigning
+	/// to the attribute calls the setter with the object and the new value. If no setter is given,
+	/// the property is read-only and assigning to it is an error.
+	///
+	/// To remove the property, use `__del_attr__`.
+	///
+	/// # Arguments
+	///
+	/// 1. (required) The name of the attribute.
+	/// 2. (required) The getter.
+	/// 3. (optional) The setter.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// Temperature = {
+	///     '()' = (class, celsius) -> { __parents__ = [class]; :0 };
+	///     :0
+	/// }();
+	///
+	/// Temperature.property('fahrenheit',
+	///     temp -> { temp.celsius * 9 / 5 + 32 },
+	///     (temp, degrees) -> { temp.celsius = (degrees - 32) * 5 / 9 });
+	///
+	/// temp = Temperature(100);
+	/// assert(temp.fahrenheit == 212);
+	///
+	/// temp.fahrenheit = 32;
+	/// assert(temp.celsius == 0);
+	/// ```
+	#[instrument(name="Pristine::property", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_property(this: &Object, args: Args) -> crate::Result<Object> {
+		let attr = args.try_arg(0)?;
+		let getter = args.try_arg(1)?;
+		let setter = args.arg(2);
+
+		let property = Property::new(getter.clone(), setter.cloned());
+		this.set_property(attr.clone(), property.clone())?;
+
+		Ok(property.into())
 	}
 
+	/// Adds a finalizer to the object, returning the object.
+	///
+	/// After the object's been dropped, the finalizer is called with what its `__id__` was. Note
+	/// that if the finalizer refers to the object (such as a block defined in the same scope as
+	/// it), the object will never be dropped.
+	///
+	/// # Arguments
+	///
+	/// 1. (required) The finalizer.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// dropped = [];
+	/// finalizer = id -> { dropped.push(id) };
+	///
+	/// id = { { :0 }().on_drop(finalizer).__id__ }();
+	/// assert(dropped == [id]);
+	/// ```
+	#[instrument(name="Pristine::on_drop", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_on_drop(this: &Object, args: Args) -> crate::Result<Object> {
+		this.add_finalizer(args.try_arg(0)?.clone());
+
+		Ok(this.clone())
+	}
+
+	/// Freezes the object, returning it.
+	///
+	/// Once an object's frozen, assigning or deleting any of its attributes, or changing its
+	/// `__parents__`, raises a frozen error. Frozen objects can't be unfrozen, although the copies
+	/// made by `clone` aren't frozen. Note that this only freezes the object itself: both the values
+	/// of its attributes and its actual data (e.g. the elements of a `List`) can still be changed.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// Config = { debug = false; :0 }().freeze();
+	///
+	/// assert(Config.frozen?());
+	/// assert(Config.debug == false);
+	/// ```
+	#[instrument(name="Pristine::freeze", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_freeze(this: &Object, _: Args) -> crate::Result<Object> {
+		this.freeze()?;
+
+		Ok(this.clone())
+	}
+
+	/// Checks to see if the object is frozen.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// foo = { :0 }();
+	/// assert(!foo.frozen?());
+	///
+	/// foo.freeze();
+	/// assert(foo.frozen?());
+	/// ```
+	#[instrument(name="Pristine::frozen?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_frozen_q(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.is_frozen().into())
+	}
+
+	/// Freezes the object and, recursively, the values of each of its attributes, returning it.
+	///
+	/// This is meant for module objects, where every function and class defined within it should be
+	/// frozen too. Only values the object owns are frozen: those that nothing else refers to, other
+	/// than the object and values it owns. So anything that's shared (such as an imported module, an
+	/// instance's class, or the builtin classes) is skipped, as is everything only reachable through
+	/// it.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// Shapes = {
+	///     Square = { sides = 4; :0 }();
+	///     :0
+	/// }().deep_freeze();
+	///
+	/// assert(Shapes.frozen?());
+	/// assert(Shapes.Square.frozen?());
+	///
+	/// Point = { '()' = (class, x) -> { __parents__ = [class]; :0 }; :0 }();
+	/// Point(1).deep_freeze();
+	/// assert(!Point.frozen?());
+	/// ```
+	#[instrument(name="Pristine::deep_freeze", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_deep_freeze(this: &Object, _: Args) -> crate::Result<Object> {
+		deep_freeze(this)?;
+
+		Ok(this.clone())
+	}
+
+	/// Runs a block with the object as its scope, returning the block's result.
+	///
+	/// Any attributes the block assigns are set on the object.
+	///
+	/// # Arguments
+	///
+	/// 1. (required) The block to run.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// foo = { :0 }();
+	///
+	/// foo.instance_exec({ bar = 3 });
+	/// assert(foo.bar == 3);
+	/// ```
 	#[instrument(name="Pristine::instance_exec", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_instance_exec(this: &Object, args: Args) -> crate::Result<Object> {
 		let to_exec = args.try_arg(0)?;
@@ -200,22 +432,76 @@ impl Pristine {
 	}
 }
 
+/// Freezes `obj` and every object it owns, recursively; see [`Pristine::qs_deep_freeze`].
+fn deep_freeze(obj: &Object) -> crate::Result<()> {
+	// every object reachable through attributes, which are the only ones that can be owned.
+	let mut candidates = vec![obj.clone()];
+	let mut seen = HashSet::new();
+	seen.insert(obj.id());
+
+	let mut idx = 0;
+
+	while let Some(current) = candidates.get(idx).cloned() {
+		for key in current.mapping_keys(false)? {
+			if let Some(Value::Object(value)) = current.get_own_definition(&key)? {
+				if seen.insert(value.id()) {
+					candidates.push(value);
+				}
+			}
+		}
+
+		idx += 1;
+	}
+
+	candidates.remove(0);
+	obj.freeze()?;
+
+	for owned in crate::obj::gc::owned_by(obj, candidates) {
+		owned.freeze()?;
+	}
+
+	Ok(())
+}
+
 impl_object_type!{
 for Pristine [(init_parent) (parents Pristine)]:
+	/// Gets an internal representation of this type as a [`Text`]
 	"inspect" => method Self::qs_inspect,
+	/// Gets a [`List`](crate::types::List) of the attributes defined on the object.
 	"__keys__" => method Self::qs___keys__,
+	/// Calls a given attribtue for this object
 	"__call_attr__" => method Self::qs___call_attr__,
+	/// Retrieves an attribute from the object or one of its parents.
 	"__get_attr__" => method Self::qs___get_attr__,
+	/// Set an attribute on the object
 	"__set_attr__" => method Self::qs___set_attr__,
+	/// Checks to see if the object or one of its parents has an attribute.
 	"__has_attr__" => method Self::qs___has_attr__,
+	/// Deletes an attribute from the object, returning its value.
 	"__del_attr__" => method Self::qs___del_attr__,
+	/// Retrieves an attribute from the object or one of its parents.
 	"::" => method Self::qs___get_attr__,
+	/// Set an attribute on the object
 	".=" => method Self::qs___set_attr__,
+	/// Retrieves an attribute from the object or one of its parents, binding functions to it.
 	"." => method Self::qs_dot_get_attr,
+	/// Retrieves an attribute, returning [`Null`](crate::types::Null) if it doesn't exist.
 	".?" => method Self::qs_dot_get_attr_q,
+	/// Runs a block with the object as its scope, returning the block's result.
 	"instance_exec" => method Self::qs_instance_exec,
+	/// Defines a computed attribute, returning the [`Property`] that was created.
+	"property" => method Self::qs_property,
+	/// Adds a finalizer to the object, returning the object.
+	"on_drop" => method Self::qs_on_drop,
+	/// Freezes the object, returning it.
+	"freeze" => method Self::qs_freeze,
+	/// Checks to see if the object is frozen.
+	"frozen?" => method Self::qs_frozen_q,
+	/// Freezes the object and, recursively, the values of each of its attributes, returning it.
+	"deep_freeze" => method Self::qs_deep_freeze,
 
 	// this is mildly deprecated
+	/// Retrieves the object as an attribute of the outermost stackframe.
 	"::@" => method |this, _| {
 		crate::Binding::with_stack(|stack| {
 			stack.read()
@@ -225,3 +511,94 @@ for Pristine [(init_parent) (parents Pristine)]:
 		})
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::error::FrozenError;
+	use crate::types::{Number, ObjectType};
+
+	fn frozen(obj: &Object) -> bool {
+		*Pristine::qs_frozen_q(obj, args!()).unwrap().downcast::<Boolean>().unwrap() == Boolean::TRUE
+	}
+
+	#[test]
+	fn freeze() {
+		crate::init();
+
+		let obj = Object::new_with_parent((), ());
+		obj.set_attr_lit("a", 1.into()).unwrap();
+		assert!(!frozen(&obj));
+
+		let result = Pristine::qs_freeze(&obj, args!()).unwrap();
+		assert!(result.is_identical(&obj));
+		assert!(frozen(&obj));
+
+		assert_matches!(obj.set_attr_lit("a", 2.into()),
+			Err(crate::Error::FrozenError(FrozenError::SetAttr { .. })));
+		assert_matches!(obj.del_attr(&"a".into()),
+			Err(crate::Error::FrozenError(FrozenError::DelAttr { .. })));
+		assert_matches!(obj.add_parent(Object::default()),
+			Err(crate::Error::FrozenError(FrozenError::AddParent { .. })));
+		assert_matches!(obj.set_attr_lit("__parents__", Object::from(Vec::<Object>::new())),
+			Err(crate::Error::FrozenError(FrozenError::SetAttr { .. })));
+
+		assert_eq!(*obj.get_attr_lit("a").unwrap().downcast::<Number>().unwrap(), Number::from(1));
+	}
+
+	#[test]
+	fn frozen_parents_cant_be_modified() {
+		crate::init();
+
+		let parent = Object::new_with_parent((), ());
+		let obj = Object::new_with_parent((), vec![parent]);
+		obj.freeze().unwrap();
+
+		let parents = obj.get_attr_lit("__parents__").unwrap();
+		assert_matches!(parents.call_attr_lit("push", &[&Object::default()]),
+			Err(crate::Error::FrozenError(FrozenError::Modify { .. })));
+		assert_matches!(parents.call_attr_lit("[]=", &[&Object::from(vec![0.into()]), &Object::default()]),
+			Err(crate::Error::FrozenError(FrozenError::Modify { .. })));
+
+		assert_eq!(obj.parents().unwrap().len(), 1);
+	}
+
+	/// A module with a class, whose method has the class as its parent.
+	fn module(imported: &Object) -> Object {
+		let module = Object::new_with_parent((), ());
+		let class = Object::new_with_parent((), ());
+		class.set_attr_lit("method", Object::new_with_parent((), vec![class.clone()])).unwrap();
+
+		module.set_attr_lit("Point", class).unwrap();
+		module.set_attr_lit("imported", imported.clone()).unwrap();
+		module.set_attr_lit("kind", Number::mapping().clone()).unwrap();
+		module
+	}
+
+	#[test]
+	fn deep_freeze_only_freezes_owned_values() {
+		crate::init();
+
+		let imported = Object::new_with_parent((), ());
+		imported.set_attr_lit("value", Object::new_with_parent((), ())).unwrap();
+
+		let module = module(&imported);
+		Pristine::qs_deep_freeze(&module, args!()).unwrap();
+
+		let class = module.get_attr_lit("Point").unwrap();
+		assert!(module.is_frozen());
+		assert!(class.is_frozen(), "the class's method referring to it doesn't make it shared");
+		assert!(class.get_attr_lit("method").unwrap().is_frozen());
+		assert!(!imported.is_frozen(), "an imported module is shared");
+		assert!(!imported.get_attr_lit("value").unwrap().is_frozen());
+		assert!(!Number::mapping().is_frozen());
+
+		// an instance that isn't part of the module shares its class.
+		let module = self::module(&imported);
+		let _instance = Object::new_with_parent((), vec![module.get_attr_lit("Point").unwrap()]);
+		Pristine::qs_deep_freeze(&module, args!()).unwrap();
+
+		assert!(module.is_frozen());
+		assert!(!module.get_attr_lit("Point").unwrap().is_frozen());
+	}
+}
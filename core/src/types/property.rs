@@ -0,0 +1,161 @@
+use crate::{Object, Args, Literal};
+use crate::error::KeyError;
+use tracing::instrument;
+
+/// A computed attribute, created via [`Pristine::qs_property`](crate::types::Pristine::qs_property).
+///
+/// Properties are stored directly as attributes. Whenever one is fetched, its getter is called with
+/// the object it was fetched from, and whenever it's assigned, its setter is called with the object
+/// and the new value.
+#[derive(Debug, Clone)]
+pub struct Property {
+	getter: Object,
+	setter: Option<Object>,
+	builtin: bool
+}
+
+impl Property {
+	/// Creates a new [`Property`], which is read-only if `setter` isn't given.
+	pub fn new(getter: Object, setter: Option<Object>) -> Self {
+		Self { getter, setter, builtin: false }
+	}
+
+	/// Creates a property for a builtin type, such as `RustFn.doc`.
+	///
+	/// Unlike other properties, assigning to it just sets the attribute on the object it was
+	/// assigned through, so that assignments don't need to check for setters because of it.
+	pub fn builtin(getter: Object) -> Self {
+		Self { getter, setter: None, builtin: true }
+	}
+
+	/// Checks to see if this was created with [`builtin`](Self::builtin).
+	#[inline]
+	pub(crate) const fn is_builtin(&self) -> bool {
+		self.builtin
+	}
+
+	/// Gets the value of this property for `owner`.
+	pub fn get(&self, owner: &Object) -> crate::Result<Object> {
+		self.getter.call_attr_lit(&Literal::CALL, &[owner])
+	}
+
+	/// Sets the value of this property, which was accessed via `attr`, for `owner`.
+	pub fn set(&self, owner: &Object, attr: Object, value: Object) -> crate::Result<()> {
+		let setter = self.setter.as_ref()
+			.ok_or_else(|| KeyError::ReadOnly { attr, obj: owner.clone() })?;
+
+		setter.call_attr_lit(&Literal::CALL, &[owner, &value]).and(Ok(()))
+	}
+
+	/// Calls `f` with the getter and setter.
+	pub(crate) fn for_each_reference<F: FnMut(&Object)>(&self, mut f: F) {
+		f(&self.getter);
+		self.setter.iter().for_each(f);
+	}
+}
+
+impl Property {
+	/// Gets the getter of this property.
+	#[instrument(name="Property::getter", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_getter(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.getter.clone())
+	}
+
+	/// Gets the setter of this property, or `null` if it's read-only.
+	#[instrument(name="Property::setter", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_setter(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.setter.clone().unwrap_or_default())
+	}
+}
+
+impl_object_type!{
+for Property [(parents super::Basic)]:
+	/// Gets the getter of this property.
+	"getter" => method Self::qs_getter,
+	/// Gets the setter of this property, or `null` if it's read-only.
+	"setter" => method Self::qs_setter
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::types::{Number, Pristine, RustFn};
+
+	fn number(obj: crate::Result<Object>) -> Number {
+		*obj.unwrap().downcast::<Number>().unwrap()
+	}
+
+	fn double_getter() -> Object {
+		RustFn::function("get", |args| args.try_arg(0)?.get_attr_lit("x")?.call_attr_lit("*", &[&2.into()])).into()
+	}
+
+	fn double_setter() -> Object {
+		RustFn::function("set", |args| {
+			let half = args.try_arg(1)?.call_attr_lit("/", &[&2.into()])?;
+			args.try_arg(0)?.set_attr_lit("x", half)?;
+			Ok(Object::default())
+		}).into()
+	}
+
+	#[test]
+	fn get_and_set() {
+		crate::init();
+
+		let class = Object::new_with_parent((), ());
+		let instance = Object::new_with_parent((), vec![class.clone()]);
+		instance.set_attr_lit("x", 3.into()).unwrap();
+
+		Pristine::qs_property(&class, args!("double", double_getter(), double_setter())).unwrap();
+
+		// the getter's called with the object it's fetched from, not the one it's defined on.
+		assert_eq!(number(instance.get_attr_lit("double")), Number::from(6));
+
+		instance.set_attr_lit("double", 10.into()).unwrap();
+		assert_eq!(number(instance.get_attr_lit("x")), Number::from(5));
+		assert_eq!(number(instance.get_attr_lit("double")), Number::from(10));
+	}
+
+	#[test]
+	fn defined_after_assignments() {
+		crate::init();
+
+		let class = Object::new_with_parent((), ());
+		let instance = Object::new_with_parent((), vec![class.clone()]);
+
+		// this caches that none of `instance`'s ancestors have properties.
+		instance.set_attr_lit("x", 3.into()).unwrap();
+		Pristine::qs_property(&class, args!("double", double_getter(), double_setter())).unwrap();
+
+		instance.set_attr_lit("double", 10.into()).unwrap();
+		assert_eq!(number(instance.get_attr_lit("x")), Number::from(5));
+	}
+
+	#[test]
+	fn read_only() {
+		crate::init();
+
+		let obj = Object::new_with_parent((), ());
+		obj.set_attr_lit("x", 1.into()).unwrap();
+		Pristine::qs_property(&obj, args!("double", double_getter())).unwrap();
+
+		assert_eq!(number(obj.get_attr_lit("double")), Number::from(2));
+		assert_matches!(obj.set_attr_lit("double", 4.into()),
+			Err(crate::Error::KeyError(KeyError::ReadOnly { .. })));
+		assert_eq!(number(obj.get_attr_lit("double")), Number::from(2));
+	}
+
+	#[test]
+	fn getter_and_setter() {
+		crate::init();
+
+		let (getter, setter) = (double_getter(), double_setter());
+		let obj = Object::new_with_parent((), ());
+
+		let property = Pristine::qs_property(&obj, args!("double", getter.clone(), setter.clone())).unwrap();
+		assert!(Property::qs_getter(&property, args!()).unwrap().is_identical(&getter));
+		assert!(Property::qs_setter(&property, args!()).unwrap().is_identical(&setter));
+
+		let property = Pristine::qs_property(&obj, args!("double", getter)).unwrap();
+		assert!(Property::qs_setter(&property, args!()).unwrap().is_a::<crate::types::Null>());
+	}
+}
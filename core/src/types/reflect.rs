@@ -0,0 +1,288 @@
+use crate::{Object, Args};
+use crate::types::{RustFn, BoundRustFn, RustClosure, List, Text};
+use crate::obj::Value;
+use tracing::instrument;
+
+/// Lets Quest code inspect objects.
+///
+/// `Reflect` itself is never instantiated; its methods are called on the `Reflect` object, and
+/// take the object to inspect as their first argument. Methods for inspecting blocks are added by
+/// the parser, as blocks aren't defined in the core.
+///
+/// # Quest Examples
+/// ```quest
+/// Animal = { speak = animal -> { "..." }; :0 }();
+/// dog = { __parents__ = [Animal]; name = "Rex"; :0 }();
+///
+/// assert(Reflect.owner(dog, "speak") == Animal);
+/// assert(Reflect.kind(dog, "speak") == "Block");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Reflect;
+
+/// Gets the last segment of a Rust type's path, e.g. `Block` for `quest_parser::block::Block`.
+fn short_typename(typename: &str) -> &str {
+	typename.rsplit("::").next().unwrap_or(typename)
+}
+
+impl Reflect {
+	/// Gets the keys of the first argument, paired with the object that defines them.
+	///
+	/// Keys are returned as `[key, owner]` lists, in the order they're looked up: the object's own
+	/// keys come first, followed by the keys of each of its ancestors that aren't shadowed.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// parent = { x = 1; :0 }();
+	/// child = { __parents__ = [parent]; y = 2; :0 }();
+	///
+	/// keys = Reflect.keys(child);
+	/// assert(keys.include?(["y", child]));
+	/// assert(keys.include?(["x", parent]));
+	/// ```
+	#[instrument(name="Reflect::keys", level="trace", skip(args), fields(?args))]
+	pub fn qs_keys(_: &Object, args: Args) -> crate::Result<Object> {
+		let obj = args.try_arg(0)?;
+		let mut seen = Vec::<Object>::new();
+		let mut keys = vec![];
+
+		for owner in std::iter::once(obj.clone()).chain(obj.mro()?.iter().cloned()) {
+			for key in owner.mapping_keys(false)? {
+				if !contains(&seen, &key)? {
+					keys.push(Object::from(vec![key.clone(), owner.clone()]));
+					seen.push(key);
+				}
+			}
+		}
+
+		Ok(keys.into())
+	}
+
+	/// Gets the keys that are defined directly on the first argument.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// parent = { x = 1; :0 }();
+	/// child = { __parents__ = [parent]; y = 2; :0 }();
+	///
+	/// assert(Reflect.own_keys(child).include?("y"));
+	/// assert(!Reflect.own_keys(child).include?("x"));
+	/// ```
+	#[instrument(name="Reflect::own_keys", level="trace", skip(args), fields(?args))]
+	pub fn qs_own_keys(_: &Object, args: Args) -> crate::Result<Object> {
+		Ok(args.try_arg(0)?.mapping_keys(false)?.into())
+	}
+
+	/// Gets the object that the second argument is defined on, or `null` if it's not defined.
+	///
+	/// This is either the first argument itself, or the first of its ancestors that defines it.
+	#[instrument(name="Reflect::owner", level="trace", skip(args), fields(?args))]
+	pub fn qs_owner(_: &Object, args: Args) -> crate::Result<Object> {
+		let obj = args.try_arg(0)?;
+		let attr = args.try_arg(1)?;
+
+		Ok(obj.attr_owner(attr)?.unwrap_or_default())
+	}
+
+	/// Gets the Rust type of the first argument's data, e.g. `quest_core::types::number::Number`.
+	#[instrument(name="Reflect::typename", level="trace", skip(args), fields(?args))]
+	pub fn qs_typename(_: &Object, args: Args) -> crate::Result<Object> {
+		Ok(args.try_arg(0)?.typename().into())
+	}
+
+	/// Gets what kind of value the attribute named by the second argument is, or `null` if the
+	/// first argument doesn't have it.
+	///
+	/// This is `"RustFn"` or `"BoundRustFn"` for builtin functions, `"Block"` for functions written
+	/// in Quest (including ones made with `->`), and `"Property"` for properties. For anything
+	/// else, it's the Rust type of the value, without its path (e.g. `"Number"`).
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert(Reflect.kind(1, "+") == "RustFn");
+	/// assert(Reflect.kind(1, "missing") == null);
+	/// ```
+	#[instrument(name="Reflect::kind", level="trace", skip(args), fields(?args))]
+	pub fn qs_kind(_: &Object, args: Args) -> crate::Result<Object> {
+		let obj = args.try_arg(0)?;
+		let attr = args.try_arg(1)?;
+
+		let value =
+			match obj.attr_owner(attr)? {
+				Some(owner) => owner.get_own_definition(attr)?,
+				None => return Ok(Object::default())
+			};
+
+		let kind =
+			match value {
+				// `__id__` and friends aren't stored.
+				None => short_typename(obj.get_attr(attr)?.typename()),
+				Some(Value::RustFn(_)) => "RustFn",
+				Some(Value::Property(_)) => "Property",
+				Some(Value::Object(value)) if value.is_a::<RustFn>() => "RustFn",
+				Some(Value::Object(value)) if value.is_a::<BoundRustFn>() => "BoundRustFn",
+				Some(Value::Object(value)) if value.is_a::<RustClosure>()
+					&& value.get_own_value(&Object::from("block"))?.is_some() => "Block",
+				Some(Value::Object(value)) => short_typename(value.typename())
+			};
+
+		Ok(Text::from(kind).into())
+	}
+
+	/// Gets the parameter names of a function made with `->`.
+	///
+	/// Other functions, such as plain blocks, receive their arguments as `_0`, `_1`, etc., and so
+	/// have no parameter names.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert(Reflect.params((a, b) -> { a + b }) == ["a", "b"]);
+	/// assert(Reflect.params({ _0 + _1 }).empty?());
+	/// ```
+	#[instrument(name="Reflect::params", level="trace", skip(args), fields(?args))]
+	pub fn qs_params(_: &Object, args: Args) -> crate::Result<Object> {
+		let func = args.try_arg(0)?;
+
+		let params =
+			match func.get_own_value(&Object::from("args"))? {
+				Some(Value::Object(params)) if func.is_a::<RustClosure>() =>
+					params.downcast::<List>().map(|list| list.as_ref().to_vec()),
+				_ => None
+			};
+
+		Ok(params.unwrap_or_default().into())
+	}
+}
+
+/// Checks to see if `list` contains `key`.
+fn contains(list: &[Object], key: &Object) -> crate::Result<bool> {
+	for item in list {
+		if item.eq_obj(key)? {
+			return Ok(true);
+		}
+	}
+
+	Ok(false)
+}
+
+impl_object_type!{
+for Reflect [(parents super::Basic)]:
+	/// Gets the keys of the first argument, paired with the object that defines them.
+	"keys" => method Self::qs_keys,
+	/// Gets the keys that are defined directly on the first argument.
+	"own_keys" => method Self::qs_own_keys,
+	/// Gets the object that the second argument is defined on, or `null` if it's not defined.
+	"owner" => method Self::qs_owner,
+	/// Gets the Rust type of the first argument's data, e.g. `quest_core::types::number::Number`.
+	"typename" => method Self::qs_typename,
+	/// Gets what kind of value the attribute named by the second argument is, or `null` if the
+	/// first argument doesn't have it.
+	"kind" => method Self::qs_kind,
+	/// Gets the parameter names of a function made with `->`.
+	"params" => method Self::qs_params,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::types::{Number, ObjectType, Pristine};
+
+	fn text(obj: crate::Result<Object>) -> Option<String> {
+		obj.unwrap().downcast::<Text>().map(|text| text.to_string())
+	}
+
+	#[test]
+	fn keys_and_owners() {
+		crate::init();
+
+		let parent = Object::new_with_parent((), ());
+		parent.set_attr_lit("x", 1.into()).unwrap();
+		parent.set_attr_lit("y", 2.into()).unwrap();
+
+		let child = Object::new_with_parent((), vec![parent.clone()]);
+		child.set_attr_lit("y", 3.into()).unwrap();
+
+		let keys = Reflect::qs_keys(&Object::default(), args!(child.clone())).unwrap();
+		let keys = keys.downcast::<List>().unwrap().iter()
+			.map(|pair| {
+				let pair = pair.downcast::<List>().unwrap();
+				let key = pair.as_ref()[0].downcast::<Text>().unwrap().to_string();
+				(key, pair.as_ref()[1].id())
+			})
+			.collect::<Vec<_>>();
+
+		// own keys come first, and shadowed keys are only reported once.
+		let position = |key: &str, owner: &Object| keys.iter().position(|pair| *pair == (key.to_string(), owner.id()));
+		assert!(position("y", &child).unwrap() < position("x", &parent).unwrap());
+		assert!(!keys.contains(&("y".to_string(), parent.id())));
+
+		let own_keys = Reflect::qs_own_keys(&Object::default(), args!(child.clone())).unwrap();
+		let own_keys = own_keys.downcast::<List>().unwrap().iter()
+			.map(|key| key.downcast::<Text>().unwrap().to_string())
+			.collect::<Vec<_>>();
+		assert!(own_keys.contains(&"y".to_string()));
+		assert!(!own_keys.contains(&"x".to_string()));
+
+		let owner = |attr: &str| Reflect::qs_owner(&Object::default(), args!(child.clone(), attr)).unwrap();
+		assert!(owner("y").is_identical(&child));
+		assert!(owner("x").is_identical(&parent));
+		assert!(owner("missing").is_a::<crate::types::Null>());
+	}
+
+	#[test]
+	fn kinds() {
+		crate::init();
+
+		let obj = Object::new_with_parent((), vec![Number::mapping()]);
+		let block = RustClosure::new(|_| Ok(Object::default()));
+		let block = Object::from(block);
+		block.set_attr_lit("block", Object::default()).unwrap();
+
+		obj.set_attr_lit("num", 1.into()).unwrap();
+		obj.set_attr_lit("bound", BoundRustFn::new(|_| Ok(Object::default())).into()).unwrap();
+		obj.set_attr_lit("closure", RustClosure::new(|_| Ok(Object::default())).into()).unwrap();
+		obj.set_attr_lit("block", block).unwrap();
+		Pristine::qs_property(&obj, args!("prop", obj.get_attr_lit("bound").unwrap())).unwrap();
+
+		let kind = |attr: &str| text(Reflect::qs_kind(&Object::default(), args!(obj.clone(), attr)));
+
+		assert_eq!(kind("num").as_deref(), Some("Number"));
+		assert_eq!(kind("+").as_deref(), Some("RustFn"));
+		assert_eq!(kind("bound").as_deref(), Some("BoundRustFn"));
+		assert_eq!(kind("closure").as_deref(), Some("RustClosure"));
+		assert_eq!(kind("block").as_deref(), Some("Block"));
+		assert_eq!(kind("prop").as_deref(), Some("Property"));
+		assert_eq!(kind("missing"), None);
+	}
+
+	#[test]
+	fn typename() {
+		crate::init();
+
+		assert_eq!(text(Reflect::qs_typename(&Object::default(), args!(1))).as_deref(),
+			Some("quest_core::types::number::Number"));
+	}
+
+	#[test]
+	fn params() {
+		crate::init();
+
+		let params = |func: &Object| Reflect::qs_params(&Object::default(), args!(func.clone()))
+			.unwrap()
+			.downcast::<List>().unwrap()
+			.iter()
+			.map(|param| param.downcast::<Text>().unwrap().to_string())
+			.collect::<Vec<_>>();
+
+		let func = Object::from(RustClosure::new(|_| Ok(Object::default())));
+		assert!(params(&func).is_empty());
+
+		func.set_attr_lit("args", Object::from(vec![Object::from("a"), Object::from("b")])).unwrap();
+		assert_eq!(params(&func), ["a", "b"]);
+
+		// only functions made with `->` have parameters.
+		let obj = Object::new_with_parent((), ());
+		obj.set_attr_lit("args", Object::from(vec![Object::from("a")])).unwrap();
+		assert!(params(&obj).is_empty());
+	}
+}
@@ -1,9 +1,13 @@
-use crate::{Object, Args};
+use crate::{Object, Args, Literal};
 use std::fmt::{self, Debug, Display, Formatter};
 use std::convert::TryFrom;
-use crate::types::Text;
+use std::sync::Arc;
+use crate::types::{Text, List, Number, Iter};
 use tracing::instrument;
 
+mod match_data;
+pub use match_data::Match;
+
 bitflags::bitflags! {
 	#[derive(Default)]
 	pub struct Flags : u8 {
@@ -103,6 +107,102 @@ impl Regex {
 	pub fn gsub<'t>(&self, text: &'t str, repl: &str) -> std::borrow::Cow<'t, str> {
 		self.0.replace_all(text, repl)
 	}
+
+	/// Lazily iterates over all the non-overlapping [`Match`]es in `text`.
+	pub fn scan(&self, text: impl Into<Arc<str>>) -> Scan {
+		Scan {
+			regex: self.0.clone(),
+			haystack: text.into(),
+			pos: 0,
+			last_match_end: None
+		}
+	}
+
+	/// Replaces the first `limit` matches (or all of them, if `None`) with the result of calling
+	/// `repl` with the match.
+	pub fn replace_with<F>(&self, text: &str, limit: Option<usize>, mut repl: F) -> crate::Result<String>
+	where
+		F: FnMut(Match) -> crate::Result<String>
+	{
+		let mut result = String::with_capacity(text.len());
+		let mut last = 0;
+
+		for m in self.scan(text).take(limit.unwrap_or(usize::MAX)) {
+			let (start, end) = m.range();
+			result.push_str(&text[last..start]);
+			result.push_str(&repl(m)?);
+			last = end;
+		}
+
+		result.push_str(&text[last..]);
+		Ok(result)
+	}
+
+	/// Splits `text` on matches of this regex, returning at most `limit` pieces.
+	pub fn split<'t>(&self, text: &'t str, limit: Option<usize>) -> Vec<&'t str> {
+		match limit {
+			Some(limit) => self.0.splitn(text, limit).collect(),
+			None => self.0.split(text).collect()
+		}
+	}
+
+	/// Performs `sub` or `gsub`, calling `repl` with each [`Match`] unless it's a [`Text`].
+	pub fn replace_obj(&self, text: &str, repl: &Object, limit: Option<usize>) -> crate::Result<String> {
+		if let Some(repl) = repl.downcast::<Text>() {
+			return Ok(match limit {
+				Some(limit) => self.0.replacen(text, limit, repl.as_ref()),
+				None => self.0.replace_all(text, repl.as_ref())
+			}.into_owned());
+		}
+
+		self.replace_with(text, limit, |m| {
+			repl.call_attr_lit(&Literal::CALL, &[&m.into()])?
+				.call_downcast::<Text>()
+				.map(|text| text.to_string())
+		})
+	}
+}
+
+/// A lazy iterator over the [`Match`]es of a [`Regex`] within some text.
+///
+/// This is created by [`Regex::scan`].
+#[derive(Debug, Clone)]
+pub struct Scan {
+	regex: regex::Regex,
+	haystack: Arc<str>,
+	pos: usize,
+	last_match_end: Option<usize>
+}
+
+impl Iterator for Scan {
+	type Item = Match;
+
+	fn next(&mut self) -> Option<Match> {
+		loop {
+			if self.pos > self.haystack.len() {
+				return None;
+			}
+
+			let mut locs = self.regex.capture_locations();
+			let (start, end) = self.regex.read_captures_at(&mut locs, &self.haystack, self.pos)
+				.map(|m| (m.start(), m.end()))?;
+
+			if start == end {
+				// empty matches must advance by a character so we don't loop forever
+				self.pos = end + self.haystack[end..].chars().next().map_or(1, char::len_utf8);
+
+				// and an empty match directly after a previous match is ignored.
+				if self.last_match_end == Some(end) {
+					continue;
+				}
+			} else {
+				self.pos = end;
+			}
+
+			self.last_match_end = Some(end);
+			return Some(Match::new(&self.regex, self.haystack.clone(), &locs));
+		}
+	}
 }
 
 impl AsRef<regex::Regex> for Regex {
@@ -157,7 +257,7 @@ impl Regex {
 				.into())
 	}
 
-	/// Returns an Array of matched values.
+	/// Returns an [`Iter`] of each [`Match`], which are found lazily.
 	///
 	/// The first argument is converted to a [`Text`] before matching.
 	#[instrument(name="Regex::scan", level="trace", skip(this, args), fields(self=?this, ?args))]
@@ -165,16 +265,19 @@ impl Regex {
 		let rhs = args.try_arg(0)?.call_downcast::<Text>()?;
 		let this = this.try_downcast::<Self>()?;
 
-		Ok(this.0
-			.find_iter(rhs.as_ref())
-			.map(|m| Object::from(m.as_str().to_string()))
-			.collect::<crate::types::List>()
-			.into())
+		Ok(Iter::with_objects(this.scan(rhs.as_ref()).map(Object::from)).into())
 	}
 
-	/// Returns an Array of matched groups.
+	/// Returns a [`List`] of the groups of the first match, which is empty if nothing matched.
+	/// Groups that didn't participate in the match are `null`.
 	///
 	/// The first argument is converted to a [`Text`] before matching.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert(/(\d)(x)?/.match("a1") == ["1", "1", null]);
+	/// assert(/\d/.match("a").empty?());
+	/// ```
 	#[instrument(name="Regex::match", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_match(this: &Object, args: Args) -> crate::Result<Object> {
 		let rhs = args.try_arg(0)?.call_downcast::<Text>()?;
@@ -189,6 +292,23 @@ impl Regex {
 			).unwrap_or_else(|| vec![].into()))
 	}
 
+	/// Returns the first [`Match`], or `null` if nothing matched.
+	///
+	/// The first argument is converted to a [`Text`] before matching.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// m = /(\d)(x)?/.find("a1");
+	/// assert(m.start() == 1 && m[1] == "1" && m[2] == null);
+	/// assert(/\d/.find("a") == null);
+	/// ```
+	#[instrument(name="Regex::find", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_find(this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = args.try_arg(0)?.call_downcast::<Text>()?;
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(this.scan(rhs.as_ref()).next().map(Object::from).unwrap_or_default())
+	}
 
 	/// Checks to see if the first argument matches.
 	///
@@ -201,32 +321,136 @@ impl Regex {
 		Ok(this.0.is_match(rhs.as_ref()).into())
 	}
 
+	/// Replaces the first match in the text.
+	///
+	/// The replacement is either a [`Text`] (which may reference groups via `$1` or `${name}`), or
+	/// something callable, which is called with the [`Match`] and whose result is used.
 	#[instrument(name="Regex::sub", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_sub(this: &Object, args: Args) -> crate::Result<Object> {
 		let pat = this.call_downcast::<Self>()?;
 		let text = args.try_arg(0)?.call_downcast::<Text>()?;
-		let repl = args.try_arg(1)?.call_downcast::<Text>()?;
 
-		Ok(pat.sub(text.as_ref(), repl.as_ref()).to_string().into())
+		Ok(pat.replace_obj(text.as_ref(), args.try_arg(1)?, Some(1))?.into())
 	}
 
+	/// Replaces every match in the text; see [`Regex::qs_sub`] for the replacement.
 	#[instrument(name="Regex::gsub", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_gsub(this: &Object, args: Args) -> crate::Result<Object> {
 		let pat = this.call_downcast::<Self>()?;
 		let text = args.try_arg(0)?.call_downcast::<Text>()?;
-		let repl = args.try_arg(1)?.call_downcast::<Text>()?;
 
-		Ok(pat.gsub(text.as_ref(), repl.as_ref()).to_string().into())
+		Ok(pat.replace_obj(text.as_ref(), args.try_arg(1)?, None)?.into())
+	}
+
+	/// Splits the text on each match, with an optional limit of pieces.
+	#[instrument(name="Regex::split", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_split(this: &Object, args: Args) -> crate::Result<Object> {
+		let pat = this.call_downcast::<Self>()?;
+		let text = args.try_arg(0)?.call_downcast::<Text>()?;
+		let limit = args.arg(1)
+			.map(|limit| limit.call_downcast::<Number>().map(|limit| *limit))
+			.transpose()?
+			.map(usize::try_from)
+			.transpose()?;
+
+		Ok(pat.split(text.as_ref(), limit)
+			.into_iter()
+			.map(Object::from)
+			.collect::<List>()
+			.into())
+	}
+
+	/// The source of the regex, without any flags.
+	#[instrument(name="Regex::source", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_source(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.0.as_str().into())
+	}
+
+	/// The flags of the regex, as a [`Text`].
+	#[instrument(name="Regex::flags", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_flags(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.1.to_string().into())
 	}
 }
 
 impl_object_type!{
 for Regex [(parents super::Basic) (convert "@regex")]:
+	/// Inspects the [`Regex`].
 	"inspect" => method Self::qs_inspect,
+	/// Compares two [`Regex`]s
 	"==" => method Self::qs_eql,
+	/// Checks to see if the first argument matches.
 	"match?" => method Self::qs_match_q,
+	/// Returns a [`List`] of the groups of the first match, which is empty if nothing matched.
 	"match" => method Self::qs_match,
+	/// Returns the first [`Match`], or `null` if nothing matched.
+	"find" => method Self::qs_find,
+	/// Returns an [`Iter`] of each [`Match`], which are found lazily.
 	"scan" => method Self::qs_scan,
+	/// Replaces the first match in the text.
 	"sub" => method Self::qs_sub,
+	/// Replaces every match in the text; see [`Regex::qs_sub`] for the replacement.
 	"gsub" => method Self::qs_gsub,
+	/// Splits the text on each match, with an optional limit of pieces.
+	"split" => method Self::qs_split,
+	/// The source of the regex, without any flags.
+	"source" => method Self::qs_source,
+	/// The flags of the regex, as a [`Text`].
+	"flags" => method Self::qs_flags,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn scan(rxp: &str, text: &str) -> Vec<(usize, usize)> {
+		Regex::new(rxp).unwrap().scan(text).map(|m| m.range()).collect()
+	}
+
+	#[test]
+	fn scan_matches_find_iter() {
+		for (rxp, text) in &[(r"\d+", "a1b22c333"), ("a*", "baaa"), ("", "héllo"), ("x", "")] {
+			let expected = ::regex::Regex::new(rxp).unwrap()
+				.find_iter(text)
+				.map(|m| (m.start(), m.end()))
+				.collect::<Vec<_>>();
+
+			assert_eq!(scan(rxp, text), expected, "{:?} on {:?}", rxp, text);
+		}
+	}
+
+	#[test]
+	fn match_groups() {
+		let m = Regex::new(r"(?P<a>\d)(x)?").unwrap().scan("z1").next().unwrap();
+
+		assert_eq!(m.as_str(), "1");
+		assert_eq!(m.group(1), Some("1"));
+		assert_eq!(m.group(2), None);
+		assert_eq!(m.group(3), None);
+		assert_eq!(m.name_index("a"), Some(1));
+		assert_eq!(m.name_index("b"), None);
+	}
+
+	#[test]
+	fn match_and_find() {
+		crate::init();
+
+		let rxp = Object::from(Regex::new(r"(\d)(x)?").unwrap());
+		let groups = Regex::qs_match(&rxp, args!("a1")).unwrap();
+		assert_eq!(groups.downcast::<List>().unwrap().len(), 3);
+		assert!(Regex::qs_match(&rxp, args!("a")).unwrap().downcast::<List>().unwrap().is_empty());
+
+		let found = Regex::qs_find(&rxp, args!("a1")).unwrap();
+		assert_eq!(found.downcast::<Match>().unwrap().as_str(), "1");
+		assert!(Regex::qs_find(&rxp, args!("a")).unwrap().is_a::<crate::types::Null>());
+	}
+
+	#[test]
+	fn replace_with() {
+		let rxp = Regex::new(r"\d").unwrap();
+
+		assert_eq!(rxp.replace_with("a1b2", None, |m| Ok(format!("<{}>", m.as_str()))).unwrap(), "a<1>b<2>");
+		assert_eq!(rxp.replace_with("a1b2", Some(1), |_| Ok("_".into())).unwrap(), "a_b2");
+		assert_eq!(rxp.split("a1b2c", Some(2)), vec!["a", "b2c"]);
+	}
 }
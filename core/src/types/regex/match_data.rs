@@ -0,0 +1,215 @@
+use crate::{Object, Args};
+use crate::error::KeyError;
+use crate::types::{Basic, List, Number, Text};
+use std::convert::TryFrom;
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+use tracing::instrument;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The result of successfully matching a [`Regex`](super::Regex) against some text.
+///
+/// Offsets given back to Quest (via `start` and `end`) are grapheme indices, so they can be passed
+/// directly to `Text::get`.
+#[derive(Clone)]
+pub struct Match {
+	regex: regex::Regex,
+	haystack: Arc<str>,
+	groups: Vec<Option<(usize, usize)>>
+}
+
+impl Debug for Match {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		f.debug_tuple("Match")
+			.field(&self.as_str())
+			.field(&self.groups)
+			.finish()
+	}
+}
+
+impl Match {
+	pub(super) fn new(regex: &regex::Regex, haystack: Arc<str>, locs: &regex::CaptureLocations) -> Self {
+		Self {
+			regex: regex.clone(),
+			haystack,
+			groups: (0..locs.len()).map(|idx| locs.get(idx)).collect()
+		}
+	}
+
+	/// The byte range of the entire match.
+	#[must_use]
+	pub fn range(&self) -> (usize, usize) {
+		self.groups[0].expect("the first group always matches")
+	}
+
+	/// The entire matched text.
+	#[must_use]
+	pub fn as_str(&self) -> &str {
+		self.group(0).expect("the first group always matches")
+	}
+
+	/// Get the text matched by the group at `idx`, if it participated in the match.
+	#[must_use]
+	pub fn group(&self, idx: usize) -> Option<&str> {
+		self.groups.get(idx)?.map(|(start, end)| &self.haystack[start..end])
+	}
+
+	/// Get the index of the group with the given `name`.
+	#[must_use]
+	pub fn name_index(&self, name: &str) -> Option<usize> {
+		self.regex.capture_names().position(|group| group == Some(name))
+	}
+
+	/// Converts the argument (either an index or a name) into a group index.
+	fn group_index(&self, arg: Option<&Object>) -> crate::Result<Option<usize>> {
+		let arg =
+			match arg {
+				Some(arg) => arg,
+				None => return Ok(Some(0))
+			};
+
+		if let Some(num) = arg.downcast::<Number>() {
+			let len = self.groups.len() as isize;
+			let mut idx = isize::try_from(*num)?;
+
+			if idx.is_negative() {
+				idx += len;
+			}
+
+			Ok(if 0 <= idx && idx < len { Some(idx as usize) } else { None })
+		} else {
+			let name = arg.call_downcast::<Text>()?;
+
+			self.name_index(name.as_ref())
+				.map(Some)
+				.ok_or_else(|| KeyError::DoesntExist {
+					attr: name.clone().into(),
+					obj: self.clone().into()
+				}.into())
+		}
+	}
+
+	fn grapheme_offset(&self, byte_idx: usize) -> usize {
+		self.haystack[..byte_idx].graphemes(true).count()
+	}
+}
+
+impl Match {
+	/// Gets a group by its index or name, returning `null` if the group didn't participate.
+	#[instrument(name="Match::[]", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_get(this: &Object, args: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+		let idx = this.group_index(Some(args.try_arg(0)?))?;
+
+		Ok(idx.and_then(|idx| this.group(idx)).map(Object::from).unwrap_or_default())
+	}
+
+	/// The text before the match.
+	#[instrument(name="Match::pre", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_pre(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(this.haystack[..this.range().0].into())
+	}
+
+	/// The text after the match.
+	#[instrument(name="Match::post", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_post(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(this.haystack[this.range().1..].into())
+	}
+
+	/// The start of the match, or of the given group if one is supplied.
+	#[instrument(name="Match::start", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_start(this: &Object, args: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(this.group_index(args.arg(0))?
+			.and_then(|idx| this.groups[idx])
+			.map(|(start, _)| this.grapheme_offset(start).into())
+			.unwrap_or_default())
+	}
+
+	/// The end of the match, or of the given group if one is supplied.
+	#[instrument(name="Match::end", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_end(this: &Object, args: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(this.group_index(args.arg(0))?
+			.and_then(|idx| this.groups[idx])
+			.map(|(_, end)| this.grapheme_offset(end).into())
+			.unwrap_or_default())
+	}
+
+	/// Returns an object whose attributes are the named groups.
+	#[instrument(name="Match::named", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_named(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+		let named = Object::from(Basic);
+
+		for (idx, name) in this.regex.capture_names().enumerate() {
+			if let Some(name) = name {
+				named.set_attr(name.into(), this.group(idx).map(Object::from).unwrap_or_default())?;
+			}
+		}
+
+		Ok(named)
+	}
+
+	/// The amount of groups, including the entire match.
+	#[instrument(name="Match::len", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_len(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.groups.len().into())
+	}
+
+	/// Returns the entire matched text.
+	#[instrument(name="Match::@text", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_text(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.as_str().into())
+	}
+
+	/// Returns a list of all the groups, with `null` for those that didn't participate.
+	#[instrument(name="Match::@list", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_list(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok((0..this.groups.len())
+			.map(|idx| this.group(idx).map(Object::from).unwrap_or_default())
+			.collect::<List>()
+			.into())
+	}
+
+	#[instrument(name="Match::inspect", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_inspect(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(format!("Match({:?})", this.as_str()).into())
+	}
+}
+
+impl_object_type!{
+for Match [(parents Basic)]:
+	/// Gets a group by its index or name, returning `null` if the group didn't participate.
+	"[]" => method Self::qs_get,
+	/// Gets a group by its index or name, returning `null` if the group didn't participate.
+	"get" => method Self::qs_get,
+	/// The text before the match.
+	"pre" => method Self::qs_pre,
+	/// The text after the match.
+	"post" => method Self::qs_post,
+	/// The start of the match, or of the given group if one is supplied.
+	"start" => method Self::qs_start,
+	/// The end of the match, or of the given group if one is supplied.
+	"end" => method Self::qs_end,
+	/// Returns an object whose attributes are the named groups.
+	"named" => method Self::qs_named,
+	/// The amount of groups, including the entire match.
+	"len" => method Self::qs_len,
+	/// Returns the entire matched text.
+	"@text" => method Self::qs_at_text,
+	/// Returns a list of all the groups, with `null` for those that didn't participate.
+	"@list" => method Self::qs_at_list,
+	/// Gets a debugging representation of the match.
+	"inspect" => method Self::qs_inspect,
+}
@@ -21,7 +21,8 @@ enum FuncType {
 #[derive(Clone, Copy)]
 pub struct RustFn { 
 	name: &'static str,
-	func: FuncType
+	func: FuncType,
+	doc: &'static str
 }
 
 impl Debug for RustFn {
@@ -50,15 +51,46 @@ impl Hash for RustFn {
 impl RustFn {
 	#[inline]
 	pub fn function(name: &'static str, func: for<'s, 'o> fn(Args<'s, 'o>) -> crate::Result<Object>) -> Self {
-		Self { name, func: FuncType::Function(func) }
+		Self { name, func: FuncType::Function(func), doc: "" }
 	}
 
 	#[inline]
 	pub fn method(name: &'static str, meth: for<'s, 'o> fn(&'o Object, Args<'s, 'o>) -> crate::Result<Object>) -> Self {
-		Self { name, func: FuncType::Method(meth) }
+		Self { name, func: FuncType::Method(meth), doc: "" }
+	}
+
+	/// Sets the documentation for this function, as written in `///` comments.
+	#[inline]
+	#[must_use]
+	pub const fn with_doc(mut self, doc: &'static str) -> Self {
+		self.doc = doc;
+		self
+	}
+
+	/// The name this function was created with, such as `Kernel::disp`.
+	#[inline]
+	pub const fn name(&self) -> &'static str {
+		self.name
+	}
+
+	/// Gets the documentation for this function, if it has any.
+	///
+	/// Links to Rust items, such as ``[`Text`](crate::types::Text)``, are replaced with their text.
+	pub fn doc(&self) -> Option<String> {
+		if self.doc.trim().is_empty() {
+			return None;
+		}
+
+		let lines = self.doc.trim_end().lines()
+			.map(|line| strip_links(line.strip_prefix(' ').unwrap_or(line)))
+			.collect::<Vec<_>>();
+
+		Some(lines.join("\n"))
 	}
 
 	pub fn call_with_owner<'s, 'o>(&self, owner: &'o Object, mut args: Args<'s, 'o>) -> crate::Result<Object> {
+		let _call = crate::profile::enter(|| self.name.to_string());
+
 		match self.func {
 			FuncType::Function(func) => {
 				args.prepend(owner);
@@ -70,6 +102,8 @@ impl RustFn {
 
 	#[inline]
 	pub fn call(&self, args: Args) -> crate::Result<Object> {
+		let _call = crate::profile::enter(|| self.name.to_string());
+
 		match self.func {
 			FuncType::Function(func) => func(args),
 			FuncType::Method(meth) => {
@@ -82,6 +116,33 @@ impl RustFn {
 }
 
 
+/// Replaces the links in the markdown `line`, such as `[text](url)` and `[text]`, with their text.
+fn strip_links(line: &str) -> String {
+	let mut stripped = String::with_capacity(line.len());
+	let mut rest = line;
+
+	while let Some(start) = rest.find('[') {
+		let text_end =
+			match rest[start..].find(']') {
+				Some(end) => start + end,
+				None => break
+			};
+
+		stripped.push_str(&rest[..start]);
+		stripped.push_str(&rest[start + 1..text_end]);
+		rest = &rest[text_end + 1..];
+
+		if rest.starts_with('(') {
+			if let Some(end) = rest.find(')') {
+				rest = &rest[end + 1..];
+			}
+		}
+	}
+
+	stripped.push_str(rest);
+	stripped
+}
+
 impl From<RustFn> for Text {
 	#[inline]
 	fn from(rustfn: RustFn) -> Self {
@@ -90,6 +151,7 @@ impl From<RustFn> for Text {
 }
 
 impl RustFn {
+	/// Gets a debugging representation of the function.
 	#[instrument(name="RustFn::inspect", level="trace", skip(this), fields(self=?this))]
 	pub fn qs_inspect(this: &Object, _: Args) -> crate::Result<Object> {
 		let this = this.try_downcast::<Self>()?;
@@ -97,23 +159,37 @@ impl RustFn {
 		Ok(format!("{:?}", *this).into())
 	}
 
+	/// Gets the function's name.
 	#[instrument(name="RustFn::@text", level="trace", skip(this), fields(self=?this))]
 	pub fn qs_at_text(this: &Object, _: Args) -> crate::Result<Object> {
-		Ok(this.try_downcast::<Self>()?
-			.clone()
-			.into())
+		Ok((*this.try_downcast::<Self>()?).into())
 	}
 
+	/// Calls the function with the arguments.
 	#[instrument(name="RustFn::()", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_call(this: &Object, args: Args) -> crate::Result<Object> {
 		let this = this.try_downcast::<Self>()?;
 		this.call(args)
 	}
+
+	/// Gets the function's documentation, or `null` if it doesn't have any.
+	#[instrument(name="RustFn::doc", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_doc(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.downcast::<Self>()
+			.and_then(|this| this.doc())
+			.map(Object::from)
+			.unwrap_or_default())
+	}
 }
 
 impl_object_type! {
 for RustFn [(parents super::Function)]:
+	/// Gets a debugging representation of the function.
 	"inspect" => method Self::qs_inspect,
+	/// Gets the function's name.
 	"@text" => method Self::qs_at_text,
+	/// Calls the function with the arguments.
 	"()" => method Self::qs_call,
+	/// Gets the function's documentation, or `null` if it doesn't have any.
+	"doc" => property Self::qs_doc,
 }
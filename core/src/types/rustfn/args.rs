@@ -43,7 +43,7 @@ impl<'s, 'o> Args<'s, 'o> {
 	}
 
 	pub fn to_vec(&self) -> Vec<&'o Object> {
-		self.0.to_owned().to_vec()
+		self.0.to_vec()
 	}
 
 	pub fn to_cloned_vec(&self) -> Vec<Object> {
@@ -48,7 +48,7 @@ impl Binding {
 	where
 		F: FnOnce(&Binding) -> crate::Result<Object>,
 	{
-		struct StackGuard<'a>(&'a RwLock<Stack>, &'a Binding);
+		struct StackGuard<'a>(&'a RwLock<Stack>);
 		impl Drop for StackGuard<'_> {
 			#[inline]
 			fn drop(&mut self) {
@@ -65,7 +65,7 @@ impl Binding {
 		let _guard = span.enter();
 
 
-		Binding::with_stack(|stack| {
+		let result = Binding::with_stack(|stack| {
 			let binding = {
 				let binding = Object::from(Scope);
 
@@ -92,14 +92,19 @@ impl Binding {
 				stack.push(binding.clone());
 			};
 
-			let _guard = StackGuard(stack, &binding);
+			let _guard = StackGuard(stack);
  			
 			match func(&binding) {
 				Err(crate::Error::Return { to, obj }) if to.as_ref().eq_obj(binding.as_ref())?
 					=> Ok(obj),
 				other => other
 			}
-		})
+		});
+
+		// now that the stackframe's gone, anything that was only referenced by it is dropped.
+		crate::gc::run_finalizers();
+
+		result
 	}
 
 
@@ -107,7 +112,7 @@ impl Binding {
 	where
 		F: FnOnce(&Binding) -> crate::Result<Object>
 	{
-		struct StackGuard<'a>(&'a RwLock<Stack>, &'a Binding);
+		struct StackGuard<'a>(&'a RwLock<Stack>);
 		impl Drop for StackGuard<'_> {
 			#[inline]
 			fn drop(&mut self) {
@@ -121,7 +126,7 @@ impl Binding {
 				stack.push(binding.clone());
 			};
 
-			let _guard = StackGuard(stack, &binding);
+			let _guard = StackGuard(stack);
  			
 			match func(&binding) {
 				Err(crate::Error::Return { to, obj }) if to.as_ref().eq_obj(binding.as_ref())?
@@ -131,6 +136,11 @@ impl Binding {
 		})
 	}
 
+	/// Replaces the current thread's stack with `new`, returning the old one.
+	pub(crate) fn replace_stack(new: Vec<Binding>) -> Vec<Binding> {
+		Self::with_stack(|stack| std::mem::replace(&mut *stack.write(), new))
+	}
+
 	#[inline]
 	pub fn with_stack<F: FnOnce(&RwLock<Stack>) -> R, R>(func: F) -> R {
 		thread_local!(
@@ -152,7 +162,7 @@ impl From<Object> for Binding {
 impl AsRef<Object> for Binding {
 	#[inline]
 	fn as_ref(&self) -> &Object {
-		&self
+		self
 	}
 }
 
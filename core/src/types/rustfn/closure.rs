@@ -4,9 +4,12 @@ use std::fmt::{self, Debug, Formatter};
 use std::hash::{Hash, Hasher};
 use tracing::instrument;
 
+type ClosureFn = dyn Fn(&[Object], Args) -> crate::Result<Object> + Send + Sync;
+
 #[derive(Clone)]
 pub struct RustClosure { 
-	func: Arc<dyn Fn(Args) -> crate::Result<Object> + Send + Sync>,
+	func: Arc<ClosureFn>,
+	captures: Arc<[Object]>,
 	id: usize
 }
 
@@ -37,21 +40,40 @@ impl Hash for RustClosure {
 
 impl RustClosure {
 	pub fn new(func: impl Fn(Args) -> crate::Result<Object> + Send + Sync + 'static) -> Self {
+		Self::capturing(vec![], move |_, args| func(args))
+	}
+
+	/// Creates a closure that's given `captures` whenever it's called.
+	///
+	/// Objects the closure needs should be passed this way instead of being moved into it, so that
+	/// [`gc::leaked_objects`](crate::gc::leaked_objects) can see that they're referenced.
+	pub fn capturing<F>(captures: Vec<Object>, func: F) -> Self
+	where
+		F: Fn(&[Object], Args) -> crate::Result<Object> + Send + Sync + 'static
+	{
 		static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
 		Self {
 			func: Arc::new(func),
+			captures: captures.into(),
 			id: NEXT_ID.fetch_add(1, Ordering::Relaxed)
 		}
 	}
 
 	#[inline]
 	pub fn call(&self, args: Args) -> crate::Result<Object> {
-		(self.func)(args)
+		(self.func)(&self.captures, args)
+	}
+
+	/// Gets the objects this closure captures, which are shared by every clone of it.
+	#[inline]
+	pub(crate) fn captures(&self) -> &Arc<[Object]> {
+		&self.captures
 	}
 }
 
 
 impl RustClosure {
+	/// Gets a debugging representation of the closure.
 	#[instrument(name="RustClosure::inspect", level="trace", skip(this), fields(self=?this))]
 	pub fn qs_inspect(this: &Object, _: Args) -> crate::Result<Object> {
 		let this = this.try_downcast::<Self>()?;
@@ -59,6 +81,7 @@ impl RustClosure {
 		Ok(format!("{:?}", *this).into())
 	}
 
+	/// Calls the closure with the arguments.
 	#[instrument(name="RustClosure::()", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_call(this: &Object, args: Args) -> crate::Result<Object> {
 		let this = this.try_downcast::<Self>()?;
@@ -69,6 +92,8 @@ impl RustClosure {
 
 impl_object_type! {
 for RustClosure [(parents super::super::Function)]:
+	/// Gets a debugging representation of the closure.
 	"inspect" => method Self::qs_inspect,
+	/// Calls the closure with the arguments.
 	"()" => method Self::qs_call,
 }
@@ -1,4 +1,6 @@
-use crate::{Object, Result, Args};
+use crate::{Object, Result, Args, Binding};
+use crate::error::KeyError;
+use crate::obj::Value;
 use crate::types::Text;
 use tracing::instrument;
 
@@ -6,6 +8,7 @@ use tracing::instrument;
 pub struct Scope;
 
 impl Scope {
+	/// Gets the scope's `name`, or `"<unnamed scope>"` if it doesn't have one.
 	#[instrument(name="Scope::@text", level="trace", skip(this), fields(self=?this))]
 	pub fn qs_at_text(this: &Object, _: Args) -> crate::Result<Object> {
 		const UNNAMED_SCOPE: Text = Text::const_new("<unnamed scope>");
@@ -13,33 +16,68 @@ impl Scope {
 		Ok(this.get_attr_lit("name").unwrap_or_else(|_| UNNAMED_SCOPE.into()))
 	}
 
-	#[instrument(name="Scope::super", level="trace", skip(_this, _args), fields(self=?_this, args=?_args))]
-	pub fn qs_super(_this: &Object, _args: Args) -> Result<Object> {
-		// let attr = args.try_arg(0)?;
-		// let mut args = argstry_.args(1..)?;
-		// args.add_this(this.clone());
-
-		// let default_attr = this.get_attr_old(attr)?;
-
-		// for parent in this.get_attr_old("__parents__")?.downcast_call::<List>()?.as_ref().iter() {
-		// 	if parent.has_attr_old(attr)? {
-		// 		// TODO: this
-		// 		let attr = parent.get_attr_old(attr)?;
-		// 		if attr.is_identical(&default_attr) {
-		// 			continue;
-		// 		}
-		// 		return parent.get_attr_old(&attr)?.call_attr_old_old("()", args);
-		// 	}
-		// }
+	/// Calls the next definition of `attr` in the `__mro__` of the current receiver.
+	///
+	/// The receiver is the first argument of the current stackframe (`_0`), and the definition that's
+	/// currently executing is found by looking through the stack for a frame that's running one of
+	/// the receiver's definitions of `attr`. If none is, the receiver's own definition is used.
+	#[instrument(name="Scope::super", level="trace", skip(args), fields(?args))]
+	pub fn qs_super(args: Args) -> Result<Object> {
+		let attr = args.try_arg(0)?;
+		let rest = args.try_args(1..).unwrap_or_default();
+		let receiver = Binding::instance().get_attr_lit("_0")?;
+
+		let definitions = find_definitions(&receiver, attr)?;
+
+		let current = current_definition(&definitions)?.unwrap_or(0);
+
+		definitions.into_iter()
+			.nth(current + 1)
+			.ok_or_else(|| KeyError::DoesntExist { attr: attr.clone(), obj: receiver.clone() })?
+			.call(&receiver, rest.shorten())
+	}
+}
 
-		todo!()
-		// Err(crate::Error::Messaged(
-			// format!("attr {:?} does not exist for {:?} or its parents", attr, this).into()))
-		// this.get_attr_old("__parent__")?
-		// 	.get_attr_old("__parent__")?
-		// 	.call_attr_old_old(attr, args)
+/// Collects every definition of `attr` on `obj` and its ancestors, in method resolution order.
+fn find_definitions(obj: &Object, attr: &Object) -> Result<Vec<Value>> {
+	let mut defns = vec![];
+
+	for ancestor in std::iter::once(obj).chain(obj.mro()?.iter()) {
+		if let Some(value) = ancestor.get_own_definition(attr)? {
+			defns.push(value);
+		}
 	}
 
+	Ok(defns)
+}
+
+/// Checks to see if `defn` is the function being run by a stackframe with the parent `running`.
+///
+/// Functions created with `->` run their `block` rather than themselves, so we check that too.
+fn is_running(defn: &Value, running: &Object) -> bool {
+	match defn {
+		Value::Object(obj) => obj.is_identical(running) || obj.get_attr_lit("block")
+			.is_ok_and(|block| block.is_identical(running)),
+		Value::RustFn(_) | Value::Property(_) => false
+	}
+}
+
+/// Finds the index of the innermost definition that's currently being executed, if any.
+///
+/// Each stackframe has the block it's executing as one of its parents, so we just look for the
+/// first frame that has one of the definitions as a parent.
+fn current_definition(defns: &[Value]) -> Result<Option<usize>> {
+	for frame in Binding::stack() {
+		for running in frame.parents()? {
+			let idx = defns.iter().position(|defn| is_running(defn, &running));
+
+			if idx.is_some() {
+				return Ok(idx);
+			}
+		}
+	}
+
+	Ok(None)
 }
 
 impl_object_type!{
@@ -52,18 +90,115 @@ for Scope {
 		// lazy_static! {
 			// static ref SCOPE: Object = Object::new_with_parent(Scope, vec![Scope::mapping()]);
 		// }
-		Object::new_with_parent(crate::types::Class::new("Scope"), vec![
-			Scope::mapping(),
-			crate::types::Kernel::mapping()
-		])
+		let parents =
+			match crate::sandbox::current_kernel() {
+				Some(kernel) => vec![kernel],
+				None => vec![Scope::mapping().clone(), crate::types::Kernel::mapping().clone()]
+			};
+
+		Object::new_with_parent(crate::types::Class::new("Scope"), parents)
 		// SCOPE.deep_clone()
 	}
 }
 [(init_parents super::Kernel super::Basic) (parents super::Basic)]:
+	/// Gets the scope's `name`, or `"<unnamed scope>"` if it doesn't have one.
 	"@text" => method Self::qs_at_text,
-	"super" => method Self::qs_super,
+	/// Calls the next definition of `attr` in the `__mro__` of the current receiver.
+	"super" => function Self::qs_super,
 	"__should_be_bound__" => const false,
+	/// Replaces the current stackframe with the first argument, returning it.
 	"set_scope" => method |_, args| {
 		Ok(crate::Binding::set_binding(args.try_arg(0)?.clone()).into())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::types::RustFn;
+
+	fn greets(name: &'static str, parents: Vec<Object>, greet: Option<Object>) -> Object {
+		let obj = Object::new_with_parent((), parents);
+		obj.set_attr_lit("name", name.into()).unwrap();
+
+		if let Some(greet) = greet {
+			obj.set_attr_lit("greet", greet).unwrap();
+		}
+
+		obj
+	}
+
+	fn greeting(name: &'static str) -> Object {
+		match name {
+			"a" => RustFn::function("a", |_| Ok("a".into())),
+			"b" => RustFn::function("b", |_| Ok("b".into())),
+			"c" => RustFn::function("c", |_| Ok("c".into())),
+			// echoes the first argument after the receiver.
+			_ => RustFn::function("echo", |args| Ok(args.try_arg(1)?.clone()))
+		}.into()
+	}
+
+	/// Calls `super(args...)` from within a stackframe whose receiver is `receiver`, which is
+	/// running `running` if it's given.
+	fn call_super(receiver: &Object, running: Option<Object>, args: Args) -> Result<Object> {
+		Binding::new_stackframe(running, Args::new(vec![receiver]), |_| Scope::qs_super(args))
+	}
+
+	fn text(obj: Result<Object>) -> String {
+		obj.unwrap().downcast::<Text>().unwrap().to_string()
+	}
+
+	#[test]
+	fn calls_the_next_definition() {
+		crate::init();
+
+		let (a, b, c) = (greeting("a"), greeting("b"), greeting("c"));
+		let grandparent = greets("grandparent", vec![], Some(a));
+		let parent = greets("parent", vec![grandparent], Some(b.clone()));
+		let child = greets("child", vec![parent], Some(c.clone()));
+
+		// outside of any definition, the receiver's own definition is the current one.
+		assert_eq!(text(call_super(&child, None, args!("greet"))), "b");
+
+		// otherwise, it's the one after the definition that's being run.
+		assert_eq!(text(call_super(&child, Some(c), args!("greet"))), "b");
+		assert_eq!(text(call_super(&child, Some(b), args!("greet"))), "a");
+	}
+
+	#[test]
+	fn follows_the_mro() {
+		crate::init();
+
+		let named = greets("named", vec![], Some(greeting("a")));
+		let greeter = greets("greeter", vec![named.clone()], None);
+		let shouter = greets("shouter", vec![named], Some(greeting("b")));
+		let robot = greets("robot", vec![greeter, shouter], Some(greeting("c")));
+
+		// `shouter` comes before `named`, even though `greeter` inherits from it.
+		assert_eq!(text(call_super(&robot, None, args!("greet"))), "b");
+	}
+
+	#[test]
+	fn passes_arguments() {
+		crate::init();
+
+		let parent = greets("parent", vec![], Some(greeting("echo")));
+		let child = greets("child", vec![parent], Some(greeting("c")));
+
+		assert_eq!(text(call_super(&child, None, args!("greet", "hi"))), "hi");
+	}
+
+	#[test]
+	fn errors_without_a_next_definition() {
+		crate::init();
+
+		let a = greeting("a");
+		let parent = greets("parent", vec![], Some(a.clone()));
+		let child = greets("child", vec![parent], None);
+
+		assert_matches!(call_super(&child, Some(a), args!("greet")),
+			Err(crate::Error::KeyError(KeyError::DoesntExist { .. })));
+		assert_matches!(call_super(&child, None, args!("missing")),
+			Err(crate::Error::KeyError(KeyError::DoesntExist { .. })));
+	}
+}
@@ -29,7 +29,9 @@ impl Tcp {
 
 impl_object_type!{
 for Tcp [(parents super::Basic)]:
+	/// Connects to the address given as the first argument.
 	"()" => method Self::qs_call,
+	/// Makes an HTTP GET request to `this`'s `@text`, returning the response's body.
 	"get" => method |this, _| {
 		Ok(ureq::get(this.call_downcast::<Text>()?.as_ref())
 			.call()
@@ -37,15 +39,17 @@ for Tcp [(parents super::Basic)]:
 			.unwrap()
 			.into())
 	},
+	/// Writes the first argument's `@text`, returning the amount of bytes written.
 	"write" => method |this, args| {
 		let arg = args.try_arg(0)?.call_downcast::<Text>()?.clone();
 
 		this.try_downcast_mut::<Self>().and_then(|tcp| {
-			tcp.0.lock().unwrap().write(&arg.as_ref().as_ref())
+			tcp.0.lock().unwrap().write(arg.as_ref().as_ref())
 				.map(Object::from)
 				.map_err(|err| crate::Error::Messaged(err.to_string()))
 		})
 	},
+	/// Reads lines until there's a blank line or the connection is closed, returning what was read.
 	"read" => method |this, _| -> Result<Object> {
 		this.try_downcast_mut::<Self>().and_then(|tcp| {
 			use std::io::{BufReader, BufRead};
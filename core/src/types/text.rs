@@ -6,6 +6,9 @@ use std::borrow::Cow;
 use std::fmt::{self, Debug, Display, Formatter};
 use std::convert::TryFrom;
 use tracing::instrument;
+use unicode_segmentation::UnicodeSegmentation;
+
+mod format;
 
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 pub struct Text(Cow<'static, str>);
@@ -155,8 +158,8 @@ impl std::ops::AddAssign<&Self> for Text {
 impl From<&Text> for List {
 	fn from(text: &Text) -> Self {
 		text.as_ref()
-			.chars()
-			.map(|chr| chr.to_string().into())
+			.graphemes(true)
+			.map(Object::from)
 			.collect()
 	}
 }
@@ -184,13 +187,40 @@ impl<'a> TryFrom<&'a Text> for Number {
 	}
 }
 
+/// Unicode views of the text.
+///
+/// Unless otherwise noted, Quest methods operate on _graphemes_ (ie user-perceived characters).
+/// The `chars` and `bytes` methods expose the codepoint and byte views respectively.
 impl Text {
-	pub fn shift(&mut self) -> Option<char> {
-		if self.is_empty() {
-			None
-		} else {
-			Some(self.as_mut().remove(0))
+	/// An iterator over the extended grapheme clusters of the text.
+	pub fn graphemes(&self) -> unicode_segmentation::Graphemes<'_> {
+		self.as_ref().graphemes(true)
+	}
+
+	/// The amount of graphemes within the text.
+	#[must_use]
+	pub fn grapheme_len(&self) -> usize {
+		self.graphemes().count()
+	}
+
+	/// Converts a grapheme offset into a byte offset, returning `None` if it's out of bounds.
+	fn byte_index(&self, grapheme_idx: usize) -> Option<usize> {
+		if grapheme_idx == 0 {
+			return Some(0);
 		}
+
+		self.as_ref()
+			.grapheme_indices(true)
+			.map(|(idx, grapheme)| idx + grapheme.len())
+			.nth(grapheme_idx - 1)
+	}
+}
+
+impl Text {
+	pub fn shift(&mut self) -> Option<String> {
+		let len = self.graphemes().next()?.len();
+
+		Some(self.as_mut().drain(..len).collect())
 	}
 
 	pub fn inspect(&self) -> Self {
@@ -201,8 +231,10 @@ impl Text {
 		self.as_mut().insert_str(0, val);
 	}
 
-	pub fn pop(&mut self) -> Option<char> {
-		self.as_mut().pop()
+	pub fn pop(&mut self) -> Option<String> {
+		let start = self.len() - self.graphemes().next_back()?.len();
+
+		Some(self.as_mut().split_off(start))
 	}
 
 	pub fn push_str(&mut self, s: &str) {
@@ -214,7 +246,7 @@ impl Text {
 	}
 
 	pub fn reverse(&self) -> Self {
-		self.0.as_ref().chars().rev().collect()
+		self.graphemes().rev().collect::<String>().into()
 	}
 
 	pub fn strip(&self) -> Self {
@@ -230,9 +262,110 @@ impl Text {
 		if let Some(on) = on {
 			self.0.split(on).map(ToOwned::to_owned).collect()
 		} else {
-			self.0.chars().map(|c| c.to_string()).collect()
+			self.graphemes().map(ToOwned::to_owned).collect()
+		}
+	}
+
+	/// Uppercases the first grapheme and lowercases the rest.
+	pub fn capitalize(&self) -> Self {
+		let mut graphemes = self.graphemes();
+
+		match graphemes.next() {
+			Some(first) => (first.to_uppercase() + &graphemes.as_str().to_lowercase()).into(),
+			None => Self::default()
+		}
+	}
+
+	/// Collapses runs of identical graphemes into a single grapheme.
+	///
+	/// If `only` is given, only graphemes contained within it are collapsed.
+	pub fn squeeze(&self, only: Option<&str>) -> Self {
+		let only = only.map(|only| only.graphemes(true).collect::<Vec<_>>());
+		let mut result = String::with_capacity(self.len());
+		let mut last = None;
+
+		for grapheme in self.graphemes() {
+			let squeezable = only.as_ref().is_none_or(|only| only.contains(&grapheme));
+
+			if !(squeezable && last == Some(grapheme)) {
+				result.push_str(grapheme);
+			}
+
+			last = Some(grapheme);
+		}
+
+		result.into()
+	}
+
+	/// Pads the text to `width` graphemes, placing `left` and `right` fractions of the padding on
+	/// either side. The `padding` is repeated as needed, and truncated if it doesn't fit evenly.
+	fn pad(&self, width: usize, padding: &str, left: bool, right: bool) -> Self {
+		let amount = width.saturating_sub(self.grapheme_len());
+		let fill = |amount: usize| padding.graphemes(true).cycle().take(amount).collect::<String>();
+
+		let (lhs, rhs) =
+			match (left, right) {
+				(true, true) => (amount / 2, amount - amount / 2),
+				(true, false) => (amount, 0),
+				(false, _) => (0, amount),
+			};
+
+		format!("{}{}{}", fill(lhs), self, fill(rhs)).into()
+	}
+
+	/// Finds the first grapheme index of `needle` at or after the grapheme index `start`.
+	///
+	/// Only matches that start and end on grapheme boundaries count, so `e` isn't found in `é` when
+	/// it's written with a combining accent.
+	pub fn find(&self, needle: &str, start: usize) -> Option<usize> {
+		let haystack = self.as_ref();
+		let boundaries = self.grapheme_boundaries();
+		let mut offset = self.byte_index(start)?;
+
+		loop {
+			let idx = offset + haystack[offset..].find(needle)?;
+
+			if let Some(grapheme_idx) = Self::grapheme_match(&boundaries, idx, needle) {
+				return Some(grapheme_idx);
+			}
+
+			offset = idx + haystack[idx..].chars().next()?.len_utf8();
+		}
+	}
+
+	/// Finds the last grapheme index of `needle`; see [`find`](Self::find).
+	pub fn rfind(&self, needle: &str) -> Option<usize> {
+		let haystack = self.as_ref();
+		let boundaries = self.grapheme_boundaries();
+		let mut end = haystack.len();
+
+		loop {
+			let idx = haystack[..end].rfind(needle)?;
+
+			if let Some(grapheme_idx) = Self::grapheme_match(&boundaries, idx, needle) {
+				return Some(grapheme_idx);
+			}
+
+			// any earlier match has to end before `needle`'s last character does.
+			end = idx + needle.len() - needle.chars().next_back()?.len_utf8();
 		}
 	}
+
+	/// The byte offsets of the start of each grapheme, followed by the length of the text.
+	fn grapheme_boundaries(&self) -> Vec<usize> {
+		self.as_ref()
+			.grapheme_indices(true)
+			.map(|(idx, _)| idx)
+			.chain(std::iter::once(self.len()))
+			.collect()
+	}
+
+	/// Gets the grapheme index of `needle` matched at the byte offset `idx`, if it's on grapheme
+	/// boundaries.
+	fn grapheme_match(boundaries: &[usize], idx: usize, needle: &str) -> Option<usize> {
+		let grapheme_idx = boundaries.binary_search(&idx).ok()?;
+		boundaries.binary_search(&(idx + needle.len())).ok().and(Some(grapheme_idx))
+	}
 }
 
 impl std::iter::FromIterator<char> for Text {
@@ -274,9 +407,8 @@ impl Text {
 	pub fn qs_at_iter(this: &Object, _: Args) -> crate::Result<Object> {
 		let this = this.try_downcast::<Self>()?;
 
-		Ok(crate::types::Iter::new(
-			this.as_ref().chars().collect::<Vec<_>>().into_iter().
-				map(From::from).map(Ok)).into())
+		Ok(crate::types::Iter::with_objects(
+			this.graphemes().map(Object::from).collect::<Vec<_>>()).into())
 	}
 
 	#[instrument(name="Text::@bool", level="trace", skip(this), fields(self=?this))]
@@ -381,20 +513,34 @@ impl Text {
 	pub fn qs_len(this: &Object, _: Args) -> crate::Result<Object> {
 		let this = this.try_downcast::<Self>()?;
 
+		Ok(this.grapheme_len().into())
+	}
+
+	#[instrument(name="Text::charsize", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_charsize(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(this.as_ref().chars().count().into())
+	}
+
+	#[instrument(name="Text::bytesize", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_bytesize(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
 		Ok(this.len().into())
 	}
 
-	fn correct_index(&self, idx: isize) -> Option<usize> {
+	fn correct_index(len: usize, idx: isize) -> Option<usize> {
 		if !idx.is_negative() {
-			if (idx as usize) < self.len() {
+			if (idx as usize) < len {
 				Some(idx as usize)
 			} else {
 				None
 			}
 		} else {
 			let idx = (-idx) as usize;
-			if idx <= self.len() {
-				Some(self.len() - idx)
+			if idx <= len {
+				Some(len - idx)
 			} else {
 				None
 			}
@@ -413,25 +559,26 @@ impl Text {
 			.map(isize::try_from)
 			.transpose()?;
 
+		let graphemes = this.graphemes().collect::<Vec<_>>();
+
 		let start =
-			if let Some(start) = this.correct_index(start) {
+			if let Some(start) = Self::correct_index(graphemes.len(), start) {
 				start
 			} else {
 				return Ok(Object::default())
 			};
 
 		match end {
-			None =>
-				Ok(this.0.chars()
-					.nth(start)
-					.map(|x| x.to_string().into())
-					.unwrap_or_default()),
+			None => Ok(graphemes[start].into()),
 			Some(end) => {
-				let end = this.correct_index(end).map(|x| x + 1).unwrap_or_else(|| this.len());
+				let end = Self::correct_index(graphemes.len(), end)
+					.map(|x| x + 1)
+					.unwrap_or_else(|| graphemes.len());
+
 				if end < start {
 					Ok(Object::default())
 				} else {
-					Ok(this.0[start..end].to_owned().into())
+					Ok(graphemes[start..end].concat().into())
 				}
 			}
 		}
@@ -461,7 +608,6 @@ impl Text {
 	pub fn qs_pop(this: &Object, _: Args) -> crate::Result<Object> {
 		Ok(this.try_downcast_mut::<Self>()?
 			.pop()
-			.map(|c| c.to_string())
 			.map(Object::from)
 			.unwrap_or_default())
 	}
@@ -505,7 +651,9 @@ impl Text {
 		let this = this.try_downcast::<Self>()?;
 
 		Ok(
-			if let Some(on) = args.arg(0) {
+			if let Some(rxp) = args.arg(0).and_then(|on| on.downcast::<Regex>()) {
+				rxp.split(this.as_ref(), None).into_iter().map(ToOwned::to_owned).collect()
+			} else if let Some(on) = args.arg(0) {
 				this.split(Some(on.call_downcast::<Self>()?.as_ref()))
 			} else {
 				this.split(None)
@@ -552,11 +700,12 @@ impl Text {
 	pub fn qs_sub(this: &Object, args: Args) -> crate::Result<Object> {
 		let text = this.call_downcast::<Self>()?;
 		let pat = args.try_arg(0)?;
-		let repl = args.try_arg(1)?.call_downcast::<Text>()?;
+		let repl = args.try_arg(1)?;
 
 		if let Some(rxp) = pat.downcast::<Regex>() {
-			Ok(rxp.sub(text.as_ref(), repl.as_ref()).to_string().into())
+			Ok(rxp.replace_obj(text.as_ref(), repl, Some(1))?.into())
 		} else {
+			let repl = repl.call_downcast::<Text>()?;
 			Ok(text.0.replacen(pat.call_downcast::<Text>()?.as_ref(), repl.as_ref(), 1).into())
 		}
 	}
@@ -565,15 +714,173 @@ impl Text {
 	pub fn qs_gsub(this: &Object, args: Args) -> crate::Result<Object> {
 		let text = this.call_downcast::<Self>()?;
 		let pat = args.try_arg(0)?;
-		let repl = args.try_arg(1)?.call_downcast::<Text>()?;
+		let repl = args.try_arg(1)?;
 
 		if let Some(rxp) = pat.downcast::<Regex>() {
-			Ok(rxp.gsub(text.as_ref(), repl.as_ref()).to_string().into())
+			Ok(rxp.replace_obj(text.as_ref(), repl, None)?.into())
 		} else {
+			let repl = repl.call_downcast::<Text>()?;
 			Ok(text.0.replace(pat.call_downcast::<Text>()?.as_ref(), repl.as_ref()).into())
 		}
 	}
 
+	/// Formats `this` using the arguments given.
+	///
+	/// See the [`format`] module for the syntax of format strings. Named fields are looked up in
+	/// the last argument's attributes, and then in the current scope.
+	#[instrument(name="Text::format", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_format(this: &Object, args: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+		let scope = Binding::instance();
+
+		Ok(format::format(this.as_ref(), args.as_ref(), scope.as_ref())?.into())
+	}
+
+	/// Formats `this` with the given argument; [`List`]s are spread into positional arguments.
+	#[instrument(name="Text::%", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_mod(this: &Object, args: Args) -> crate::Result<Object> {
+		let arg = args.try_arg(0)?;
+
+		if let Some(list) = arg.downcast::<List>() {
+			let list = list.as_ref().to_vec();
+			Self::qs_format(this, Args::new(list.iter().collect::<Vec<_>>()))
+		} else {
+			Self::qs_format(this, Args::new(vec![arg]))
+		}
+	}
+
+	#[instrument(name="Text::chars", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_chars(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(crate::types::Iter::with_objects(
+			this.as_ref().chars().map(Object::from).collect::<Vec<_>>()).into())
+	}
+
+	#[instrument(name="Text::bytes", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_bytes(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(crate::types::Iter::with_objects(
+			this.as_ref().bytes().map(Object::from).collect::<Vec<_>>()).into())
+	}
+
+	#[instrument(name="Text::lines", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_lines(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(crate::types::Iter::with_objects(
+			this.as_ref().lines().map(Object::from).collect::<Vec<_>>()).into())
+	}
+
+	#[instrument(name="Text::upcase", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_upcase(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(this.as_ref().to_uppercase().into())
+	}
+
+	#[instrument(name="Text::downcase", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_downcase(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(this.as_ref().to_lowercase().into())
+	}
+
+	#[instrument(name="Text::capitalize", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_capitalize(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(this.capitalize().into())
+	}
+
+	fn qs_pad(this: &Object, args: Args, left: bool, right: bool) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+		let width = usize::try_from(*args.try_arg(0)?.call_downcast::<Number>()?)?;
+		let padding =
+			match args.arg(1) {
+				Some(padding) => padding.call_downcast::<Self>()?.to_string(),
+				None => " ".to_string()
+			};
+
+		if padding.is_empty() {
+			return Err(ValueError::Messaged("padding cannot be empty".to_string()).into());
+		}
+
+		Ok(this.pad(width, &padding, left, right).into())
+	}
+
+	#[instrument(name="Text::ljust", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_ljust(this: &Object, args: Args) -> crate::Result<Object> {
+		Self::qs_pad(this, args, false, true)
+	}
+
+	#[instrument(name="Text::rjust", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_rjust(this: &Object, args: Args) -> crate::Result<Object> {
+		Self::qs_pad(this, args, true, false)
+	}
+
+	#[instrument(name="Text::center", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_center(this: &Object, args: Args) -> crate::Result<Object> {
+		Self::qs_pad(this, args, true, true)
+	}
+
+	#[instrument(name="Text::find", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_find(this: &Object, args: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+		let needle = args.try_arg(0)?.call_downcast::<Self>()?;
+		let start =
+			match args.arg(1) {
+				Some(start) => isize::try_from(*start.call_downcast::<Number>()?)?,
+				None => 0
+			};
+
+		let len = this.grapheme_len();
+		let start =
+			if start.is_negative() {
+				len.saturating_sub(start.unsigned_abs())
+			} else {
+				start as usize
+			};
+
+		Ok(this.find(needle.as_ref(), start).map(Object::from).unwrap_or_default())
+	}
+
+	#[instrument(name="Text::rfind", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_rfind(this: &Object, args: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+		let needle = args.try_arg(0)?.call_downcast::<Self>()?;
+
+		Ok(this.rfind(needle.as_ref()).map(Object::from).unwrap_or_default())
+	}
+
+	#[instrument(name="Text::squeeze", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_squeeze(this: &Object, args: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		if let Some(only) = args.arg(0) {
+			Ok(this.squeeze(Some(only.call_downcast::<Self>()?.as_ref())).into())
+		} else {
+			Ok(this.squeeze(None).into())
+		}
+	}
+
+	#[instrument(name="Text::starts_with?", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_starts_with_q(this: &Object, args: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+		let prefix = args.try_arg(0)?.call_downcast::<Self>()?;
+
+		Ok(this.as_ref().starts_with(prefix.as_ref()).into())
+	}
+
+	#[instrument(name="Text::ends_with?", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_ends_with_q(this: &Object, args: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+		let suffix = args.try_arg(0)?.call_downcast::<Self>()?;
+
+		Ok(this.as_ref().ends_with(suffix.as_ref()).into())
+	}
+
 	#[instrument(name="Text::~", level="trace", skip(this), fields(self=?this))]
 	pub fn qs_bitnot(this: &Object, args: Args) -> crate::Result<Object> {
 		let this = this.clone();
@@ -597,7 +904,7 @@ for Text
 		}
 
 		// this is a hack until I get `quest_core::init()` working
-		if self.as_ref().starts_with(|x| 'A' <= x && x <= 'Z') {
+		if self.as_ref().starts_with(|x: char| x.is_ascii_uppercase()) {
 			return Object::new_with_parent(self, vec![Text::mapping()]);
 		}
 
@@ -613,48 +920,135 @@ for Text
 	}
 }
 [(init_parent super::Basic super::Comparable super::Iterable) (parents super::Basic) (convert "@text")]:
+	/// Simply returns `this`.
 	"@text" => method Self::qs_at_text,
+	/// Compiles `this` into a [`Regex`].
 	"@regex" => method Self::qs_at_regex,
+	/// Gets `this` surrounded by quotes, with special characters escaped.
 	"inspect"  => method Self::qs_inspect,
+	/// Parses `this` as a [`Number`], with an optional base.
 	"@num"    => method Self::qs_at_num,
+	/// Gets a [`List`] of the graphemes in `this`.
 	"@list"   => method Self::qs_at_list,
+	/// Checks to see if `this` isn't empty.
 	"@bool"   => method Self::qs_at_bool,
+	/// Gets an [`Iter`](crate::types::Iter) over the graphemes in `this`.
 	"@iter"   => method Self::qs_at_iter,
+	/// Gets the variable named `this` from the current scope.
 	"()"      => method Self::qs_call,
 
+	/// Creates a function that calls the attribute named `this` on its first argument, passing
+	/// along the rest.
 	"~"       => method Self::qs_bitnot,
+	/// Assigns the first argument to the variable named `this`, in the second argument if given
+	/// and the current scope otherwise, returning the first argument.
 	"="       => method Self::qs_assign,
+	/// Creates a function that binds its argument to the name `this`, then runs a block.
 	"->"      => method Self::qs_arrow,
+	/// Compares `this` to the first argument's `@text`, returning [`Null`](crate::types::Null) if
+	/// it doesn't have one.
 	"<=>"     => method Self::qs_cmp,
+	/// Checks to see if the first argument is a [`Text`] with the same contents as `this`.
 	"=="      => method Self::qs_eql,
+	/// Concatenates `this` and the first argument.
 	"+"       => method Self::qs_add,
+	/// Appends the first argument to `this`, in place, returning `this`.
 	"+="      => method Self::qs_add_assign,
 
+	/// Gets the amount of graphemes in `this`.
 	"len"     => method Self::qs_len,
+	/// Gets the amount of unicode codepoints in `this`.
+	"charsize" => method Self::qs_charsize,
+	/// Gets the amount of bytes in `this`.
+	"bytesize" => method Self::qs_bytesize,
+	/// Gets the grapheme at an index, or the graphemes from a start to an inclusive end, returning
+	/// [`Null`](crate::types::Null) if out of range.
 	"get"     => method Self::qs_get,
+	/// Gets the grapheme at an index, or the graphemes from a start to an inclusive end, returning
+	/// [`Null`](crate::types::Null) if out of range.
 	"[]"      => method Self::qs_get,
+	/// Not yet implemented.
 	"[]="  => method |this, args| {
 		let mut arg = args.try_arg(0)?.downcast_mut::<crate::types::List>().expect("`[]=` called without List.");
 		arg.push(args.try_arg(1)?.clone());
 
 		Self::qs_set(this, arg.as_ref().iter().collect())
 	},
+	/// Not yet implemented.
 	"set"     => method Self::qs_set,
+	/// Appends the first argument to `this`, in place, returning `this`.
 	"push"    => method Self::qs_push,
+	/// Removes the last grapheme from `this`, returning it or [`Null`](crate::types::Null) if empty.
 	"pop"     => method Self::qs_pop,
+	/// Prepends the first argument to `this`, in place, returning `this`.
 	"unshift" => method Self::qs_unshift,
+	/// Removes the first grapheme from `this`, returning it or [`Null`](crate::types::Null) if
+	/// empty.
 	"shift"   => method Self::qs_shift,
+	/// Removes everything from `this`, returning `this`.
 	"clear"   => method Self::qs_clear,
+	/// Splits `this` on the first argument (a [`Text`] or [`Regex`]), or into graphemes if it's
+	/// not given.
 	"split"   => method Self::qs_split,
+	/// Gets `this` with its graphemes in reverse order.
 	"reverse" => method Self::qs_reverse, 
+	/// Gets `this` without leading or trailing whitespace.
 	"strip"   => method Self::qs_strip,
+	/// Replaces the contents of `this` with the first argument, returning `this`.
 	"replace" => method Self::qs_replace,
+	/// Replaces the first match of a [`Text`] or [`Regex`] with the second argument.
 	"sub" => method Self::qs_sub,
+	/// Replaces every match of a [`Text`] or [`Regex`] with the second argument.
 	"gsub" => method Self::qs_gsub,
-
+	/// Formats `this` using the arguments given.
+	"format" => method Self::qs_format,
+	/// Formats `this` with the given argument; [`List`]s are spread into positional arguments.
+	"%"       => method Self::qs_mod,
+
+	/// Gets an [`Iter`](crate::types::Iter) over the graphemes in `this`.
+	"graphemes" => method Self::qs_at_iter,
+	/// Gets an [`Iter`](crate::types::Iter) over the unicode codepoints in `this`.
+	"chars"   => method Self::qs_chars,
+	/// Gets an [`Iter`](crate::types::Iter) over the bytes in `this`, as [`Number`]s.
+	"bytes"   => method Self::qs_bytes,
+	/// Gets an [`Iter`](crate::types::Iter) over the lines in `this`, without their line endings.
+	"lines"   => method Self::qs_lines,
+	/// Gets `this` in uppercase.
+	"upcase"  => method Self::qs_upcase,
+	/// Gets `this` in lowercase.
+	"downcase" => method Self::qs_downcase,
+	/// Gets `this` with its first grapheme uppercased and the rest lowercased.
+	"capitalize" => method Self::qs_capitalize,
+	/// Pads the end of `this` to the first argument's width, with the second argument (or spaces).
+	"ljust"   => method Self::qs_ljust,
+	/// Pads the start of `this` to the first argument's width, with the second argument (or
+	/// spaces).
+	"rjust"   => method Self::qs_rjust,
+	/// Pads both sides of `this` to the first argument's width, with the second argument (or
+	/// spaces).
+	"center"  => method Self::qs_center,
+	/// Finds the first index of the first argument, starting at the second argument (or `0`),
+	/// returning [`Null`](crate::types::Null) if it's not found.
+	"find"    => method Self::qs_find,
+	/// Finds the first index of the first argument, starting at the second argument (or `0`),
+	/// returning [`Null`](crate::types::Null) if it's not found.
+	"index_of" => method Self::qs_find,
+	/// Finds the last index of the first argument, returning [`Null`](crate::types::Null) if it's
+	/// not found.
+	"rfind"   => method Self::qs_rfind,
+	/// Collapses runs of identical graphemes, optionally only those in the first argument.
+	"squeeze" => method Self::qs_squeeze,
+	/// Checks to see if `this` starts with the first argument.
+	"starts_with?" => method Self::qs_starts_with_q,
+	/// Checks to see if `this` ends with the first argument.
+	"ends_with?" => method Self::qs_ends_with_q,
+
+	/// Counts the non-overlapping occurrences of the first argument in `this`.
 	"count" => method Self::qs_count,
+	/// Checks to see if `this` is empty.
 	"empty?" => method Self::qs_empty_q,
 
+	/// Checks to see if the first argument is contained within `this`.
 	"includes?" => method |this, args| {
 		let this = this.try_downcast::<Self>()?;
 		let rhs = args.try_arg(0)?.call_downcast::<Self>()?;
@@ -662,3 +1056,72 @@ for Text
 		Ok(this.as_ref().contains(rhs.as_ref()).into())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn graphemes() {
+		let text = Text::from("ae\u{301}z");
+		assert_eq!(text.grapheme_len(), 3);
+		assert_eq!(text.reverse(), Text::from("ze\u{301}a"));
+		assert_eq!(text.byte_index(2), Some(4));
+		assert_eq!(text.find("z", 0), Some(2));
+
+		assert_call_eq!(Text::qs_len(Text::from("e\u{301}")) -> Number, 1);
+		assert_call_eq!(Text::qs_charsize(Text::from("e\u{301}")) -> Number, 2);
+		assert_call_eq!(Text::qs_bytesize(Text::from("e\u{301}")) -> Number, 3);
+		assert_call_eq!(Text::qs_get(Text::from("ae\u{301}z"), 1) -> Text, *"e\u{301}");
+		assert_call_eq!(Text::qs_pop(Text::from("ae\u{301}")) -> Text, *"e\u{301}");
+	}
+
+	#[test]
+	fn case() {
+		assert_call_eq!(Text::qs_upcase(Text::from("straße")) -> Text, *"STRASSE");
+		assert_call_eq!(Text::qs_downcase(Text::from("ÀB")) -> Text, *"àb");
+		assert_call_eq!(Text::qs_capitalize(Text::from("éCOLE")) -> Text, *"École");
+		assert_call_eq!(Text::qs_capitalize(Text::from("")) -> Text, *"");
+	}
+
+	#[test]
+	fn padding() {
+		assert_call_eq!(Text::qs_ljust(Text::from("é"), 3) -> Text, *"é  ");
+		assert_call_eq!(Text::qs_rjust(Text::from("ab"), 5, "xy") -> Text, *"xyxab");
+		assert_call_eq!(Text::qs_center(Text::from("ab"), 5, "*") -> Text, *"*ab**");
+		assert_call_eq!(Text::qs_center(Text::from("abc"), 1) -> Text, *"abc");
+		assert_call_err!(Text::qs_ljust(Text::from("a"), 3, ""), crate::Error::ValueError(..));
+	}
+
+	#[test]
+	fn find() {
+		assert_call_eq!(Text::qs_find(Text::from("héllo"), "l") -> Number, 2);
+		assert_call_eq!(Text::qs_find(Text::from("héllo"), "l", 3) -> Number, 3);
+		assert_call_eq!(Text::qs_rfind(Text::from("héllo"), "l") -> Number, 3);
+		assert_call!(Text::qs_find(Text::from("héllo"), "z") -> crate::types::Null; |_| true);
+	}
+
+	#[test]
+	fn find_combining_characters() {
+		// `e` followed by a combining acute accent is a single grapheme, so it isn't an `e`.
+		let text = Text::from("e\u{301}xe\u{301}e");
+		assert_eq!(text.find("e", 0), Some(3));
+		assert_eq!(text.rfind("e"), Some(3));
+		assert_eq!(text.find("e\u{301}", 1), Some(2));
+		assert_eq!(text.rfind("e\u{301}"), Some(2));
+		assert_eq!(text.find("x", 0), Some(1));
+		assert_eq!(text.find("\u{301}", 0), None);
+		assert_eq!(text.rfind("\u{301}x"), None);
+		assert_eq!(text.find("", 4), Some(4));
+		assert_eq!(text.rfind(""), Some(4));
+
+		assert_call_eq!(Text::qs_find(Text::from("ae\u{301}e"), "e") -> Number, 2);
+		assert_call!(Text::qs_rfind(Text::from("ae\u{301}"), "e") -> crate::types::Null; |_| true);
+	}
+
+	#[test]
+	fn squeeze() {
+		assert_call_eq!(Text::qs_squeeze(Text::from("aaabbba")) -> Text, *"aba");
+		assert_call_eq!(Text::qs_squeeze(Text::from("aaabbba"), "b") -> Text, *"aaaba");
+	}
+}
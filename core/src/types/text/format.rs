@@ -0,0 +1,427 @@
+//! The formatting mini-language used by [`Text::format`](super::Text::qs_format).
+//!
+//! A format string is plain text interspersed with replacement fields surrounded by `{}`. (A
+//! literal brace is written `{{` or `}}`.) Each field has the form `{[argument][:spec]}`:
+//!
+//! - `argument` is either omitted (the next positional argument), a number (the positional
+//!   argument at that index), or a name. Names are looked up in the attributes of the last
+//!   argument, such as a scope returned from a block, and then in the current scope.
+//! - `spec` is `[[fill]align][sign][#][0][width][.precision][type]`, where
+//!   - `align` is one of `<` (left), `>` (right), or `^` (center), and `fill` is any character.
+//!   - `sign` is `+` to always print a sign for numbers.
+//!   - `#` prefixes radix conversions with `0b`, `0o` or `0x`.
+//!   - `0` pads numbers with zeros after the sign.
+//!   - `precision` is the amount of decimal places for numbers, or the maximum length for text.
+//!   - `type` is one of `?` (`inspect`), `b`, `o`, `x`, `X` (radixes), `e`, `E` (exponents) or
+//!     `f` (fixed point). If omitted, the argument is converted via `@text`.
+//!
+//! # Quest Examples
+//! ```quest
+//! assert("{} and {}".format(1, 2) == "1 and 2");
+//! assert("{1}{0}".format("a", "b") == "ba");
+//! assert("{:*^7}".format("hi") == "**hi***");
+//! assert("{:#06x}".format(255) == "0x00ff");
+//! assert("{:.2}".format(3.14159) == "3.14");
+//! assert("{:?}".format("q") == '"q"');
+//! assert("{a}-{b}".format({ a = 1; b = 2; :0 }()) == "1-2");
+//! ```
+
+use crate::{Object, Literal};
+use crate::error::{KeyError, ValueError};
+use crate::types::{Number, Text};
+use crate::types::number::{FloatType, IntegerType};
+use std::convert::TryFrom;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Align {
+	Left,
+	Right,
+	Center
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+	Text,
+	Inspect,
+	Binary,
+	Octal,
+	LowerHex,
+	UpperHex,
+	LowerExp,
+	UpperExp,
+	Fixed
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Spec {
+	fill: char,
+	align: Option<Align>,
+	plus: bool,
+	alternate: bool,
+	zero: bool,
+	width: Option<usize>,
+	precision: Option<usize>,
+	kind: Kind
+}
+
+impl Default for Spec {
+	fn default() -> Self {
+		Self {
+			fill: ' ',
+			align: None,
+			plus: false,
+			alternate: false,
+			zero: false,
+			width: None,
+			precision: None,
+			kind: Kind::Text
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field<'a> {
+	Next,
+	Index(usize),
+	Name(&'a str)
+}
+
+fn bad_format(msg: impl std::fmt::Display) -> crate::Error {
+	ValueError::Messaged(format!("bad format string: {}", msg)).into()
+}
+
+fn parse_align(chr: char) -> Option<Align> {
+	match chr {
+		'<' => Some(Align::Left),
+		'>' => Some(Align::Right),
+		'^' => Some(Align::Center),
+		_ => None
+	}
+}
+
+fn parse_number(spec: &str) -> (Option<usize>, &str) {
+	let end = spec.find(|c: char| !c.is_ascii_digit()).unwrap_or(spec.len());
+
+	if end == 0 {
+		(None, spec)
+	} else {
+		(spec[..end].parse().ok(), &spec[end..])
+	}
+}
+
+impl Spec {
+	fn parse(mut spec: &str) -> crate::Result<Self> {
+		let mut this = Self::default();
+		let mut chars = spec.chars();
+
+		match (chars.next(), chars.next()) {
+			(Some(fill), Some(align)) if parse_align(align).is_some() => {
+				this.fill = fill;
+				this.align = parse_align(align);
+				spec = chars.as_str();
+			},
+			(Some(align), _) if parse_align(align).is_some() => {
+				this.align = parse_align(align);
+				spec = &spec[1..];
+			},
+			_ => {}
+		}
+
+		if let Some(rest) = spec.strip_prefix('+') {
+			this.plus = true;
+			spec = rest;
+		}
+
+		if let Some(rest) = spec.strip_prefix('#') {
+			this.alternate = true;
+			spec = rest;
+		}
+
+		if let Some(rest) = spec.strip_prefix('0') {
+			this.zero = true;
+			spec = rest;
+		}
+
+		let (width, rest) = parse_number(spec);
+		this.width = width;
+		spec = rest;
+
+		if let Some(rest) = spec.strip_prefix('.') {
+			let (precision, rest) = parse_number(rest);
+			this.precision = Some(precision.ok_or_else(|| bad_format("missing precision after `.`"))?);
+			spec = rest;
+		}
+
+		this.kind =
+			match spec {
+				"" => Kind::Text,
+				"?" => Kind::Inspect,
+				"b" => Kind::Binary,
+				"o" => Kind::Octal,
+				"x" => Kind::LowerHex,
+				"X" => Kind::UpperHex,
+				"e" => Kind::LowerExp,
+				"E" => Kind::UpperExp,
+				"f" => Kind::Fixed,
+				other => return Err(bad_format(format_args!("unknown format type `{}`", other)))
+			};
+
+		Ok(this)
+	}
+
+	fn is_numeric(&self) -> bool {
+		!matches!(self.kind, Kind::Text | Kind::Inspect)
+	}
+
+	/// Pads `body` (with an optional `sign` and radix prefix) out to `self.width`.
+	fn pad(&self, sign: &str, body: &str, default_align: Align) -> String {
+		let len = sign.chars().count() + body.chars().count();
+		let padding = self.width.map(|width| width.saturating_sub(len)).unwrap_or(0);
+
+		if self.zero && self.align.is_none() {
+			return format!("{}{}{}", sign, "0".repeat(padding), body);
+		}
+
+		let fill = |amnt: usize| std::iter::repeat_n(self.fill, amnt).collect::<String>();
+
+		match self.align.unwrap_or(default_align) {
+			Align::Left => format!("{}{}{}", sign, body, fill(padding)),
+			Align::Right => format!("{}{}{}", fill(padding), sign, body),
+			Align::Center => format!("{}{}{}{}", fill(padding / 2), sign, body, fill(padding - padding / 2)),
+		}
+	}
+
+	fn format_text(&self, text: &str) -> String {
+		if let Some(precision) = self.precision {
+			let truncated = text.chars().take(precision).collect::<String>();
+			self.pad("", &truncated, Align::Left)
+		} else {
+			self.pad("", text, Align::Left)
+		}
+	}
+
+	fn format_number(&self, number: Number) -> crate::Result<String> {
+		let float = FloatType::from(number);
+		let negative = float.is_sign_negative() && !number.is_nan() && number != Number::ZERO;
+		let sign = if negative { "-" } else if self.plus { "+" } else { "" };
+
+		let radix = |prefix: &'static str, func: fn(u64) -> String| -> crate::Result<_> {
+			let int = IntegerType::try_from(number)?;
+			let prefix = if self.alternate { prefix } else { "" };
+
+			Ok((prefix, func(int.unsigned_abs())))
+		};
+
+		let (prefix, body) =
+			match self.kind {
+				Kind::Binary => radix("0b", |n| format!("{:b}", n))?,
+				Kind::Octal => radix("0o", |n| format!("{:o}", n))?,
+				Kind::LowerHex => radix("0x", |n| format!("{:x}", n))?,
+				Kind::UpperHex => radix("0x", |n| format!("{:X}", n))?,
+				Kind::LowerExp => ("", match self.precision {
+					Some(precision) => format!("{:.*e}", precision, float.abs()),
+					None => format!("{:e}", float.abs())
+				}),
+				Kind::UpperExp => ("", match self.precision {
+					Some(precision) => format!("{:.*E}", precision, float.abs()),
+					None => format!("{:E}", float.abs())
+				}),
+				Kind::Fixed => ("", format!("{:.*}", self.precision.unwrap_or(6), float.abs())),
+				Kind::Text => ("", match self.precision {
+					Some(precision) => format!("{:.*}", precision, float.abs()),
+					None => number.abs().to_string()
+				}),
+				Kind::Inspect => unreachable!("inspect is never formatted as a number")
+			};
+
+		Ok(self.pad(&format!("{}{}", sign, prefix), &body, Align::Right))
+	}
+
+	fn format(&self, arg: &Object) -> crate::Result<String> {
+		match self.kind {
+			Kind::Inspect =>
+				Ok(self.format_text(arg.call_attr_lit(&Literal::INSPECT, &[])?
+					.call_downcast::<Text>()?
+					.as_ref())),
+			Kind::Text if !arg.is_a::<Number>() =>
+				Ok(self.format_text(arg.call_downcast::<Text>()?.as_ref())),
+			_ => {
+				debug_assert!(self.is_numeric() || arg.is_a::<Number>());
+				self.format_number(*arg.call_downcast::<Number>()?)
+			}
+		}
+	}
+}
+
+fn parse_field(field: &str) -> crate::Result<(Field<'_>, Spec)> {
+	let (name, spec) =
+		match field.find(':') {
+			Some(idx) => (&field[..idx], Spec::parse(&field[idx + 1..])?),
+			None => (field, Spec::default())
+		};
+
+	let name =
+		if name.is_empty() {
+			Field::Next
+		} else if let Ok(idx) = name.parse::<usize>() {
+			Field::Index(idx)
+		} else {
+			Field::Name(name)
+		};
+
+	Ok((name, spec))
+}
+
+/// Formats `fmt` with the given positional arguments.
+///
+/// Named fields (e.g. `{foo}`) are resolved by looking up the attribute on the last positional
+/// argument, if it's defined directly on it, and otherwise on `scope`, which is usually the
+/// calling scope.
+pub fn format(fmt: &str, positional: &[&Object], scope: &Object) -> crate::Result<String> {
+	let mut result = String::with_capacity(fmt.len());
+	let mut next_positional = 0;
+	let mut rest = fmt;
+
+	while let Some(idx) = rest.find(['{', '}']) {
+		result.push_str(&rest[..idx]);
+
+		if rest[idx..].starts_with("{{") {
+			result.push('{');
+			rest = &rest[idx + 2..];
+			continue;
+		} else if rest[idx..].starts_with("}}") {
+			result.push('}');
+			rest = &rest[idx + 2..];
+			continue;
+		} else if rest[idx..].starts_with('}') {
+			return Err(bad_format("unmatched `}`"));
+		}
+
+		let end = rest[idx..].find('}')
+			.map(|end| idx + end)
+			.ok_or_else(|| bad_format("unmatched `{`"))?;
+
+		let (field, spec) = parse_field(&rest[idx + 1..end])?;
+
+		let arg =
+			match field {
+				Field::Next => {
+					next_positional += 1;
+					Positional(next_positional - 1).get(positional)?
+				},
+				Field::Index(idx) => Positional(idx).get(positional)?,
+				Field::Name(name) => named(name, positional, scope)?
+			};
+
+		result.push_str(&spec.format(&arg)?);
+		rest = &rest[end + 1..];
+	}
+
+	result.push_str(rest);
+	Ok(result)
+}
+
+fn named(name: &str, positional: &[&Object], scope: &Object) -> crate::Result<Object> {
+	let name = Object::from(name);
+
+	if let Some(last) = positional.last() {
+		if let Some(value) = last.get_own_value(&name)? {
+			return Ok(value.into());
+		}
+	}
+
+	scope.get_attr(&name)
+}
+
+struct Positional(usize);
+
+impl Positional {
+	fn get(self, args: &[&Object]) -> Result<Object, KeyError> {
+		args.get(self.0)
+			.map(|arg| (*arg).clone())
+			.ok_or(KeyError::OutOfBounds { idx: self.0 as isize, len: args.len() })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn fmt(fmt: &str, args: &[&Object]) -> String {
+		crate::init();
+		format(fmt, args, &Object::default()).unwrap()
+	}
+
+	#[test]
+	fn positional() {
+		assert_eq!(fmt("plain", &[]), "plain");
+		assert_eq!(fmt("{} {}", &[&1.into(), &"a".into()]), "1 a");
+		assert_eq!(fmt("{1}{0}{1}", &[&"x".into(), &"y".into()]), "yxy");
+		assert_eq!(fmt("{{}}", &[]), "{}");
+	}
+
+	#[test]
+	fn alignment() {
+		assert_eq!(fmt("[{:5}]", &[&"ab".into()]), "[ab   ]");
+		assert_eq!(fmt("[{:>5}]", &[&"ab".into()]), "[   ab]");
+		assert_eq!(fmt("[{:-^6}]", &[&"ab".into()]), "[--ab--]");
+		assert_eq!(fmt("[{:5}]", &[&12.into()]), "[   12]");
+		assert_eq!(fmt("[{:<5}]", &[&12.into()]), "[12   ]");
+		assert_eq!(fmt("[{:.2}]", &[&"abc".into()]), "[ab]");
+	}
+
+	#[test]
+	fn numbers() {
+		assert_eq!(fmt("{:+}", &[&3.into()]), "+3");
+		assert_eq!(fmt("{:05}", &[&(-42).into()]), "-0042");
+		assert_eq!(fmt("{:.3}", &[&2.5.into()]), "2.500");
+		assert_eq!(fmt("{:f}", &[&1.into()]), "1.000000");
+		assert_eq!(fmt("{:x}", &[&255.into()]), "ff");
+		assert_eq!(fmt("{:#X}", &[&255.into()]), "0xFF");
+		assert_eq!(fmt("{:#010b}", &[&5.into()]), "0b00000101");
+		assert_eq!(fmt("{:o}", &[&(-8).into()]), "-10");
+		assert_eq!(fmt("{:.2e}", &[&1234.5.into()]), "1.23e3");
+	}
+
+	#[test]
+	fn inspect() {
+		assert_eq!(fmt("{:?}", &[&"a".into()]), "\"a\"");
+		assert_eq!(fmt("{}", &[&"a".into()]), "a");
+	}
+
+	#[test]
+	fn named() {
+		crate::init();
+
+		let scope = Object::default();
+		scope.set_attr_lit("a", 1.into()).unwrap();
+		scope.set_attr_lit("b", 2.into()).unwrap();
+
+		let fields = Object::default();
+		fields.set_attr_lit("a", "x".into()).unwrap();
+
+		assert_eq!(format("{a}{b}", &[], &scope).unwrap(), "12");
+		assert_eq!(format("{a}{b}", &[&fields], &scope).unwrap(), "x2");
+		assert_eq!(format("{a:>3}", &[&fields], &scope).unwrap(), "  x");
+		assert_matches!(format("{c}", &[&fields], &scope), Err(crate::Error::KeyError(_)));
+
+		// properties are computed, rather than formatting the property itself.
+		let getter = Object::from(crate::types::RustFn::function("get", |_| Ok("p".into())));
+		crate::types::Pristine::qs_property(&fields, args!("c", getter)).unwrap();
+		assert_eq!(format("{c}", &[&fields], &scope).unwrap(), "p");
+	}
+
+	#[test]
+	fn errors() {
+		crate::init();
+
+		assert_matches!(format("{", &[], &Object::default()), Err(crate::Error::ValueError(_)));
+		assert_matches!(format("}", &[], &Object::default()), Err(crate::Error::ValueError(_)));
+		assert_matches!(format("{:q}", &[&1.into()], &Object::default()),
+			Err(crate::Error::ValueError(_)));
+		assert_matches!(format("{}", &[], &Object::default()),
+			Err(crate::Error::KeyError(KeyError::OutOfBounds { idx: 0, len: 0 })));
+		assert_matches!(format("{:x}", &[&1.5.into()], &Object::default()),
+			Err(crate::Error::ValueError(_)));
+	}
+}
@@ -0,0 +1,53 @@
+use crate::Object;
+use crate::gc::WeakObject;
+use tracing::instrument;
+
+/// A reference to an object that doesn't keep it alive.
+///
+/// # Quest Examples
+/// ```quest
+/// make_ref = { WeakRef({ :0 }()) };
+///
+/// ref = make_ref();
+/// assert(ref.get() == null);
+/// ```
+#[crate::object]
+#[derive(Debug, Clone)]
+pub struct WeakRef(WeakObject);
+
+impl WeakRef {
+	/// Creates a new [`WeakRef`] to `obj`.
+	#[inline]
+	pub fn new(obj: &Object) -> Self {
+		Self(obj.downgrade())
+	}
+
+	/// Gets the object, if it's still alive.
+	#[inline]
+	pub fn get(&self) -> Option<Object> {
+		self.0.upgrade()
+	}
+}
+
+#[crate::methods]
+impl WeakRef {
+	/// Creates a new weak reference to the first argument.
+	#[quest(name = "()")]
+	#[instrument(name="WeakRef::()", level="trace")]
+	pub fn qs_call(obj: Object) -> Self {
+		Self::new(&obj)
+	}
+
+	/// Gets the referenced object, or `null` if it's been dropped.
+	#[instrument(name="WeakRef::get", level="trace", skip(self))]
+	pub fn qs_get(&self) -> Object {
+		self.get().unwrap_or_default()
+	}
+
+	/// Checks to see if the referenced object is still alive.
+	#[quest(name = "alive?")]
+	#[instrument(name="WeakRef::alive?", level="trace", skip(self))]
+	pub fn qs_alive_q(&self) -> bool {
+		self.get().is_some()
+	}
+}
@@ -0,0 +1,120 @@
+use std::fmt::{self, Display, Formatter};
+use std::path::PathBuf;
+
+/// Where in some Quest code an error occurred.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Location {
+	/// The file the code is in, or `None` if it was evaluated from a string.
+	pub file: Option<PathBuf>,
+	/// The line number.
+	pub lineno: usize,
+	/// The column, if it's known. Only parse errors have columns.
+	pub column: Option<usize>
+}
+
+impl From<&quest_parser::Span> for Location {
+	fn from(span: &quest_parser::Span) -> Self {
+		Self { file: span.start.file.clone(), lineno: span.start.lineno, column: Some(span.column()) }
+	}
+}
+
+impl From<quest_parser::block::ErrorLocation> for Location {
+	fn from(location: quest_parser::block::ErrorLocation) -> Self {
+		Self { file: location.file, lineno: location.lineno, column: None }
+	}
+}
+
+impl Display for Location {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self.file {
+			Some(ref file) => write!(f, "{}", file.display())?,
+			None => write!(f, "<eval>")?
+		}
+
+		write!(f, ":{}", self.lineno)?;
+
+		if let Some(column) = self.column {
+			write!(f, ":{}", column)?;
+		}
+
+		Ok(())
+	}
+}
+
+/// An error from running Quest code.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+	/// The code couldn't be parsed.
+	Parse {
+		/// Where the first problem with the code is.
+		location: Location,
+		/// Every problem with the code.
+		error: quest_parser::Error
+	},
+
+	/// The code raised an error while running.
+	Runtime {
+		/// The innermost line of code that failed, if it's known.
+		location: Option<Location>,
+		/// The error that was raised.
+		error: quest_core::Error
+	},
+
+	/// A file couldn't be read.
+	Io(std::io::Error)
+}
+
+impl Error {
+	/// Gets where the error occurred, if it's known.
+	pub fn location(&self) -> Option<&Location> {
+		match self {
+			Self::Parse { location, .. } => Some(location),
+			Self::Runtime { location, .. } => location.as_ref(),
+			Self::Io(_) => None
+		}
+	}
+}
+
+impl From<quest_parser::Error> for Error {
+	fn from(error: quest_parser::Error) -> Self {
+		Self::Parse { location: error.span().into(), error }
+	}
+}
+
+impl From<quest_core::Error> for Error {
+	fn from(error: quest_core::Error) -> Self {
+		Self::Runtime { location: None, error }
+	}
+}
+
+impl From<std::io::Error> for Error {
+	fn from(error: std::io::Error) -> Self {
+		Self::Io(error)
+	}
+}
+
+impl Display for Error {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			// parse errors already include their location.
+			Self::Parse { error, .. } => Display::fmt(error, f),
+			Self::Runtime { location: Some(location), error } => write!(f, "{}: {}", location, error),
+			Self::Runtime { location: None, error } => Display::fmt(error, f),
+			Self::Io(error) => Display::fmt(error, f)
+		}
+	}
+}
+
+impl std::error::Error for Error {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			Self::Parse { error, .. } => Some(error),
+			Self::Runtime { error, .. } => Some(error),
+			Self::Io(error) => Some(error)
+		}
+	}
+}
+
+/// An alias for results from running Quest code.
+pub type Result<T> = std::result::Result<T, Error>;
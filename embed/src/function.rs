@@ -0,0 +1,44 @@
+use crate::FromObject;
+use quest_core::Object;
+use quest_core::types::RustClosure;
+
+/// Rust functions that can be called from Quest.
+///
+/// This is implemented for functions of up to six arguments that implement [`FromObject`], and
+/// that return a `quest_core::Result` of something that can be converted into an [`Object`].
+/// `Params` is only there so functions with different arities don't conflict.
+pub trait IntoFunction<Params> {
+	/// Wraps `self` in a closure that converts its arguments, and checks there's the right amount.
+	///
+	/// Like methods defined with `#[quest::methods]`, arguments of the wrong type raise an
+	/// `ArgumentError`.
+	fn into_function(self) -> RustClosure;
+}
+
+macro_rules! impl_into_function {
+	($count:literal; $($param:ident $idx:tt),*) => {
+		impl<F, R, $($param),*> IntoFunction<($($param,)*)> for F
+		where
+			F: Fn($($param),*) -> quest_core::Result<R> + Send + Sync + 'static,
+			R: Into<Object>,
+			$($param: FromObject),*
+		{
+			fn into_function(self) -> RustClosure {
+				RustClosure::new(move |args| {
+					#[allow(unused_variables)]
+					let args = args.as_ref_checked($count)?;
+
+					self($($param::from_argument(args[$idx], $idx)?),*).map(Into::into)
+				})
+			}
+		}
+	};
+}
+
+impl_into_function!(0; );
+impl_into_function!(1; A 0);
+impl_into_function!(2; A 0, B 1);
+impl_into_function!(3; A 0, B 1, C 2);
+impl_into_function!(4; A 0, B 1, C 2, D 3);
+impl_into_function!(5; A 0, B 1, C 2, D 3, E 4);
+impl_into_function!(6; A 0, B 1, C 2, D 3, E 4, G 5);
@@ -0,0 +1,214 @@
+//! Running Quest from within Rust programs.
+//!
+//! An [`Interpreter`] sets Quest up, runs code, and lets Rust functions and constants be called
+//! from that code.
+//!
+//! # Examples
+//! ```
+//! use quest_embed::Interpreter;
+//!
+//! let interpreter = Interpreter::new();
+//! interpreter.define_fn("double", |n: i64| Ok(n * 2)).unwrap();
+//!
+//! let result = interpreter.eval("x = double(21); x").unwrap();
+//! assert_eq!(interpreter.convert::<i64>(&result).unwrap(), 42);
+//! assert_eq!(interpreter.get::<i64>("x").unwrap(), 42);
+//! ```
+
+mod error;
+mod function;
+
+pub use error::{Error, Location, Result};
+pub use function::IntoFunction;
+pub use quest_core::{Object, Args, types::FromObject};
+
+use quest_core::Binding;
+use quest_core::types::{ObjectType, Kernel, RustClosure, Scope};
+use quest_parser::{Expression, Stream, expression::Executable, stream::BufStream};
+use std::convert::TryFrom;
+use std::io::BufRead;
+use std::path::Path;
+
+/// Runs Quest code.
+///
+/// Code is run in the interpreter's `main` scope unless another binding is given, so variables
+/// assigned by one call to [`eval`](Self::eval) can be used by the next.
+///
+/// Functions and constants are defined on `Kernel`, which is shared by every interpreter.
+#[derive(Debug, Clone)]
+pub struct Interpreter {
+	main: Object
+}
+
+impl Default for Interpreter {
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Interpreter {
+	/// Creates a new interpreter, initializing Quest if it hasn't been already.
+	pub fn new() -> Self {
+		quest_core::init();
+		quest_parser::init();
+
+		Self { main: Self::new_scope_named("main") }
+	}
+
+	fn new_scope_named(name: &str) -> Object {
+		let scope = Object::new(Scope);
+		scope.set_attr_lit("name", Object::from(name))
+			.expect("a new scope isn't frozen");
+		scope
+	}
+
+	/// Gets the scope that code is run in by default.
+	#[inline]
+	pub fn main(&self) -> &Object {
+		&self.main
+	}
+
+	/// Creates a new scope that can be given to [`eval_in`](Self::eval_in), which doesn't share
+	/// any variables with `main`.
+	pub fn new_scope(&self) -> Object {
+		Self::new_scope_named("scope")
+	}
+
+	/// Runs `code` in `main`, returning the value of its last line.
+	#[inline]
+	pub fn eval(&self, code: &str) -> Result<Object> {
+		self.eval_in(code, &self.main)
+	}
+
+	/// Runs `code` with `binding` as the current scope.
+	pub fn eval_in(&self, code: &str, binding: &Object) -> Result<Object> {
+		run(BufStream::from(code.to_string()), binding)
+	}
+
+	/// Runs the file at `path` in `main`, returning the value of its last line.
+	#[inline]
+	pub fn eval_file(&self, path: impl AsRef<Path>) -> Result<Object> {
+		self.eval_file_in(path, &self.main)
+	}
+
+	/// Runs the file at `path` with `binding` as the current scope.
+	pub fn eval_file_in(&self, path: impl AsRef<Path>, binding: &Object) -> Result<Object> {
+		run(BufStream::try_from(path.as_ref())?, binding)
+	}
+
+	/// Defines `name` as a constant on `Kernel`, so it can be used from any scope.
+	pub fn define_const(&self, name: &str, value: impl Into<Object>) -> Result<()> {
+		Kernel::mapping().set_attr(name.into(), value.into()).map_err(From::from)
+	}
+
+	/// Defines `name` as a function on `Kernel` that calls `func`.
+	///
+	/// Quest arguments are converted to `func`'s parameter types with [`FromObject`]; calling it
+	/// with the wrong number of arguments, or arguments that can't be converted, raises an error.
+	pub fn define_fn<P>(&self, name: &str, func: impl IntoFunction<P>) -> Result<()> {
+		self.define_const(name, func.into_function())
+	}
+
+	/// Defines `name` as a function on `Kernel` that's given its arguments unconverted.
+	pub fn define_raw_fn<F>(&self, name: &str, func: F) -> Result<()>
+	where
+		F: Fn(Args) -> quest_core::Result<Object> + Send + Sync + 'static
+	{
+		self.define_const(name, RustClosure::new(func))
+	}
+
+	/// Gets the variable `name` from `main`, converted to `T`.
+	pub fn get<T: FromObject>(&self, name: &str) -> Result<T> {
+		let value = self.main.get_attr(&name.into())?;
+
+		self.convert(&value)
+	}
+
+	/// Converts `obj` to `T`.
+	#[inline]
+	pub fn convert<T: FromObject>(&self, obj: &Object) -> Result<T> {
+		T::from_object(obj).map_err(From::from)
+	}
+}
+
+fn run<B: BufRead>(stream: BufStream<B>, binding: &Object) -> Result<Object> {
+	let expr = Expression::parse_stream(stream.tokens())?;
+
+	// don't report where an error that's already been handled was.
+	quest_parser::block::take_error_location();
+
+	Binding::run_stackframe(binding.clone().into(), |_| expr.execute())
+		.map_err(|error| Error::Runtime {
+			location: quest_parser::block::take_error_location().map(Location::from),
+			error
+		})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn variables_persist_in_main() {
+		let interpreter = Interpreter::new();
+
+		interpreter.eval("x = 3").unwrap();
+		interpreter.eval("y = x * 2").unwrap();
+
+		assert_eq!(interpreter.get::<i64>("y").unwrap(), 6);
+	}
+
+	#[test]
+	fn eval_in_scope() {
+		let interpreter = Interpreter::new();
+		let scope = interpreter.new_scope();
+
+		interpreter.eval_in("z = 'scoped'", &scope).unwrap();
+
+		assert_eq!(String::from_object(&scope.get_attr(&"z".into()).unwrap()).unwrap(), "scoped");
+		assert!(interpreter.get::<Object>("z").is_err());
+	}
+
+	#[test]
+	fn typed_functions() {
+		let interpreter = Interpreter::new();
+
+		interpreter.define_fn("embed_join", |items: Vec<String>, sep: Option<String>|
+			Ok(items.join(&sep.unwrap_or_default()))).unwrap();
+		interpreter.define_const("EMBED_ANSWER", 42).unwrap();
+
+		let joined = interpreter.eval("embed_join(['a', 'b'], '-')").unwrap();
+		assert_eq!(interpreter.convert::<String>(&joined).unwrap(), "a-b");
+
+		let joined = interpreter.eval("embed_join([EMBED_ANSWER, 1], null)").unwrap();
+		assert_eq!(interpreter.convert::<String>(&joined).unwrap(), "421");
+
+		match interpreter.eval("embed_join([])") {
+			Err(Error::Runtime { error: quest_core::Error::ArgumentError(_), .. }) => {},
+			other => panic!("wrong number of arguments was accepted: {:?}", other)
+		}
+	}
+
+	#[test]
+	fn parse_errors_have_locations() {
+		let interpreter = Interpreter::new();
+		let err = interpreter.eval("x = 1;\ny = 2 +;").unwrap_err();
+
+		assert!(matches!(err, Error::Parse { .. }), "{:?}", err);
+		assert_eq!(err.location().unwrap().lineno, 2);
+	}
+
+	#[test]
+	fn runtime_errors_have_locations() {
+		let interpreter = Interpreter::new();
+		let err = interpreter.eval("f = { \n\n missing_variable };\nf()").unwrap_err();
+
+		assert!(matches!(err, Error::Runtime { .. }), "{:?}", err);
+		assert_eq!(err.location().unwrap().lineno, 3);
+
+		// a handled error isn't reported for later ones.
+		let err = interpreter.eval("1 + missing_variable").unwrap_err();
+		assert_eq!(err.location().unwrap().lineno, 1);
+	}
+}
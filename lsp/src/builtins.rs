@@ -0,0 +1,131 @@
+//! Looking up Quest's builtin attributes.
+//!
+//! Their names and docs come from the `impl_object_type!` mappings they're defined in: each
+//! function's [`RustFn`] knows its name (eg `Kernel::disp`) and the `///` comments written above it.
+
+use quest_core::Object;
+use quest_core::types::{ObjectType, Basic, Kernel, List, RustFn, Scope, Text};
+
+/// A builtin attribute.
+#[derive(Debug, Clone)]
+pub struct Builtin {
+	pub name: String,
+	pub value: Object
+}
+
+impl Builtin {
+	/// Whether this is a builtin function, such as `disp`.
+	pub fn is_function(&self) -> bool {
+		self.value.is_a::<RustFn>()
+	}
+
+	/// Whether this is a builtin type, such as `Text`.
+	pub fn is_type(&self) -> bool {
+		!self.is_function() && self.name.starts_with(char::is_uppercase)
+	}
+
+	/// A short description, such as `Kernel::disp` for functions, or the kind of value otherwise.
+	pub fn detail(&self) -> String {
+		match self.value.downcast::<RustFn>() {
+			Some(rustfn) => rustfn.name().to_string(),
+			None if self.is_type() => format!("type {}", self.name),
+			None => self.value.typename().to_string()
+		}
+	}
+
+	/// The builtin's docs, if it has any.
+	pub fn doc(&self) -> Option<String> {
+		self.value.downcast::<RustFn>().and_then(|rustfn| rustfn.doc())
+	}
+
+	/// The markdown that's shown when hovering over the builtin.
+	pub fn hover(&self) -> String {
+		let mut hover = format!("```\n{}\n```", self.detail());
+
+		if let Some(doc) = self.doc() {
+			hover.push_str("\n\n");
+			hover.push_str(&doc);
+		}
+
+		hover
+	}
+}
+
+/// The attributes that `object` itself defines, in order of their names.
+fn attributes(object: &Object) -> Vec<Builtin> {
+	let keys = match object.call_attr_lit("__keys__", &[]) {
+		Ok(keys) => keys,
+		Err(_) => return vec![]
+	};
+
+	let mut builtins = keys.downcast::<List>()
+		.map(|keys| keys.iter()
+			.filter_map(|key| key.downcast::<Text>().map(|key| key.to_string()))
+			.filter_map(|name| object.get_attr_lit(name.as_str()).ok().map(|value| Builtin { name, value }))
+			.collect::<Vec<_>>())
+		.unwrap_or_default();
+
+	builtins.sort_by(|lhs, rhs| lhs.name.cmp(&rhs.name));
+	builtins
+}
+
+/// The attributes of `Kernel` and `Basic`, which can be used anywhere.
+pub fn globals() -> Vec<Builtin> {
+	let mut globals = attributes(Kernel::mapping());
+
+	for builtin in attributes(Basic::mapping()) {
+		if !globals.iter().any(|global| global.name == builtin.name) {
+			globals.push(builtin);
+		}
+	}
+
+	globals
+}
+
+/// The builtin that `name` refers to when used as a variable.
+pub fn global(name: &str) -> Option<Builtin> {
+	let scope = Object::from(Scope);
+
+	if !scope.has_attr_lit(name).unwrap_or(false) {
+		return None;
+	}
+
+	scope.get_attr_lit(name).ok().map(|value| Builtin { name: name.to_string(), value })
+}
+
+/// The attributes that could be accessed by `.name`.
+///
+/// If `receiver` is a builtin, such as `Text` in `Text.new`, only its attribute is returned.
+/// Otherwise, every builtin type that defines `name` itself is searched, as we don't know what
+/// type the receiver is.
+pub fn attribute(receiver: Option<&str>, name: &str) -> Vec<Builtin> {
+	if let Some(receiver) = receiver.and_then(global) {
+		return receiver.value.get_attr_lit(name)
+			.ok()
+			.filter(|_| receiver.value.has_attr_lit(name).unwrap_or(false))
+			.map(|value| Builtin { name: name.to_string(), value })
+			.into_iter()
+			.collect();
+	}
+
+	attributes(Kernel::mapping())
+		.into_iter()
+		.filter(Builtin::is_type)
+		.flat_map(|class| attributes(&class.value))
+		.filter(|builtin| builtin.name == name)
+		.collect()
+}
+
+/// The attributes that can be completed after `receiver.`: those of `receiver` if it's a builtin,
+/// and those of `Basic`, which most objects have.
+pub fn attributes_of(receiver: Option<&str>) -> Vec<Builtin> {
+	let mut builtins = receiver.and_then(global).map_or_else(Vec::new, |receiver| attributes(&receiver.value));
+
+	for builtin in attributes(Basic::mapping()) {
+		if !builtins.iter().any(|existing| existing.name == builtin.name) {
+			builtins.push(builtin);
+		}
+	}
+
+	builtins
+}
@@ -0,0 +1,463 @@
+//! What the server knows about an open file: its tokens, the scopes they're in, and where each
+//! variable is assigned.
+//!
+//! As with the linter, each `{ ... }` block is its own scope, and `( ... )` and `[ ... ]` blocks are
+//! part of the scope they're in. The parameters of an `->` belong to the scope of its body.
+
+use quest_parser::{Context, Contexted, Expression, Severity, Stream, Token};
+use quest_parser::lint::{self, Finding};
+use quest_parser::stream::BufStream;
+use quest_parser::token::{Operator, ParenType, Primitive};
+use std::io::Cursor;
+
+/// A place in a document, counting both lines and characters from zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct Position {
+	pub line: usize,
+	pub column: usize
+}
+
+impl From<&Context> for Position {
+	fn from(context: &Context) -> Self {
+		Self { line: context.lineno.saturating_sub(1), column: context.column }
+	}
+}
+
+/// A token, and where it is.
+#[derive(Debug)]
+struct Lexeme {
+	token: Token,
+	start: Position,
+	end: Position,
+	scope: usize,
+	/// For parens, the index of the matching paren.
+	pair: Option<usize>,
+	/// For `{`, the scope it starts.
+	opens: Option<usize>
+}
+
+impl Lexeme {
+	fn variable(&self) -> Option<String> {
+		match self.token {
+			Token::Primitive(Primitive::Variable(ref var)) => Some(var.to_string()),
+			_ => None
+		}
+	}
+
+	fn is_operator(&self, oper: Operator) -> bool {
+		matches!(self.token, Token::Operator(op) if op == oper)
+	}
+
+	fn is_attribute_access(&self) -> bool {
+		self.is_operator(Operator::Dot)
+			|| self.is_operator(Operator::DotQuestion)
+			|| self.is_operator(Operator::Scoped)
+	}
+}
+
+/// Where a variable is assigned, or declared as a parameter.
+#[derive(Debug)]
+struct Definition {
+	name: String,
+	lexeme: usize,
+	scope: usize,
+	is_parameter: bool
+}
+
+/// A problem with a document, from either the parser or the linter.
+#[derive(Debug)]
+pub struct Problem {
+	pub start: Position,
+	pub end: Position,
+	pub severity: Severity,
+	/// The lint's name, if the linter found it.
+	pub code: Option<&'static str>,
+	pub message: String
+}
+
+/// A block that's assigned to a variable.
+#[derive(Debug)]
+pub struct Symbol {
+	pub name: String,
+	/// From the variable to the end of the block.
+	pub start: Position,
+	pub end: Position,
+	/// Where the variable is.
+	pub name_start: Position,
+	pub name_end: Position,
+	/// The blocks assigned within this one.
+	pub children: Vec<Symbol>
+}
+
+/// The attribute being accessed at a position, such as `bar` in `foo.bar`.
+#[derive(Debug)]
+pub struct Attribute {
+	/// The variable the attribute is accessed on, if there is one.
+	pub receiver: Option<String>,
+	pub name: Option<String>
+}
+
+/// An open file.
+#[derive(Debug)]
+pub struct Document {
+	text: String,
+	lines: Vec<Vec<char>>,
+	lexemes: Vec<Lexeme>,
+	/// The parent of each scope; the file itself is scope `0`.
+	scopes: Vec<Option<usize>>,
+	definitions: Vec<Definition>
+}
+
+impl Document {
+	pub fn new(text: String) -> Self {
+		let lines = text.split('\n').map(|line| line.chars().collect()).collect();
+		let mut document = Self { text, lines, lexemes: vec![], scopes: vec![None], definitions: vec![] };
+
+		document.tokenize();
+		document.find_definitions();
+		document
+	}
+
+	/// Reads every token, stopping at the first one that can't be read.
+	fn tokenize(&mut self) {
+		let mut tokens = BufStream::new(Cursor::new(self.text.clone()), None).tokens();
+		let mut opened: Vec<usize> = vec![];
+		let mut scope = 0;
+
+		while let Some(Ok(token)) = tokens.next() {
+			let idx = self.lexemes.len();
+			let mut lexeme = Lexeme {
+				start: tokens.start_context().into(),
+				end: tokens.context().into(),
+				token,
+				scope,
+				pair: None,
+				opens: None
+			};
+
+			match lexeme.token {
+				Token::Left(ParenType::Curly) => {
+					scope = self.scopes.len();
+					self.scopes.push(Some(lexeme.scope));
+					lexeme.opens = Some(scope);
+					opened.push(idx);
+				},
+				Token::Left(_) => opened.push(idx),
+				Token::Right(_) => if let Some(open) = opened.pop() {
+					if let Some(inner) = self.lexemes[open].opens {
+						scope = self.scopes[inner].unwrap_or_default();
+						lexeme.scope = scope;
+					}
+
+					self.lexemes[open].pair = Some(idx);
+					lexeme.pair = Some(open);
+				},
+				_ => {}
+			}
+
+			self.lexemes.push(lexeme);
+		}
+	}
+
+	fn find_definitions(&mut self) {
+		for (idx, lexeme) in self.lexemes.iter().enumerate() {
+			if let Some(name) = lexeme.variable() {
+				let is_assigned = self.lexemes.get(idx + 1).is_some_and(|next| next.is_operator(Operator::Assign))
+					&& !idx.checked_sub(1).is_some_and(|prev| self.lexemes[prev].is_attribute_access());
+
+				if is_assigned {
+					self.definitions.push(Definition { name, lexeme: idx, scope: lexeme.scope, is_parameter: false });
+				}
+			} else if lexeme.is_operator(Operator::Arrow) {
+				self.definitions.extend(self.parameters(idx));
+			}
+		}
+	}
+
+	/// The parameters of the `->` at `arrow`, such as `a` and `b` in `(a, b) -> { ... }`, or `a` in
+	/// `a -> { ... }`.
+	fn parameters(&self, arrow: usize) -> Vec<Definition> {
+		let (before, body) =
+			match (arrow.checked_sub(1), self.lexemes.get(arrow + 1).and_then(|body| body.opens)) {
+				(Some(before), Some(body)) => (before, body),
+				_ => return vec![]
+			};
+
+		if let Some(name) = self.lexemes[before].variable() {
+			return vec![Definition { name, lexeme: before, scope: body, is_parameter: true }];
+		}
+
+		let open =
+			match self.lexemes[before] {
+				Lexeme { token: Token::Right(ParenType::Round), pair: Some(open), .. } => open,
+				_ => return vec![]
+			};
+
+		let mut parameters = vec![];
+		let mut idx = open + 1;
+
+		while idx < arrow - 1 {
+			let lexeme = &self.lexemes[idx];
+
+			if let Some(name) = lexeme.variable() {
+				parameters.push(Definition { name, lexeme: idx, scope: body, is_parameter: true });
+			}
+
+			// parens within the parameters aren't parameters themselves.
+			idx = lexeme.pair.filter(|&close| close > idx).unwrap_or(idx) + 1;
+		}
+
+		parameters
+	}
+
+	fn encloses(&self, outer: usize, mut inner: usize) -> bool {
+		loop {
+			if inner == outer {
+				return true;
+			}
+
+			match self.scopes[inner] {
+				Some(parent) => inner = parent,
+				None => return false
+			}
+		}
+	}
+
+	fn depth(&self, mut scope: usize) -> usize {
+		let mut depth = 0;
+
+		while let Some(parent) = self.scopes[scope] {
+			scope = parent;
+			depth += 1;
+		}
+
+		depth
+	}
+
+	/// The index of the lexeme at `position`, preferring variables if it's between two lexemes.
+	fn lexeme_at(&self, position: Position) -> Option<usize> {
+		let mut found = None;
+
+		for (idx, lexeme) in self.lexemes.iter().enumerate() {
+			if lexeme.start <= position && position <= lexeme.end {
+				if lexeme.variable().is_some() {
+					return Some(idx);
+				}
+
+				found = found.or(Some(idx));
+			}
+		}
+
+		found
+	}
+
+	/// The index of the last lexeme that ends at or before `position`.
+	fn lexeme_before(&self, position: Position) -> Option<usize> {
+		self.lexemes.iter().rposition(|lexeme| lexeme.end <= position)
+	}
+
+	/// The scope that's open at `position`.
+	fn scope_at(&self, position: Position) -> usize {
+		self.lexeme_before(position).map_or(0, |idx| {
+			let lexeme = &self.lexemes[idx];
+			lexeme.opens.unwrap_or(lexeme.scope)
+		})
+	}
+
+	/// The variable at `position`, and where it starts and ends.
+	pub fn variable_at(&self, position: Position) -> Option<(String, Position, Position)> {
+		let lexeme = &self.lexemes[self.lexeme_at(position)?];
+
+		lexeme.variable().map(|name| (name, lexeme.start, lexeme.end))
+	}
+
+	/// The attribute at `position`, if it's accessed with `.`, `.?` or `::`.
+	///
+	/// If `position` is just after the `.`, the attribute's name is `None`.
+	pub fn attribute_at(&self, position: Position) -> Option<Attribute> {
+		let receiver = |access: usize| access.checked_sub(1).and_then(|idx| self.lexemes[idx].variable());
+
+		if let Some(idx) = self.lexeme_before(position).filter(|&idx| self.lexemes[idx].is_attribute_access()) {
+			if self.lexemes[idx].end == position {
+				return Some(Attribute { receiver: receiver(idx), name: None });
+			}
+		}
+
+		let idx = self.lexeme_at(position)?;
+		let access = idx.checked_sub(1).filter(|&access| self.lexemes[access].is_attribute_access())?;
+
+		Some(Attribute { receiver: receiver(access), name: Some(self.lexemes[idx].variable()?) })
+	}
+
+	/// Where the variable at `position` was defined, as a start and end.
+	///
+	/// The innermost visible scope that defines the variable is used, and within it, the closest
+	/// definition before `position` (or the first one, if they're all after).
+	pub fn definition_at(&self, position: Position) -> Option<(Position, Position)> {
+		let idx = self.lexeme_at(position)?;
+		let lexeme = &self.lexemes[idx];
+		let name = lexeme.variable()?;
+
+		if idx.checked_sub(1).is_some_and(|prev| self.lexemes[prev].is_attribute_access()) {
+			return None;
+		}
+
+		let visible = self.definitions.iter()
+			.filter(|def| def.name == name && self.encloses(def.scope, lexeme.scope))
+			.collect::<Vec<_>>();
+		let scope = visible.iter().map(|def| def.scope).max_by_key(|&scope| self.depth(scope))?;
+		let in_scope = visible.iter().filter(|def| def.scope == scope);
+
+		let definition = in_scope.clone()
+			.rev()
+			.find(|def| def.lexeme <= idx)
+			.or_else(|| in_scope.clone().next())?;
+		let lexeme = &self.lexemes[definition.lexeme];
+
+		Some((lexeme.start, lexeme.end))
+	}
+
+	/// The names of the variables that are defined in a scope that's visible at `position`.
+	pub fn variables_at(&self, position: Position) -> Vec<String> {
+		let scope = self.scope_at(position);
+		let mut names = vec![];
+
+		for def in &self.definitions {
+			if self.encloses(def.scope, scope) && !names.contains(&def.name) {
+				names.push(def.name.clone());
+			}
+		}
+
+		names
+	}
+
+	/// The index of the last lexeme of the block assigned by the definition at `idx`, if a block
+	/// (or an `->` with a block body) is assigned.
+	fn assigned_block_end(&self, idx: usize) -> Option<usize> {
+		let value = self.lexemes.get(idx + 2)?;
+
+		match value.token {
+			Token::Left(ParenType::Curly) => value.pair,
+			Token::Left(ParenType::Round) | Token::Primitive(Primitive::Variable(_)) => {
+				let arrow = value.pair.unwrap_or(idx + 2) + 1;
+
+				if !self.lexemes.get(arrow)?.is_operator(Operator::Arrow) {
+					return None;
+				}
+
+				self.lexemes.get(arrow + 1).filter(|body| body.opens.is_some())?.pair
+			},
+			_ => None
+		}
+	}
+
+	/// The blocks that are assigned to variables, nested by where they're assigned.
+	pub fn symbols(&self) -> Vec<Symbol> {
+		let mut roots = vec![];
+		let mut open: Vec<(usize, Symbol)> = vec![];
+
+		fn close(open: &mut Vec<(usize, Symbol)>, roots: &mut Vec<Symbol>) {
+			let (_, symbol) = open.pop().expect("closed a symbol that wasn't open");
+
+			match open.last_mut() {
+				Some((_, parent)) => parent.children.push(symbol),
+				None => roots.push(symbol)
+			}
+		}
+
+		for def in self.definitions.iter().filter(|def| !def.is_parameter) {
+			let end = match self.assigned_block_end(def.lexeme) {
+				Some(end) => end,
+				None => continue
+			};
+
+			while open.last().is_some_and(|&(parent_end, _)| parent_end < def.lexeme) {
+				close(&mut open, &mut roots);
+			}
+
+			let name = &self.lexemes[def.lexeme];
+
+			open.push((end, Symbol {
+				name: def.name.clone(),
+				start: name.start,
+				end: self.lexemes[end].end,
+				name_start: name.start,
+				name_end: name.end,
+				children: vec![]
+			}));
+		}
+
+		while !open.is_empty() {
+			close(&mut open, &mut roots);
+		}
+
+		roots
+	}
+
+	/// The problems with the document: its parse errors, or if there aren't any, what the linter
+	/// finds.
+	pub fn problems(&self) -> Vec<Problem> {
+		let parsed = Expression::parse_stream(BufStream::new(Cursor::new(self.text.clone()), None).tokens());
+
+		if let Err(err) = parsed {
+			return err.diagnostics().iter().map(|diagnostic| {
+				let span = diagnostic.span();
+				let line = span.start.lineno.saturating_sub(1);
+				let (start, end) = span.columns();
+				let mut message = diagnostic.error_type().to_string();
+
+				if let Some(suggestion) = diagnostic.suggestion() {
+					message.push_str("\nhelp: ");
+					message.push_str(suggestion);
+				}
+
+				Problem {
+					start: Position { line, column: start },
+					end: Position { line, column: end },
+					severity: diagnostic.severity(),
+					code: None,
+					message
+				}
+			}).collect();
+		}
+
+		lint::lint(&self.text, None)
+			.unwrap_or_default()
+			.into_iter()
+			.map(|Finding { context, lint, message }| {
+				let line = context.lineno.saturating_sub(1);
+				let chars = self.lines.get(line).map_or(&[][..], |line| &line[..]);
+				let indent = chars.iter().take_while(|chr| chr.is_whitespace()).count();
+				let length = chars.iter().rposition(|chr| !chr.is_whitespace()).map_or(indent, |last| last + 1);
+
+				Problem {
+					start: Position { line, column: indent },
+					end: Position { line, column: length },
+					severity: Severity::Warning,
+					code: Some(lint.name()),
+					message
+				}
+			})
+			.collect()
+	}
+
+	/// Converts a character-based column into a UTF-16 based one, as LSP uses.
+	pub fn lsp_position(&self, Position { line, column }: Position) -> (usize, usize) {
+		let chars = self.lines.get(line).map_or(&[][..], |line| &line[..]);
+		let column = chars.iter().take(column).map(|chr| chr.len_utf16()).sum();
+
+		(line, column)
+	}
+
+	/// Converts a UTF-16 based column, as LSP uses, into a character-based one.
+	pub fn position_at(&self, line: usize, column: usize) -> Position {
+		let chars = self.lines.get(line).map_or(&[][..], |line| &line[..]);
+		let mut units = 0;
+		let column = chars.iter().take_while(|chr| {
+			units += chr.len_utf16();
+			units <= column
+		}).count();
+
+		Position { line, column }
+	}
+}
@@ -0,0 +1,25 @@
+//! A language server for Quest, which talks to editors over STDIN and STDOUT.
+//!
+//! It reports parse errors and lint findings as diagnostics, and supports going to where variables
+//! are assigned, hovering over builtins to see their docs, completing builtins and variables, and
+//! listing the blocks that are assigned to variables as document symbols.
+
+mod builtins;
+mod document;
+mod rpc;
+mod server;
+
+fn main() {
+	quest_core::init();
+	quest_parser::init();
+
+	let stdin = std::io::stdin();
+	let stdout = std::io::stdout();
+
+	let code = server::Server::new().run(stdin.lock(), stdout.lock()).unwrap_or_else(|err| {
+		eprintln!("quest-lsp: {}", err);
+		1
+	});
+
+	std::process::exit(code);
+}
@@ -0,0 +1,53 @@
+//! Reading and writing JSON-RPC messages, which are framed by a `Content-Length` header.
+
+use serde_json::Value;
+use std::io::{self, BufRead, Write};
+
+fn invalid_data(message: impl Into<String>) -> io::Error {
+	io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
+
+/// Reads the next message from `input`, returning `None` if the input has ended.
+pub fn read_message(input: &mut impl BufRead) -> io::Result<Option<Value>> {
+	let mut content_length = None;
+	let mut line = String::new();
+
+	loop {
+		line.clear();
+
+		if input.read_line(&mut line)? == 0 {
+			return Ok(None);
+		}
+
+		let line = line.trim_end();
+
+		if line.is_empty() {
+			if content_length.is_some() {
+				break;
+			}
+
+			continue;
+		}
+
+		let (name, value) = line.split_at(line.find(':')
+			.ok_or_else(|| invalid_data(format!("bad header `{}`", line)))?);
+
+		if name.eq_ignore_ascii_case("Content-Length") {
+			content_length = Some(value[1..].trim().parse::<usize>()
+				.map_err(|err| invalid_data(format!("bad content length: {}", err)))?);
+		}
+	}
+
+	let mut content = vec![0; content_length.unwrap_or_default()];
+	input.read_exact(&mut content)?;
+
+	serde_json::from_slice(&content).map(Some).map_err(|err| invalid_data(err.to_string()))
+}
+
+/// Writes `message` to `output`, along with its header.
+pub fn write_message(output: &mut impl Write, message: &Value) -> io::Result<()> {
+	let content = message.to_string();
+
+	write!(output, "Content-Length: {}\r\n\r\n{}", content.len(), content)?;
+	output.flush()
+}
@@ -0,0 +1,276 @@
+//! Handling the messages that a client sends.
+
+use crate::builtins::{self, Builtin};
+use crate::document::{Document, Position, Symbol};
+use crate::rpc;
+use quest_parser::Severity;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+
+/// The JSON-RPC error code for a method the server doesn't know about.
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// The JSON-RPC error code for a request whose parameters are missing something.
+const INVALID_PARAMS: i64 = -32602;
+
+/// The LSP `CompletionItemKind`s that builtins and variables are completed as.
+const FUNCTION_KIND: u8 = 3;
+const VARIABLE_KIND: u8 = 6;
+const CLASS_KIND: u8 = 7;
+const CONSTANT_KIND: u8 = 21;
+
+/// The LSP `SymbolKind` that assigned blocks are reported as.
+const FUNCTION_SYMBOL: u8 = 12;
+
+type RequestResult = std::result::Result<Value, (i64, String)>;
+
+/// A language server, which keeps track of the documents the client has open.
+#[derive(Debug, Default)]
+pub struct Server {
+	documents: HashMap<String, Document>,
+	is_shutdown: bool
+}
+
+fn missing(what: &str) -> (i64, String) {
+	(INVALID_PARAMS, format!("missing `{}`", what))
+}
+
+fn uri(params: &Value) -> std::result::Result<&str, (i64, String)> {
+	params["textDocument"]["uri"].as_str().ok_or_else(|| missing("textDocument.uri"))
+}
+
+impl Server {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Handles messages from `input` until the client exits, returning the exit code the server
+	/// should use.
+	pub fn run(&mut self, mut input: impl BufRead, mut output: impl Write) -> io::Result<i32> {
+		while let Some(message) = rpc::read_message(&mut input)? {
+			let method = match message["method"].as_str() {
+				Some(method) => method,
+				None => continue // a response to something we sent.
+			};
+
+			if method == "exit" {
+				return Ok(if self.is_shutdown { 0 } else { 1 });
+			}
+
+			let params = &message["params"];
+
+			if message.get("id").is_none() {
+				self.notify(method, params, &mut output)?;
+				continue;
+			}
+
+			let response =
+				match self.request(method, params) {
+					Ok(result) => json!({ "jsonrpc": "2.0", "id": message["id"], "result": result }),
+					Err((code, message_)) => json!({
+						"jsonrpc": "2.0",
+						"id": message["id"],
+						"error": { "code": code, "message": message_ }
+					})
+				};
+
+			rpc::write_message(&mut output, &response)?;
+		}
+
+		Ok(1)
+	}
+
+	fn request(&mut self, method: &str, params: &Value) -> RequestResult {
+		match method {
+			"initialize" => Ok(json!({
+				"capabilities": {
+					"textDocumentSync": 1,
+					"hoverProvider": true,
+					"definitionProvider": true,
+					"completionProvider": { "triggerCharacters": [".", ":"] },
+					"documentSymbolProvider": true
+				},
+				"serverInfo": { "name": "quest-lsp", "version": env!("CARGO_PKG_VERSION") }
+			})),
+			"shutdown" => {
+				self.is_shutdown = true;
+				Ok(Value::Null)
+			},
+			"textDocument/hover" => self.hover(params),
+			"textDocument/definition" => self.definition(params),
+			"textDocument/completion" => self.completion(params),
+			"textDocument/documentSymbol" => self.document_symbols(params),
+			_ => Err((METHOD_NOT_FOUND, format!("unknown method `{}`", method)))
+		}
+	}
+
+	fn notify(&mut self, method: &str, params: &Value, output: &mut impl Write) -> io::Result<()> {
+		let uri = params["textDocument"]["uri"].as_str().unwrap_or_default().to_string();
+
+		match method {
+			"textDocument/didOpen" => {
+				let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+				self.documents.insert(uri.clone(), Document::new(text.to_string()));
+			},
+			// we only ask for the full text, so the last change is the document.
+			"textDocument/didChange" => match params["contentChanges"].as_array().and_then(|changes| changes.last()) {
+				Some(change) => {
+					let text = change["text"].as_str().unwrap_or_default();
+					self.documents.insert(uri.clone(), Document::new(text.to_string()));
+				},
+				None => return Ok(())
+			},
+			"textDocument/didClose" => { self.documents.remove(&uri); },
+			_ => return Ok(())
+		}
+
+		self.publish_diagnostics(&uri, output)
+	}
+
+	fn publish_diagnostics(&self, uri: &str, output: &mut impl Write) -> io::Result<()> {
+		let diagnostics = self.documents.get(uri).map_or_else(Vec::new, |document| {
+			document.problems().into_iter().map(|problem| {
+				let mut diagnostic = json!({
+					"range": range(document, problem.start, problem.end),
+					"severity": if problem.severity == Severity::Error { 1 } else { 2 },
+					"source": "quest",
+					"message": problem.message
+				});
+
+				if let Some(code) = problem.code {
+					diagnostic["code"] = code.into();
+				}
+
+				diagnostic
+			}).collect()
+		});
+
+		rpc::write_message(output, &json!({
+			"jsonrpc": "2.0",
+			"method": "textDocument/publishDiagnostics",
+			"params": { "uri": uri, "diagnostics": diagnostics }
+		}))
+	}
+
+	/// The document and position that a `TextDocumentPositionParams` refers to.
+	fn position<'a>(&'a self, params: &Value) -> std::result::Result<(&'a Document, Position), (i64, String)> {
+		let uri = uri(params)?;
+		let document = self.documents.get(uri)
+			.ok_or_else(|| (INVALID_PARAMS, format!("`{}` isn't open", uri)))?;
+
+		let line = params["position"]["line"].as_u64().ok_or_else(|| missing("position.line"))?;
+		let column = params["position"]["character"].as_u64().ok_or_else(|| missing("position.character"))?;
+
+		Ok((document, document.position_at(line as usize, column as usize)))
+	}
+
+	fn hover(&self, params: &Value) -> RequestResult {
+		let (document, position) = self.position(params)?;
+
+		let builtins =
+			match document.attribute_at(position) {
+				Some(attribute) => attribute.name.as_deref().map_or_else(Vec::new, |name|
+					builtins::attribute(attribute.receiver.as_deref(), name)),
+				// variables that are assigned shadow builtins.
+				None => document.variable_at(position)
+					.filter(|_| document.definition_at(position).is_none())
+					.and_then(|(name, _, _)| builtins::global(&name))
+					.into_iter()
+					.collect()
+			};
+
+		if builtins.is_empty() {
+			return Ok(Value::Null);
+		}
+
+		let contents = builtins.iter().map(Builtin::hover).collect::<Vec<_>>().join("\n\n---\n\n");
+		let mut hover = json!({ "contents": { "kind": "markdown", "value": contents } });
+
+		if let Some((_, start, end)) = document.variable_at(position) {
+			hover["range"] = range(document, start, end);
+		}
+
+		Ok(hover)
+	}
+
+	fn definition(&self, params: &Value) -> RequestResult {
+		let (document, position) = self.position(params)?;
+		let uri = uri(params)?;
+
+		Ok(document.definition_at(position).map_or(Value::Null, |(start, end)| json!({
+			"uri": uri,
+			"range": range(document, start, end)
+		})))
+	}
+
+	fn completion(&self, params: &Value) -> RequestResult {
+		let (document, position) = self.position(params)?;
+
+		let item = |builtin: Builtin| {
+			let kind =
+				if builtin.is_function() {
+					FUNCTION_KIND
+				} else if builtin.is_type() {
+					CLASS_KIND
+				} else {
+					CONSTANT_KIND
+				};
+
+			let mut item = json!({ "label": builtin.name, "kind": kind, "detail": builtin.detail() });
+
+			if let Some(doc) = builtin.doc() {
+				item["documentation"] = json!({ "kind": "markdown", "value": doc });
+			}
+
+			item
+		};
+
+		if let Some(attribute) = document.attribute_at(position) {
+			let items = builtins::attributes_of(attribute.receiver.as_deref()).into_iter().map(item);
+			return Ok(Value::Array(items.collect()));
+		}
+
+		let variables = document.variables_at(position);
+		let mut items = variables.iter()
+			.map(|name| json!({ "label": name, "kind": VARIABLE_KIND }))
+			.collect::<Vec<_>>();
+
+		items.extend(builtins::globals()
+			.into_iter()
+			.filter(|builtin| !variables.contains(&builtin.name))
+			.map(item));
+
+		Ok(Value::Array(items))
+	}
+
+	fn document_symbols(&self, params: &Value) -> RequestResult {
+		let uri = uri(params)?;
+		let document = self.documents.get(uri)
+			.ok_or_else(|| (INVALID_PARAMS, format!("`{}` isn't open", uri)))?;
+
+		fn to_json(document: &Document, symbol: Symbol) -> Value {
+			json!({
+				"name": symbol.name,
+				"kind": FUNCTION_SYMBOL,
+				"range": range(document, symbol.start, symbol.end),
+				"selectionRange": range(document, symbol.name_start, symbol.name_end),
+				"children": symbol.children.into_iter()
+					.map(|child| to_json(document, child))
+					.collect::<Vec<_>>()
+			})
+		}
+
+		Ok(document.symbols().into_iter().map(|symbol| to_json(document, symbol)).collect())
+	}
+}
+
+/// An LSP `Range` from `start` to `end`.
+fn range(document: &Document, start: Position, end: Position) -> Value {
+	let position = |position| {
+		let (line, character) = document.lsp_position(position);
+		json!({ "line": line, "character": character })
+	};
+
+	json!({ "start": position(start), "end": position(end) })
+}
@@ -0,0 +1,282 @@
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+const URI: &str = "file:///tmp/greet.qs";
+
+const SOURCE: &str = r#"# greets someone
+greet = name -> {
+	message = "hi, " + name;
+	disp(message)
+};
+
+Counter = {
+	count = 0;
+	bump = { count += 1 };
+	:0
+};
+
+greet("world").then({ disp(Counter) });
+"#;
+
+/// Talks to a `quest-lsp` process the way an editor would.
+struct Client {
+	server: Child,
+	stdin: ChildStdin,
+	stdout: BufReader<ChildStdout>,
+	next_id: u64,
+	notifications: Vec<Value>
+}
+
+impl Client {
+	fn start() -> Self {
+		let mut server = Command::new(env!("CARGO_BIN_EXE_quest-lsp"))
+			.stdin(Stdio::piped())
+			.stdout(Stdio::piped())
+			.spawn()
+			.expect("couldn't start quest-lsp");
+
+		let stdin = server.stdin.take().unwrap();
+		let stdout = BufReader::new(server.stdout.take().unwrap());
+
+		Self { server, stdin, stdout, next_id: 0, notifications: vec![] }
+	}
+
+	fn send(&mut self, message: Value) {
+		let content = message.to_string();
+		write!(self.stdin, "Content-Length: {}\r\n\r\n{}", content.len(), content).unwrap();
+		self.stdin.flush().unwrap();
+	}
+
+	fn receive(&mut self) -> Value {
+		let mut length = 0;
+
+		loop {
+			let mut header = String::new();
+			assert_ne!(self.stdout.read_line(&mut header).unwrap(), 0, "server closed its output");
+
+			match header.trim_end() {
+				"" => break,
+				header => if let Some(value) = header.strip_prefix("Content-Length: ") {
+					length = value.parse().unwrap();
+				}
+			}
+		}
+
+		let mut content = vec![0; length];
+		self.stdout.read_exact(&mut content).unwrap();
+		serde_json::from_slice(&content).unwrap()
+	}
+
+	fn notify(&mut self, method: &str, params: Value) {
+		self.send(json!({ "jsonrpc": "2.0", "method": method, "params": params }));
+	}
+
+	/// Sends a request, and returns its response, keeping any notifications sent before it.
+	fn request(&mut self, method: &str, params: Value) -> Value {
+		self.next_id += 1;
+		let id = self.next_id;
+		self.send(json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }));
+
+		loop {
+			let message = self.receive();
+
+			if message["id"] == id {
+				return message;
+			}
+
+			self.notifications.push(message);
+		}
+	}
+
+	/// Waits for the diagnostics of the next `publishDiagnostics` notification.
+	fn diagnostics(&mut self) -> Vec<Value> {
+		let notification =
+			if self.notifications.is_empty() {
+				self.receive()
+			} else {
+				self.notifications.remove(0)
+			};
+
+		assert_eq!(notification["method"], "textDocument/publishDiagnostics");
+		assert_eq!(notification["params"]["uri"], URI);
+		notification["params"]["diagnostics"].as_array().unwrap().clone()
+	}
+
+	fn at(&mut self, method: &str, line: u64, character: u64) -> Value {
+		let response = self.request(method, json!({
+			"textDocument": { "uri": URI },
+			"position": { "line": line, "character": character }
+		}));
+
+		response["result"].clone()
+	}
+
+	fn open(&mut self, text: &str) {
+		self.notify("textDocument/didOpen", json!({
+			"textDocument": { "uri": URI, "languageId": "quest", "version": 1, "text": text }
+		}));
+	}
+
+	fn change(&mut self, text: &str) {
+		self.notify("textDocument/didChange", json!({
+			"textDocument": { "uri": URI, "version": 2 },
+			"contentChanges": [{ "text": text }]
+		}));
+	}
+
+	/// Shuts the server down, returning its exit code.
+	fn shutdown(mut self) -> i32 {
+		assert_eq!(self.request("shutdown", Value::Null)["result"], Value::Null);
+		self.notify("exit", Value::Null);
+		self.server.wait().unwrap().code().unwrap()
+	}
+}
+
+fn initialized() -> Client {
+	let mut client = Client::start();
+	let response = client.request("initialize", json!({ "processId": null, "rootUri": null, "capabilities": {} }));
+	let capabilities = &response["result"]["capabilities"];
+
+	assert_eq!(capabilities["textDocumentSync"], 1);
+	assert_eq!(capabilities["hoverProvider"], true);
+	assert_eq!(capabilities["definitionProvider"], true);
+	assert_eq!(capabilities["documentSymbolProvider"], true);
+	assert!(capabilities["completionProvider"].is_object());
+
+	client.notify("initialized", json!({}));
+	client
+}
+
+fn range(start: (u64, u64), end: (u64, u64)) -> Value {
+	json!({
+		"start": { "line": start.0, "character": start.1 },
+		"end": { "line": end.0, "character": end.1 }
+	})
+}
+
+#[test]
+fn diagnostics() {
+	let mut client = initialized();
+
+	client.open(SOURCE);
+	assert_eq!(client.diagnostics(), Vec::<Value>::new());
+
+	client.change("disp(1, 2");
+	let diagnostics = client.diagnostics();
+	assert_eq!(diagnostics.len(), 1);
+	assert_eq!(diagnostics[0]["severity"], 1);
+	assert!(diagnostics[0]["message"].as_str().unwrap().contains("missing closing paren"));
+
+	client.change("x = 3;\ndisp(y);\n");
+	let diagnostics = client.diagnostics();
+	assert_eq!(diagnostics.len(), 1);
+	assert_eq!(diagnostics[0]["severity"], 2);
+	assert_eq!(diagnostics[0]["code"], "undefined-variable");
+	assert_eq!(diagnostics[0]["range"], range((1, 0), (1, 8)));
+
+	client.notify("textDocument/didClose", json!({ "textDocument": { "uri": URI } }));
+	assert_eq!(client.diagnostics(), Vec::<Value>::new());
+
+	assert_eq!(client.shutdown(), 0);
+}
+
+#[test]
+fn definition() {
+	let mut client = initialized();
+	client.open(SOURCE);
+
+	// `name`, in `"hi, " + name`, is `greet`'s parameter
+	assert_eq!(client.at("textDocument/definition", 2, 22), json!({ "uri": URI, "range": range((1, 8), (1, 12)) }));
+
+	// `message`, in `disp(message)`
+	assert_eq!(client.at("textDocument/definition", 3, 8), json!({ "uri": URI, "range": range((2, 1), (2, 8)) }));
+
+	// `count`, in `bump`, is from the enclosing block
+	assert_eq!(client.at("textDocument/definition", 8, 11), json!({ "uri": URI, "range": range((7, 1), (7, 6)) }));
+
+	// `greet`, at the bottom
+	assert_eq!(client.at("textDocument/definition", 12, 2), json!({ "uri": URI, "range": range((1, 0), (1, 5)) }));
+
+	// builtins and attributes aren't assigned anywhere
+	assert_eq!(client.at("textDocument/definition", 3, 2), Value::Null);
+	assert_eq!(client.at("textDocument/definition", 12, 17), Value::Null);
+
+	assert_eq!(client.shutdown(), 0);
+}
+
+#[test]
+fn hover() {
+	let mut client = initialized();
+	client.open(SOURCE);
+
+	let hover = client.at("textDocument/hover", 3, 2);
+	let contents = hover["contents"]["value"].as_str().unwrap();
+	assert!(contents.contains("Kernel::disp"), "{}", contents);
+	assert!(contents.contains("Prints each argument's `@text`, followed by a newline."), "{}", contents);
+	assert_eq!(hover["range"], range((3, 1), (3, 5)));
+
+	let hover = client.at("textDocument/hover", 12, 17);
+	let contents = hover["contents"]["value"].as_str().unwrap();
+	assert!(contents.contains("Basic::then"), "{}", contents);
+
+	// variables that are assigned don't have builtin docs.
+	assert_eq!(client.at("textDocument/hover", 12, 2), Value::Null);
+
+	assert_eq!(client.shutdown(), 0);
+}
+
+#[test]
+fn completion() {
+	let mut client = initialized();
+	client.open(SOURCE);
+
+	let items = client.at("textDocument/completion", 3, 1);
+	let item = |label: &str| items.as_array().unwrap().iter()
+		.find(|item| item["label"] == label)
+		.unwrap_or_else(|| panic!("no completion for `{}`", label))
+		.clone();
+
+	assert_eq!(item("disp")["kind"], 3);
+	assert_eq!(item("disp")["detail"], "Kernel::disp");
+	assert_eq!(item("Text")["kind"], 7);
+	assert_eq!(item("then")["detail"], "Basic::then");
+	assert_eq!(item("message")["kind"], 6);
+	assert_eq!(item("greet")["kind"], 6);
+
+	assert_eq!(client.shutdown(), 0);
+}
+
+#[test]
+fn document_symbols() {
+	let mut client = initialized();
+	client.open(SOURCE);
+
+	let response = client.request("textDocument/documentSymbol", json!({ "textDocument": { "uri": URI } }));
+	let symbols = response["result"].as_array().unwrap();
+	let names = symbols.iter().map(|symbol| symbol["name"].as_str().unwrap()).collect::<Vec<_>>();
+	assert_eq!(names, ["greet", "Counter"]);
+
+	assert_eq!(symbols[0]["kind"], 12);
+	assert_eq!(symbols[0]["range"], range((1, 0), (4, 1)));
+	assert_eq!(symbols[0]["selectionRange"], range((1, 0), (1, 5)));
+	assert_eq!(symbols[0]["children"], json!([]));
+
+	let children = symbols[1]["children"].as_array().unwrap();
+	assert_eq!(children.len(), 1);
+	assert_eq!(children[0]["name"], "bump");
+	assert_eq!(children[0]["range"], range((8, 1), (8, 22)));
+
+	assert_eq!(client.shutdown(), 0);
+}
+
+#[test]
+fn unknown_methods_and_exiting_early() {
+	let mut client = initialized();
+
+	let response = client.request("textDocument/rename", json!({}));
+	assert_eq!(response["error"]["code"], -32601);
+
+	client.notify("exit", Value::Null);
+	assert_eq!(client.server.wait().unwrap().code(), Some(1));
+}
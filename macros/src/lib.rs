@@ -0,0 +1,320 @@
+//! Attributes for defining Quest types in Rust.
+//!
+//! These are re-exported by `quest_core`, and are meant to be used as `#[quest::object]` and
+//! `#[quest::methods]` after `use quest_core as quest;`.
+//!
+//! ```ignore
+//! use quest_core as quest;
+//!
+//! #[quest::object]
+//! #[derive(Debug, Clone)]
+//! struct Point { x: f64, y: f64 }
+//!
+//! #[quest::methods]
+//! impl Point {
+//!     #[quest(name = "()")]
+//!     fn new(x: f64, y: f64) -> Self { Self { x, y } }
+//!
+//!     fn x(&self) -> f64 { self.x }
+//!
+//!     #[quest(name = "+")]
+//!     fn add(&self, rhs: Point) -> Self { Self { x: self.x + rhs.x, y: self.y + rhs.y } }
+//! }
+//! ```
+//!
+//! The generated code refers to `::quest_core`, so it must be a dependency of any crate that uses
+//! these.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{
+	parse_macro_input, AttributeArgs, Error, FnArg, ImplItem, ImplItemMethod, ItemImpl, ItemStruct,
+	Lit, Meta, NestedMeta, Path, ReturnType, Type
+};
+
+/// Implements `ObjectType` for a struct, so it can be used as a Quest object.
+///
+/// The struct's Quest methods are defined by a `#[quest::methods]` impl block, which is required
+/// even if it's empty. It can be given these options:
+///
+/// - `name = "..."`, the name of the type in Quest. This defaults to the struct's name.
+/// - `parents(...)`, the types whose mappings are the parents of the type's mapping. This
+///   defaults to `Basic`.
+#[proc_macro_attribute]
+pub fn object(args: TokenStream, item: TokenStream) -> TokenStream {
+	let args = parse_macro_input!(args as AttributeArgs);
+	let item = parse_macro_input!(item as ItemStruct);
+
+	expand_object(args, item).unwrap_or_else(|err| err.to_compile_error()).into()
+}
+
+/// Defines the Quest methods of a `#[quest::object]` struct from the functions in an impl block.
+///
+/// Each function becomes a method with the function's name (without a leading `qs_`), unless it's
+/// renamed with `#[quest(name = "...")]` (e.g. for operators like `+`, or names ending in `?`).
+/// Functions marked `#[quest(skip)]` aren't exposed.
+///
+/// Functions that take `self` are called on the object the method's called on. Functions that
+/// don't are called when the method's called on the type itself (e.g. `Point.new(1, 2)`).
+///
+/// Parameters are converted from their arguments with `FromObject`, and return values are
+/// converted back with `Object::from`. A `Result` that's returned has its error raised. Calling
+/// a method with the wrong number of arguments, or arguments of the wrong type, raises an
+/// `ArgumentError`.
+#[proc_macro_attribute]
+pub fn methods(args: TokenStream, item: TokenStream) -> TokenStream {
+	let args = parse_macro_input!(args as AttributeArgs);
+	let item = parse_macro_input!(item as ItemImpl);
+
+	expand_methods(args, item).unwrap_or_else(|err| err.to_compile_error()).into()
+}
+
+fn expand_object(args: AttributeArgs, item: ItemStruct) -> syn::Result<TokenStream2> {
+	if !item.generics.params.is_empty() {
+		return Err(Error::new(item.generics.span(), "quest objects can't be generic"));
+	}
+
+	let ident = &item.ident;
+	let mut name = ident.to_string();
+	let mut parents: Vec<Path> = vec![];
+
+	for arg in args {
+		match arg {
+			NestedMeta::Meta(Meta::NameValue(ref nv)) if nv.path.is_ident("name") =>
+				match nv.lit {
+					Lit::Str(ref lit) => name = lit.value(),
+					ref other => return Err(Error::new(other.span(), "expected a string"))
+				},
+			NestedMeta::Meta(Meta::List(ref list)) if list.path.is_ident("parents") =>
+				for parent in &list.nested {
+					match parent {
+						NestedMeta::Meta(Meta::Path(path)) => parents.push(path.clone()),
+						other => return Err(Error::new(other.span(), "expected a type"))
+					}
+				},
+			other => return Err(Error::new(other.span(), "expected `name = \"...\"` or `parents(...)`"))
+		}
+	}
+
+	let parents =
+		if parents.is_empty() {
+			quote!(<::quest_core::types::Basic as ::quest_core::types::ObjectType>::mapping().clone())
+		} else {
+			quote!(#(<#parents as ::quest_core::types::ObjectType>::mapping().clone()),*)
+		};
+
+	Ok(quote! {
+		#item
+
+		impl ::quest_core::types::ObjectType for #ident {
+			fn initialize() -> ::quest_core::Result<()> {
+				use ::std::sync::atomic::{AtomicBool, Ordering};
+
+				static INITIALIZED: AtomicBool = AtomicBool::new(false);
+
+				if INITIALIZED.swap(true, Ordering::SeqCst) {
+					return Ok(());
+				}
+
+				let class = <Self as ::quest_core::types::ObjectType>::mapping();
+				class.set_attr_lit(::quest_core::Literal::NAME, ::quest_core::Object::from(#name))?;
+
+				Self::__quest_define_methods(class)
+			}
+
+			fn mapping() -> &'static ::quest_core::Object {
+				static CLASS: ::std::sync::OnceLock<::quest_core::Object> = ::std::sync::OnceLock::new();
+
+				CLASS.get_or_init(|| ::quest_core::Object::new_with_parent(
+					::quest_core::types::Class::new(#name),
+					vec![#parents]
+				))
+			}
+		}
+	})
+}
+
+/// The options given to a function with `#[quest(...)]`.
+struct MethodOptions {
+	name: Option<String>,
+	skip: bool
+}
+
+/// Removes the `#[quest(...)]` attributes from `method`, returning the options they contained.
+fn take_options(method: &mut ImplItemMethod) -> syn::Result<MethodOptions> {
+	let mut options = MethodOptions { name: None, skip: false };
+	let mut error = None;
+
+	method.attrs.retain(|attr| {
+		if !attr.path.is_ident("quest") {
+			return true;
+		}
+
+		let result = attr.parse_meta().and_then(|meta| match meta {
+			Meta::List(list) => list.nested.iter().try_for_each(|nested| match nested {
+				NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("name") =>
+					match nv.lit {
+						Lit::Str(ref lit) => { options.name = Some(lit.value()); Ok(()) },
+						ref other => Err(Error::new(other.span(), "expected a string"))
+					},
+				NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip") => {
+					options.skip = true;
+					Ok(())
+				},
+				other => Err(Error::new(other.span(), "expected `name = \"...\"` or `skip`"))
+			}),
+			other => Err(Error::new(other.span(), "expected `#[quest(...)]`"))
+		});
+
+		if let Err(err) = result {
+			error.get_or_insert(err);
+		}
+
+		false
+	});
+
+	error.map_or(Ok(options), Err)
+}
+
+/// Checks to see if `ty` is a `Result`, which is assumed to be a `quest_core::Result`.
+fn is_result(ty: &Type) -> bool {
+	match ty {
+		Type::Path(path) => path.path.segments.last().is_some_and(|seg| seg.ident == "Result"),
+		_ => false
+	}
+}
+
+/// Joins the `///` comments in `attrs` together, one per line.
+fn doc_comment(attrs: &[syn::Attribute]) -> String {
+	attrs.iter()
+		.filter(|attr| attr.path.is_ident("doc"))
+		.filter_map(|attr| match attr.parse_meta() {
+			Ok(Meta::NameValue(nv)) => match nv.lit {
+				Lit::Str(lit) => Some(lit.value() + "\n"),
+				_ => None
+			},
+			_ => None
+		})
+		.collect()
+}
+
+/// Creates the `RustFn` that calls `method` with converted arguments.
+fn wrap_method(ty: &Type, method: &ImplItemMethod, name: &str) -> syn::Result<TokenStream2> {
+	let sig = &method.sig;
+	let ident = &sig.ident;
+
+	if !sig.generics.params.is_empty() {
+		return Err(Error::new(sig.generics.span(), "quest methods can't be generic"));
+	}
+
+	if let Some(asyncness) = sig.asyncness {
+		return Err(Error::new(asyncness.span(), "quest methods can't be async"));
+	}
+
+	let mut receiver = None;
+	let mut params = vec![];
+
+	for input in &sig.inputs {
+		match input {
+			FnArg::Receiver(recv) => receiver = Some(recv),
+			FnArg::Typed(pat) => params.push(&*pat.ty)
+		}
+	}
+
+	let count = params.len();
+	let arg_names = (0..count)
+		.map(|idx| syn::Ident::new(&format!("arg{}", idx), Span::call_site()))
+		.collect::<Vec<_>>();
+	let conversions = params.iter().zip(&arg_names).enumerate().map(|(idx, (param, arg))| {
+		quote_spanned! { param.span() =>
+			let #arg = <#param as ::quest_core::types::FromObject>::from_argument(args[#idx], #idx)?;
+		}
+	});
+
+	// Arguments are converted before `this` is locked, in case converting them needs it.
+	let call =
+		match receiver {
+			None => quote!(<#ty>::#ident(#(#arg_names),*)),
+			Some(recv) if recv.reference.is_none() => quote! {
+				<#ty>::#ident((*this.try_downcast::<#ty>()?).clone(), #(#arg_names),*)
+			},
+			Some(recv) if recv.mutability.is_some() => quote! {
+				<#ty>::#ident(&mut *this.try_downcast_mut::<#ty>()?, #(#arg_names),*)
+			},
+			Some(_) => quote! {
+				<#ty>::#ident(&*this.try_downcast::<#ty>()?, #(#arg_names),*)
+			}
+		};
+
+	let result =
+		match sig.output {
+			ReturnType::Type(_, ref ret) if is_result(ret) => quote!(#call?),
+			_ => call
+		};
+
+	let fn_name = format!("{}::{}", quote!(#ty).to_string().replace(' ', ""), name);
+	let doc = doc_comment(&method.attrs);
+
+	Ok(quote! {
+		::quest_core::types::RustFn::method(#fn_name, |this, args| {
+			#[allow(unused_variables)]
+			let args = args.as_ref_checked(#count)?;
+			#(#conversions)*
+
+			Ok(::quest_core::Object::from(#result))
+		}).with_doc(#doc)
+	})
+}
+
+fn expand_methods(args: AttributeArgs, mut item: ItemImpl) -> syn::Result<TokenStream2> {
+	if let Some(arg) = args.first() {
+		return Err(Error::new(arg.span(), "`#[quest::methods]` doesn't take any options"));
+	}
+
+	if let Some((_, ref trait_, _)) = item.trait_ {
+		return Err(Error::new(trait_.span(), "`#[quest::methods]` must be on an inherent impl"));
+	}
+
+	let ty = &*item.self_ty.clone();
+	let mut definitions = vec![];
+
+	for impl_item in &mut item.items {
+		let method =
+			match impl_item {
+				ImplItem::Method(method) => method,
+				_ => continue
+			};
+
+		let options = take_options(method)?;
+
+		if options.skip {
+			continue;
+		}
+
+		let name = options.name.unwrap_or_else(|| {
+			let ident = method.sig.ident.to_string();
+			ident.strip_prefix("qs_").map(String::from).unwrap_or(ident)
+		});
+		let wrapped = wrap_method(ty, method, &name)?;
+
+		definitions.push(quote! {
+			class.set_value_lit(#name, #wrapped)?;
+		});
+	}
+
+	Ok(quote! {
+		#item
+
+		impl #ty {
+			#[doc(hidden)]
+			pub(crate) fn __quest_define_methods(class: &::quest_core::Object) -> ::quest_core::Result<()> {
+				#(#definitions)*
+				Ok(())
+			}
+		}
+	})
+}
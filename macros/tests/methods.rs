@@ -0,0 +1,105 @@
+use quest_core as quest;
+use quest_core::{Object, Error, types::ObjectType, error::ArgumentError};
+
+#[quest::object(name = "Point")]
+#[derive(Debug, Clone, PartialEq)]
+struct Point {
+	x: i64,
+	y: i64
+}
+
+#[quest::methods]
+impl Point {
+	#[quest(name = "()")]
+	fn new(x: i64, y: i64) -> Self {
+		Self { x, y }
+	}
+
+	fn x(&self) -> i64 {
+		self.x
+	}
+
+	fn set_x(&mut self, x: i64) {
+		self.x = x;
+	}
+
+	#[quest(name = "+")]
+	fn qs_add(&self, rhs: Point) -> Self {
+		Self::new(self.x + rhs.x, self.y + rhs.y)
+	}
+
+	#[quest(name = "scale")]
+	fn checked_scale(self, by: Option<i64>) -> quest_core::Result<Self> {
+		match by {
+			Some(0) => Err(Error::Messaged("can't scale by zero".into())),
+			Some(by) => Ok(Self::new(self.x * by, self.y * by)),
+			None => Ok(self)
+		}
+	}
+
+	#[quest(skip)]
+	#[allow(dead_code)]
+	fn helper(&self) {}
+}
+
+fn setup() {
+	quest_core::init();
+	Point::initialize().unwrap();
+}
+
+fn call(obj: &Object, attr: &'static str, args: &[&Object]) -> quest_core::Result<Object> {
+	obj.call_attr_lit(attr, args)
+}
+
+#[test]
+fn defines_mapping() {
+	setup();
+
+	let point = Object::from(Point::new(1, 2));
+
+	assert!(point.is_a::<Point>());
+	assert!(Point::mapping().has_attr_lit("x").unwrap());
+	assert!(Point::mapping().has_attr_lit("+").unwrap());
+	assert!(!Point::mapping().has_attr_lit("helper").unwrap());
+	assert!(!Point::mapping().has_attr_lit("qs_add").unwrap());
+}
+
+#[test]
+fn converts_arguments_and_results() {
+	setup();
+
+	let point = call(Point::mapping(), "()", &[&3.into(), &4.into()]).unwrap();
+	assert_eq!(*point.downcast::<Point>().unwrap(), Point::new(3, 4));
+
+	let sum = call(&point, "+", &[&point]).unwrap();
+	assert_eq!(*sum.downcast::<Point>().unwrap(), Point::new(6, 8));
+
+	call(&point, "set_x", &[&10.into()]).unwrap();
+	let x = call(&point, "x", &[]).unwrap();
+	assert_eq!(*x.downcast::<quest_core::types::Number>().unwrap(), 10);
+
+	let same = call(&point, "scale", &[&Object::default()]).unwrap();
+	assert_eq!(*same.downcast::<Point>().unwrap(), Point::new(10, 4));
+}
+
+#[test]
+fn raises_argument_errors() {
+	setup();
+
+	let point = Object::from(Point::new(1, 2));
+
+	match call(&point, "+", &[]) {
+		Err(Error::ArgumentError(ArgumentError::InvalidLength { given: 0, expected: 1 })) => {},
+		other => panic!("wrong number of arguments accepted: {:?}", other)
+	}
+
+	match call(&point, "+", &[&1.into()]) {
+		Err(Error::ArgumentError(ArgumentError::WrongType { position: 0, .. })) => {},
+		other => panic!("wrong type accepted: {:?}", other)
+	}
+
+	match call(&point, "scale", &[&0.into()]) {
+		Err(Error::Messaged(msg)) => assert_eq!(msg, "can't scale by zero"),
+		other => panic!("error wasn't raised: {:?}", other)
+	}
+}
@@ -0,0 +1,444 @@
+//! Converting expressions to and from trees of Quest objects, so Quest code can inspect and
+//! transform code as data.
+//!
+//! Every node in a tree has a `kind` attribute, which determines its other attributes:
+//!
+//! | `kind`           | attributes                                                              |
+//! |------------------|-------------------------------------------------------------------------|
+//! | `"block"`        | `paren` (`"()"`, `"[]"`, or `"{}"`), and `lines`. Each line is either a |
+//! |                  | node, or a list of nodes if it's comma-separated.                       |
+//! | `"operator"`     | `operator` (eg `"+"`, or `"-@"` for unary minus), and `operands`.       |
+//! | `"call"`         | `callee`, and `args`, a `"block"` node.                                 |
+//! | `"variable"`     | `name`.                                                                 |
+//! | `"text"`         | `value`.                                                                |
+//! | `"interpolated"` | `segments`, a list of texts and `"block"` nodes.                        |
+//! | `"number"`       | `value`.                                                                |
+//! | `"regex"`        | `value`.                                                                |
+//! | `"stackpos"`     | `value`, the number after the `:`.                                      |
+//!
+//! Trees created from code are made of [`Node`]s, but any object with the right attributes can be
+//! converted back.
+
+use crate::Block;
+use crate::block::Line;
+use crate::expression::{Expression, BoundOperator, Executable};
+use crate::expression::bound_operator::OperArgs;
+use crate::stream::{BufStream, Context, Stream};
+use crate::token::{Operator, ParenType, Primitive};
+use crate::token::primitive::{Interpolated, text::Segment};
+use quest_core::{impl_object_type, Object, Args, Binding};
+use quest_core::types::{List, Number, Regex, Text, RustClosure};
+use quest_core::error::ValueError;
+use std::convert::TryInto;
+
+/// A node in a tree created by [`Block::qs_ast`] or [`Ast::qs_parse`].
+///
+/// Its contents are stored entirely in its attributes; see the [module docs](self).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Node;
+
+/// Functions for working with trees of [`Node`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Ast;
+
+fn node(kind: &'static str, attrs: Vec<(&'static str, Object)>) -> quest_core::Result<Object> {
+	let node = Object::from(Node);
+	node.set_attr_lit("kind", kind.into())?;
+
+	for (attr, value) in attrs {
+		node.set_attr_lit(attr, value)?;
+	}
+
+	Ok(node)
+}
+
+/// Converts `block` into a `"block"` node.
+pub fn block_to_node(block: &Block) -> quest_core::Result<Object> {
+	let lines = block.lines.iter()
+		.map(|line| match line {
+			Line::Single(expr) => to_node(expr),
+			Line::Multiple(exprs) => exprs.iter()
+				.map(to_node)
+				.collect::<quest_core::Result<Vec<_>>>()
+				.map(Object::from)
+		})
+		.collect::<quest_core::Result<Vec<_>>>()?;
+
+	let mut attrs = vec![
+		("paren", Object::from(block.paren_type.to_string())),
+		("lines", lines.into()),
+		("line", block.context.lineno.into()),
+	];
+
+	if let Some(ref file) = block.context.file {
+		attrs.push(("file", file.display().to_string().into()));
+	}
+
+	node("block", attrs)
+}
+
+/// Converts `expr` into a node.
+pub fn to_node(expr: &Expression) -> quest_core::Result<Object> {
+	match expr {
+		Expression::Block(block) => block_to_node(block),
+		Expression::FunctionCall(callee, args) =>
+			node("call", vec![("callee", to_node(callee)?), ("args", block_to_node(args)?)]),
+		Expression::Operator(BoundOperator { oper, this, args }) => {
+			let mut operands = vec![to_node(this)?];
+
+			match args.as_ref() {
+				OperArgs::Unary => {},
+				OperArgs::Binary(rhs) => operands.push(to_node(rhs)?),
+				OperArgs::Ternary(mid, rhs) => {
+					operands.push(to_node(mid)?);
+					operands.push(to_node(rhs)?);
+				}
+			}
+
+			node("operator", vec![("operator", oper.repr().into()), ("operands", operands.into())])
+		},
+		Expression::Primitive(Primitive::Variable(var)) =>
+			node("variable", vec![("name", Text::from(var.clone()).into())]),
+		Expression::Primitive(Primitive::Text(text)) =>
+			node("text", vec![("value", text.clone().into())]),
+		Expression::Primitive(Primitive::Number(number)) =>
+			node("number", vec![("value", (*number).into())]),
+		Expression::Primitive(Primitive::Regex(regex)) =>
+			node("regex", vec![("value", regex.clone().into())]),
+		Expression::Primitive(Primitive::StackPos(pos)) =>
+			node("stackpos", vec![("value", isize::from(*pos).into())]),
+		Expression::Primitive(Primitive::Interpolated(interpolated)) => {
+			let segments = interpolated.segments().iter()
+				.map(|segment| match segment {
+					Segment::Text(text) => Ok(text.clone().into()),
+					Segment::Code(block) => block_to_node(block)
+				})
+				.collect::<quest_core::Result<Vec<_>>>()?;
+
+			node("interpolated", vec![("segments", segments.into())])
+		}
+	}
+}
+
+fn malformed(node: &Object, what: impl std::fmt::Display) -> quest_core::Error {
+	ValueError::Messaged(format!("malformed ast node {:?}: {}", node, what)).into()
+}
+
+fn text_attr(node: &Object, attr: &'static str) -> quest_core::Result<String> {
+	Ok(node.get_attr_lit(attr)?.call_downcast::<Text>()?.to_string())
+}
+
+fn list_attr(node: &Object, attr: &'static str) -> quest_core::Result<Vec<Object>> {
+	Ok(node.get_attr_lit(attr)?.call_downcast::<List>()?.as_ref().to_vec())
+}
+
+/// Converts a `"block"` node into a [`Block`].
+pub fn node_to_block(node: &Object) -> quest_core::Result<Block> {
+	match from_node(node)? {
+		Expression::Block(block) => Ok(block),
+		_ => Err(malformed(node, "expected a block"))
+	}
+}
+
+/// Converts `node` back into an [`Expression`].
+pub fn from_node(node: &Object) -> quest_core::Result<Expression> {
+	let kind = text_attr(node, "kind")?;
+
+	Ok(match kind.as_str() {
+		"block" => {
+			let paren_type =
+				match text_attr(node, "paren")?.as_str() {
+					"()" => ParenType::Round,
+					"[]" => ParenType::Square,
+					"{}" => ParenType::Curly,
+					other => return Err(malformed(node, format!("unknown paren {:?}", other)))
+				};
+
+			let lines = list_attr(node, "lines")?.iter()
+				.map(|line| match line.downcast::<List>() {
+					Some(exprs) => exprs.as_ref().iter()
+						.map(from_node)
+						.collect::<quest_core::Result<_>>()
+						.map(Line::Multiple),
+					None => from_node(line).map(Line::Single)
+				})
+				.collect::<quest_core::Result<_>>()?;
+
+			let mut context = Context::default();
+
+			if node.has_attr_lit("file")? {
+				context.file = Some(text_attr(node, "file")?.into());
+			}
+
+			if node.has_attr_lit("line")? {
+				context.lineno = (*node.get_attr_lit("line")?.call_downcast::<Number>()?).try_into()?;
+			}
+
+			Block { lines, paren_type, context, linenos: vec![], doc: None }.into()
+		},
+		"call" => Expression::FunctionCall(
+			Box::new(from_node(&node.get_attr_lit("callee")?)?),
+			node_to_block(&node.get_attr_lit("args")?)?
+		),
+		"operator" => {
+			let repr = text_attr(node, "operator")?;
+			let oper = Operator::from_repr(&repr)
+				.ok_or_else(|| malformed(node, format!("unknown operator {:?}", repr)))?;
+
+			let mut operands = list_attr(node, "operands")?.iter()
+				.map(from_node)
+				.collect::<quest_core::Result<Vec<_>>>()?
+				.into_iter();
+
+			let this = Box::new(operands.next().ok_or_else(|| malformed(node, "no operands"))?);
+			let args =
+				match (operands.next(), operands.next(), operands.next()) {
+					(None, _, _) => OperArgs::Unary,
+					(Some(rhs), None, _) => OperArgs::Binary(rhs),
+					(Some(mid), Some(rhs), None) => OperArgs::Ternary(mid, rhs),
+					_ => return Err(malformed(node, "too many operands"))
+				};
+
+			BoundOperator { oper, this, args: Box::new(args) }.into()
+		},
+		"variable" => Primitive::Variable(Text::from(text_attr(node, "name")?).into()).into(),
+		"text" => Primitive::Text(text_attr(node, "value")?.into()).into(),
+		"number" => Primitive::Number(*node.get_attr_lit("value")?.call_downcast::<Number>()?).into(),
+		"regex" => Primitive::Regex(node.get_attr_lit("value")?.try_downcast::<Regex>()?.clone()).into(),
+		"stackpos" => {
+			let pos: isize = (*node.get_attr_lit("value")?.call_downcast::<Number>()?).try_into()?;
+			Primitive::StackPos(pos.into()).into()
+		},
+		"interpolated" => {
+			let segments = list_attr(node, "segments")?.iter()
+				.map(|segment| match segment.downcast::<Text>() {
+					Some(text) => Ok(Segment::Text(text.clone())),
+					None => node_to_block(segment).map(Segment::Code)
+				})
+				.collect::<quest_core::Result<Vec<_>>>()?;
+
+			Primitive::Interpolated(Interpolated::from(segments)).into()
+		},
+		other => return Err(malformed(node, format!("unknown kind {:?}", other)))
+	})
+}
+
+/// Runs `node` in `binding`, or the current binding if it's not given.
+///
+/// If `node` is a block, its lines are run rather than the block being created.
+fn eval(node: &Object, binding: Option<&Object>) -> quest_core::Result<Object> {
+	let expr = from_node(node)?;
+
+	let run = || match expr {
+		Expression::Block(ref block) => block.run_block_to_object(),
+		ref other => other.execute()
+	};
+
+	match binding {
+		Some(binding) => {
+			let old = Binding::instance();
+			Binding::set_binding(binding.clone());
+			let result = run();
+			Binding::set_binding(old.as_ref().clone());
+			result
+		},
+		None => run()
+	}
+}
+
+impl Node {
+	/// Gets the source code of this node.
+	#[inline]
+	pub fn qs_at_text(this: &Object, _: Args) -> quest_core::Result<Object> {
+		Ok(from_node(this)?.to_string().into())
+	}
+}
+
+impl Ast {
+	/// Runs the tree given as the first argument, in the binding given as the second argument (or
+	/// the current one if it's not given).
+	#[inline]
+	pub fn qs_eval(_: &Object, args: Args) -> quest_core::Result<Object> {
+		eval(args.try_arg(0)?, args.arg(1))
+	}
+
+	/// Gets the source code of the tree given as the first argument.
+	#[inline]
+	pub fn qs_to_source(_: &Object, args: Args) -> quest_core::Result<Object> {
+		Ok(from_node(args.try_arg(0)?)?.to_string().into())
+	}
+
+	/// Parses the first argument into a `"block"` node containing its code.
+	pub fn qs_parse(_: &Object, args: Args) -> quest_core::Result<Object> {
+		let code = args.try_arg(0)?.call_downcast::<Text>()?.to_string();
+
+		let expr = Expression::parse_stream(BufStream::from(code).tokens())
+			.map_err(|err| Box::new(err) as Box<_>)?;
+
+		match expr {
+			Expression::Block(mut block) => {
+				block.paren_type = ParenType::Curly;
+				block_to_node(&block)
+			},
+			other => unreachable!("parsed code isn't wrapped in a block: {:?}", other)
+		}
+	}
+
+	/// Creates a macro from the first argument.
+	///
+	/// When the macro's called, the function is called with the trees of each of its arguments,
+	/// which must be blocks. The tree it returns is then run in the scope the macro was called
+	/// from.
+	pub fn qs_macro(_: &Object, args: Args) -> quest_core::Result<Object> {
+		let func = args.try_arg(0)?.clone();
+
+		Ok(RustClosure::new(move |args| {
+			let trees = args.iter()
+				.map(|arg| block_to_node(&*arg.try_downcast::<Block>()?))
+				.collect::<quest_core::Result<Vec<_>>>()?;
+
+			let tree = func.call_attr_lit(&quest_core::Literal::CALL, trees.iter().collect::<Args>())?;
+
+			eval(&tree, None)
+		}).into())
+	}
+}
+
+impl_object_type!{
+for Node [(parents quest_core::types::Basic)]:
+	/// Gets the source code of this node.
+	"@text" => method Node::qs_at_text,
+}
+
+impl_object_type!{
+for Ast [(parents quest_core::types::Basic)]:
+	"Node" => const Node::mapping().clone(),
+	/// Runs the tree given as the first argument, in the binding given as the second argument (or
+	/// the current one if it's not given).
+	"eval" => method Ast::qs_eval,
+	/// Gets the source code of the tree given as the first argument.
+	"to_source" => method Ast::qs_to_source,
+	/// Parses the first argument into a `"block"` node containing its code.
+	"parse" => method Ast::qs_parse,
+	/// Creates a macro from the first argument, which is called with the trees of the macro's
+	/// arguments and returns the tree to run in their place.
+	"macro" => method Ast::qs_macro,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use quest_core::error::ValueError;
+
+	fn parse(code: &str) -> Expression {
+		Expression::parse_stream(BufStream::from(code.to_string()).tokens()).unwrap()
+	}
+
+	fn eval(code: &str) -> quest_core::Result<Object> {
+		quest_core::init();
+		crate::init();
+
+		Binding::new_stackframe(None, Args::default(), |_| parse(code).execute())
+	}
+
+	fn number(obj: quest_core::Result<Object>) -> Number {
+		*obj.unwrap().downcast::<Number>().unwrap()
+	}
+
+	#[test]
+	fn round_trips() {
+		quest_core::init();
+		crate::init();
+
+		let codes = [
+			"a + -b",
+			"x[1] = 2",
+			"f(1) { 2 }",
+			"(a, *b) -> { a }",
+			"\"x ${y} z\"",
+			"[1, 2]; (3,)",
+			":1 + :0",
+			"/a+b/.match('ab')",
+		];
+
+		for &code in codes.iter() {
+			let expr = parse(code);
+			let node = to_node(&expr).unwrap();
+			assert_eq!(from_node(&node).unwrap().to_string(), expr.to_string(), "{:?} didn't round trip", code);
+		}
+	}
+
+	#[test]
+	fn nodes() {
+		quest_core::init();
+		crate::init();
+
+		let block = node_to_block(&to_node(&parse("\n\n{ x.y + 1 }")).unwrap()).unwrap();
+		let tree = &list_attr(&block_to_node(&block).unwrap(), "lines").unwrap()[0];
+		assert_eq!(text_attr(tree, "kind").unwrap(), "block");
+		assert_eq!(text_attr(tree, "paren").unwrap(), "{}");
+		assert_eq!(number(tree.get_attr_lit("line")), Number::from(3));
+
+		let sum = &list_attr(tree, "lines").unwrap()[0];
+		assert_eq!(text_attr(sum, "kind").unwrap(), "operator");
+		assert_eq!(text_attr(sum, "operator").unwrap(), "+");
+
+		let operands = list_attr(sum, "operands").unwrap();
+		assert_eq!(text_attr(&operands[0], "operator").unwrap(), ".");
+		assert_eq!(text_attr(&operands[1], "kind").unwrap(), "number");
+	}
+
+	#[test]
+	fn malformed_nodes() {
+		quest_core::init();
+		crate::init();
+
+		let malformed = |attrs: Vec<(&'static str, Object)>| {
+			match from_node(&node("operator", attrs).unwrap()) {
+				Err(quest_core::Error::ValueError(ValueError::Messaged(msg))) => msg,
+				other => panic!("node wasn't malformed: {:?}", other)
+			}
+		};
+
+		let one = || node("number", vec![("value", 1.into())]).unwrap();
+
+		assert!(malformed(vec![("operator", "nope".into()), ("operands", vec![one()].into())])
+			.ends_with("unknown operator \"nope\""));
+		assert!(malformed(vec![("operator", "+".into()), ("operands", Vec::<Object>::new().into())])
+			.ends_with("no operands"));
+		assert!(malformed(vec![("operator", "+".into()), ("operands", vec![one(), one(), one(), one()].into())])
+			.ends_with("too many operands"));
+		assert!(from_node(&node("bogus", vec![]).unwrap()).is_err());
+	}
+
+	#[test]
+	fn eval_and_to_source() {
+		assert_eq!(number(eval("Ast.eval(Ast.parse('1 + 2'))")), Number::from(3));
+		assert_eq!(number(eval("x = 2; Ast.eval({ x * 3 }.ast())")), Number::from(6));
+
+		// trees can be run in other scopes, without changing the current one.
+		assert_eq!(number(eval("x = 2; Ast.eval({ x * 3 }.ast(), { x = 5; :0 }()) + x")), Number::from(17));
+
+		let source = eval("Ast.to_source(Ast.parse(\"x.y = 'z'\"))").unwrap();
+		assert_eq!(source.downcast::<Text>().unwrap().to_string(), "{ x.y = 'z' }");
+	}
+
+	#[test]
+	fn macros() {
+		let code = "
+			swap = Ast.macro((a, b) -> {
+				a = a.lines.get(0);
+				b = b.lines.get(0);
+				Ast.parse(\"tmp = ${a}; ${a} = ${b}; ${b} = tmp\")
+			});
+			left = 1;
+			right = 2;
+			swap({ left }, { right });
+			left * 10 + right";
+
+		assert_eq!(number(eval(code)), Number::from(21));
+
+		// macros can only be given blocks.
+		assert!(eval("Ast.macro({ _0 })(1)").is_err());
+	}
+}
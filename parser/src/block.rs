@@ -6,6 +6,8 @@ use crate::token::{Token, ParenType};
 use crate::stream::{Context, Contexted};
 use crate::expression::{Constructable, Expression, PutBack, Executable};
 use std::fmt::{self, Debug, Display, Formatter};
+use std::cell::RefCell;
+use std::path::PathBuf;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) enum Line {
@@ -18,6 +20,10 @@ pub struct Block {
 	pub(crate) lines: Vec<Line>,
 	pub(crate) paren_type: ParenType,
 	pub(crate) context: Context,
+	/// The line number each of `lines` starts on. Blocks that weren't parsed may not have them.
+	pub(crate) linenos: Vec<usize>,
+	/// The `##` doc comments before the assignment this block is the value of, if any.
+	pub(crate) doc: Option<String>,
 }
 
 impl Block {
@@ -37,12 +43,14 @@ impl Block {
 		fn fix_expr(expr: Expression) -> Expression {
 			match expr {
 				Primitive(Variable(var)) => Primitive(Text(var.into())),
-				ExprBlock(Block { lines, paren_type, context })
+				ExprBlock(Block { lines, paren_type, context, linenos, doc })
 					if paren_type != ParenType::Curly =>
 					ExprBlock(Block {
 						lines: lines.into_iter().map(fix_line).collect(),
 						paren_type,
-						context }),
+						context,
+						linenos,
+						doc }),
 				ExprOper(BoundOperator { oper, this, args }) if oper == Splat || oper == SplatSplat
 					=> ExprOper(BoundOperator { oper, this: Box::new(fix_expr(*this)), args }),
 				other => other
@@ -52,7 +60,9 @@ impl Block {
 		Self {
 			context: self.context,
 			paren_type: self.paren_type,
-			lines: self.lines.into_iter().map(fix_line).collect()
+			lines: self.lines.into_iter().map(fix_line).collect(),
+			linenos: self.linenos,
+			doc: self.doc
 		}
 	}
 }
@@ -75,8 +85,15 @@ impl Debug for Block {
 
 
 impl Display for Block {
-	#[allow(clippy::all)]
+	#[inline]
 	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		self.fmt_with(f, |f, expr| Display::fmt(expr, f))
+	}
+}
+
+impl Block {
+	/// Writes this block's source, using `fmt_expr` to write each of its expressions.
+	pub(crate) fn fmt_with(&self, f: &mut Formatter, fmt_expr: fn(&mut Formatter, &Expression) -> fmt::Result) -> fmt::Result {
 		write!(f, "{}", self.paren_type.left())?;
 
 		if self.lines.len() == 1 {
@@ -90,9 +107,9 @@ impl Display for Block {
 				write!(f, "\t")?;
 			}
 
-			Display::fmt(line, f)?;
+			line.fmt_with(f, fmt_expr)?;
 
-			if i < self.lines.len() && i != self.lines.len() - 1 {
+			if i != self.lines.len() - 1 {
 				write!(f, ";")?
 			}
 
@@ -108,22 +125,31 @@ impl Display for Block {
 	}
 }
 
-
 impl Display for Line {
+	#[inline]
 	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		self.fmt_with(f, |f, expr| Display::fmt(expr, f))
+	}
+}
+
+impl Line {
+	fn fmt_with(&self, f: &mut Formatter, fmt_expr: fn(&mut Formatter, &Expression) -> fmt::Result) -> fmt::Result {
 		match self {
-			Self::Single(expr) => Display::fmt(expr, f),
-			// OPTIMIZE: I'm sure there's a builtin way to make this easier
+			Self::Single(expr) => fmt_expr(f, expr),
 			Self::Multiple(exprs) => {
-				let mut is_first_expr = true;
-				for expr in exprs.iter() {
-					if is_first_expr {
-						is_first_expr = false;
-					} else {
+				for (i, expr) in exprs.iter().enumerate() {
+					if i != 0 {
 						write!(f, ", ")?
 					}
-					Display::fmt(&expr, f)?;
+
+					fmt_expr(f, expr)?;
 				}
+
+				// without a trailing comma, a single expression isn't parsed as multiple.
+				if exprs.len() <= 1 {
+					write!(f, ",")?;
+				}
+
 				Ok(())
 			}
 		}
@@ -167,6 +193,26 @@ impl Line {
 	}
 }
 
+/// Where an error was raised: the innermost line of code that failed.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ErrorLocation {
+	/// The file the line is in, if it's in one.
+	pub file: Option<PathBuf>,
+	/// The line's number.
+	pub lineno: usize
+}
+
+thread_local!(
+	// Errors propagate through every line that encloses the one that raised them, so only the first
+	// line to fail records itself. It's cleared once a line succeeds, as the error's been handled.
+	static ERROR_LOCATION: RefCell<Option<ErrorLocation>> = const { RefCell::new(None) };
+);
+
+/// Takes the location of the last error that was raised, if it hasn't been handled since.
+pub fn take_error_location() -> Option<ErrorLocation> {
+	ERROR_LOCATION.with(|location| location.borrow_mut().take())
+}
+
 impl Block {
 	#[must_use]
 	#[inline]
@@ -174,14 +220,45 @@ impl Block {
 		self.paren_type
 	}
 
+	/// The `##` doc comments before the assignment this block is the value of, if any.
+	#[must_use]
+	#[inline]
+	pub fn doc(&self) -> Option<&str> {
+		self.doc.as_deref()
+	}
+
+	/// Runs the line at `idx`, recording where it is if it's the first line to fail, and letting the
+	/// [debugger](crate::debugger) pause before it and [coverage](crate::coverage) count it.
+	fn execute_line(&self, idx: usize) -> quest_core::Result<LineResult> {
+		let lineno = self.linenos.get(idx).copied().unwrap_or(self.context.lineno);
+		let _counted = crate::coverage::enter_line(self.context.file.as_deref(), lineno);
+		let _line = crate::debugger::enter_line(self.context.file.as_deref(), lineno);
+		let result = self.lines[idx].execute();
+
+		ERROR_LOCATION.with(|location| {
+			let mut location = location.borrow_mut();
+
+			match result {
+				Ok(_) if location.is_some() => *location = None,
+				Err(_) if location.is_none() => *location = Some(ErrorLocation {
+					file: self.context.file.clone(),
+					lineno
+				}),
+				_ => {}
+			}
+		});
+
+		result
+	}
+
 	#[tracing::instrument(name="Block::run_block")]
 	pub(super) fn run_block(&self) -> quest_core::Result<Option<LineResult>> {
-		if let Some((last, rest)) = self.lines.split_last() {
-			for line in rest {
-				line.execute()?;
+		if let Some(last) = self.lines.len().checked_sub(1) {
+			for idx in 0..last {
+				self.execute_line(idx)?;
 			}
 
-			let mut ret = last.execute()?;
+			let mut ret = self.execute_line(last)?;
 
 			if self.paren_type == ParenType::Square {
 				ret = ret.force_multiple();
@@ -195,7 +272,7 @@ impl Block {
 		}
 	}
 
-	fn run_block_to_object(&self) -> quest_core::Result<quest_core::Object> {
+	pub(crate) fn run_block_to_object(&self) -> quest_core::Result<quest_core::Object> {
 		let lines = self.run_block()?;
 		let lines_obj = lines.map(Object::from).unwrap_or_default();
 		Ok(lines_obj)
@@ -221,45 +298,180 @@ impl Constructable for Block {
 	where
 		C: Iterator<Item=Result<Token>> + PutBack + Contexted
 	{
-		let paren = 
-			match ctor.next().transpose()? {
-				Some(Token::Left(paren)) => paren,
-				Some(tkn) => { ctor.put_back(Ok(tkn)); return Ok(None) },
-				None => return Ok(None)
-			};
+		match ctor.next().transpose()? {
+			Some(Token::Left(paren)) => {
+				let opened_at = crate::Span::new(ctor.start_context().clone(), ctor.context().clone());
+				Self::construct_body(ctor, paren, Some(opened_at)).map(Some)
+			},
+			Some(tkn) => { ctor.put_back(Ok(tkn)); Ok(None) },
+			None => Ok(None)
+		}
+	}
+}
+
+/// Skips the rest of a line that couldn't be parsed, so the lines after it can be.
+///
+/// This stops after the line's endline, or before the paren that closes the block it's in. There's
+/// no such paren when `in_parens` is false, so stray closing parens are skipped too.
+fn skip_line<C>(ctor: &mut C, in_parens: bool) -> Result<()>
+where
+	C: Iterator<Item=Result<Token>> + PutBack + Contexted
+{
+	let mut depth = 0usize;
+
+	while let Some(tkn) = ctor.next() {
+		match tkn {
+			Err(err) => ctor.recover(err)?,
+			Ok(Token::Left(_)) => depth += 1,
+			Ok(rparen @ Token::Right(_)) if depth == 0 && in_parens => { ctor.put_back(Ok(rparen)); break },
+			Ok(Token::Right(_)) if depth == 0 => {},
+			Ok(Token::Right(_)) => depth -= 1,
+			Ok(Token::Endline(_)) if depth == 0 => break,
+			Ok(_) => {}
+		}
+	}
+
+	Ok(())
+}
+
+/// Gives `doc` to the block that `expr` assigns, if it's an assignment of one.
+///
+/// The block can be assigned directly (`foo = { ... }`), as the body of a function
+/// (`foo = x -> { ... }`), or called right away, as classes are (`Foo = { ... }()`).
+fn attach_doc(expr: &mut Expression, doc: String) {
+	use crate::expression::{BoundOperator, bound_operator::OperArgs};
+	use crate::token::Operator;
+
+	let value =
+		match expr {
+			Expression::Operator(BoundOperator { oper: Operator::Assign, args, .. }) =>
+				match &mut **args {
+					OperArgs::Binary(value) => value,
+					_ => return
+				},
+			Expression::Operator(BoundOperator { oper: Operator::DotAssign, args, .. }) =>
+				match &mut **args {
+					OperArgs::Ternary(_, value) => value,
+					_ => return
+				},
+			_ => return
+		};
+
+	let block =
+		match value {
+			Expression::Block(block) => block,
+			Expression::Operator(BoundOperator { oper: Operator::Arrow, args, .. }) =>
+				match &mut **args {
+					OperArgs::Binary(Expression::Block(block)) => block,
+					_ => return
+				},
+			Expression::FunctionCall(callee, _) =>
+				match &mut **callee {
+					Expression::Block(block) => block,
+					_ => return
+				},
+			_ => return
+		};
+
+	if block.paren_type == ParenType::Curly {
+		block.doc = Some(doc);
+	}
+}
 
+impl Block {
+	/// Parses the lines of a block, up to and including the paren that closes it.
+	///
+	/// `opened_at` is where the block's opening paren is. If it's `None`, the block is the whole
+	/// program, and so ends at the end of `ctor` instead of at a paren.
+	pub(crate) fn construct_body<C>(ctor: &mut C, paren: ParenType, opened_at: Option<crate::Span>)
+		-> Result<Self>
+	where
+		C: Iterator<Item=Result<Token>> + PutBack + Contexted
+	{
 		let mut block = Self {
 			lines: vec![],
 			paren_type: paren,
 			context: ctor.context().clone(),
+			linenos: vec![],
+			doc: None,
 		};
 		let mut curr_line: Option<Line> = None;
 
-		while let Some(tkn) = ctor.next().transpose()? {
+		while let Some(tkn) = ctor.next() {
+			let tkn =
+				match tkn {
+					Ok(tkn) => tkn,
+					Err(err) => {
+						ctor.recover(err)?;
+						if curr_line.take().is_some() {
+							block.linenos.pop();
+						}
+						skip_line(ctor, opened_at.is_some())?;
+						continue;
+					}
+				};
+
 			match tkn {
-				Token::Right(rparen) if rparen == paren => {
+				Token::Right(rparen) if opened_at.is_some() => {
+					// assume the wrong paren was used, rather than a closing one being forgotten.
+					if rparen != paren {
+						ctor.recover(parse_error!(ctor, UnexpectedToken(Token::Right(rparen)))
+							.with_suggestion(format!("did you mean `{}`?", paren.right())))?;
+					}
+
 					if let Some(curr_line) = curr_line {
 						block.lines.push(curr_line);
 					}
 
-					return Ok(Some(block))
+					crate::coverage::add_lines(block.context.file.as_deref(), &block.linenos);
+					return Ok(block)
 				},
 
-				rparen @ Token::Right(..) => return Err(parse_error!(ctor, UnexpectedToken(rparen))),
+				rparen @ Token::Right(..) => ctor.recover(parse_error!(ctor, UnexpectedToken(rparen)))?,
 				Token::Endline(_) => 
 					if let Some(curr_line) = curr_line.take() {
 						block.lines.push(curr_line);
 					},
-				Token::Comma => 
+				Token::Comma => {
+					if curr_line.is_none() {
+						block.linenos.push(ctor.context().lineno);
+					}
+
 					match curr_line {
 						Some(Line::Multiple(_)) => { /* do nothing; commas are used to make `multiple` */},
 						Some(Line::Single(first)) => curr_line = Some(Line::Multiple(vec![first])),
 						None => curr_line = Some(Line::Multiple(vec![]))
-					},
+					}
+				},
 
 				other => {
+					let mut doc = None;
+
+					if curr_line.is_none() {
+						block.linenos.push(ctor.context().lineno);
+						doc = ctor.doc_comment().map(str::to_string);
+					}
+
 					ctor.put_back(Ok(other));
-					let expr = Expression::try_construct(ctor)?;
+					let expr =
+						match Expression::try_construct(ctor) {
+							Ok(mut expr) => {
+								if let Some(doc) = doc {
+									attach_doc(&mut expr, doc);
+								}
+
+								expr
+							},
+							Err(err) => {
+								ctor.recover(err)?;
+								if curr_line.take().is_some() {
+									block.linenos.pop();
+								}
+								skip_line(ctor, opened_at.is_some())?;
+								continue;
+							}
+						};
+
 					match curr_line {
 						Some(Line::Multiple(ref mut exprs)) => exprs.push(expr),
 						Some(Line::Single(first)) => curr_line = Some(Line::Multiple(vec![first, expr])),
@@ -269,15 +481,35 @@ impl Constructable for Block {
 			}
 		}
 
-		Err(parse_error!(ctor, MissingClosingParen(paren)))
+		if let Some(opened_at) = opened_at {
+			let missing = crate::Error::spanning(opened_at, crate::ErrorType::MissingClosingParen(paren))
+				.with_suggestion(format!("did you forget a `{}` to close this?", paren.right()));
+
+			ctor.recover(missing)?;
+		}
+
+		if let Some(curr_line) = curr_line {
+			block.lines.push(curr_line);
+		}
+
+		crate::coverage::add_lines(block.context.file.as_deref(), &block.linenos);
+		Ok(block)
 	}
 }
 
-
 impl Block {
+	/// Runs the block in a new stackframe, with the arguments as `_0`, `_1`, etc.
 	#[inline]
 	pub fn qs_call(this: &Object, args: Args) -> quest_core::Result<Object> {
 		let this_cloned = this.try_downcast::<Self>()?;
+		quest_core::sandbox::step()?;
+
+		let _call = quest_core::profile::enter(|| {
+			let context = &this_cloned.context;
+			let file = context.file.as_ref().map_or_else(|| "<eval>".to_string(), |file| file.display().to_string());
+			format!("{}:{}", file, context.lineno)
+		});
+
 		Binding::new_stackframe(Some(this.clone()), args, move |binding| {
 			binding.as_ref().set_attr_lit("source_location", format!("{:?}", this_cloned.context).into())?;
 			/*match */this_cloned.run_block_to_object()/* {
@@ -288,11 +520,28 @@ impl Block {
 		})
 	}
 
+	/// Gets the source code of the block.
 	#[inline]
 	pub fn qs_at_text(this: &Object, _: Args) -> quest_core::Result<Object> {
 		Ok(this.try_downcast::<Self>()?.to_string().into())
 	}
 
+	/// Gets the tree of this block's code; see [`ast`](crate::ast) for its format.
+	#[inline]
+	pub fn qs_ast(this: &Object, _: Args) -> quest_core::Result<Object> {
+		crate::ast::block_to_node(&*this.try_downcast::<Self>()?)
+	}
+
+	/// Gets the `##` doc comments before the assignment this block is the value of, or `null`.
+	#[inline]
+	pub fn qs_doc(this: &Object, _: Args) -> quest_core::Result<Object> {
+		Ok(this.downcast::<Self>()
+			.and_then(|this| this.doc.clone())
+			.map(Object::from)
+			.unwrap_or_default())
+	}
+
+	/// Runs the block in the current stackframe.
 	#[inline]
 	pub fn qs_call_noscope(this: &Object, _: Args) -> quest_core::Result<Object> {
 		this.try_downcast::<Self>()?.run_block_to_object()
@@ -301,18 +550,143 @@ impl Block {
 
 impl_object_type!{
 for Block [(parents quest_core::types::Function)]:
+	/// Gets the source code of the block.
 	"@text" => method Block::qs_at_text,
+	/// Runs the block in a new stackframe, with the arguments as `_0`, `_1`, etc.
 	"()" => method Block::qs_call,
+	/// Runs the block in the current stackframe.
 	"call_noscope" => method Block::qs_call_noscope,
+	/// Gets the tree of this block's code.
+	"ast" => method Block::qs_ast,
+	/// Gets the `##` doc comments before the assignment this block is the value of, or `null`.
+	"doc" => property Block::qs_doc,
 }
 
 #[cfg(test)]
 mod tests {
-	
+	use crate::Expression;
+	use crate::stream::{BufStream, Stream};
+
+	fn source_of(code: &str) -> String {
+		let source = Expression::parse_stream(BufStream::from(code.to_string()).tokens())
+			.unwrap()
+			.to_string();
+
+		// remove the parens that `parse_stream` wraps the code in.
+		source[2..source.len() - 2].to_string()
+	}
 
 	#[test]
 	#[ignore]
 	fn call() { todo!(); }
+
+	#[test]
+	fn source_reparses() {
+		let codes = [
+			("a + -b", "a + ( -b )"),
+			("a.b = 'c'", "a.b = 'c'"),
+			("x[1] = 2", "x[ 1 ] = 2"),
+			("print 3", "print 3"),
+			("f(1) { 2 }", "f( 1, { 2 } )"),
+			("(a, *b) -> { a }", "( a, *b ) -> { a }"),
+			("a - (b - c) - d", "a - ( b - c ) - d"),
+			("a ** b ** c", "a ** b ** c"),
+			("x.len() == 3", "x.len() == 3"),
+			("'b c' = 'it\\'s'", "'b c' = 'it\\'s'"),
+			("\"x ${y}\"", "\"x ${ y }\""),
+			("(1,)", "( 1, )"),
+		];
+
+		for &(code, expected) in codes.iter() {
+			let source = source_of(code);
+			assert_eq!(source, expected);
+			assert_eq!(source_of(&source), source, "{:?} doesn't reparse", code);
+		}
+	}
+
+	#[test]
+	fn recovers_from_errors() {
+		let code = "x = (1 + ;\ny = 2 *;\nz = [1, 2);\nw = ) + 3;\nv = {";
+		let err = Expression::parse_stream(BufStream::from(code.to_string()).tokens()).unwrap_err();
+		let diagnostics = err.diagnostics();
+
+		let summary = diagnostics.iter()
+			.map(|diag| (diag.span().start.lineno, diag.error_type().to_string(), diag.suggestion()))
+			.collect::<Vec<_>>();
+
+		assert_eq!(summary, vec![
+			(1, "expected an expression".to_string(), None),
+			(2, "expected an expression".to_string(), None),
+			(3, "unexpected token `)`".to_string(), Some("did you mean `]`?")),
+			(4, "expected an expression".to_string(), None),
+			(5, "missing closing paren `}`".to_string(), Some("did you forget a `}` to close this?")),
+		]);
+	}
+
+	#[test]
+	fn assigning_to_arguments() {
+		// these are valid, even if they're probably missing a `;`; the linter warns about them instead.
+		for &code in ["a b = 2", "a = 1 b = 2", "x.y = z 'w' = 2", "x[1] = 2; y.z[3] = 4"].iter() {
+			assert!(Expression::parse_stream(BufStream::from(code.to_string()).tokens()).is_ok(), "{:?}", code);
+		}
+	}
+
+	#[test]
+	fn errors_are_underlined() {
+		let err = Expression::parse_stream(BufStream::from("x = foo(1, 2]".to_string()).tokens())
+			.unwrap_err();
+
+		assert_eq!(err.to_string(), concat!(
+			"<eval>:1:13: parse error, unexpected token `]`\n",
+			"    |\n",
+			" 1  | x = foo(1, 2]\n",
+			"    |             ^ here\n",
+			"    = help: did you mean `)`?"));
+	}
+
+	#[test]
+	fn doc_comments() {
+		use crate::expression::Executable;
+		use quest_core::{Args, Binding};
+
+		quest_core::init();
+		crate::init();
+
+		let code = "\
+## Doubles `n`.
+##
+##  Indented.
+double = n -> { n * 2 };
+### not a doc
+plain = { 1 };
+## discarded
+# by this comment
+other = { 2 };
+## Adds one.
+Number.inc = { _0 + 1 };
+[double.doc, plain.doc, other.doc, Number.inc.doc, Number.round.doc, { doc = \"mine\"; doc }()]";
+
+		let docs = Binding::new_stackframe(None, Args::default(), |_| {
+			Expression::parse_stream(BufStream::from(code.to_string()).tokens())
+				.map_err(|err| Box::new(err) as Box<_>)?
+				.execute()
+		}).unwrap();
+
+		let docs = docs.downcast::<quest_core::types::List>().unwrap()
+			.iter()
+			.map(|doc| doc.downcast::<quest_core::types::Text>().map(|doc| doc.to_string()))
+			.collect::<Vec<_>>();
+
+		assert_eq!(docs, [
+			Some("Doubles `n`.\n\n Indented.".to_string()),
+			None,
+			None,
+			Some("Adds one.".to_string()),
+			Some("Returns `this`, rounded towards the nearest integer. (`##.5` rounds away from zero.)".to_string()),
+			// `doc` can still be used as a variable.
+			Some("mine".to_string()),
+		]);
+	}
 }
 
 
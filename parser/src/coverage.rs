@@ -0,0 +1,200 @@
+//! Recording which lines of code are run.
+//!
+//! Once coverage is [started](start), every [`Block`](crate::Block) that's parsed from a file
+//! records the lines it has, and each time one of them runs it's counted. Blocks that weren't parsed
+//! from a file, such as ones from `-e` or `Text::eval`, aren't recorded.
+//!
+//! What's recorded can be written in the `lcov` format with [`Coverage::lcov`], which most coverage
+//! tools read.
+
+use quest_core::Binding;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// How many times each line of each file ran.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Coverage {
+	/// For each file, how many times each of its lines ran. Only lines with code on them are included.
+	pub files: BTreeMap<PathBuf, BTreeMap<usize, usize>>
+}
+
+impl Coverage {
+	/// Writes the coverage in the `lcov` tracefile format, with one record for each file.
+	#[must_use]
+	pub fn lcov(&self) -> String {
+		let mut lcov = String::new();
+
+		for (file, lines) in &self.files {
+			writeln!(lcov, "TN:").and_then(|_| writeln!(lcov, "SF:{}", file.display()))
+				.expect("writing to a string can't fail");
+
+			for (lineno, count) in lines {
+				writeln!(lcov, "DA:{},{}", lineno, count).expect("writing to a string can't fail");
+			}
+
+			let hit = lines.values().filter(|&&count| count != 0).count();
+
+			writeln!(lcov, "LF:{}\nLH:{}\nend_of_record", lines.len(), hit)
+				.expect("writing to a string can't fail");
+		}
+
+		lcov
+	}
+}
+
+// checked before anything else, so lines only run a little slower when we aren't recording.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+static COVERAGE: Mutex<Option<Coverage>> = Mutex::new(None);
+
+fn with_coverage(func: impl FnOnce(&mut Coverage)) {
+	let mut coverage = COVERAGE.lock().unwrap_or_else(|err| err.into_inner());
+
+	if let Some(coverage) = coverage.as_mut() {
+		func(coverage);
+	}
+}
+
+/// Starts recording coverage, discarding anything that was recorded before.
+///
+/// Only blocks that are parsed after this are recorded, so it should be called before parsing.
+pub fn start() {
+	*COVERAGE.lock().unwrap_or_else(|err| err.into_inner()) = Some(Coverage::default());
+	ENABLED.store(true, Ordering::SeqCst);
+}
+
+/// Stops recording coverage, returning what was recorded since it was [started](start).
+pub fn stop() -> Option<Coverage> {
+	ENABLED.store(false, Ordering::SeqCst);
+	COVERAGE.lock().unwrap_or_else(|err| err.into_inner()).take()
+}
+
+/// Records that `file` has code on each of `linenos`.
+pub(crate) fn add_lines(file: Option<&Path>, linenos: &[usize]) {
+	let file =
+		match file {
+			Some(file) if ENABLED.load(Ordering::Relaxed) => file,
+			_ => return
+		};
+
+	with_coverage(|coverage| {
+		let lines = coverage.files.entry(file.to_path_buf()).or_default();
+
+		for &lineno in linenos {
+			lines.entry(lineno).or_default();
+		}
+	});
+}
+
+/// A line that's running.
+struct Running {
+	binding: Binding,
+	file: PathBuf,
+	lineno: usize
+}
+
+thread_local!(
+	static RUNNING: RefCell<Vec<Running>> = const { RefCell::new(Vec::new()) };
+);
+
+/// A line that's running; it's forgotten when this is dropped.
+#[derive(Debug)]
+#[must_use = "the line is forgotten when this is dropped"]
+pub(crate) struct LineGuard(());
+
+impl Drop for LineGuard {
+	fn drop(&mut self) {
+		RUNNING.with(|running| running.borrow_mut().pop());
+	}
+}
+
+/// Called before the line `lineno` in `file` runs, to count it; the returned guard should be kept
+/// until it's finished.
+///
+/// Lines of `( ... )` and `[ ... ]` blocks that are on the same line as the one running them, such
+/// as the arguments in `foo(1, 2)`, are only counted once, along with it.
+pub(crate) fn enter_line(file: Option<&Path>, lineno: usize) -> Option<LineGuard> {
+	let file =
+		match file {
+			Some(file) if ENABLED.load(Ordering::Relaxed) => file,
+			_ => return None
+		};
+
+	let binding = Binding::instance();
+	let is_nested = RUNNING.with(|running| {
+		let mut running = running.borrow_mut();
+		let is_nested = running.last().is_some_and(|line|
+			line.lineno == lineno && line.file == file && line.binding.is_identical(&binding));
+
+		running.push(Running { binding, file: file.to_path_buf(), lineno });
+		is_nested
+	});
+
+	if !is_nested {
+		with_coverage(|coverage| {
+			*coverage.files.entry(file.to_path_buf()).or_default().entry(lineno).or_default() += 1;
+		});
+	}
+
+	Some(LineGuard(()))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::expression::{Executable, Expression};
+	use crate::stream::{BufStream, Stream};
+	use quest_core::Args;
+	use std::io::Cursor;
+
+	const CODE: &str = "\
+# doubles `n`
+double = n -> {
+	n * 2
+};
+
+x = double(3);
+(x == 6).else({
+	disp(\"unreachable\")
+});
+";
+
+	#[test]
+	fn records_lines_that_ran() {
+		quest_core::init();
+		crate::init();
+
+		let file = PathBuf::from("coverage-test.qs");
+		let stream = BufStream::new(Cursor::new(CODE), Some(file.clone()));
+
+		start();
+		let result = Binding::new_stackframe(None, Args::default(), |_| {
+			Expression::parse_stream(stream.tokens())
+				.map_err(|err| Box::new(err) as Box<_>)?
+				.execute()
+		});
+		let coverage = stop().unwrap();
+
+		result.unwrap();
+
+		let lines = coverage.files[&file].iter().map(|(&lineno, &count)| (lineno, count)).collect::<Vec<_>>();
+		assert_eq!(lines, [(2, 1), (3, 1), (6, 1), (7, 1), (8, 0)]);
+
+		assert_eq!(Coverage { files: vec![(file, lines.into_iter().collect())].into_iter().collect() }.lcov(), "\
+TN:
+SF:coverage-test.qs
+DA:2,1
+DA:3,1
+DA:6,1
+DA:7,1
+DA:8,0
+LF:5
+LH:4
+end_of_record
+");
+	}
+}
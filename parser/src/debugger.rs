@@ -0,0 +1,442 @@
+//! Pausing code as it runs, so that it can be inspected.
+//!
+//! Once a [`Debugger`] is [attached](attach), it's checked before each line runs, and pauses when
+//! the line has a [`Breakpoint`], when it's stepping through code, or when `Kernel.breakpoint()` is
+//! called. While paused, its [`Frontend`] decides how to carry on; in the meantime, it can look at
+//! the paused [`Frame`]s and [`eval`] code in them.
+//!
+//! Only lines that start a statement in their stackframe are paused at, so the lines of a
+//! `( ... )` or `[ ... ]` block within a line aren't. Lines that run while the frontend is deciding
+//! what to do, such as ones it `eval`s, aren't paused at either.
+
+use crate::expression::{Executable, Expression};
+use crate::stream::{BufStream, Stream};
+use quest_core::{Args, Binding, Object};
+use std::cell::{Cell, RefCell};
+use std::fmt::{self, Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// A line to pause at.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Breakpoint {
+	/// The file the line is in. If it's `None`, the line in any file is paused at; otherwise, files
+	/// that end with it are, so `foo.qs` matches `src/foo.qs`.
+	pub file: Option<PathBuf>,
+	/// The line's number.
+	pub lineno: usize
+}
+
+impl Breakpoint {
+	/// Checks to see if the line `lineno` in `file` should be paused at.
+	#[must_use]
+	pub fn matches(&self, file: Option<&Path>, lineno: usize) -> bool {
+		lineno == self.lineno
+			&& match (&self.file, file) {
+				(None, _) => true,
+				(Some(expected), Some(file)) => file.ends_with(expected),
+				(Some(_), None) => false
+			}
+	}
+}
+
+/// The problem with a breakpoint that couldn't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BadBreakpoint(String);
+
+impl Display for BadBreakpoint {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "bad breakpoint `{}`: expected `file:line` or `line`", self.0)
+	}
+}
+
+impl std::error::Error for BadBreakpoint {}
+
+impl FromStr for Breakpoint {
+	type Err = BadBreakpoint;
+
+	/// Parses a breakpoint of the form `file:line`, or just `line`.
+	fn from_str(input: &str) -> Result<Self, Self::Err> {
+		let (file, lineno) =
+			match input.rfind(':') {
+				Some(idx) => (Some(PathBuf::from(&input[..idx])), &input[idx + 1..]),
+				None => (None, input)
+			};
+
+		match lineno.trim().parse() {
+			Ok(lineno) if lineno != 0 => Ok(Self { file, lineno }),
+			_ => Err(BadBreakpoint(input.to_string()))
+		}
+	}
+}
+
+impl Display for Breakpoint {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self.file {
+			Some(ref file) => write!(f, "{}:{}", file.display(), self.lineno),
+			None => write!(f, "{}", self.lineno)
+		}
+	}
+}
+
+/// How to carry on after pausing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Resume {
+	/// Run until the next breakpoint.
+	Continue,
+	/// Pause at the next line, even if it's in a block that's called.
+	Step,
+	/// Pause at the next line in this stackframe, or the ones that called it.
+	Next,
+	/// Pause once this stackframe has returned.
+	Finish
+}
+
+/// Why the code was paused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Reason {
+	/// The line has the breakpoint with this index.
+	Breakpoint(usize),
+	/// The debugger was stepping through the code.
+	Step,
+	/// `Kernel.breakpoint()` was called.
+	Call
+}
+
+/// A stackframe, and the line it's running.
+#[derive(Debug, Clone)]
+pub struct Frame {
+	pub binding: Binding,
+	/// The file the line is in, if it's in one.
+	pub file: Option<PathBuf>,
+	/// The line's number, or `None` if the frame isn't running any lines, such as ones that were
+	/// created before the debugger was attached.
+	pub lineno: Option<usize>
+}
+
+/// Where, and why, the code was paused.
+#[derive(Debug, Clone)]
+pub struct Pause {
+	pub reason: Reason,
+	/// The file the line that's about to run is in, if it's in one.
+	pub file: Option<PathBuf>,
+	/// The number of the line that's about to run.
+	pub lineno: usize,
+	/// Every stackframe, from [`Binding::stack()`], starting with the innermost one.
+	pub frames: Vec<Frame>
+}
+
+/// Decides what to do when code is paused, such as by asking the user.
+pub trait Frontend {
+	/// Called when the code is paused; `breakpoints` can be changed before it resumes.
+	fn pause(&mut self, pause: &Pause, breakpoints: &mut Vec<Breakpoint>) -> Resume;
+}
+
+/// When to pause next, other than at breakpoints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+	Run,
+	Step,
+	/// Pause at a line in a stackframe at most this deep.
+	Next(usize),
+	/// Pause at a line in a stackframe shallower than this.
+	Finish(usize)
+}
+
+/// Pauses code at breakpoints, and steps through it.
+pub struct Debugger {
+	breakpoints: Vec<Breakpoint>,
+	mode: Mode,
+	frontend: Box<dyn Frontend>
+}
+
+impl Debugger {
+	/// Creates a debugger that pauses at `breakpoints`, and asks `frontend` what to do then.
+	pub fn new(breakpoints: Vec<Breakpoint>, frontend: impl Frontend + 'static) -> Self {
+		Self { breakpoints, mode: Mode::Run, frontend: Box::new(frontend) }
+	}
+
+	/// Pause at the first line that's run, rather than waiting for a breakpoint.
+	#[must_use]
+	pub fn stepping(mut self) -> Self {
+		self.mode = Mode::Step;
+		self
+	}
+
+	/// Checks to see if the line `lineno` in `file`, in a stackframe `depth` deep, should be paused
+	/// at, and why.
+	fn should_pause(&self, file: Option<&Path>, lineno: usize, depth: usize) -> Option<Reason> {
+		if let Some(idx) = self.breakpoints.iter().position(|bp| bp.matches(file, lineno)) {
+			return Some(Reason::Breakpoint(idx));
+		}
+
+		match self.mode {
+			Mode::Step => Some(Reason::Step),
+			Mode::Next(max) if depth <= max => Some(Reason::Step),
+			Mode::Finish(max) if depth < max => Some(Reason::Step),
+			_ => None
+		}
+	}
+
+	fn pause(&mut self, pause: &Pause, depth: usize) {
+		self.mode =
+			match self.frontend.pause(pause, &mut self.breakpoints) {
+				Resume::Continue => Mode::Run,
+				Resume::Step => Mode::Step,
+				Resume::Next => Mode::Next(depth),
+				Resume::Finish => Mode::Finish(depth)
+			};
+	}
+}
+
+impl std::fmt::Debug for Debugger {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		f.debug_struct("Debugger")
+			.field("breakpoints", &self.breakpoints)
+			.field("mode", &self.mode)
+			.finish()
+	}
+}
+
+thread_local!(
+	// Checked before anything else, so lines run as quickly as usual when nothing's attached.
+	static ATTACHED: Cell<bool> = const { Cell::new(false) };
+
+	// This is `None` while the frontend is deciding what to do, so it isn't paused recursively.
+	static DEBUGGER: RefCell<Option<Debugger>> = const { RefCell::new(None) };
+
+	// The lines that are running, outermost first.
+	static LINES: RefCell<Vec<Frame>> = const { RefCell::new(Vec::new()) };
+);
+
+/// Starts pausing code that's run on this thread with `debugger`.
+pub fn attach(debugger: Debugger) {
+	DEBUGGER.with(|slot| *slot.borrow_mut() = Some(debugger));
+	ATTACHED.with(|attached| attached.set(true));
+}
+
+/// Stops pausing code on this thread, returning the debugger that was attached.
+pub fn detach() -> Option<Debugger> {
+	ATTACHED.with(|attached| attached.set(false));
+	LINES.with(|lines| lines.borrow_mut().clear());
+	DEBUGGER.with(|slot| slot.borrow_mut().take())
+}
+
+/// Removes its line from the running lines when it's dropped.
+#[derive(Debug)]
+pub(crate) struct LineGuard(());
+
+impl Drop for LineGuard {
+	fn drop(&mut self) {
+		LINES.with(|lines| lines.borrow_mut().pop());
+	}
+}
+
+fn depth() -> usize {
+	Binding::with_stack(|stack| stack.read().len())
+}
+
+/// Every stackframe, innermost first, along with the lines they're running.
+fn frames() -> Vec<Frame> {
+	LINES.with(|lines| {
+		let lines = lines.borrow();
+
+		Binding::stack().into_iter().map(|binding| {
+			match lines.iter().rev().find(|line| line.binding.is_identical(&binding)) {
+				Some(line) => Frame { binding, file: line.file.clone(), lineno: line.lineno },
+				None => Frame { binding, file: None, lineno: None }
+			}
+		}).collect()
+	})
+}
+
+/// Asks the debugger what to do, if `reason` is given or it decides the line should be paused at.
+fn pause_at(file: Option<&Path>, lineno: usize, reason: Option<Reason>) {
+	let depth = depth();
+	let mut debugger =
+		match DEBUGGER.with(|slot| slot.borrow_mut().take()) {
+			Some(debugger) => debugger,
+			None => return
+		};
+
+	if let Some(reason) = reason.or_else(|| debugger.should_pause(file, lineno, depth)) {
+		let pause = Pause { reason, file: file.map(Path::to_path_buf), lineno, frames: frames() };
+		debugger.pause(&pause, depth);
+	}
+
+	// the frontend may have detached (or replaced) the debugger in the meantime.
+	if ATTACHED.with(Cell::get) {
+		DEBUGGER.with(|slot| {
+			slot.borrow_mut().get_or_insert(debugger);
+		});
+	}
+}
+
+/// Called before the line `lineno` in `file` runs; the returned guard should be kept until it's
+/// finished.
+pub(crate) fn enter_line(file: Option<&Path>, lineno: usize) -> Option<LineGuard> {
+	if !ATTACHED.with(Cell::get) {
+		return None;
+	}
+
+	let binding = Binding::instance();
+	let is_statement = LINES.with(|lines| {
+		let mut lines = lines.borrow_mut();
+		let is_statement = lines.last().is_none_or(|line| !line.binding.is_identical(&binding));
+
+		lines.push(Frame { binding, file: file.map(Path::to_path_buf), lineno: Some(lineno) });
+		is_statement
+	});
+
+	if is_statement {
+		pause_at(file, lineno, None);
+	}
+
+	Some(LineGuard(()))
+}
+
+/// Evaluates `code` in the stackframe `binding`, as if it were a line there.
+pub fn eval(binding: &Binding, code: &str) -> quest_core::Result<Object> {
+	let expr = Expression::parse_stream(BufStream::from(code.to_string()).tokens())
+		.map_err(|err| Box::new(err) as Box<_>)?;
+
+	Binding::run_stackframe(binding.clone(), |_| expr.execute())
+}
+
+/// Pauses at the line that called this, if a debugger is attached; otherwise, does nothing.
+pub fn qs_breakpoint(_: Args) -> quest_core::Result<Object> {
+	let line = LINES.with(|lines| lines.borrow().last().cloned());
+
+	if let Some(Frame { file, lineno: Some(lineno), .. }) = line {
+		pause_at(file.as_deref(), lineno, Some(Reason::Call));
+	}
+
+	Ok(Object::default())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::rc::Rc;
+
+	/// Records where it's paused, and resumes with each of its `resumes` in turn.
+	struct Scripted {
+		resumes: Vec<Resume>,
+		pauses: Rc<RefCell<Vec<(usize, Reason, usize)>>>
+	}
+
+	impl Frontend for Scripted {
+		fn pause(&mut self, pause: &Pause, _: &mut Vec<Breakpoint>) -> Resume {
+			self.pauses.borrow_mut().push((pause.lineno, pause.reason, pause.frames.len()));
+
+			if self.resumes.is_empty() {
+				Resume::Continue
+			} else {
+				self.resumes.remove(0)
+			}
+		}
+	}
+
+	const CODE: &str = "\
+double = n -> {
+	result = n * 2;
+	result
+};
+x = double(3);
+y = (x + 1);
+z = x + y;
+";
+
+	/// Runs `CODE`, returning each pause's line number, reason, and how many stackframes there were.
+	fn pauses(breakpoints: &[&str], stepping: bool, resumes: &[Resume]) -> Vec<(usize, Reason, usize)> {
+		quest_core::init();
+		crate::init();
+
+		let pauses = Rc::new(RefCell::new(vec![]));
+		let breakpoints = breakpoints.iter().map(|bp| bp.parse().unwrap()).collect();
+		let mut debugger = Debugger::new(breakpoints, Scripted { resumes: resumes.to_vec(), pauses: pauses.clone() });
+
+		if stepping {
+			debugger = debugger.stepping();
+		}
+
+		attach(debugger);
+		let result = Binding::new_stackframe(None, Args::default(), |_| {
+			Expression::parse_stream(BufStream::from(CODE.to_string()).tokens())
+				.map_err(|err| Box::new(err) as Box<_>)?
+				.execute()
+		});
+		detach();
+
+		result.unwrap();
+		let pauses = pauses.borrow().clone();
+		pauses
+	}
+
+	#[test]
+	fn parses_breakpoints() {
+		assert_eq!("foo.qs:3".parse(), Ok(Breakpoint { file: Some("foo.qs".into()), lineno: 3 }));
+		assert_eq!("12".parse(), Ok(Breakpoint { file: None, lineno: 12 }));
+		assert!("foo.qs".parse::<Breakpoint>().is_err());
+		assert!("foo.qs:0".parse::<Breakpoint>().is_err());
+
+		let bp = "src/foo.qs:3".parse::<Breakpoint>().unwrap();
+		assert!(bp.matches(Some(Path::new("/home/me/src/foo.qs")), 3));
+		assert!(!bp.matches(Some(Path::new("/home/me/foo.qs")), 3));
+		assert!(!bp.matches(None, 3));
+	}
+
+	#[test]
+	fn breakpoints() {
+		assert_eq!(pauses(&["2", "7"], false, &[]), [(2, Reason::Breakpoint(0), 3), (7, Reason::Breakpoint(1), 2)]);
+	}
+
+	#[test]
+	fn step_next_and_finish() {
+		use Resume::*;
+
+		// stepping goes into `double`, but not into `( ... )`, as it's the same stackframe.
+		assert_eq!(
+			pauses(&[], true, &[Step, Step, Step, Step, Step, Step, Continue]).iter().map(|p| p.0).collect::<Vec<_>>(),
+			[1, 5, 2, 3, 6, 7]
+		);
+
+		// `next` steps over `double`.
+		assert_eq!(pauses(&[], true, &[Next, Next, Next, Next]).iter().map(|p| p.0).collect::<Vec<_>>(), [1, 5, 6, 7]);
+
+		// `finish` runs until `double` has returned.
+		assert_eq!(pauses(&["2"], false, &[Finish]).iter().map(|p| p.0).collect::<Vec<_>>(), [2, 6]);
+	}
+
+	#[test]
+	fn frames_can_be_evaluated_in() {
+		quest_core::init();
+		crate::init();
+
+		struct Inspect(Rc<RefCell<Vec<String>>>);
+
+		impl Frontend for Inspect {
+			fn pause(&mut self, pause: &Pause, _: &mut Vec<Breakpoint>) -> Resume {
+				let value = |frame: &Frame, code| eval(&frame.binding, code).unwrap()
+					.call_attr_lit("@text", &[]).unwrap()
+					.downcast::<quest_core::types::Text>().unwrap()
+					.to_string();
+
+				self.0.borrow_mut().push(value(&pause.frames[0], "n + result"));
+				self.0.borrow_mut().push(value(&pause.frames[1], "x"));
+				Resume::Continue
+			}
+		}
+
+		let values = Rc::new(RefCell::new(vec![]));
+		attach(Debugger::new(vec!["4".parse().unwrap()], Inspect(values.clone())));
+		Binding::new_stackframe(None, Args::default(), |_| {
+			Expression::parse_stream(BufStream::from(format!("x = 9;\n{}", CODE.replacen("x = ", "w = ", 1))).tokens())
+				.map_err(|err| Box::new(err) as Box<_>)?
+				.execute()
+		}).unwrap();
+		detach();
+
+		assert_eq!(*values.borrow(), ["9", "9"]);
+	}
+}
@@ -0,0 +1,373 @@
+//! Generating documentation for Quest modules from their `##` doc comments.
+//!
+//! A doc comment is a line starting with `##` (but not `###`), and documents the assignment right
+//! after it, if it assigns a `{ ... }` block; the docs are kept on the [`Block`], and are its `doc`
+//! attribute when the code is run:
+//!
+//! ```quest
+//! ## Doubles `n`.
+//! double = n -> { n * 2 };
+//! ```
+//!
+//! Assignments within blocks that are called right after they're made, as classes are (eg
+//! `Person = { ... }();`), are documented too, with their names qualified by the class's, such as
+//! `Person.greet`. Assignments without doc comments are left out.
+//!
+//! A [`Module`]'s docs can be written as Markdown, with [`Module::markdown`], or as a standalone
+//! HTML page, with [`Module::html`].
+
+use crate::{Block, Expression, Result};
+use crate::block::Line;
+use crate::expression::BoundOperator;
+use crate::expression::bound_operator::OperArgs;
+use crate::stream::{BufStream, Stream};
+use crate::token::{Operator, ParenType, Primitive};
+use std::fmt::Write;
+use std::io::Cursor;
+use std::path::PathBuf;
+
+/// Something that's documented, such as a function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Item {
+	/// The name it's assigned to, qualified with the names of the classes it's in.
+	pub name: String,
+	/// The parameters, if it's a function that's defined with `->`.
+	pub params: Option<String>,
+	/// The text of its doc comments.
+	pub doc: String,
+	/// The line it's assigned on.
+	pub lineno: usize
+}
+
+impl Item {
+	/// How the item is shown in headings, eg `double(n)`.
+	#[must_use]
+	pub fn signature(&self) -> String {
+		match &self.params {
+			Some(params) => format!("{}({})", self.name, params),
+			None => self.name.clone()
+		}
+	}
+}
+
+/// The documented items of a module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Module {
+	/// The module's name, which is usually its file's name without the extension.
+	pub name: String,
+	/// The documented items, in the order they're assigned.
+	pub items: Vec<Item>
+}
+
+/// Parses `source`, which is from `file` if it's given, and collects the documented items in it.
+/// The code isn't run.
+///
+/// The module's named after `file`, or `main` if there isn't one. An error is returned if `source`
+/// can't be parsed.
+pub fn document(source: &str, file: Option<PathBuf>) -> Result<Module> {
+	let name = file.as_ref()
+		.and_then(|file| file.file_stem())
+		.map_or_else(|| "main".to_string(), |stem| stem.to_string_lossy().into_owned());
+
+	let block =
+		match Expression::parse_stream(BufStream::new(Cursor::new(source), file).tokens())? {
+			Expression::Block(block) => block,
+			_ => unreachable!("`parse_stream` always returns a block")
+		};
+
+	let mut items = vec![];
+	collect_items(&block, None, &mut items);
+
+	Ok(Module { name, items })
+}
+
+/// Gets the name that `expr` is for when it's assigned to, or used as an attribute.
+fn name_of(expr: &Expression) -> String {
+	match expr {
+		Expression::Primitive(Primitive::Variable(var)) => var.to_string(),
+		Expression::Primitive(Primitive::Text(text)) => text.as_ref().to_string(),
+		other => other.to_string()
+	}
+}
+
+/// Gets the parameters of an `->`, without the parens around them, eg `a, *b`.
+fn params_of(arrow: &BoundOperator) -> String {
+	let params = arrow.this.to_string();
+	let params = params.strip_prefix("( ").and_then(|params| params.strip_suffix(" )")).unwrap_or(&params);
+
+	// parameters are converted into text when they're parsed.
+	params.split(", ")
+		.map(|param| {
+			let (splat, name) = param.split_at(param.len() - param.trim_start_matches('*').len());
+			let name = name.strip_prefix('\'').and_then(|name| name.strip_suffix('\'')).unwrap_or(name);
+			format!("{}{}", splat, name)
+		})
+		.collect::<Vec<_>>()
+		.join(", ")
+}
+
+/// Adds the documented assignments in `block` to `items`, prefixing their names with `prefix`.
+fn collect_items(block: &Block, prefix: Option<&str>, items: &mut Vec<Item>) {
+	for (idx, line) in block.lines.iter().enumerate() {
+		let exprs =
+			match line {
+				Line::Single(expr) => std::slice::from_ref(expr),
+				Line::Multiple(exprs) => exprs.as_slice()
+			};
+
+		let lineno = block.linenos.get(idx).copied().unwrap_or(block.context.lineno);
+
+		for expr in exprs {
+			collect_assignment(expr, prefix, lineno, items);
+		}
+	}
+}
+
+/// Adds `expr` to `items` if it's an assignment of a documented block, and then anything that's
+/// documented within it if it's a class.
+fn collect_assignment(expr: &Expression, prefix: Option<&str>, lineno: usize, items: &mut Vec<Item>) {
+	let (name, value) =
+		match expr {
+			Expression::Operator(BoundOperator { oper: Operator::Assign, this, args }) =>
+				match &**args {
+					OperArgs::Binary(value) => (name_of(this), value),
+					_ => return
+				},
+			Expression::Operator(BoundOperator { oper: Operator::DotAssign, this, args }) =>
+				match &**args {
+					OperArgs::Ternary(attr, value) => (format!("{}.{}", name_of(this), name_of(attr)), value),
+					_ => return
+				},
+			_ => return
+		};
+
+	let name = prefix.map_or_else(|| name.clone(), |prefix| format!("{}.{}", prefix, name));
+
+	let (block, params, is_class) =
+		match value {
+			Expression::Block(block) => (block, None, false),
+			Expression::Operator(arrow @ BoundOperator { oper: Operator::Arrow, .. }) =>
+				match &*arrow.args {
+					OperArgs::Binary(Expression::Block(block)) => (block, Some(params_of(arrow)), false),
+					_ => return
+				},
+			Expression::FunctionCall(callee, _) =>
+				match &**callee {
+					Expression::Block(block) => (block, None, true),
+					_ => return
+				},
+			_ => return
+		};
+
+	if block.paren_type() != ParenType::Curly {
+		return;
+	}
+
+	if let Some(doc) = block.doc() {
+		items.push(Item { name: name.clone(), params, doc: doc.to_string(), lineno });
+	}
+
+	if is_class {
+		collect_items(block, Some(&name), items);
+	}
+}
+
+/// Escapes `text` so it can be put in HTML.
+fn escape_html(text: &str) -> String {
+	text.chars().fold(String::with_capacity(text.len()), |mut escaped, chr| {
+		match chr {
+			'&' => escaped.push_str("&amp;"),
+			'<' => escaped.push_str("&lt;"),
+			'>' => escaped.push_str("&gt;"),
+			'"' => escaped.push_str("&quot;"),
+			other => escaped.push(other)
+		}
+
+		escaped
+	})
+}
+
+/// Converts the `` `code` `` spans in `text` into `<code>` tags, escaping the rest of it.
+fn inline_html(text: &str) -> String {
+	let mut html = String::with_capacity(text.len());
+
+	for (idx, part) in text.split('`').enumerate() {
+		// the odd parts are between backticks, unless the last backtick isn't closed.
+		if idx % 2 == 1 && text.matches('`').count() > idx {
+			write!(html, "<code>{}</code>", escape_html(part)).expect("writing to a string can't fail");
+		} else {
+			if idx % 2 == 1 {
+				html.push('`');
+			}
+
+			html.push_str(&escape_html(part));
+		}
+	}
+
+	html
+}
+
+/// Converts the text of doc comments into HTML, as paragraphs and ```` ``` ```` code blocks.
+fn doc_html(doc: &str) -> String {
+	let mut html = String::new();
+	let mut paragraph = vec![];
+	let mut code: Option<Vec<&str>> = None;
+
+	let end_paragraph = |html: &mut String, paragraph: &mut Vec<&str>| {
+		if !paragraph.is_empty() {
+			writeln!(html, "<p>{}</p>", inline_html(&paragraph.join(" "))).expect("writing to a string can't fail");
+			paragraph.clear();
+		}
+	};
+
+	for line in doc.lines() {
+		match &mut code {
+			Some(lines) if line.trim_start().starts_with("```") => {
+				writeln!(html, "<pre><code>{}</code></pre>", escape_html(&lines.join("\n")))
+					.expect("writing to a string can't fail");
+				code = None;
+			},
+			Some(lines) => lines.push(line),
+			None if line.trim_start().starts_with("```") => {
+				end_paragraph(&mut html, &mut paragraph);
+				code = Some(vec![]);
+			},
+			None if line.trim().is_empty() => end_paragraph(&mut html, &mut paragraph),
+			None => paragraph.push(line.trim())
+		}
+	}
+
+	// an unclosed code block goes until the end of the docs.
+	if let Some(lines) = code {
+		writeln!(html, "<pre><code>{}</code></pre>", escape_html(&lines.join("\n")))
+			.expect("writing to a string can't fail");
+	}
+
+	end_paragraph(&mut html, &mut paragraph);
+	html
+}
+
+impl Module {
+	/// Writes the docs as Markdown, with a heading for the module and one for each item.
+	#[must_use]
+	pub fn markdown(&self) -> String {
+		let mut markdown = format!("# {}\n", self.name);
+
+		for item in &self.items {
+			write!(markdown, "\n## `{}`\n\n{}\n", item.signature(), item.doc.trim_end())
+				.expect("writing to a string can't fail");
+		}
+
+		markdown
+	}
+
+	/// Writes the docs as a standalone HTML page, with a list of the items at the top.
+	#[must_use]
+	pub fn html(&self) -> String {
+		let name = escape_html(&self.name);
+		let mut html = format!("\
+<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>{0}</title>
+<style>
+body {{ font-family: sans-serif; max-width: 50em; margin: auto; padding: 1em; }}
+code, pre {{ background: #f4f4f4; }}
+pre {{ padding: 0.5em; overflow-x: auto; }}
+</style>
+</head>
+<body>
+<h1>{0}</h1>
+", name);
+
+		if !self.items.is_empty() {
+			html.push_str("<ul>\n");
+
+			for item in &self.items {
+				writeln!(html, "<li><a href=\"#{}\"><code>{}</code></a></li>",
+					escape_html(&item.name), escape_html(&item.name)).expect("writing to a string can't fail");
+			}
+
+			html.push_str("</ul>\n");
+		}
+
+		for item in &self.items {
+			write!(html, "<h2 id=\"{}\"><code>{}</code></h2>\n{}",
+				escape_html(&item.name), escape_html(&item.signature()), doc_html(&item.doc))
+				.expect("writing to a string can't fail");
+		}
+
+		html.push_str("</body>\n</html>\n");
+		html
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const CODE: &str = "\
+## Doubles `n`.
+##
+## ```
+## double(2) # => 4
+## ```
+double = n -> { n * 2 };
+
+### not a doc comment
+undocumented = { 1 };
+
+## A person.
+Person = {
+	## Makes a person.
+	'()' = (class, *names) -> { :0 };
+
+	## Says hi to `other`.
+	greet = other -> { disp(\"hi\", other) };
+
+	## Not a class, so what's in it isn't documented.
+	helper = {
+		## hidden
+		inner = { 2 };
+	};
+
+	:0
+}();
+";
+
+	#[test]
+	fn collects_documented_assignments() {
+		let module = document(CODE, Some(PathBuf::from("lib/people.qs"))).unwrap();
+		assert_eq!(module.name, "people");
+
+		let items = module.items.iter()
+			.map(|item| (item.signature(), item.lineno))
+			.collect::<Vec<_>>();
+
+		assert_eq!(items, [
+			("double(n)".to_string(), 6),
+			("Person".to_string(), 12),
+			("Person.()(class, *names)".to_string(), 14),
+			("Person.greet(other)".to_string(), 17),
+			("Person.helper".to_string(), 20),
+		]);
+
+		assert_eq!(module.items[0].doc, "Doubles `n`.\n\n```\ndouble(2) # => 4\n```");
+	}
+
+	#[test]
+	fn markdown_and_html() {
+		let module = document(CODE, None).unwrap();
+		let markdown = module.markdown();
+
+		assert!(markdown.starts_with("# main\n\n## `double(n)`\n\nDoubles `n`.\n\n```\n"), "{}", markdown);
+		assert!(markdown.contains("\n## `Person.helper`\n\nNot a class, so what's in it isn't documented.\n"), "{}", markdown);
+
+		let html = module.html();
+		assert!(html.contains("<h2 id=\"double\"><code>double(n)</code></h2>\n<p>Doubles <code>n</code>.</p>\n\
+			<pre><code>double(2) # =&gt; 4</code></pre>\n"), "{}", html);
+		assert!(html.contains("<li><a href=\"#Person.()\"><code>Person.()</code></a></li>"), "{}", html);
+		assert!(html.ends_with("</body>\n</html>\n"));
+	}
+}
@@ -23,47 +23,245 @@ pub enum ErrorType {
 	// Constructable(ConstructableError)
 }
 
+/// How serious a [`Diagnostic`] is.
+///
+/// The parser itself only reports errors; warnings come from tools built on it, such as the
+/// language server's lints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+	/// The code is probably wrong, but can still be run.
+	Warning,
+	/// The code can't be run.
+	Error
+}
+
+impl Display for Severity {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::Warning => write!(f, "warning"),
+			Self::Error => write!(f, "error")
+		}
+	}
+}
+
+/// The part of the source code a [`Diagnostic`] is about.
+///
+/// If `start` and `end` are the same, the span only refers to the character just before them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Span {
+	/// Where the span starts.
+	pub start: Context,
+	/// Where the span ends; this is exclusive.
+	pub end: Context
+}
+
+impl Span {
+	/// Creates a span between `start` and `end`.
+	#[must_use]
+	pub const fn new(start: Context, end: Context) -> Self {
+		Self { start, end }
+	}
+
+	/// Creates a span that refers to the character just before `context`.
+	#[must_use]
+	pub fn at(context: Context) -> Self {
+		Self { start: context.clone(), end: context }
+	}
+
+	/// The range of characters in `start.line` that are underlined in messages, counting from zero.
+	///
+	/// The end is exclusive, and is always after the start.
+	#[must_use]
+	pub fn columns(&self) -> (usize, usize) {
+		if self.start == self.end {
+			let column = self.start.column.saturating_sub(1);
+			(column, column + 1)
+		} else if self.start.lineno == self.end.lineno && self.end.column > self.start.column {
+			(self.start.column, self.end.column)
+		} else {
+			(self.start.column, self.start.column + 1)
+		}
+	}
+
+	/// The column the span starts on, counting from one.
+	#[must_use]
+	pub fn column(&self) -> usize {
+		self.columns().0 + 1
+	}
+}
+
+/// A problem with some code, and possibly how to fix it.
+#[derive(Debug)]
+pub struct Diagnostic {
+	span: Span,
+	severity: Severity,
+	r#type: ErrorType,
+	suggestion: Option<String>
+}
+
+impl Diagnostic {
+	pub fn new(span: Span, severity: Severity, r#type: ErrorType) -> Self {
+		Self { span, severity, r#type, suggestion: None }
+	}
+
+	/// Adds a suggestion for how to fix the problem, such as "did you forget a `)`?".
+	#[must_use]
+	pub fn with_suggestion(mut self, suggestion: impl Into<String>) -> Self {
+		self.suggestion = Some(suggestion.into());
+		self
+	}
+
+	/// Where the problem is.
+	#[inline]
+	pub fn span(&self) -> &Span {
+		&self.span
+	}
+
+	/// How serious the problem is.
+	#[inline]
+	pub fn severity(&self) -> Severity {
+		self.severity
+	}
+
+	/// What the problem is.
+	#[inline]
+	pub fn error_type(&self) -> &ErrorType {
+		&self.r#type
+	}
+
+	/// How the problem could be fixed, if it's obvious.
+	#[inline]
+	pub fn suggestion(&self) -> Option<&str> {
+		self.suggestion.as_deref()
+	}
+}
+
+/// The errors from parsing some code.
+///
+/// The parser tries to carry on after an error, so this usually contains every problem with the
+/// code, not just the first.
 #[derive(Debug)]
 pub struct Error {
-	context: Context,
-	r#type: ErrorType
+	diagnostics: Vec<Diagnostic>
 }
 
 #[must_use]
+#[allow(unused_attributes)]
 pub type Result<T> = std::result::Result<T, Error>;
 
 impl Error {
 	pub fn new(context: Context, r#type: ErrorType) -> Self {
-		Error { context, r#type }
+		Self::spanning(Span::at(context), r#type)
+	}
+
+	/// Creates an error about the code in `span`.
+	pub fn spanning(span: Span, r#type: ErrorType) -> Self {
+		Diagnostic::new(span, Severity::Error, r#type).into()
+	}
+
+	/// Creates an error from several diagnostics.
+	///
+	/// # Panics
+	/// Panics if `diagnostics` is empty.
+	pub fn from_diagnostics(diagnostics: Vec<Diagnostic>) -> Self {
+		assert!(!diagnostics.is_empty(), "an error needs at least one diagnostic");
+		Self { diagnostics }
+	}
+
+	/// Adds a suggestion for how to fix the first problem.
+	#[must_use]
+	pub fn with_suggestion(mut self, suggestion: impl Into<String>) -> Self {
+		self.diagnostics[0].suggestion = Some(suggestion.into());
+		self
+	}
+
+	/// Where the first problem starts.
+	#[inline]
+	pub fn context(&self) -> &Context {
+		&self.span().start
+	}
+
+	/// Where the first problem is.
+	#[inline]
+	pub fn span(&self) -> &Span {
+		self.diagnostics[0].span()
+	}
+
+	/// What went wrong first.
+	#[inline]
+	pub fn error_type(&self) -> &ErrorType {
+		self.diagnostics[0].error_type()
+	}
+
+	/// Every problem that was found, in the order they were found.
+	#[inline]
+	pub fn diagnostics(&self) -> &[Diagnostic] {
+		&self.diagnostics
+	}
+
+	pub fn into_diagnostics(self) -> Vec<Diagnostic> {
+		self.diagnostics
 	}
 }
 
-impl Display for Error {
+impl From<Diagnostic> for Error {
+	#[inline]
+	fn from(diagnostic: Diagnostic) -> Self {
+		Self { diagnostics: vec![diagnostic] }
+	}
+}
+
+impl Display for Diagnostic {
 	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
 		const TAB_REPLACEMENT: &str = "  ";
 
-		let Context { ref file, lineno, mut column, ref line } = self.context;
+		let Context { ref file, lineno, ref line, .. } = self.span.start;
 		let file = file.as_ref()
-			.map(|x| x.to_string_lossy().to_owned().to_string())
+			.map(|x| x.to_string_lossy().to_string())
 			.unwrap_or_else(|| "<eval>".to_string());
 
 		// replace tabs with a standardized representation for error messages
-		let mut line = line.clone();
-		while let Some(tab_pos) = line.find('\t') {
-			line.replace_range(tab_pos..=tab_pos, TAB_REPLACEMENT);
-			column += TAB_REPLACEMENT.len() - 1;
-		}
+		let width = |chars: &[char]| chars.iter()
+			.map(|&chr| if chr == '\t' { TAB_REPLACEMENT.len() } else { 1 })
+			.sum::<usize>();
+
+		let (start, end) = self.span.columns();
+		let chars = line.trim_end().chars().collect::<Vec<_>>();
+		let padding = 1 + width(&chars[..start.min(chars.len())]);
+		let carets = width(&chars[start.min(chars.len())..end.min(chars.len())]).max(1);
 
-		write!(f, concat!("{file}:{lineno}:{column}: parse error, {error}",
+		write!(f, concat!("{file}:{lineno}:{column}: parse {severity}, {error}",
 					 "\n    |",
 					 "\n {lineno:<3}| {context}",
-					 "\n    |{padding}^ here"),
+					 "\n    |{padding}{carets} here"),
 			file=file,
 			lineno=lineno,
-			column=column,
+			column=self.span.column(),
+			severity=self.severity,
 			error=self.r#type,
-			context=line.trim_end(),
-			padding=" ".repeat(column))
+			context=line.trim_end().replace('\t', TAB_REPLACEMENT),
+			padding=" ".repeat(padding),
+			carets="^".repeat(carets))?;
+
+		if let Some(ref suggestion) = self.suggestion {
+			write!(f, "\n    = help: {}", suggestion)?;
+		}
+
+		Ok(())
+	}
+}
+
+impl Display for Error {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		for (i, diagnostic) in self.diagnostics.iter().enumerate() {
+			if i != 0 {
+				write!(f, "\n\n")?;
+			}
+
+			Display::fmt(diagnostic, f)?;
+		}
+
+		Ok(())
 	}
 }
 
@@ -90,7 +288,7 @@ impl Display for ErrorType {
 
 impl std::error::Error for Error {
 	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-		match self.r#type {
+		match self.error_type() {
 			ErrorType::CantReadStream(ref err) => Some(err),
 			ErrorType::BadNumber(ref err) => Some(err),
 			ErrorType::BadRegex(ref err) => Some(err),
@@ -98,7 +296,3 @@ impl std::error::Error for Error {
 		}
 	}
 }
-
-
-
-
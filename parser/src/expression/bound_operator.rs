@@ -1,5 +1,6 @@
 use crate::token::{Token, Operator, operator::Associativity, ParenType};
 use crate::expression::{Expression, Constructable, PutBack, Executable};
+use crate::token::{Primitive, primitive::Variable};
 use crate::stream::Contexted;
 use crate::Result;
 use std::fmt::{self, Display, Formatter};
@@ -18,24 +19,112 @@ pub struct BoundOperator {
 	pub(crate) args: Box<OperArgs>
 }
 
+/// Gets the operator that `expr` is bound by, if any.
+fn bound_by(expr: &Expression) -> Option<Operator> {
+	match expr {
+		Expression::Operator(op) => Some(op.oper),
+		Expression::FunctionCall(..) => Some(Operator::Call),
+		_ => None
+	}
+}
+
+/// Writes `expr`, which is an operand of `parent`, surrounding it with parentheses if it'd
+/// otherwise be parsed differently.
+fn fmt_operand(f: &mut Formatter, expr: &Expression, parent: Operator, is_lhs: bool) -> fmt::Result {
+	let needs_parens =
+		match bound_by(expr) {
+			None => false,
+			// unary operators take everything after them as their operand.
+			Some(oper) if oper.assoc() == Associativity::UnaryOperOnLeft => true,
+			Some(oper) if oper.precedence() == parent.precedence() =>
+				(parent.assoc() == Associativity::RightToLeft) == is_lhs,
+			Some(oper) => oper > parent
+		};
+
+	if needs_parens {
+		write!(f, "( {} )", expr)
+	} else {
+		Display::fmt(expr, f)
+	}
+}
+
+/// Writes `expr`, which is used as an attribute or variable name, without quotes if possible.
+fn fmt_name(f: &mut Formatter, expr: &Expression) -> fmt::Result {
+	match expr {
+		Expression::Primitive(Primitive::Text(text)) if Variable::is_valid_name(text.as_ref()) =>
+			f.write_str(text.as_ref()),
+		other => Display::fmt(other, f)
+	}
+}
+
+/// Writes the parameters of an `->`, which have been converted into text.
+fn fmt_params(f: &mut Formatter, expr: &Expression) -> fmt::Result {
+	match expr {
+		Expression::Block(block) if block.paren_type() != ParenType::Curly =>
+			block.fmt_with(f, fmt_params),
+		Expression::Operator(BoundOperator { oper, this, args }) if matches!(**args, OperArgs::Unary) => {
+			f.write_str(oper.unary_repr())?;
+			fmt_params(f, this)
+		},
+		other => fmt_name(f, other)
+	}
+}
+
 impl Display for BoundOperator {
 	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
 		match &*self.args {
 			OperArgs::Unary if self.oper.assoc() == Associativity::UnaryOperOnLeft =>
-				write!(f, "{}{}", self.oper, self.this),
-			OperArgs::Unary => write!(f, "{}{}", self.this, self.oper),
-			OperArgs::Binary(rhs) if self.oper <= Operator::Dot => 
-				write!(f, "{}{}{}", self.this, self.oper, rhs),
-			OperArgs::Binary(rhs) if self.oper == Operator::Call => 
-				write!(f, "{} {}", self.this, rhs),
-			OperArgs::Binary(rhs) if self.oper < Operator::Assign => 
-				write!(f, "({}) {} ({})", self.this, self.oper, rhs),
-			OperArgs::Binary(rhs) =>
-				write!(f, "{} {} {}", self.this, self.oper, rhs),
-			OperArgs::Ternary(mid, rhs) if self.oper == Operator::DotAssign =>
-				write!(f, "{}.{} = {}", self.this, mid, rhs),
-			OperArgs::Ternary(mid, rhs) if self.oper == Operator::IndexAssign =>
-				write!(f, "{}[{}] = {}", self.this, mid, rhs),
+				write!(f, "{}{}", self.oper.unary_repr(), self.this),
+			OperArgs::Unary => {
+				fmt_operand(f, &self.this, self.oper, true)?;
+				Display::fmt(&self.oper, f)
+			},
+			OperArgs::Binary(rhs) if self.oper == Operator::Call => {
+				fmt_operand(f, &self.this, self.oper, true)?;
+
+				if !matches!(rhs, Expression::Block(_)) {
+					f.write_str(" ")?;
+				}
+
+				fmt_operand(f, rhs, self.oper, false)
+			},
+			OperArgs::Binary(rhs) if self.oper <= Operator::Dot => {
+				fmt_operand(f, &self.this, self.oper, true)?;
+				Display::fmt(&self.oper, f)?;
+
+				if matches!(self.oper, Operator::Dot | Operator::DotQuestion | Operator::Scoped) {
+					fmt_name(f, rhs)
+				} else {
+					fmt_operand(f, rhs, self.oper, false)
+				}
+			},
+			OperArgs::Binary(rhs) => {
+				match self.oper {
+					Operator::Arrow => fmt_params(f, &self.this)?,
+					Operator::Assign | Operator::Colon => fmt_name(f, &self.this)?,
+					_ => fmt_operand(f, &self.this, self.oper, true)?
+				}
+
+				write!(f, " {} ", self.oper)?;
+				fmt_operand(f, rhs, self.oper, false)
+			},
+			OperArgs::Ternary(mid, rhs) if self.oper == Operator::DotAssign => {
+				fmt_operand(f, &self.this, Operator::Dot, true)?;
+				f.write_str(".")?;
+				fmt_name(f, mid)?;
+				write!(f, " = {}", rhs)
+			},
+			OperArgs::Ternary(mid, rhs) if self.oper == Operator::IndexAssign => {
+				fmt_operand(f, &self.this, Operator::Call, true)?;
+
+				match mid {
+					Expression::Block(block) if block.paren_type() == ParenType::Square =>
+						Display::fmt(block, f)?,
+					other => write!(f, "[ {} ]", other)?
+				}
+
+				write!(f, " = {}", rhs)
+			},
 			OperArgs::Ternary(mid, rhs) =>
 				write!(f, "{}{}({}, {})", self.this, self.oper, mid, rhs)
 		}
@@ -116,8 +205,6 @@ fn build_op<C>(oper: Operator, ctor: &mut C, mut this: Expression) -> Result<Exp
 where
 	C: Iterator<Item=Result<Token>> + PutBack + Contexted
 {
-	use crate::token::Primitive;
-
 	let rhs = Expression::try_construct_precedence(ctor, Some(oper))?
 		.ok_or_else(|| parse_error!(ctor, ExpectedExpression))?;
 
@@ -219,6 +306,8 @@ where
 						context: block.context.clone(),
 						paren_type: ParenType::Round,
 						lines: vec![crate::block::Line::Single(Expression::Block(block))],
+						linenos: vec![],
+						doc: None,
 					}),
 				lhs => Expression::FunctionCall(Box::new(lhs), block)
 			},
@@ -1,14 +1,32 @@
 use super::PutBack;
 use crate::stream::{Context, Contexted};
 use crate::token::Token;
-use crate::Result;
+use crate::{Error, ErrorType, Result};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Constructor<I>(I, Vec<Token>);
+#[derive(Debug)]
+pub struct Constructor<I>(I, Vec<Token>, Option<Vec<Error>>);
 
 impl<I> Constructor<I> {
 	pub fn new(iter: I) -> Self {
-		Constructor(iter, vec![])
+		Constructor(iter, vec![], None)
+	}
+
+	/// Creates a constructor that records the errors it can recover from instead of stopping.
+	pub fn recovering(iter: I) -> Self {
+		Constructor(iter, vec![], Some(vec![]))
+	}
+
+	/// Combines `result` with the errors that were recovered from.
+	pub fn finish<T>(self, result: Result<T>) -> Result<T> {
+		let mut errors = self.2.unwrap_or_default();
+
+		match result {
+			Ok(value) if errors.is_empty() => return Ok(value),
+			Ok(_) => {},
+			Err(err) => errors.push(err)
+		}
+
+		Err(Error::from_diagnostics(errors.into_iter().flat_map(Error::into_diagnostics).collect()))
 	}
 }
 
@@ -16,12 +34,29 @@ impl<I: Iterator<Item=Result<Token>>> PutBack for Constructor<I> {
 	fn put_back(&mut self, tkn: Result<Token>) {
 		self.1.push(tkn.unwrap());
 	}
+
+	fn recover(&mut self, err: Error) -> Result<()> {
+		match self.2 {
+			// if the stream can't be read, there's nothing left to parse.
+			Some(_) if matches!(err.error_type(), ErrorType::CantReadStream(_)) => Err(err),
+			Some(ref mut errors) => { errors.push(err); Ok(()) },
+			None => Err(err)
+		}
+	}
 }
 
 impl<I: Contexted> Contexted for Constructor<I> {
 	fn context(&self) -> &Context {
 		self.0.context()
 	}
+
+	fn start_context(&self) -> &Context {
+		self.0.start_context()
+	}
+
+	fn doc_comment(&self) -> Option<&str> {
+		self.0.doc_comment()
+	}
 }
 
 impl<I: Iterator<Item=Result<Token>>> Iterator for Constructor<I> {
@@ -1,6 +1,6 @@
 use crate::{Result, Block};
 use crate::expression::{Constructable, Constructor, Executable, BoundOperator};
-use crate::stream::{Context, Contexted};
+use crate::stream::Contexted;
 use crate::token::{Token, Primitive, Operator, ParenType};
 use quest_core::{Object, Args};
 use std::fmt::{self, Display, Formatter};
@@ -45,11 +45,13 @@ impl Executable for Expression {
 	fn execute(&self) -> quest_core::Result<Object> {
 		// dbg!(self);
 		// std::process::exit(0);
+		quest_core::sandbox::step()?;
+
 		match self {
 			Self::Primitive(prim) => prim.execute(),
 			Self::Block(block) => block.execute(),
 			Self::Operator(op) => op.execute(),
-			Self::FunctionCall(this, block) => call_function(&this, &block)
+			Self::FunctionCall(this, block) => call_function(this, block)
 		}
 	}
 }
@@ -120,52 +122,17 @@ impl Expression {
 }
 
 impl Expression {
+	/// Parses all of `iter` as the lines of a block.
+	///
+	/// Parsing carries on after errors, so the error that's returned has every problem that was
+	/// found.
 	pub fn parse_stream<I>(iter: I) -> Result<Self>
 	where
 		I: Iterator<Item=Result<Token>> + Contexted
 	{
+		let mut ctor = Constructor::recovering(iter);
+		let block = Block::construct_body(&mut ctor, ParenType::Round, None);
 
-		#[derive(PartialEq, Debug)]
-		enum Where { Start, GivenCode, End }
-
-		#[derive(Debug)]
-		struct WrappedBlock<I>(Where, Constructor<I>);
-
-		impl<I: Iterator<Item=Result<Token>>> super::PutBack for WrappedBlock<I> {
-			fn put_back(&mut self, item: Self::Item) {
-				if self.0 == Where::End {
-					self.0 = Where::GivenCode
-				}
-
-				self.1.put_back(item);
-			}
-		}
-
-		impl<I: Iterator<Item=Result<Token>>> Iterator for WrappedBlock<I> {
-			type Item = Result<Token>;
-			fn next(&mut self) -> Option<Self::Item> {
-				match self.0 {
-					Where::Start => {
-						self.0 = Where::GivenCode;
-						Some(Ok(Token::Left(ParenType::Round)))
-					},
-					Where::GivenCode => self.1.next().or_else(|| {
-						self.0 = Where::End;
-						Some(Ok(Token::Right(ParenType::Round)))
-					}),
-					Where::End => None,
-				}
-			}
-		}
-
-		impl<I: Contexted> Contexted for WrappedBlock<I> {
-			fn context(&self) -> &Context {
-				self.1.context()
-			}
-		}
-
-		Self::try_construct(&mut WrappedBlock(Where::Start, Constructor::new(iter)))
+		ctor.finish(block).map(Self::Block)
 	}
 }
-
-	
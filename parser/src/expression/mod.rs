@@ -1,6 +1,6 @@
 mod expression;
 mod constructor;
-mod bound_operator;
+pub(crate) mod bound_operator;
 
 pub trait Executable {
 	fn execute(&self) -> quest_core::Result<quest_core::Object>;
@@ -8,6 +8,14 @@ pub trait Executable {
 
 pub trait PutBack : Iterator {
 	fn put_back(&mut self, item: Self::Item);
+
+	/// Called with an error that the parser can recover from by skipping the code it's about.
+	///
+	/// Returning `Ok` records the error and continues parsing, so later errors can be found too.
+	/// By default, the error is returned and parsing stops.
+	fn recover(&mut self, err: crate::Error) -> crate::Result<()> {
+		Err(err)
+	}
 }
 
 pub trait Constructable {
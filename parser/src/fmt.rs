@@ -0,0 +1,763 @@
+//! Formatting Quest code in a standard style.
+//!
+//! Code is split into tokens, keeping the comments and line breaks between them, which are then
+//! laid out again:
+//! - Blocks that were written over several lines, or that don't fit within [`MAX_WIDTH`], have
+//!   one statement per line, indented with a tab. Argument lists that don't fit have one element
+//!   per line instead, and lists are filled in as many elements per line as fit. Where lists and
+//!   arguments were split originally doesn't matter.
+//! - Binary operators have a space on each side, except for `.`, `.?` and `::`, which have none,
+//!   and `:`, which only has one after it.
+//! - Comments, single blank lines between statements and line breaks before the `.`s of method
+//!   chains are kept.
+//!
+//! Formatting never changes what code does: the formatted code is parsed again, and an error is
+//! returned if it's any different.
+
+use crate::{Error, ErrorType, Expression, Result, Token};
+use crate::stream::{BufStream, Context, Contexted, Stream};
+use crate::token::{Operator, ParenType};
+use std::io::Cursor;
+use std::path::PathBuf;
+
+/// The widest a line can be before the blocks on it are split over several lines.
+pub const MAX_WIDTH: usize = 100;
+
+/// How wide a tab is when measuring lines.
+const TAB_WIDTH: usize = 4;
+
+/// The comment that ends a file's code. Everything after it is kept as-is.
+const EOF_MARKER: &str = "##__EOF__##";
+
+/// Formats `source`, which is from `file` if it's given.
+///
+/// An error is returned if `source` can't be parsed.
+pub fn format(source: &str, file: Option<PathBuf>) -> Result<String> {
+	let before = parse(source, file.clone())?;
+	let formatted = Formatter::new(source, file.clone())?.format();
+
+	match parse(&formatted, file.clone()) {
+		Ok(after) if after == before => Ok(formatted),
+		_ => Err(Error::new(Context::new(file),
+			ErrorType::Message("formatting would change what the code does; this is a bug in `quest fmt`")))
+	}
+}
+
+/// Checks to see if `source` is already formatted.
+pub fn is_formatted(source: &str, file: Option<PathBuf>) -> Result<bool> {
+	format(source, file).map(|formatted| formatted == source)
+}
+
+/// Parses `source`, returning its code without any comments or formatting.
+fn parse(source: &str, file: Option<PathBuf>) -> Result<String> {
+	Expression::parse_stream(BufStream::new(Cursor::new(source), file).tokens())
+		.map(|expr| expr.to_string())
+}
+
+fn width(text: &str) -> usize {
+	text.chars().map(|chr| if chr == '\t' { TAB_WIDTH } else { 1 }).sum()
+}
+
+#[derive(Debug, Clone)]
+struct Comment {
+	text: String,
+	is_line: bool,
+	/// How many line breaks there were between this and the code before it.
+	newlines_before: usize
+}
+
+/// The comments and whitespace between two tokens.
+#[derive(Debug, Default)]
+struct Gap {
+	comments: Vec<Comment>,
+	/// How many line breaks there were after the last comment.
+	newlines: usize,
+	/// Everything from the end-of-file marker on, if there was one.
+	tail: Option<String>
+}
+
+impl Gap {
+	fn parse(text: &str) -> Self {
+		let mut gap = Self::default();
+		let mut chars = text.char_indices().peekable();
+
+		while let Some((idx, chr)) = chars.next() {
+			match chr {
+				'\n' => gap.newlines += 1,
+				'#' if text[idx..].starts_with(EOF_MARKER) => {
+					gap.tail = Some(text[idx..].to_string());
+					break;
+				},
+				'#' => {
+					let end = text[idx..].find('\n').map_or(text.len(), |len| idx + len);
+					gap.push(text[idx..end].trim_end(), true);
+
+					while chars.peek().is_some_and(|&(idx, _)| idx < end) {
+						chars.next();
+					}
+				},
+				'/' if chars.peek().map(|&(_, chr)| chr) == Some('*') => {
+					let mut depth = 0;
+					let mut end = text.len();
+					let mut prev = '/';
+
+					for (idx, chr) in chars.by_ref() {
+						match (prev, chr) {
+							('/', '*') => { depth += 1; prev = ' '; continue },
+							('*', '/') if depth == 1 => { end = idx + 1; break },
+							('*', '/') => { depth -= 1; prev = ' '; continue },
+							_ => {}
+						}
+
+						prev = chr;
+					}
+
+					gap.push(&text[idx..end], false);
+				},
+				_ => { /* other whitespace is ignored */ }
+			}
+		}
+
+		gap
+	}
+
+	fn push(&mut self, text: &str, is_line: bool) {
+		self.comments.push(Comment { text: text.to_string(), is_line, newlines_before: self.newlines });
+		self.newlines = 0;
+	}
+}
+
+/// A token and the comments around it.
+#[derive(Debug)]
+struct Lexeme {
+	token: Token,
+	/// How the token was written.
+	text: String,
+	/// Comments on the lines before the token.
+	leading: Vec<Comment>,
+	/// How many line breaks there were just before the token.
+	newlines: usize,
+	/// Whether there was anything at all between this token and the one before it.
+	spaced: bool,
+	/// Comments after the token on the same line.
+	trailing: Vec<Comment>
+}
+
+impl Lexeme {
+	/// Whether the token was on a different line to the one before it.
+	fn breaks_before(&self) -> bool {
+		self.newlines != 0 || !self.leading.is_empty()
+	}
+
+	/// Whether there was a line comment after the token.
+	fn breaks_after(&self) -> bool {
+		self.trailing.iter().any(|comment| comment.is_line)
+	}
+
+	/// Whether there was a blank line before the token or the comments before it.
+	fn blank_before(&self) -> bool {
+		self.leading.first().map_or(self.newlines, |comment| comment.newlines_before) >= 2
+	}
+}
+
+#[derive(Debug)]
+enum Node {
+	Token(Lexeme),
+	Group { paren: ParenType, open: Lexeme, body: Vec<Node>, close: Lexeme }
+}
+
+impl Node {
+	fn first(&self) -> &Lexeme {
+		match self {
+			Self::Token(lexeme) => lexeme,
+			Self::Group { open, .. } => open
+		}
+	}
+
+	fn last(&self) -> &Lexeme {
+		match self {
+			Self::Token(lexeme) => lexeme,
+			Self::Group { close, .. } => close
+		}
+	}
+}
+
+/// A statement in a block (or element of a list), and what separates it from the next one.
+struct Item<'a> {
+	nodes: &'a [Node],
+	separator: Option<&'a Lexeme>
+}
+
+impl Item<'_> {
+	fn first(&self) -> Option<&Lexeme> {
+		self.nodes.first().map(Node::first).or(self.separator)
+	}
+
+	/// Splits `body` up into statements, and into elements as well if `split_commas` is true.
+	fn split(body: &[Node], split_commas: bool) -> Vec<Item<'_>> {
+		let mut items = vec![];
+		let mut start = 0;
+
+		for (idx, node) in body.iter().enumerate() {
+			let separator =
+				match node {
+					Node::Token(lexeme @ Lexeme { token: Token::Endline(_), .. }) => lexeme,
+					Node::Token(lexeme @ Lexeme { token: Token::Comma, .. }) if split_commas => lexeme,
+					_ => continue
+				};
+
+			let nodes = &body[start..idx];
+			start = idx + 1;
+
+			// empty statements don't do anything, so they're removed (unless they have comments).
+			if nodes.is_empty() && separator.token != Token::Comma && separator.leading.is_empty()
+				&& separator.trailing.is_empty()
+			{
+				continue;
+			}
+
+			items.push(Item { nodes, separator: Some(separator) });
+		}
+
+		if start != body.len() {
+			items.push(Item { nodes: &body[start..], separator: None });
+		}
+
+		items
+	}
+}
+
+/// What came before a token in a statement, which determines how it's spaced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Prev {
+	Start,
+	Operand,
+	Comma,
+	Unary,
+	Binary(Operator)
+}
+
+impl Prev {
+	fn expects_operand(self) -> bool {
+		!matches!(self, Self::Operand)
+	}
+
+	/// Whether something that isn't an operator needs a space before it.
+	fn spaced(self) -> bool {
+		match self {
+			Self::Start | Self::Unary => false,
+			Self::Binary(op) => !is_tight(op),
+			Self::Operand | Self::Comma => true
+		}
+	}
+}
+
+/// Whether `op` is written without spaces around it.
+fn is_tight(op: Operator) -> bool {
+	matches!(op, Operator::Dot | Operator::DotQuestion | Operator::Scoped)
+}
+
+/// Whether `lhs` and `rhs` are still read as two tokens when there's nothing between them.
+fn joins_cleanly(lhs: &str, rhs: &str) -> bool {
+	let lhs = lhs.rsplit('\n').next().unwrap_or_default();
+	let rhs = rhs.split('\n').next().unwrap_or_default();
+	let mut tokens = BufStream::from(format!("{}{}", lhs, rhs)).tokens();
+
+	matches!(tokens.next(), Some(Ok(_)))
+		&& tokens.context().column == lhs.chars().count()
+		&& matches!(tokens.next(), Some(Ok(_)))
+		&& tokens.context().column == lhs.chars().count() + rhs.chars().count()
+}
+
+struct Formatter {
+	body: Vec<Node>,
+	/// The comments at the end of the code.
+	end: Gap
+}
+
+impl Formatter {
+	fn new(source: &str, file: Option<PathBuf>) -> Result<Self> {
+		let chars = source.chars().collect::<Vec<_>>();
+		let mut line_starts = vec![0];
+		line_starts.extend(chars.iter().enumerate().filter(|(_, &chr)| chr == '\n').map(|(idx, _)| idx + 1));
+
+		let offset = |context: &Context|
+			context.lineno.checked_sub(1).map_or(0, |line| line_starts[line] + context.column);
+		let slice = |start: usize, end: usize| chars[start..end].iter().collect::<String>();
+
+		let mut tokens = BufStream::new(Cursor::new(source), file).tokens();
+		let mut stack: Vec<(ParenType, Lexeme, Vec<Node>)> = vec![];
+		let mut body = vec![];
+		let mut prev_end = 0;
+
+		while let Some(token) = tokens.next() {
+			let token = token?;
+			let start = offset(tokens.start_context());
+			let end = offset(tokens.context());
+			let gap = Gap::parse(&slice(prev_end, start));
+
+			let mut comments = gap.comments.into_iter().peekable();
+
+			// comments on the same line as the previous token belong to it.
+			if let Some(prev) = stack.last_mut().map_or(&mut body, |(_, _, body)| body).last_mut() {
+				let prev =
+					match prev {
+						Node::Token(lexeme) => lexeme,
+						Node::Group { close, .. } => close
+					};
+
+				while let Some(comment) = comments.next_if(|comment| comment.newlines_before == 0) {
+					prev.trailing.push(comment);
+				}
+			} else if let Some((_, open, _)) = stack.last_mut() {
+				while let Some(comment) = comments.next_if(|comment| comment.newlines_before == 0) {
+					open.trailing.push(comment);
+				}
+			}
+
+			let lexeme = Lexeme {
+				token,
+				text: slice(start, end),
+				leading: comments.collect(),
+				newlines: gap.newlines,
+				spaced: start != prev_end,
+				trailing: vec![]
+			};
+
+			prev_end = end;
+
+			match lexeme.token {
+				Token::Left(paren) => stack.push((paren, lexeme, vec![])),
+				Token::Right(paren) => {
+					let (open_paren, open, group) = stack.pop()
+						.ok_or_else(|| parse_error!(&tokens, UnexpectedToken(lexeme.token.clone())))?;
+
+					if open_paren != paren {
+						return Err(parse_error!(&tokens, UnexpectedToken(lexeme.token)));
+					}
+
+					let node = Node::Group { paren, open, body: group, close: lexeme };
+					stack.last_mut().map_or(&mut body, |(_, _, body)| body).push(node);
+				},
+				_ => stack.last_mut().map_or(&mut body, |(_, _, body)| body).push(Node::Token(lexeme))
+			}
+		}
+
+		if let Some((paren, ..)) = stack.pop() {
+			return Err(parse_error!(&tokens, MissingClosingParen(paren)));
+		}
+
+		let mut end = Gap::parse(&slice(prev_end, chars.len()));
+
+		// like with tokens, comments on the same line as the last token belong to it.
+		if let Some(last) = body.last_mut() {
+			let last =
+				match last {
+					Node::Token(lexeme) => lexeme,
+					Node::Group { close, .. } => close
+				};
+
+			let count = end.comments.iter().take_while(|comment| comment.newlines_before == 0).count();
+			last.trailing.extend(end.comments.drain(..count));
+		}
+
+		Ok(Self { body, end })
+	}
+
+	fn format(&self) -> String {
+		let mut printer = Printer::default();
+
+		for (idx, item) in Item::split(&self.body, false).iter().enumerate() {
+			if idx != 0 {
+				printer.newline(0);
+
+				if item.first().is_some_and(Lexeme::blank_before) {
+					printer.newline(0);
+				}
+			}
+
+			printer.print_item(item, 0);
+		}
+
+		for comment in &self.end.comments {
+			if !printer.out.is_empty() {
+				printer.newline(0);
+
+				if comment.newlines_before >= 2 {
+					printer.newline(0);
+				}
+			}
+
+			printer.write(&comment.text);
+		}
+
+		let mut out = printer.out;
+
+		if let Some(ref tail) = self.end.tail {
+			if !out.is_empty() {
+				out.push('\n');
+			}
+
+			out.push_str(tail);
+		} else if !out.is_empty() {
+			out.push('\n');
+		}
+
+		out
+	}
+}
+
+#[derive(Debug, Default)]
+struct Printer {
+	out: String,
+	column: usize,
+	/// The text of the last token that was written.
+	last: String,
+	/// Whether the next token has to go on a new line, because of a line comment.
+	break_pending: bool,
+	/// Only used when measuring: blocks are only split if they have to be.
+	measuring: bool
+}
+
+impl Printer {
+	fn at_line_start(&self) -> bool {
+		self.out.is_empty() || self.out.ends_with(&['\n', '\t'][..])
+	}
+
+	fn newline(&mut self, indent: usize) {
+		let trimmed = self.out.trim_end_matches(&[' ', '\t'][..]).len();
+		self.out.truncate(trimmed);
+		self.out.push('\n');
+		self.out.extend(std::iter::repeat_n('\t', indent));
+		self.column = indent * TAB_WIDTH;
+		self.break_pending = false;
+	}
+
+	fn write(&mut self, text: &str) {
+		self.out.push_str(text);
+
+		match text.rfind('\n') {
+			Some(idx) => self.column = width(&text[idx + 1..]),
+			None => self.column += width(text)
+		}
+	}
+
+	/// Writes `lexeme` with its comments. Any line breaks needed are indented by `indent`.
+	fn print_lexeme(&mut self, lexeme: &Lexeme, indent: usize, mut space: bool, force_break: bool) {
+		let mut after_comment = false;
+
+		for (idx, comment) in lexeme.leading.iter().enumerate() {
+			if !self.at_line_start() && !after_comment {
+				self.newline(indent);
+			}
+
+			self.write(&comment.text);
+
+			let newlines_after = lexeme.leading.get(idx + 1)
+				.map_or(lexeme.newlines, |next| next.newlines_before);
+
+			// block comments that were on the same line as what's after them stay there.
+			after_comment = !comment.is_line && newlines_after == 0;
+
+			if after_comment {
+				self.write(" ");
+			} else {
+				self.newline(indent);
+
+				if newlines_after >= 2 {
+					self.newline(indent);
+				}
+			}
+		}
+
+		if (self.break_pending || force_break) && !self.at_line_start() && !after_comment {
+			self.newline(indent);
+		} else if !self.at_line_start() && !after_comment {
+			if !space && lexeme.spaced && !joins_cleanly(&self.last, &lexeme.text) {
+				space = true;
+			}
+
+			if space {
+				self.write(" ");
+			}
+		}
+
+		self.write(&lexeme.text);
+		self.last.clone_from(&lexeme.text);
+
+		for comment in &lexeme.trailing {
+			self.write(" ");
+			self.write(&comment.text);
+			self.break_pending |= comment.is_line;
+		}
+	}
+
+	fn print_item(&mut self, item: &Item, indent: usize) {
+		self.print_statement(item.nodes, indent);
+
+		if let Some(separator) = item.separator {
+			self.print_lexeme(separator, indent + 1, false, false);
+		}
+	}
+
+	fn print_statement(&mut self, nodes: &[Node], indent: usize) {
+		let mut prev = Prev::Start;
+		// the indentation of the line that blocks in the statement start on.
+		let mut base_indent = indent;
+
+		for (idx, node) in nodes.iter().enumerate() {
+			// only the start of the statement isn't a continuation.
+			let break_indent = if idx == 0 { indent } else { indent + 1 };
+
+			// line breaks within statements (such as before the `.`s of a method chain) are kept.
+			let line_break = idx != 0 && node.first().breaks_before();
+
+			if line_break || idx != 0 && self.break_pending {
+				base_indent = indent + 1;
+			}
+
+			let (space, next) =
+				match node {
+					Node::Token(Lexeme { token: Token::Comma, .. }) => (false, Prev::Comma),
+					Node::Token(Lexeme { token: Token::Operator(_), .. }) if prev.expects_operand() =>
+						(prev.spaced(), Prev::Unary),
+					Node::Token(Lexeme { token: Token::Operator(op), .. }) =>
+						(!is_tight(*op) && *op != Operator::Colon, Prev::Binary(*op)),
+					Node::Group { paren, .. } if prev == Prev::Operand =>
+						(*paren == ParenType::Curly, Prev::Operand),
+					_ => (prev.spaced(), Prev::Operand)
+				};
+
+			match node {
+				Node::Token(lexeme) => self.print_lexeme(lexeme, break_indent, space, line_break),
+				Node::Group { paren, open, body, close } => {
+					self.print_lexeme(open, break_indent, space, line_break);
+					self.print_group_body(*paren, body, close, base_indent);
+				}
+			}
+
+			prev = next;
+		}
+	}
+
+	/// Whether the group whose opening paren was just written has to be split over several lines.
+	///
+	/// Blocks are split if they were written over several lines. Lists and arguments are only split
+	/// if they have comments that need their own lines, as otherwise splitting them (which adds line
+	/// breaks) could change how they're formatted the next time.
+	fn must_break(&self, paren: ParenType, body: &[Node], close: &Lexeme) -> bool {
+		if self.break_pending || body.iter().any(|node| node.last().breaks_after()) {
+			return true;
+		}
+
+		if paren == ParenType::Curly {
+			close.breaks_before() || body.iter().any(|node| node.first().breaks_before())
+		} else {
+			!close.leading.is_empty() || body.iter().any(|node| !node.first().leading.is_empty())
+		}
+	}
+
+	/// Measures how wide a group's body and closing paren would be, if only split where needed.
+	fn measure(paren: ParenType, body: &[Node], close: &Lexeme) -> usize {
+		let mut printer = Self { measuring: true, ..Self::default() };
+		printer.print_group_body(paren, body, close, 0);
+		width(printer.out.split('\n').next().unwrap_or_default())
+	}
+
+	/// Measures how wide `item` is, if it fits on one line.
+	fn measure_item(item: &Item) -> Option<usize> {
+		let mut printer = Self { measuring: true, ..Self::default() };
+		printer.print_item(item, 0);
+
+		if printer.out.contains('\n') || printer.break_pending {
+			None
+		} else {
+			Some(width(&printer.out))
+		}
+	}
+
+	fn print_group_body(&mut self, paren: ParenType, body: &[Node], close: &Lexeme, indent: usize) {
+		let items = Item::split(body, paren != ParenType::Curly);
+
+		// there's no point splitting a block if the line's already too long before it.
+		let split = self.must_break(paren, body, close)
+			|| !self.measuring && !items.is_empty() && self.column < MAX_WIDTH
+				&& self.column + Self::measure(paren, body, close) > MAX_WIDTH;
+
+		// lists of things that fit on one line are filled in, rather than having one per line.
+		let widths =
+			if split && paren == ParenType::Square && close.leading.is_empty() {
+				items.iter().map(Self::measure_item).collect::<Option<Vec<_>>>()
+			} else {
+				None
+			};
+
+		if let Some(widths) = widths {
+			self.newline(indent + 1);
+
+			for (idx, (item, width)) in items.iter().zip(widths).enumerate() {
+				if idx != 0 {
+					if self.column + 1 + width > MAX_WIDTH {
+						self.newline(indent + 1);
+					} else {
+						self.write(" ");
+					}
+				}
+
+				self.print_item(item, indent + 1);
+			}
+
+			self.newline(indent);
+		} else if split {
+			for (idx, item) in items.iter().enumerate() {
+				self.newline(indent + 1);
+
+				if idx != 0 && item.first().is_some_and(Lexeme::blank_before) {
+					self.newline(indent + 1);
+				}
+
+				self.print_item(item, indent + 1);
+			}
+
+			for comment in &close.leading {
+				self.newline(indent + 1);
+				self.write(&comment.text);
+			}
+
+			self.newline(indent);
+		} else {
+			// `{{ ... }}` is used for a block that has its own scope, so it's kept together.
+			let padded = paren == ParenType::Curly && !items.is_empty()
+				&& !matches!(body.first(), Some(Node::Group { paren: ParenType::Curly, .. }));
+
+			if padded {
+				self.write(" ");
+			}
+
+			for (idx, item) in items.iter().enumerate() {
+				if idx != 0 {
+					self.write(" ");
+				}
+
+				self.print_item(item, indent);
+			}
+
+			if padded {
+				self.write(" ");
+			}
+		}
+
+		self.write(&close.text);
+		self.last.clone_from(&close.text);
+
+		for comment in &close.trailing {
+			self.write(" ");
+			self.write(&comment.text);
+			self.break_pending |= comment.is_line;
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn fmt(code: &str) -> String {
+		format(code, None).unwrap()
+	}
+
+	#[test]
+	fn spacing() {
+		assert_eq!(fmt("x=1+ 2*-y"), "x = 1 + 2 * -y\n");
+		assert_eq!(fmt("a . b ( c , d ) .? e"), "a.b(c, d).?e\n");
+		assert_eq!(fmt("f  { 1 }; Text :: '='"), "f { 1 };\nText::'='\n");
+		assert_eq!(fmt("(a,*b)->{a}"), "(a, *b) -> { a }\n");
+		assert_eq!(fmt("foo(x :y,);;"), "foo(x: y,);\n");
+	}
+
+	#[test]
+	fn blocks_and_comments() {
+		let code = concat!(
+			"# a comment\n",
+			"x = {  # about x\n",
+			"  a = 1;\n",
+			"\n\n",
+			"    /* about b */ b = 2 # trailing\n",
+			" # at the end\n",
+			"};\n",
+			"while({ i < 3 }, {\n",
+			"i += 1;\n",
+			"});\n",
+			"\n",
+			"# the end\n");
+
+		assert_eq!(fmt(code), concat!(
+			"# a comment\n",
+			"x = { # about x\n",
+			"\ta = 1;\n",
+			"\n",
+			"\t/* about b */ b = 2 # trailing\n",
+			"\t# at the end\n",
+			"};\n",
+			"while({ i < 3 }, {\n",
+			"\ti += 1;\n",
+			"});\n",
+			"\n",
+			"# the end\n"));
+	}
+
+	#[test]
+	fn long_lists_are_split() {
+		let args = (0..30).map(|i| format!("arg{}", i)).collect::<Vec<_>>();
+		let formatted = fmt(&format!("foo({});", args.join(",")));
+
+		assert_eq!(formatted, format!("foo(\n\t{}\n);\n", args.join(",\n\t")));
+	}
+
+	#[test]
+	fn method_chains_stay_split() {
+		assert_eq!(fmt("x\n.map(f)\n  .each(g)"), "x\n\t.map(f)\n\t.each(g)\n");
+	}
+
+	#[test]
+	fn lists_are_split_the_same_way_each_time() {
+		let items = (0..40).map(|i| format!("item{}", i)).collect::<Vec<_>>();
+		let formatted = fmt(&format!("x = [{}];", items.join(", ")));
+
+		assert!(formatted.lines().all(|line| width(line) <= MAX_WIDTH));
+		assert_eq!(fmt(&formatted), formatted);
+
+		// lists that were split by hand are joined again if they fit.
+		assert_eq!(fmt("x = [\n\t1,\n\t2\n];\nfoo(\n\ta,\n\tb\n);"), "x = [1, 2];\nfoo(a, b);\n");
+		assert_eq!(fmt("x = [\n\t1, # one\n\t2\n];"), "x = [\n\t1, # one\n\t2\n];\n");
+	}
+
+	/// Gets every `.qs` file in `dir` or its subdirectories.
+	fn quest_files(dir: &std::path::Path, files: &mut Vec<PathBuf>) {
+		for entry in std::fs::read_dir(dir).unwrap() {
+			let path = entry.unwrap().path();
+
+			if path.is_dir() {
+				quest_files(&path, files);
+			} else if path.extension().is_some_and(|ext| ext == "qs") {
+				files.push(path);
+			}
+		}
+	}
+
+	#[test]
+	fn idempotent_on_examples() {
+		let examples = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../examples");
+		let mut files = vec![];
+		quest_files(&examples, &mut files);
+
+		for path in files {
+
+			let source = std::fs::read_to_string(&path).unwrap();
+			let formatted = format(&source, Some(path.clone()))
+				.unwrap_or_else(|err| panic!("couldn't format {}: {}", path.display(), err));
+
+			assert_eq!(format(&formatted, None).unwrap(), formatted, "{} isn't idempotent", path.display());
+		}
+	}
+}
@@ -0,0 +1,262 @@
+//! Loading modules with `import`.
+//!
+//! `import(name)` runs the module `name` in its own scope, and returns that scope, so the module's
+//! variables are its attributes; they're usually accessed with `::`, so functions aren't passed
+//! the module (eg `import("geometry")::area(3)`). Each module is only run once, and importing it
+//! again returns the same scope.
+//!
+//! When a [package](set_packages) is being run, such as with `quest run`, a name is resolved as:
+//! - `dep`, where `dep` is the name of a package: that package's entry point.
+//! - `dep/path`, where `dep` is the name of a package: `path.qs` in one of its source directories.
+//! - anything else: `name.qs` in one of the source directories of the package that's importing it,
+//!   which is the `__package__` of the module the `import` is written in.
+//!
+//! Otherwise, `name.qs` is found relative to the current directory.
+
+use crate::Expression;
+use crate::stream::{BufStream, Stream};
+use crate::expression::Executable;
+use quest_core::{Args, Binding, Object};
+use quest_core::types::{Scope, Text};
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::path::{Path, PathBuf};
+use std::sync::{Condvar, Mutex, MutexGuard, RwLock};
+use std::thread::{self, ThreadId};
+
+/// A package whose modules can be imported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Package {
+	/// The package's name, which its modules are imported under.
+	pub name: String,
+	/// The module that's imported when the package itself is.
+	pub entry: PathBuf,
+	/// The directories that the package's modules are in.
+	pub source_dirs: Vec<PathBuf>
+}
+
+impl Package {
+	/// Finds the module `path` in this package's source directories.
+	fn find(&self, path: &str) -> Option<PathBuf> {
+		self.source_dirs.iter()
+			.map(|dir| dir.join(format!("{}.qs", path)))
+			.find(|file| file.is_file())
+	}
+}
+
+/// A module that's been imported.
+enum Module {
+	/// The module's still being run by the given thread.
+	Loading(ThreadId),
+	Loaded(Object)
+}
+
+// these are shared by every thread, so generators and spawned threads can import modules too.
+
+// the packages that can be imported; the first is the one that's being run.
+static PACKAGES: RwLock<Vec<Package>> = RwLock::new(Vec::new());
+// each module that's been imported, keyed by its file.
+static MODULES: Mutex<BTreeMap<PathBuf, Module>> = Mutex::new(BTreeMap::new());
+// notified whenever a module's finished loading.
+static LOADED: Condvar = Condvar::new();
+
+fn lock_modules() -> MutexGuard<'static, BTreeMap<PathBuf, Module>> {
+	MODULES.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+/// Sets the packages that modules are imported from: `root` is the one being run, and
+/// `dependencies` are all the packages it depends on, directly or not.
+pub fn set_packages(root: Package, dependencies: Vec<Package>) {
+	let mut packages = PACKAGES.write().unwrap_or_else(|err| err.into_inner());
+	packages.clear();
+	packages.push(root);
+	packages.extend(dependencies);
+}
+
+/// Finds the file for the module `name`, along with the index of the package it's in.
+fn resolve(name: &str) -> quest_core::Result<(PathBuf, Option<usize>)> {
+	let not_found = || quest_core::Error::Messaged(format!("no module named {:?}", name));
+
+	let packages = PACKAGES.read().unwrap_or_else(|err| err.into_inner());
+
+	if packages.is_empty() {
+		let file = PathBuf::from(format!("{}.qs", name));
+		return if file.is_file() { Ok((file, None)) } else { Err(not_found()) };
+	}
+
+	let (head, rest) =
+		match name.find('/') {
+			Some(idx) => (&name[..idx], Some(&name[idx + 1..])),
+			None => (name, None)
+		};
+
+	if let Some(idx) = packages.iter().position(|package| package.name == head) {
+		let package = &packages[idx];
+
+		return match rest {
+			None => Ok((package.entry.clone(), Some(idx))),
+			Some(rest) => package.find(rest).map(|file| (file, Some(idx))).ok_or_else(not_found)
+		};
+	}
+
+	// modules are looked for in the package of the module that's importing them, or the root.
+	let current = Binding::instance().get_attr_lit("__package__").ok()
+		.and_then(|package| package.downcast::<Text>().map(|package| package.to_string()))
+		.and_then(|package| packages.iter().position(|other| other.name == package))
+		.unwrap_or(0);
+
+	packages[current].find(name).map(|file| (file, Some(current))).ok_or_else(not_found)
+}
+
+/// Runs the module in `file`, which is in the package `package`, and returns its scope.
+fn load(name: &str, file: &Path, package: Option<usize>) -> quest_core::Result<Object> {
+	let module = Object::new(Scope);
+	module.set_attr_lit("name", Object::from(name))?;
+
+	if let Some(package) = package {
+		let package = PACKAGES.read().unwrap_or_else(|err| err.into_inner())[package].name.clone();
+		module.set_attr_lit("__package__", Object::from(package))?;
+	}
+
+	let stream = BufStream::try_from(file)
+		.map_err(|err| quest_core::Error::Messaged(format!("couldn't import {:?}: {}", name, err)))?;
+
+	Binding::new_stackframe(Some(module), Args::default(), |binding| {
+		Expression::parse_stream(stream.tokens())
+			.map_err(|err| Box::new(err) as Box<_>)?
+			.execute()?;
+
+		Ok(binding.as_ref().clone())
+	})
+}
+
+/// Imports the module named by the first argument, returning its scope; see the
+/// [module docs](self) for how names are resolved.
+pub fn qs_import(args: Args) -> quest_core::Result<Object> {
+	let name = args.try_arg(0)?.call_downcast::<Text>()?.to_string();
+	let (file, package) = resolve(&name)?;
+	let key = file.canonicalize().unwrap_or_else(|_| file.clone());
+
+	let mut modules = lock_modules();
+
+	// wait for any other thread that's importing the same module.
+	loop {
+		match modules.get(&key) {
+			Some(Module::Loaded(module)) => return Ok(module.clone()),
+			Some(Module::Loading(thread)) if *thread == thread::current().id() =>
+				return Err(quest_core::Error::Messaged(
+					format!("module {:?} is imported while it's being imported", name))),
+			Some(Module::Loading(_)) => modules = LOADED.wait(modules).unwrap_or_else(|err| err.into_inner()),
+			None => break
+		}
+	}
+
+	modules.insert(key.clone(), Module::Loading(thread::current().id()));
+	drop(modules);
+
+	let result = load(&name, &file, package);
+	let mut modules = lock_modules();
+
+	match &result {
+		Ok(module) => modules.insert(key, Module::Loaded(module.clone())),
+		Err(_) => modules.remove(&key)
+	};
+
+	LOADED.notify_all();
+	result
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn fixture(dir: &str) -> PathBuf {
+		let root = std::env::temp_dir().join(format!("quest-import-{}-{}", dir, std::process::id()));
+		let _ = std::fs::remove_dir_all(&root);
+
+		for (path, code) in [
+			("app/src/main.qs", "shapes = import(\"shapes\"); helpers = import(\"helpers\"); :0"),
+			("app/src/helpers.qs", "answer = 42;"),
+			("shapes/lib/shapes.qs", "square = n -> { import(\"math\")::times(n, n) };"),
+			("shapes/lib/math.qs", "times = (a, b) -> { a * b };"),
+			("app/src/cycle.qs", "import(\"cycle\");"),
+			("app/src/generated.qs", "values = Iter.generate({
+				yield(import(\"shapes\")::square(3));
+				yield(import(\"helpers\")::answer);
+			}).@list();"),
+		].iter() {
+			let path = root.join(path);
+			std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+			std::fs::write(path, code).unwrap();
+		}
+
+		root
+	}
+
+	// the packages are shared by every thread, so tests that set them can't run at the same time.
+	static SERIAL: Mutex<()> = Mutex::new(());
+
+	fn set_fixture_packages(root: &Path) {
+		set_packages(
+			Package { name: "app".into(), entry: root.join("app/src/main.qs"), source_dirs: vec![root.join("app/src")] },
+			vec![Package { name: "shapes".into(), entry: root.join("shapes/lib/shapes.qs"), source_dirs: vec![root.join("shapes/lib")] }]
+		);
+	}
+
+	fn number(obj: &Object) -> quest_core::types::Number {
+		*obj.downcast::<quest_core::types::Number>().unwrap()
+	}
+
+	#[test]
+	fn imports_from_packages() {
+		quest_core::init();
+		crate::init();
+
+		let _serial = SERIAL.lock().unwrap_or_else(|err| err.into_inner());
+		let root = fixture("packages");
+		set_fixture_packages(&root);
+
+		let main = qs_import(Args::new(vec![&"app".into()])).unwrap();
+		let shapes = main.get_attr_lit("shapes").unwrap();
+		let nine = shapes.call_attr_lit("::", &[&"square".into()]).unwrap()
+			.call_attr_lit("()", &[&3.into()]).unwrap();
+		assert_eq!(*nine.downcast::<quest_core::types::Number>().unwrap(), quest_core::types::Number::from(9));
+
+		let answer = main.get_attr_lit("helpers").unwrap().get_attr_lit("answer").unwrap();
+		assert_eq!(*answer.downcast::<quest_core::types::Number>().unwrap(), quest_core::types::Number::from(42));
+
+		// modules are only run once.
+		let again = qs_import(Args::new(vec![&"shapes".into()])).unwrap();
+		assert!(again.is_identical(&shapes));
+
+		let err = qs_import(Args::new(vec![&"cycle".into()])).unwrap_err();
+		assert!(err.to_string().contains("is imported while it's being imported"), "{}", err);
+
+		let err = qs_import(Args::new(vec![&"shapes/missing".into()])).unwrap_err();
+		assert_eq!(err.to_string(), "no module named \"shapes/missing\"");
+
+		std::fs::remove_dir_all(&root).unwrap();
+	}
+
+	#[test]
+	fn imports_from_generators() {
+		quest_core::init();
+		crate::init();
+
+		let _serial = SERIAL.lock().unwrap_or_else(|err| err.into_inner());
+		let root = fixture("generators");
+		set_fixture_packages(&root);
+
+		// generators run on their own thread, but still see the packages and the imported modules.
+		let shapes = qs_import(Args::new(vec![&"shapes".into()])).unwrap();
+		let generated = qs_import(Args::new(vec![&"generated".into()])).unwrap();
+		let values = generated.get_attr_lit("values").unwrap();
+		let values = values.downcast::<quest_core::types::List>().unwrap();
+
+		assert_eq!(values.as_ref().iter().map(number).collect::<Vec<_>>(),
+			[quest_core::types::Number::from(9), quest_core::types::Number::from(42)]);
+		assert!(qs_import(Args::new(vec![&"shapes".into()])).unwrap().is_identical(&shapes));
+
+		std::fs::remove_dir_all(&root).unwrap();
+	}
+}
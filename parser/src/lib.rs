@@ -1,4 +1,4 @@
-#![allow(clippy::module_inception, clippy::missing_const_for_fn)]
+#![allow(clippy::module_inception, clippy::missing_const_for_fn, clippy::tabs_in_doc_comments)]
 
 /// Setup the quest parser. This should be run before anything within `quest_parser` is used.
 pub fn init() {
@@ -15,6 +15,34 @@ pub fn init() {
 		Kernel::mapping().set_attr_lit("Block", Block::mapping().clone())
 			.expect("couldn't defined Block");
 
+		ast::Node::initialize().expect("couldn't initialize ast node");
+		ast::Ast::initialize().expect("couldn't initialize ast");
+
+		Kernel::mapping().set_attr_lit("Ast", ast::Ast::mapping().clone())
+			.expect("couldn't define Ast");
+
+		sandbox::Sandbox::initialize().expect("couldn't initialize sandbox");
+
+		Kernel::mapping().set_attr_lit("Sandbox", sandbox::Sandbox::mapping().clone())
+			.expect("couldn't define Sandbox");
+
+		testing::Test::initialize().expect("couldn't initialize test");
+
+		Kernel::mapping().set_attr_lit("Test", testing::Test::mapping().clone())
+			.expect("couldn't define Test");
+
+		reflect::init().expect("couldn't define `Reflect` block methods");
+
+		Kernel::mapping().set_value_lit("breakpoint",
+			RustFn::function("Kernel::breakpoint", debugger::qs_breakpoint)
+				.with_doc("Pauses here if the program is being debugged, such as with `quest debug`."))
+			.expect("couldn't define `breakpoint`");
+
+		Kernel::mapping().set_value_lit("import",
+			RustFn::function("Kernel::import", import::qs_import)
+				.with_doc("Runs the module named by the first argument, if it hasn't been already, and returns its scope."))
+			.expect("couldn't define `import`");
+
 		Text::mapping().set_value_lit("eval", RustFn::method("Text::eval", |this, args| {
 			this.try_downcast::<Text>().and_then(|this| {
 				if let Some(binding) = args.arg(0) {
@@ -25,7 +53,8 @@ pub fn init() {
 					.map_err(|err| Box::new(err) as Box<_>)?
 					.execute()
 			})
-		})).expect("couldn't define `eval`");
+		}).with_doc("Runs `this` as Quest code, in the binding given as the first argument (or the current one)."))
+			.expect("couldn't define `eval`");
 	});
 }
 
@@ -36,10 +65,62 @@ pub mod expression;
 pub mod token;
 pub mod stream;
 pub mod block;
+pub mod ast;
+mod reflect;
+pub mod sandbox;
+pub mod fmt;
+pub mod lint;
+pub mod debugger;
+pub mod coverage;
+pub mod testing;
+pub mod doc;
+pub mod import;
 
 // TODO: change public exports to more minimal.
 pub use block::Block;
-pub use error::{Error, ErrorType, Result};
+pub use error::{Error, ErrorType, Result, Diagnostic, Severity, Span};
 pub use token::Token;
 pub use expression::Expression;
 pub use stream::{Stream, Context, Contexted};
+
+#[cfg(test)]
+mod tests {
+	use quest_core::Object;
+	use quest_core::types::{ObjectType, RustFn, Text, List, Kernel, Reflect};
+
+	#[test]
+	fn every_builtin_is_documented() {
+		quest_core::init();
+		crate::init();
+
+		let classes: [(&str, &Object); 8] = [
+			("Block", crate::Block::mapping()),
+			("Ast", crate::ast::Ast::mapping()),
+			("Ast.Node", crate::ast::Node::mapping()),
+			("Sandbox", crate::sandbox::Sandbox::mapping()),
+			("Test", crate::testing::Test::mapping()),
+			("Reflect", Reflect::mapping()),
+			("Kernel", Kernel::mapping()),
+			("Text", Text::mapping()),
+		];
+
+		let mut missing = vec![];
+
+		for (name, class) in classes.iter() {
+			let keys = class.call_attr_lit("__keys__", &[]).unwrap();
+
+			for key in keys.downcast::<List>().unwrap().iter() {
+				let value = match class.get_attr(key) {
+					Ok(value) => value,
+					Err(_) => continue
+				};
+
+				if value.downcast::<RustFn>().is_some_and(|rustfn| rustfn.doc().is_none()) {
+					missing.push(format!("{}.{}", name, key.downcast::<Text>().unwrap().as_ref()));
+				}
+			}
+		}
+
+		assert!(missing.is_empty(), "builtins without docs:\n{}", missing.join("\n"));
+	}
+}
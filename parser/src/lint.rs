@@ -0,0 +1,516 @@
+//! Finding likely mistakes in Quest code without running it.
+//!
+//! Each `{ ... }` block is its own scope, as it gets its own stackframe when it's called, whereas
+//! `( ... )` and `[ ... ]` blocks run in the scope they're in. The following are reported:
+//! - [`Lint::UnusedAssignment`]: a variable that's assigned but never read in its scope, or any
+//!   scope within it. Scopes that are returned with a stack position (eg `:0`) aren't checked, as
+//!   their variables are used as attributes, and neither are variables starting with `_`, ones
+//!   that are only attributes (eg `'()'` and `@text`), or the ones at the top of a file, which
+//!   code that `eval`s it can use.
+//! - [`Lint::UndefinedVariable`]: a variable that's read, but not assigned in any enclosing scope
+//!   and isn't a builtin.
+//! - [`Lint::ExtraPositional`]: a positional argument (eg `_2`) in the body of an `->` that doesn't
+//!   declare that many parameters.
+//! - [`Lint::UnreachableCode`]: lines after an unconditional `return` in the same block.
+//! - [`Lint::StackPosTooDeep`]: a stack position (eg `:3`) that's more scopes up than there are.
+//! - [`Lint::MissingSemicolon`]: an assignment whose value assigns to a call's argument that isn't
+//!   in brackets (eg `a = 1 b = 2`), which is usually two statements without a `;` between them.
+//!
+//! A finding is ignored if its line has a `# lint:allow` comment, or a `# lint:allow(...)` comment
+//! that lists its lint by [name](Lint::name).
+
+use crate::{Block, Expression, Result};
+use crate::block::Line;
+use crate::expression::BoundOperator;
+use crate::expression::bound_operator::OperArgs;
+use crate::stream::{BufStream, Context, Stream};
+use crate::token::{Operator, ParenType, Primitive};
+use crate::token::primitive::{Variable, text::Segment};
+use quest_core::Object;
+use std::fmt::{self, Display, Formatter};
+use std::io::Cursor;
+use std::path::PathBuf;
+
+/// A kind of problem that the linter looks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Lint {
+	UnusedAssignment,
+	UndefinedVariable,
+	ExtraPositional,
+	UnreachableCode,
+	StackPosTooDeep,
+	MissingSemicolon
+}
+
+impl Lint {
+	/// The name that's used for this lint in messages and `lint:allow(...)` comments.
+	#[must_use]
+	pub const fn name(self) -> &'static str {
+		match self {
+			Self::UnusedAssignment => "unused-assignment",
+			Self::UndefinedVariable => "undefined-variable",
+			Self::ExtraPositional => "extra-positional",
+			Self::UnreachableCode => "unreachable-code",
+			Self::StackPosTooDeep => "stackpos-too-deep",
+			Self::MissingSemicolon => "missing-semicolon"
+		}
+	}
+}
+
+impl Display for Lint {
+	#[inline]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		f.write_str(self.name())
+	}
+}
+
+/// A problem that was found, and the line it's on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+	/// The line the problem is on.
+	pub context: Context,
+	/// What kind of problem it is.
+	pub lint: Lint,
+	/// A description of the problem.
+	pub message: String
+}
+
+impl Display for Finding {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		let file = self.context.file.as_ref()
+			.map(|file| file.display().to_string())
+			.unwrap_or_else(|| "<eval>".to_string());
+
+		write!(f, "{}:{}: warning[{}]: {}", file, self.context.lineno, self.lint, self.message)?;
+
+		if !self.context.line.trim().is_empty() {
+			write!(f, "\n {:<3}| {}", self.context.lineno, self.context.line.trim())?;
+		}
+
+		Ok(())
+	}
+}
+
+/// Lints `source`, which is from `file` if it's given.
+///
+/// An error is returned if `source` can't be parsed.
+pub fn lint(source: &str, file: Option<PathBuf>) -> Result<Vec<Finding>> {
+	let block =
+		match Expression::parse_stream(BufStream::new(Cursor::new(source), file.clone()).tokens())? {
+			Expression::Block(block) => block,
+			_ => unreachable!("`parse_stream` always returns a block")
+		};
+
+	quest_core::init();
+	crate::init();
+
+	let mut linter = Linter {
+		file,
+		lines: source.lines().collect(),
+		scopes: vec![],
+		findings: vec![],
+		builtins: Object::from(quest_core::types::Scope)
+	};
+
+	linter.lint_scope(&block, vec![], None);
+
+	let mut findings = linter.findings;
+	findings.retain(|finding| !is_allowed(source, finding));
+	findings.sort_by_key(|finding| finding.context.lineno);
+
+	Ok(findings)
+}
+
+/// Checks to see if `finding`'s line has a comment allowing it.
+fn is_allowed(source: &str, finding: &Finding) -> bool {
+	const ALLOW: &str = "lint:allow";
+
+	let line = source.lines().nth(finding.context.lineno.saturating_sub(1)).unwrap_or_default();
+
+	let comment =
+		match line.find('#') {
+			Some(idx) => line[idx + 1..].trim_start(),
+			None => return false
+		};
+
+	match comment.strip_prefix(ALLOW) {
+		Some(rest) if rest.starts_with('(') => rest[1..].split(')').next().unwrap_or_default()
+			.split(',')
+			.any(|name| name.trim() == finding.lint.name()),
+		Some(_) => true,
+		None => false
+	}
+}
+
+/// Variables that every stackframe has, but aren't attributes of a `Scope`.
+const FRAME_VARIABLES: [&str; 4] = ["__args__", "__callee__", "__stack__", "source_location"];
+
+/// Gets the `N` from a positional argument's name, `_N`.
+fn positional(name: &str) -> Option<usize> {
+	name.strip_prefix('_')
+		.filter(|digits| !digits.is_empty() && digits.chars().all(|chr| chr.is_ascii_digit()))
+		.and_then(|digits| digits.parse().ok())
+}
+
+#[derive(Debug)]
+struct Assignment {
+	name: String,
+	lineno: usize,
+	read: bool
+}
+
+#[derive(Debug, Default)]
+struct Scope {
+	assignments: Vec<Assignment>,
+	/// How many parameters the scope's `->` declares, if it's the body of one without a splat.
+	arity: Option<usize>,
+	/// Whether the scope itself is used via a stack position.
+	escapes: bool
+}
+
+struct Linter<'a> {
+	file: Option<PathBuf>,
+	lines: Vec<&'a str>,
+	/// The scopes the code being linted is in, innermost last.
+	scopes: Vec<Scope>,
+	findings: Vec<Finding>,
+	/// A scope with nothing assigned in it, for checking which variables are builtins.
+	builtins: Object
+}
+
+impl Linter<'_> {
+	fn report(&mut self, lineno: usize, lint: Lint, message: String) {
+		let context = Context {
+			file: self.file.clone(),
+			lineno,
+			column: 0,
+			line: self.lines.get(lineno.saturating_sub(1)).copied().unwrap_or_default().to_string()
+		};
+
+		self.findings.push(Finding { context, lint, message });
+	}
+
+	/// Lints `block` as a new scope, which has `params` already assigned.
+	fn lint_scope(&mut self, block: &Block, params: Vec<Assignment>, arity: Option<usize>) {
+		let mut scope = Scope { assignments: params, arity, escapes: false };
+		collect_block(block, &mut scope.assignments);
+
+		self.scopes.push(scope);
+		self.lint_block(block);
+		let scope = self.scopes.pop().expect("scope was just pushed");
+
+		if scope.escapes || self.scopes.is_empty() {
+			return;
+		}
+
+		for assignment in scope.assignments {
+			// names that can't be written as variables (eg `'()'`) and conversions (eg `@text`) are
+			// only used as attributes.
+			let is_variable = Variable::is_valid_name(&assignment.name) && !assignment.name.starts_with('@');
+
+			if !assignment.read && is_variable && !assignment.name.starts_with('_') {
+				self.report(assignment.lineno, Lint::UnusedAssignment,
+					format!("`{}` is assigned, but never read", assignment.name));
+			}
+		}
+	}
+
+	fn lint_block(&mut self, block: &Block) {
+		let mut returned = false;
+
+		for (idx, line) in block.lines.iter().enumerate() {
+			let lineno = block.linenos.get(idx).copied().unwrap_or(block.context.lineno);
+
+			if returned {
+				self.report(lineno, Lint::UnreachableCode, "this code is after a `return`".to_string());
+				returned = false;
+			}
+
+			match line {
+				Line::Single(expr) => {
+					self.lint_expr(expr, lineno);
+					returned = is_return(expr) && idx + 1 != block.lines.len();
+				},
+				Line::Multiple(exprs) => exprs.iter().for_each(|expr| self.lint_expr(expr, lineno))
+			}
+		}
+	}
+
+	fn lint_expr(&mut self, expr: &Expression, lineno: usize) {
+		match expr {
+			Expression::Block(block) if block.paren_type == ParenType::Curly =>
+				self.lint_scope(block, vec![], None),
+			Expression::Block(block) => self.lint_block(block),
+			Expression::FunctionCall(callee, args) => {
+				self.lint_expr(callee, lineno);
+				self.lint_block(args);
+			},
+			Expression::Operator(BoundOperator { oper: Operator::Arrow, this, args }) => {
+				match args.as_ref() {
+					OperArgs::Binary(Expression::Block(body)) if body.paren_type == ParenType::Curly => {
+						let mut params = vec![];
+						let arity = collect_params(this, &mut params, lineno).then_some(params.len());
+						self.lint_scope(body, params, arity);
+					},
+					OperArgs::Binary(rhs) => self.lint_expr(rhs, lineno),
+					_ => {}
+				}
+			},
+			Expression::Operator(BoundOperator { oper, this, args }) => {
+				if is_assignment(*oper) && is_run_together(args) {
+					self.report(lineno, Lint::MissingSemicolon,
+						"this assigns to an argument of a call; did you forget a `;` before it?".to_string());
+				}
+
+				// the names that are assigned to aren't read.
+				if !matches!(oper, Operator::Assign | Operator::Colon) {
+					self.lint_expr(this, lineno);
+				}
+
+				match args.as_ref() {
+					OperArgs::Unary => {},
+					OperArgs::Binary(rhs) => self.lint_expr(rhs, lineno),
+					OperArgs::Ternary(mid, rhs) => {
+						self.lint_expr(mid, lineno);
+						self.lint_expr(rhs, lineno);
+					}
+				}
+			},
+			Expression::Primitive(Primitive::Variable(var)) => self.read(&var.to_string(), lineno),
+			Expression::Primitive(Primitive::StackPos(pos)) => self.stackpos((*pos).into(), lineno),
+			Expression::Primitive(Primitive::Interpolated(interpolated)) =>
+				for segment in interpolated.segments() {
+					if let Segment::Code(block) = segment {
+						self.lint_block(block);
+					}
+				},
+			Expression::Primitive(_) => {}
+		}
+	}
+
+	fn read(&mut self, name: &str, lineno: usize) {
+		for scope in self.scopes.iter_mut().rev() {
+			if let Some(assignment) = scope.assignments.iter_mut().find(|assignment| assignment.name == name) {
+				assignment.read = true;
+				return;
+			}
+		}
+
+		if let Some(pos) = positional(name) {
+			match self.scopes.last().and_then(|scope| scope.arity) {
+				Some(arity) if pos >= arity => self.report(lineno, Lint::ExtraPositional,
+					format!("`{}` is used, but only {} parameter(s) are declared", name, arity)),
+				_ => {}
+			}
+
+			return;
+		}
+
+		if FRAME_VARIABLES.contains(&name) || self.builtins.has_attr_lit(name).unwrap_or(false) {
+			return;
+		}
+
+		self.report(lineno, Lint::UndefinedVariable, format!("`{}` is never assigned", name));
+	}
+
+	fn stackpos(&mut self, pos: isize, lineno: usize) {
+		if pos < 0 {
+			return;
+		}
+
+		match (self.scopes.len() - 1).checked_sub(pos as usize) {
+			Some(idx) => self.scopes[idx].escapes = true,
+			None => self.report(lineno, Lint::StackPosTooDeep,
+				format!("`:{}` is used, but it's only within {} scope(s)", pos, self.scopes.len() - 1))
+		}
+	}
+}
+
+/// Checks to see if `expr` always returns, ie is a call to `return`.
+fn is_return(expr: &Expression) -> bool {
+	let callee =
+		match expr {
+			Expression::FunctionCall(callee, _) => callee,
+			Expression::Operator(BoundOperator { oper: Operator::Call, this, .. }) => this,
+			_ => return false
+		};
+
+	match callee.as_ref() {
+		Expression::Primitive(Primitive::Variable(var)) => var.to_string() == "return",
+		Expression::Operator(BoundOperator { oper: Operator::Dot, args, .. }) =>
+			matches!(args.as_ref(), OperArgs::Binary(Expression::Primitive(Primitive::Text(text)))
+				if text.as_ref() == "return"),
+		_ => false
+	}
+}
+
+fn is_assignment(oper: Operator) -> bool {
+	matches!(oper, Operator::Assign | Operator::DotAssign | Operator::IndexAssign)
+}
+
+/// Checks to see if the value in an assignment's `args` is itself an assignment to an argument
+/// that isn't in brackets, such as the `1 b = 2` in `a = 1 b = 2`.
+fn is_run_together(args: &OperArgs) -> bool {
+	let value =
+		match args {
+			OperArgs::Binary(value) | OperArgs::Ternary(_, value) => value,
+			OperArgs::Unary => return false
+		};
+
+	match value {
+		Expression::Operator(BoundOperator { oper: Operator::IndexAssign, args, .. }) =>
+			matches!(args.as_ref(), OperArgs::Ternary(index, _) if !matches!(index, Expression::Block(_))),
+		_ => false
+	}
+}
+
+/// Adds the parameters of an `->` in `params` to `assignments`, returning whether there's a fixed
+/// number of them (ie there's no splat).
+// `all` would stop at the first splat, but every parameter still needs to be collected.
+#[allow(clippy::unnecessary_fold)]
+fn collect_params(params: &Expression, assignments: &mut Vec<Assignment>, lineno: usize) -> bool {
+	match params {
+		Expression::Primitive(Primitive::Text(name)) => {
+			assignments.push(Assignment { name: name.to_string(), lineno, read: true });
+			true
+		},
+		Expression::Block(block) => block.lines.iter()
+			.flat_map(|line| match line {
+				Line::Single(expr) => std::slice::from_ref(expr),
+				Line::Multiple(exprs) => exprs.as_slice()
+			})
+			.fold(true, |fixed, param| collect_params(param, assignments, lineno) && fixed),
+		Expression::Operator(BoundOperator { oper: Operator::Splat, this, .. })
+			| Expression::Operator(BoundOperator { oper: Operator::SplatSplat, this, .. }) => {
+			collect_params(this, assignments, lineno);
+			false
+		},
+		Expression::Operator(BoundOperator { this, .. }) => collect_params(this, assignments, lineno),
+		_ => true
+	}
+}
+
+/// Adds the variables assigned in `block` to `assignments`, without looking in nested scopes.
+fn collect_block(block: &Block, assignments: &mut Vec<Assignment>) {
+	for (idx, line) in block.lines.iter().enumerate() {
+		let lineno = block.linenos.get(idx).copied().unwrap_or(block.context.lineno);
+
+		match line {
+			Line::Single(expr) => collect_expr(expr, assignments, lineno),
+			Line::Multiple(exprs) => exprs.iter().for_each(|expr| collect_expr(expr, assignments, lineno))
+		}
+	}
+}
+
+fn collect_expr(expr: &Expression, assignments: &mut Vec<Assignment>, lineno: usize) {
+	match expr {
+		Expression::Block(block) if block.paren_type == ParenType::Curly => {},
+		Expression::Block(block) => collect_block(block, assignments),
+		Expression::FunctionCall(callee, args) => {
+			collect_expr(callee, assignments, lineno);
+			collect_block(args, assignments);
+		},
+		Expression::Operator(BoundOperator { oper: Operator::Assign, this, args }) => {
+			// destructuring assignments are declared the same way as parameters are.
+			let mut targets = vec![];
+			collect_params(this, &mut targets, lineno);
+
+			for mut target in targets {
+				if !assignments.iter().any(|assignment| assignment.name == target.name) {
+					target.read = false;
+					assignments.push(target);
+				}
+			}
+
+			if let OperArgs::Binary(rhs) = args.as_ref() {
+				collect_expr(rhs, assignments, lineno);
+			}
+		},
+		Expression::Operator(BoundOperator { oper: Operator::Arrow, .. }) => {},
+		Expression::Operator(BoundOperator { this, args, .. }) => {
+			collect_expr(this, assignments, lineno);
+
+			match args.as_ref() {
+				OperArgs::Unary => {},
+				OperArgs::Binary(rhs) => collect_expr(rhs, assignments, lineno),
+				OperArgs::Ternary(mid, rhs) => {
+					collect_expr(mid, assignments, lineno);
+					collect_expr(rhs, assignments, lineno);
+				}
+			}
+		},
+		Expression::Primitive(_) => {}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn findings(code: &str) -> Vec<(usize, Lint)> {
+		lint(code, None).unwrap()
+			.into_iter()
+			.map(|finding| (finding.context.lineno, finding.lint))
+			.collect()
+	}
+
+	#[test]
+	fn variables() {
+		let code = concat!(
+			"add = (a, b) -> {\n",
+			"\tunused = 3;\n",
+			"\tsum = a + b;\n",
+			"\tsum + typo\n",
+			"};\n",
+			"print(add(1, 2));\n",
+			"[x, y] = [1, 2];\n",
+			"print(x);");
+
+		assert_eq!(findings(code), vec![(2, Lint::UnusedAssignment), (4, Lint::UndefinedVariable)]);
+	}
+
+	#[test]
+	fn closures_read_enclosing_scopes() {
+		let code = concat!(
+			"counter = {\n",
+			"\tcount = 0;\n",
+			"\t{ count += 1; _0 }\n",
+			"}();\n",
+			"print(counter(1));");
+
+		assert_eq!(findings(code), vec![]);
+	}
+
+	#[test]
+	fn positionals_and_stackpos() {
+		let code = concat!(
+			"f = x -> { _0 + _1 };\n",
+			"g = { _5 + :1 };\n",
+			"print(f(1), g(1), :1);\n",
+			"Cls = { name = 'Cls'; :0 }();");
+
+		assert_eq!(findings(code), vec![(1, Lint::ExtraPositional), (3, Lint::StackPosTooDeep)]);
+	}
+
+	#[test]
+	fn unreachable_code() {
+		let code = "f = {\n\treturn(:1, 3);\n\tprint(4);\n\tprint(5)\n};\nf();";
+		assert_eq!(findings(code), vec![(3, Lint::UnreachableCode)]);
+	}
+
+	#[test]
+	fn missing_semicolons() {
+		let code = "b = 0;\na = 1 b = 2;\nx = {:0}();\nx.y = a 'w' = 2;\nx a = 3;\nx[a] = 4;";
+		assert_eq!(findings(code), vec![(2, Lint::MissingSemicolon), (4, Lint::MissingSemicolon)]);
+	}
+
+	#[test]
+	fn suppressions() {
+		let code = concat!(
+			"print(a); # lint:allow\n",
+			"print(b); # lint:allow(undefined-variable)\n",
+			"print(c); # lint:allow(unused-assignment)\n");
+
+		assert_eq!(findings(code), vec![(3, Lint::UndefinedVariable)]);
+	}
+}
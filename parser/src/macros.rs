@@ -4,6 +4,10 @@ macro_rules! parse_error {
 	};
 
 	($stream:expr, $type:ident $($tt:tt)*) => {
-		parse_error!(context=$crate::stream::Contexted::context($stream).clone(), $type$($tt)*)
+		$crate::Error::spanning(
+			$crate::Span::new(
+				$crate::stream::Contexted::start_context($stream).clone(),
+				$crate::stream::Contexted::context($stream).clone()),
+			$crate::ErrorType::$type$($tt)*)
 	};
-}
\ No newline at end of file
+}
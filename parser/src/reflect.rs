@@ -0,0 +1,137 @@
+//! The parts of `Reflect` that need to know about [`Block`]s.
+
+use crate::Block;
+use crate::block::Line;
+use crate::token::ParenType;
+use crate::expression::Expression;
+use crate::stream::Stream;
+use quest_core::{Object, Args, Binding};
+use quest_core::types::{ObjectType, RustFn, Reflect, Text};
+use quest_core::error::TypeError;
+
+/// Gets the block the first argument was defined with: either itself, or the block of a function
+/// made with `->`.
+fn block_of(args: &Args) -> quest_core::Result<Object> {
+	let func = args.try_arg(0)?;
+
+	if func.is_a::<Block>() {
+		return Ok(func.clone());
+	}
+
+	match func.get_attr_lit("block") {
+		Ok(block) if block.is_a::<Block>() => Ok(block),
+		_ => Err(TypeError::WrongType { expected: std::any::type_name::<Block>(), got: func.typename() }.into())
+	}
+}
+
+/// Gets the source code of a block or `->` function.
+///
+/// For `->` functions, this is the source of their body. The source can be parsed again by
+/// [`parse`].
+fn source(_: &Object, args: Args) -> quest_core::Result<Object> {
+	Ok(block_of(&args)?.try_downcast::<Block>()?.to_string().into())
+}
+
+/// Gets the file a block or `->` function was defined in, or `null` if it wasn't in a file.
+fn file(_: &Object, args: Args) -> quest_core::Result<Object> {
+	let block = block_of(&args)?;
+	let block = block.try_downcast::<Block>()?;
+
+	Ok(block.context.file.as_ref()
+		.map(|file| Object::from(file.display().to_string()))
+		.unwrap_or_default())
+}
+
+/// Gets the line number a block or `->` function was defined on.
+fn line(_: &Object, args: Args) -> quest_core::Result<Object> {
+	Ok(block_of(&args)?.try_downcast::<Block>()?.context.lineno.into())
+}
+
+/// Parses the first argument as Quest code, returning a block that runs it.
+///
+/// If the code is a single block (such as the output of [`source`]), that block's returned instead.
+fn parse(_: &Object, args: Args) -> quest_core::Result<Object> {
+	let code = args.try_arg(0)?.call_downcast::<Text>()?.to_string();
+
+	let expr = Expression::parse_stream(crate::stream::BufStream::from(code).tokens())
+		.map_err(|err| Box::new(err) as Box<_>)?;
+
+	let block =
+		match expr {
+			Expression::Block(mut block) => {
+				match block.lines.as_slice() {
+					[Line::Single(Expression::Block(inner))] if inner.paren_type == ParenType::Curly =>
+						block = inner.clone(),
+					_ => block.paren_type = ParenType::Curly
+				}
+				block
+			},
+			other => unreachable!("parsed code isn't wrapped in a block: {:?}", other)
+		};
+
+	let block = Object::from(block);
+	block.add_parent(Binding::instance().as_ref().clone())?;
+	Ok(block)
+}
+
+/// Adds the block-related methods to `Reflect`.
+pub(crate) fn init() -> quest_core::Result<()> {
+	let reflect = Reflect::mapping();
+
+	reflect.set_value_lit("source", RustFn::method("Reflect::source", source)
+		.with_doc("Gets the source code of a block or `->` function."))?;
+	reflect.set_value_lit("file", RustFn::method("Reflect::file", file)
+		.with_doc("Gets the file a block or `->` function was defined in, or `null` if it wasn't in a file."))?;
+	reflect.set_value_lit("line", RustFn::method("Reflect::line", line)
+		.with_doc("Gets the line number a block or `->` function was defined on."))?;
+	reflect.set_value_lit("parse", RustFn::method("Reflect::parse", parse)
+		.with_doc("Parses the first argument as Quest code, returning a block that runs it."))?;
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::Expression;
+	use crate::expression::Executable;
+	use crate::stream::{BufStream, Stream};
+	use quest_core::{Object, Args, Binding};
+	use quest_core::types::{Number, Text};
+
+	fn eval(code: &str) -> quest_core::Result<Object> {
+		quest_core::init();
+		crate::init();
+
+		Binding::new_stackframe(None, Args::default(), |_| {
+			Expression::parse_stream(BufStream::from(code.to_string()).tokens())
+				.map_err(|err| Box::new(err) as Box<_>)?
+				.execute()
+		})
+	}
+
+	fn text(code: &str) -> String {
+		eval(code).unwrap().downcast::<Text>().unwrap().to_string()
+	}
+
+	#[test]
+	fn source() {
+		assert_eq!(text("Reflect.source({ _0 + 1 })"), "{ _0 + 1 }");
+		assert_eq!(text("Reflect.source((a, b) -> { a * b })"), "{ a * b }");
+		assert!(eval("Reflect.source(1)").is_err());
+	}
+
+	#[test]
+	fn file_and_line() {
+		assert!(eval("Reflect.file({ 1 })").unwrap().is_a::<quest_core::types::Null>());
+		assert_eq!(*eval("\n\nReflect.line({ 1 })").unwrap().downcast::<Number>().unwrap(), Number::from(3));
+	}
+
+	#[test]
+	fn parse() {
+		assert_eq!(*eval("Reflect.parse('_0 * 2')(21)").unwrap().downcast::<Number>().unwrap(), Number::from(42));
+
+		// the source of a block parses back into an equivalent block.
+		assert_eq!(text("Reflect.source(Reflect.parse(Reflect.source({ [1, 'a b'] })))"), "{ [ 1, 'a b' ] }");
+		assert!(eval("Reflect.parse('(')").is_err());
+	}
+}
@@ -0,0 +1,213 @@
+//! Running untrusted code; see [`quest_core::sandbox`] for how it's isolated.
+
+use crate::expression::{Expression, Executable};
+use crate::stream::{BufStream, Stream};
+use quest_core::{impl_object_type, Object, Args};
+use quest_core::types::{List, Null, Number, Text};
+use quest_core::error::ValueError;
+use quest_core::sandbox::{self, Budget};
+use std::convert::TryFrom;
+use std::time::Duration;
+
+/// How many steps sandboxed code may take if no budget is given.
+pub const DEFAULT_STEPS: usize = 1_000_000;
+
+/// How long sandboxed code may run for if no budget is given.
+pub const DEFAULT_TIME: Duration = Duration::from_secs(1);
+
+/// A restricted environment to run code in.
+///
+/// # Quest Examples
+/// ```quest
+/// sandbox = Sandbox.new(["disp"], 1000);
+///
+/// assert(sandbox.eval("1 + 2") == 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Sandbox {
+	kernel: Object,
+	budget: Budget
+}
+
+impl Sandbox {
+	/// Creates a sandbox whose kernel has the default attributes, plus the ones in `allow`.
+	pub fn new(allow: &[Object], budget: Budget) -> quest_core::Result<Self> {
+		Ok(Self { kernel: sandbox::kernel_with(allow)?, budget })
+	}
+
+	/// Runs `code` in a new stackframe in this sandbox.
+	pub fn eval(&self, code: String) -> quest_core::Result<Object> {
+		let expr = Expression::parse_stream(BufStream::from(code).tokens())
+			.map_err(|err| Box::new(err) as Box<_>)?;
+
+		sandbox::run(self.kernel.clone(), self.budget, |_| expr.execute())
+	}
+}
+
+/// Converts a step budget given to `Sandbox.new`.
+fn steps_budget(steps: &Object) -> quest_core::Result<usize> {
+	Ok(usize::try_from(*steps.call_downcast::<Number>()?)?)
+}
+
+/// Converts a time budget, in seconds, given to `Sandbox.new`.
+fn time_budget(secs: &Object) -> quest_core::Result<Duration> {
+	let secs = f64::from(*secs.call_downcast::<Number>()?);
+
+	if secs.is_sign_negative() || !secs.is_finite() {
+		Err(ValueError::Messaged(format!("invalid time budget: {}", secs)).into())
+	} else {
+		Ok(Duration::from_secs_f64(secs))
+	}
+}
+
+impl Sandbox {
+	/// Creates a new sandbox.
+	///
+	/// The first argument is a list of the names of `Kernel` attributes to allow in addition to
+	/// the defaults. The second and third are the step and time (in seconds) budgets for each
+	/// call to `eval`; they default to a million steps and one second, and `null` means unlimited.
+	pub fn qs_new(_: &Object, args: Args) -> quest_core::Result<Object> {
+		let allow =
+			match args.arg(0) {
+				Some(allow) => allow.call_downcast::<List>()?.as_ref().to_vec(),
+				None => vec![]
+			};
+
+		let steps =
+			match args.arg(1) {
+				Some(steps) if steps.is_a::<Null>() => None,
+				Some(steps) => Some(steps_budget(steps)?),
+				None => Some(DEFAULT_STEPS)
+			};
+
+		let time =
+			match args.arg(2) {
+				Some(secs) if secs.is_a::<Null>() => None,
+				Some(secs) => Some(time_budget(secs)?),
+				None => Some(DEFAULT_TIME)
+			};
+
+		Ok(Self::new(&allow, Budget { steps, time })?.into())
+	}
+
+	/// Runs the first argument as code in this sandbox, returning its result.
+	///
+	/// Each call runs in a new stackframe with a fresh budget.
+	pub fn qs_eval(this: &Object, args: Args) -> quest_core::Result<Object> {
+		let code = args.try_arg(0)?.call_downcast::<Text>()?.to_string();
+		let sandbox = this.try_downcast::<Self>()?.clone();
+
+		sandbox.eval(code)
+	}
+
+	/// Gets this sandbox's kernel, which can be given extra attributes.
+	pub fn qs_kernel(this: &Object, _: Args) -> quest_core::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.kernel.clone())
+	}
+}
+
+impl_object_type!{
+for Sandbox [(parents quest_core::types::Basic)]:
+	/// Creates a new sandbox, allowing the `Kernel` attributes named by the first argument and
+	/// with the step and time budgets given as the second and third arguments.
+	"new" => method Sandbox::qs_new,
+	/// Runs the first argument as code in this sandbox, returning its result.
+	"eval" => method Sandbox::qs_eval,
+	/// Gets this sandbox's kernel, which can be given extra attributes.
+	"kernel" => method Sandbox::qs_kernel,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use quest_core::Error;
+	use quest_core::error::{BudgetError, KeyError};
+
+	fn sandbox(allow: &[&str], budget: Budget) -> Sandbox {
+		quest_core::init();
+		crate::init();
+
+		let allow = allow.iter().map(|&name| Object::from(name)).collect::<Vec<_>>();
+		Sandbox::new(&allow, budget).unwrap()
+	}
+
+	#[test]
+	fn evaluates() {
+		let sandbox = sandbox(&[], Budget::default());
+		let result = sandbox.eval("x = 3; x * 2".to_string()).unwrap();
+
+		assert_eq!(*result.downcast::<Number>().unwrap(), Number::from(6));
+	}
+
+	#[test]
+	fn kernel_is_restricted() {
+		let sandbox = sandbox(&[], Budget::default());
+
+		for code in &["system('ls')", "Io", "Kernel", "__callee__"] {
+			match sandbox.eval(code.to_string()) {
+				Err(Error::KeyError(KeyError::DoesntExist { .. })) => {},
+				other => panic!("{:?} wasn't restricted: {:?}", code, other)
+			}
+		}
+	}
+
+	#[test]
+	fn allows_extra_attributes() {
+		let sandbox = sandbox(&["Io"], Budget::default());
+
+		assert!(sandbox.eval("Io".to_string()).is_ok());
+	}
+
+	#[test]
+	fn step_budget() {
+		let sandbox = sandbox(&[], Budget { steps: Some(100), time: None });
+
+		match sandbox.eval("loop({})".to_string()) {
+			Err(Error::BudgetError(BudgetError::Steps(100))) => {},
+			other => panic!("loop wasn't stopped: {:?}", other)
+		}
+	}
+
+	#[test]
+	fn time_budget() {
+		let time = Duration::from_millis(10);
+		let sandbox = sandbox(&[], Budget { steps: None, time: Some(time) });
+
+		match sandbox.eval("while({ true }, { 1 })".to_string()) {
+			Err(Error::BudgetError(BudgetError::Time(t))) if t == time => {},
+			other => panic!("loop wasn't stopped: {:?}", other)
+		}
+	}
+
+	#[test]
+	fn generators_are_sandboxed() {
+		let sandbox = sandbox(&[], Budget::default());
+		let path = std::env::temp_dir().join(format!("quest-sandbox-{}", std::process::id()));
+		let code = format!("Iter.generate({{ yield(system('touch', {:?})) }})()", path.display().to_string());
+
+		match sandbox.eval(code) {
+			Err(Error::KeyError(KeyError::DoesntExist { .. })) => {},
+			other => panic!("generator wasn't restricted: {:?}", other)
+		}
+
+		assert!(!path.exists());
+	}
+
+	#[test]
+	fn generators_share_the_budget() {
+		let steps = sandbox(&[], Budget { steps: Some(100), time: None });
+
+		match steps.eval("Iter.generate({ loop({}) })()".to_string()) {
+			Err(Error::BudgetError(BudgetError::Steps(100))) => {},
+			other => panic!("loop wasn't stopped: {:?}", other)
+		}
+
+		let time = Duration::from_millis(10);
+		let sandbox = sandbox(&[], Budget { steps: None, time: Some(time) });
+
+		match sandbox.eval("Iter.generate({ while({ true }, { 1 }) })()".to_string()) {
+			Err(Error::BudgetError(BudgetError::Time(t))) if t == time => {},
+			other => panic!("loop wasn't stopped: {:?}", other)
+		}
+	}
+}
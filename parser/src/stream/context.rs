@@ -6,6 +6,21 @@ use std::path::PathBuf;
 pub trait Contexted {
 	/// Get the current context.
 	fn context(&self) -> &Context;
+
+	/// Get the context from just before the last thing that was read, such as a token.
+	///
+	/// This is used to find where the thing an error's about starts. By default, it's the same as
+	/// the current context.
+	fn start_context(&self) -> &Context {
+		self.context()
+	}
+
+	/// Get the `##` doc comments directly before the last thing that was read, if there were any.
+	///
+	/// By default, there aren't any.
+	fn doc_comment(&self) -> Option<&str> {
+		None
+	}
 }
 
 /// A type representing the current state of a [`Stream`](trait.Stream.html).
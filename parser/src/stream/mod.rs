@@ -39,7 +39,7 @@ pub trait Stream : Seek + Contexted + Iterator<Item=Result<char>> {
 
 	/// Converts this stream into an iterator over tokens.
 	fn tokens(self) -> TokenIter<Self> where Self: Sized {
-		TokenIter(self)
+		TokenIter(self, Context::default(), None)
 	}
 }
 
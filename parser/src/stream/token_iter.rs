@@ -10,13 +10,22 @@ use crate::{Result, Stream, Token, Context, Contexted};
 /// [`Stream`]: trait.Stream.html
 /// [`Stream::tokens()`]: trait.Stream.html#method.tokens
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
-pub struct TokenIter<S: Stream>(pub(super) S);
+pub struct TokenIter<S: Stream>(pub(super) S, pub(super) Context, pub(super) Option<String>);
 
 impl<S: Stream> Iterator for TokenIter<S> {
 	type Item = Result<Token>;
 
 	/// Returns a token parsed by [`Token`](../token/enum.Token.html)
 	fn next(&mut self) -> Option<Result<Token>> {
+		self.2 = None;
+
+		match Token::skip_ignored_with_docs(&mut self.0, &mut self.2) {
+			Ok(true) => {},
+			Ok(false) => return None,
+			Err(err) => return Some(Err(err))
+		}
+
+		self.1.clone_from(self.0.context());
 		Token::try_parse(&mut self.0).transpose()
 	}
 }
@@ -26,6 +35,16 @@ impl<S: Stream> Contexted for TokenIter<S> {
 	fn context(&self) -> &Context {
 		self.0.context()
 	}
+
+	/// Returns the context from just before the last token.
+	fn start_context(&self) -> &Context {
+		&self.1
+	}
+
+	/// Returns the `##` doc comments just before the last token.
+	fn doc_comment(&self) -> Option<&str> {
+		self.2.as_deref()
+	}
 }
 
 #[cfg(test)]
@@ -0,0 +1,438 @@
+//! Writing and running tests in Quest.
+//!
+//! Tests are written with the `Test` object: `Test.describe(name, block)` groups the tests in
+//! `block` under `name`, and `Test.it(name, block)` is a test that passes if `block` runs without
+//! an error. As well as `Kernel.assert`, tests can use `Test.assert_eq`, which shows how the values
+//! differ, and `Test.assert_raises`.
+//!
+//! When a file is [run as tests](run_file), each test's [`Outcome`] is recorded, and tests that fail
+//! don't stop the others from running. Otherwise, `Test.it` just runs its block, so test files can
+//! be run like any other file.
+//!
+//! Timeouts are cooperative: they're checked as each expression is run (and by `sleep`), so a test
+//! that's stuck inside a builtin function, such as one waiting on `Io` or `system`, isn't stopped
+//! until that function returns.
+//!
+//! # Quest Examples
+//! ```quest
+//! Test.describe("Number", {
+//! 	Test.it("adds", {
+//! 		Test.assert_eq(1 + 2, 3);
+//! 	});
+//!
+//! 	Test.it("can't divide by a list", {
+//! 		Test.assert_raises({ 1 / [] });
+//! 	}, 0.5); # this test times out after half a second
+//! });
+//! ```
+
+use crate::expression::{Executable, Expression};
+use crate::stream::{BufStream, Contexted, Stream};
+use quest_core::{impl_object_type, Args, Binding, Error, Object};
+use quest_core::error::BudgetError;
+use quest_core::sandbox::{self, Budget};
+use quest_core::types::{Boolean, Number, Scope, Text};
+use std::cell::RefCell;
+use std::convert::TryFrom;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// How long a test may run for if no timeout is given.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Whether a test passed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+	/// The test ran without an error.
+	Passed,
+	/// The test failed with this error message.
+	Failed(String),
+	/// The test ran for longer than its timeout.
+	TimedOut(Duration)
+}
+
+/// The result of running a test.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+	/// The test's name, after the names of the `describe`s it's in.
+	pub name: String,
+	/// The file the test is in.
+	pub file: Option<PathBuf>,
+	/// Whether it passed.
+	pub status: Status,
+	/// How long it took to run.
+	pub time: Duration
+}
+
+impl Outcome {
+	/// Checks to see if the test passed.
+	#[must_use]
+	pub fn passed(&self) -> bool {
+		self.status == Status::Passed
+	}
+
+	/// Why the test failed, if it did.
+	#[must_use]
+	pub fn message(&self) -> Option<String> {
+		match &self.status {
+			Status::Passed => None,
+			Status::Failed(message) => Some(message.clone()),
+			Status::TimedOut(timeout) => Some(format!("timed out after {:?}", timeout))
+		}
+	}
+}
+
+/// How to run tests.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Options {
+	/// Only run tests whose names contain this.
+	pub filter: Option<String>,
+	/// How long each test may run for, unless it gives its own timeout. `None` means
+	/// [`DEFAULT_TIMEOUT`].
+	pub timeout: Option<Duration>
+}
+
+/// The tests being run.
+#[derive(Debug)]
+struct Runner {
+	options: Options,
+	file: Option<PathBuf>,
+	/// The names of the `describe`s we're in, outermost first.
+	groups: Vec<String>,
+	outcomes: Vec<Outcome>
+}
+
+thread_local!(
+	static RUNNER: RefCell<Option<Runner>> = const { RefCell::new(None) };
+);
+
+fn with_runner<T>(func: impl FnOnce(&mut Runner) -> T) -> Option<T> {
+	RUNNER.with(|runner| runner.borrow_mut().as_mut().map(func))
+}
+
+/// Runs the tests in the file `path`.
+///
+/// If the file can't be run, or fails outside of a test, that's recorded as a failed test named
+/// after the file.
+pub fn run_file(path: &Path, options: &Options) -> Vec<Outcome> {
+	match BufStream::try_from(path) {
+		Ok(stream) => run_stream(stream, options),
+		Err(err) => vec![Outcome {
+			name: path.display().to_string(),
+			file: Some(path.to_path_buf()),
+			status: Status::Failed(err.to_string()),
+			time: Duration::default()
+		}]
+	}
+}
+
+/// Runs the tests in `stream`; see [`run_file`].
+pub fn run_stream<B: BufRead>(stream: BufStream<B>, options: &Options) -> Vec<Outcome> {
+	let file = stream.context().file.clone();
+	let runner = Runner { options: options.clone(), file: file.clone(), groups: vec![], outcomes: vec![] };
+	let previous = RUNNER.with(|slot| slot.replace(Some(runner)));
+	let start = Instant::now();
+
+	let main = Object::new(Scope);
+	let args = file.iter().map(|file| Object::from(file.display().to_string())).collect::<Vec<_>>();
+
+	let result = main.set_attr_lit("name", Object::from("main")).and_then(|_| {
+		Binding::new_stackframe(Some(main), args.iter().collect(), move |_| {
+			Expression::parse_stream(stream.tokens())
+				.map_err(|err| Box::new(err) as Box<_>)?
+				.execute()
+		})
+	});
+
+	let mut outcomes = RUNNER.with(|slot| slot.replace(previous))
+		.map(|runner| runner.outcomes)
+		.unwrap_or_default();
+
+	if let Err(err) = result {
+		outcomes.push(Outcome {
+			name: file.as_ref().map_or_else(|| "<stream>".to_string(), |file| file.display().to_string()),
+			file,
+			status: Status::Failed(err.to_string()),
+			time: start.elapsed()
+		});
+	}
+
+	outcomes
+}
+
+/// Describes how `actual` differs from `expected`.
+///
+/// Single lines are shown one above the other, with a `^` under the first character that differs;
+/// otherwise, the lines that only `expected` has are prefixed with `-` and those that only `actual`
+/// has with `+`.
+#[must_use]
+pub fn diff(expected: &str, actual: &str) -> String {
+	let expected_lines = expected.lines().collect::<Vec<_>>();
+	let actual_lines = actual.lines().collect::<Vec<_>>();
+
+	if expected_lines.len() <= 1 && actual_lines.len() <= 1 {
+		let column = expected.chars().zip(actual.chars())
+			.take_while(|(lhs, rhs)| lhs == rhs)
+			.count();
+
+		return format!("expected: {}\n  actual: {}\n          {}^", expected, actual, " ".repeat(column));
+	}
+
+	// the length of the longest common subsequence of `expected_lines[i..]` and `actual_lines[j..]`.
+	let mut common = vec![vec![0usize; actual_lines.len() + 1]; expected_lines.len() + 1];
+
+	for i in (0..expected_lines.len()).rev() {
+		for j in (0..actual_lines.len()).rev() {
+			common[i][j] =
+				if expected_lines[i] == actual_lines[j] {
+					common[i + 1][j + 1] + 1
+				} else {
+					common[i + 1][j].max(common[i][j + 1])
+				};
+		}
+	}
+
+	let mut lines = vec!["- expected".to_string(), "+ actual".to_string()];
+	let (mut i, mut j) = (0, 0);
+
+	while i < expected_lines.len() || j < actual_lines.len() {
+		if i < expected_lines.len() && j < actual_lines.len() && expected_lines[i] == actual_lines[j] {
+			lines.push(format!("  {}", expected_lines[i]));
+			i += 1;
+			j += 1;
+		} else if j == actual_lines.len() || (i < expected_lines.len() && common[i + 1][j] >= common[i][j + 1]) {
+			lines.push(format!("- {}", expected_lines[i]));
+			i += 1;
+		} else {
+			lines.push(format!("+ {}", actual_lines[j]));
+			j += 1;
+		}
+	}
+
+	lines.join("\n")
+}
+
+/// A short description of `value`, from its `inspect`.
+fn inspect(value: &Object) -> quest_core::Result<String> {
+	Ok(value.call_attr_lit("inspect", &[])?.call_downcast::<Text>()?.to_string())
+}
+
+/// The object that tests are written with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Test;
+
+impl Test {
+	/// Runs the second argument, a block, with its tests named after the first argument.
+	pub fn qs_describe(_: &Object, args: Args) -> quest_core::Result<Object> {
+		let name = args.try_arg(0)?.call_downcast::<Text>()?.to_string();
+		let block = args.try_arg(1)?;
+
+		struct GroupGuard;
+		impl Drop for GroupGuard {
+			fn drop(&mut self) {
+				with_runner(|runner| runner.groups.pop());
+			}
+		}
+
+		let _guard = with_runner(|runner| runner.groups.push(name)).map(|_| GroupGuard);
+
+		block.call_attr_lit("()", &[])
+	}
+
+	/// A test named after the first argument, which runs the second argument, a block.
+	///
+	/// The optional third argument is how many seconds the test may run for. When tests are being
+	/// run, this returns whether the test passed; otherwise, it returns what the block did.
+	pub fn qs_it(_: &Object, args: Args) -> quest_core::Result<Object> {
+		let name = args.try_arg(0)?.call_downcast::<Text>()?.to_string();
+		let block = args.try_arg(1)?;
+		let timeout =
+			match args.arg(2) {
+				Some(secs) => Some(Duration::try_from_secs_f64(f64::from(*secs.call_downcast::<Number>()?))
+					.map_err(|err| quest_core::error::ValueError::Messaged(format!("invalid timeout: {}", err)))?),
+				None => None
+			};
+
+		let test = with_runner(|runner| {
+			let mut names = runner.groups.clone();
+			names.push(name);
+			let name = names.join(" ");
+
+			let should_run = runner.options.filter.as_ref().is_none_or(|filter| name.contains(filter.as_str()));
+			let timeout = timeout.or(runner.options.timeout).unwrap_or(DEFAULT_TIMEOUT);
+
+			(name, should_run, timeout)
+		});
+
+		let (name, timeout) =
+			match test {
+				None => return block.call_attr_lit("()", &[]),
+				Some((_, false, _)) => return Ok(Object::default()),
+				Some((name, true, timeout)) => (name, timeout)
+			};
+
+		let start = Instant::now();
+		let result = sandbox::limit(Budget { steps: None, time: Some(timeout) }, || block.call_attr_lit("()", &[]));
+		let time = start.elapsed();
+
+		let status =
+			match result {
+				Ok(_) => Status::Passed,
+				Err(Error::BudgetError(BudgetError::Time(limit))) if limit == timeout => Status::TimedOut(timeout),
+				Err(err @ Error::Return { .. }) => return Err(err),
+				Err(err) => Status::Failed(err.to_string())
+			};
+
+		let passed = status == Status::Passed;
+
+		with_runner(|runner| {
+			let file = runner.file.clone();
+			runner.outcomes.push(Outcome { name, file, status, time });
+		});
+
+		Ok(passed.into())
+	}
+
+	/// Asserts that the first two arguments are equal (with `==`), returning the first.
+	///
+	/// If they aren't, the error shows how their `inspect`s differ, after the optional third
+	/// argument.
+	pub fn qs_assert_eq(_: &Object, args: Args) -> quest_core::Result<Object> {
+		let actual = args.try_arg(0)?;
+		let expected = args.try_arg(1)?;
+
+		if actual.call_attr_lit("==", &[expected])?.call_downcast::<Boolean>()?.into_inner() {
+			return Ok(actual.clone());
+		}
+
+		let message =
+			match args.arg(2) {
+				Some(message) => message.call_downcast::<Text>()?.to_string(),
+				None => "values aren't equal".to_string()
+			};
+
+		let diff = diff(&inspect(expected)?, &inspect(actual)?);
+		let diff = diff.lines().map(|line| format!("  {}", line)).collect::<Vec<_>>().join("\n");
+
+		Err(Error::AssertionFailed(Some(format!("{}\n{}", message, diff))))
+	}
+
+	/// Asserts that calling the first argument, a block, raises an error, returning its message.
+	///
+	/// If a second argument is given, the error's message must contain it.
+	pub fn qs_assert_raises(_: &Object, args: Args) -> quest_core::Result<Object> {
+		let block = args.try_arg(0)?;
+		let expected = args.arg(1).map(|message| message.call_downcast::<Text>().map(|text| text.to_string())).transpose()?;
+
+		let message =
+			match block.call_attr_lit("()", &[]) {
+				Ok(value) => return Err(Error::AssertionFailed(Some(
+					format!("expected an error, but the block returned {}", inspect(&value)?)))),
+				Err(err @ Error::Return { .. }) => return Err(err),
+				Err(err) => err.to_string()
+			};
+
+		match expected {
+			Some(expected) if !message.contains(expected.as_str()) =>
+				Err(Error::AssertionFailed(Some(
+					format!("expected an error containing {:?}, but got: {}", expected, message)))),
+			_ => Ok(message.into())
+		}
+	}
+}
+
+impl_object_type!{
+for Test [(parents quest_core::types::Basic)]:
+	/// Runs the second argument, a block, with its tests named after the first argument.
+	"describe" => method Test::qs_describe,
+	/// A test named after the first argument, which runs the second argument, a block, for at most
+	/// the third argument's number of seconds.
+	"it" => method Test::qs_it,
+	/// Asserts that the first two arguments are equal (with `==`), returning the first.
+	"assert_eq" => method Test::qs_assert_eq,
+	/// Asserts that calling the first argument, a block, raises an error, returning its message.
+	"assert_raises" => method Test::qs_assert_raises,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::io::Cursor;
+
+	const CODE: &str = r#"
+Test.describe("math", {
+	Test.it("adds", { Test.assert_eq(1 + 2, 3) });
+	Test.it("subtracts", { Test.assert_eq(3 - 1, 1, "subtraction is broken") });
+
+	Test.describe("division", {
+		Test.it("fails on zero", { Test.assert_raises({ 1 / "a" }) });
+		Test.it("doesn't fail", { Test.assert_raises({ 1 / 2 }) });
+	});
+});
+
+Test.it("loops forever", { while({ true }, {}) }, 0.01);
+Test.it("sleeps forever", { sleep() }, 0.01);
+"#;
+
+	fn run(filter: Option<&str>) -> Vec<(String, Status)> {
+		quest_core::init();
+		crate::init();
+
+		let options = Options { filter: filter.map(str::to_string), timeout: None };
+		let stream = BufStream::new(Cursor::new(CODE), Some("math_test.qs".into()));
+
+		run_stream(stream, &options).into_iter()
+			.map(|outcome| (outcome.name, outcome.status))
+			.collect()
+	}
+
+	#[test]
+	fn records_outcomes() {
+		let outcomes = run(None);
+		let names = outcomes.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>();
+
+		assert_eq!(names, [
+			"math adds",
+			"math subtracts",
+			"math division fails on zero",
+			"math division doesn't fail",
+			"loops forever",
+			"sleeps forever"
+		]);
+
+		assert_eq!(outcomes[0].1, Status::Passed);
+		assert_eq!(outcomes[1].1, Status::Failed("assertion failed: subtraction is broken\n  expected: 1\n    actual: 2\n            ^".to_string()));
+		assert_eq!(outcomes[2].1, Status::Passed);
+		assert!(matches!(&outcomes[3].1, Status::Failed(message) if message.contains("expected an error")));
+		assert_eq!(outcomes[4].1, Status::TimedOut(Duration::from_millis(10)));
+		assert_eq!(outcomes[5].1, Status::TimedOut(Duration::from_millis(10)));
+	}
+
+	#[test]
+	fn filters_by_name() {
+		let names = run(Some("division")).into_iter().map(|(name, _)| name).collect::<Vec<_>>();
+
+		assert_eq!(names, ["math division fails on zero", "math division doesn't fail"]);
+	}
+
+	#[test]
+	fn failures_outside_tests() {
+		quest_core::init();
+		crate::init();
+
+		let stream = BufStream::new(Cursor::new("Test.it('runs', {}); undefined_variable"), Some("bad_test.qs".into()));
+		let outcomes = run_stream(stream, &Options::default());
+
+		assert_eq!(outcomes.len(), 2);
+		assert!(outcomes[0].passed());
+		assert_eq!(outcomes[1].name, "bad_test.qs");
+		assert!(!outcomes[1].passed());
+	}
+
+	#[test]
+	fn diffs() {
+		assert_eq!(diff("[1, 2, 3]", "[1, 2, 4]"), "expected: [1, 2, 3]\n  actual: [1, 2, 4]\n                 ^");
+		assert_eq!(diff("a\nb\nc", "a\nc\nd"), "- expected\n+ actual\n  a\n- b\n  c\n+ d");
+	}
+}
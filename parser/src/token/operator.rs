@@ -19,7 +19,7 @@ macro_rules! operator_enum {
 		[$lhs, rhs][($lhs < rhs) as usize]
 	}};
 
-	(; ASSOC ) => { operator_enum!(; ASSOC LeftToRight ); };
+	(; ASSOC ) => { operator_enum!(; ASSOC LeftToRight ) };
 	(; ASSOC $which:ident ) => { Associativity::$which };
 	(; TRY_PARSE $_repr:literal ()) => { None };
 	(; TRY_PARSE $repr:literal) => { Some($repr) };
@@ -49,6 +49,14 @@ macro_rules! operator_enum {
 		impl Operator {
 			pub const MAX_PRECEDENCE: usize = operator_enum!(; MAX_PRECEDENCE $($ord)+) as usize;
 
+			/// Gets the operator whose [`repr`](Self::repr) is `repr`.
+			pub fn from_repr(repr: &str) -> Option<Self> {
+				match repr {
+					$($repr => Some(Operator::$variant),)+
+					_ => None
+				}
+			}
+
 			pub fn repr(&self) -> &'static str {
 				match self {
 					$(Operator::$variant => $repr),+
@@ -102,6 +110,23 @@ operator_enum!{
 	WithBlock("{}" () 1)
 }
 
+impl Operator {
+	/// Gets how this operator is written in source code when it's a unary operator on the left.
+	///
+	/// Some of these operators share a representation with a binary operator, so their
+	/// [`repr`](Self::repr) is different.
+	pub fn unary_repr(&self) -> &'static str {
+		match self {
+			Operator::Neg => "-",
+			Operator::Pos => "+",
+			Operator::Splat => "*",
+			Operator::SplatSplat => "**",
+			Operator::RootScope => "::",
+			other => other.repr()
+		}
+	}
+}
+
 impl Display for Operator {
 	#[inline]
 	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
@@ -140,7 +165,7 @@ impl From<Operator> for Token {
 impl PartialOrd for Operator {
 	#[inline]
 	fn partial_cmp(&self, rhs: &Operator) -> Option<std::cmp::Ordering> {
-		Some(self.cmp(&rhs))
+		Some(self.cmp(rhs))
 	}
 }
 
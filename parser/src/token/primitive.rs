@@ -14,6 +14,9 @@ pub mod regex;
 /// A text literal.
 pub use text::Text;
 
+/// A text literal with embedded expressions.
+pub use text::Interpolated;
+
 /// A number literal.
 pub use number::Number;
 
@@ -39,6 +42,10 @@ pub enum Primitive {
 	///
 	/// See [`Text`](#) for more information on parsing.
 	Text(Text),
+	/// A piece of text with expressions embedded within it.
+	///
+	/// See [`Interpolated`](#) for more information on parsing.
+	Interpolated(Interpolated),
 	/// A literal number.
 	///
 	/// See [`Number`](#) for more information on parsing.
@@ -55,7 +62,8 @@ pub enum Primitive {
 impl Display for Primitive {
 	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
 		match self {
-			Primitive::Text(t) => Display::fmt(&t, f),
+			Primitive::Text(t) => text::fmt_quoted(t.as_ref(), f),
+			Primitive::Interpolated(i) => Display::fmt(&i, f),
 			Primitive::Number(n) => Display::fmt(&n, f),
 			Primitive::Variable(v) => Display::fmt(&v, f),
 			Primitive::Regex(r) => Display::fmt(&r, f),
@@ -68,6 +76,7 @@ impl Executable for Primitive {
 	fn execute(&self) -> quest_core::Result<quest_core::Object> {
 		match self {
 			Primitive::Text(t) => t.execute(),
+			Primitive::Interpolated(i) => i.execute(),
 			Primitive::Number(n) => n.execute(),
 			Primitive::Variable(v) => v.execute(),
 			Primitive::Regex(r) => r.execute(),
@@ -76,6 +85,16 @@ impl Executable for Primitive {
 	}
 }
 
+impl From<Interpolated> for Primitive {
+	fn from(interpolated: Interpolated) -> Self {
+		if interpolated.is_interpolated() {
+			Primitive::Interpolated(interpolated)
+		} else {
+			Primitive::Text(interpolated.into_text())
+		}
+	}
+}
+
 impl From<Primitive> for Token {
 	fn from(lit: Primitive) -> Token {
 		Token::Primitive(lit)
@@ -94,6 +113,11 @@ impl Tokenizable for Primitive {
 			other => return Ok(other)
 		}
 
+		match Interpolated::try_tokenize(stream)?.map(Primitive::from) {
+			None => { /* do nothing, parse the next one */ },
+			other => return Ok(other)
+		}
+
 		match Text::try_tokenize(stream)?.map(Primitive::Text) {
 			None => { /* do nothing, parse the next one */ },
 			other => return Ok(other)
@@ -14,7 +14,7 @@ pub use quest_core::types::number::FromStrError as ParseError;
 
 impl Executable for Number {
 	fn execute(&self) -> quest_core::Result<Object> {
-		Ok(self.clone().into())
+		Ok((*self).into())
 	}
 }
 
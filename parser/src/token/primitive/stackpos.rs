@@ -7,6 +7,20 @@ use std::fmt::{self, Display, Formatter};
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct StackPos(isize);
 
+impl From<isize> for StackPos {
+	#[inline]
+	fn from(pos: isize) -> Self {
+		Self(pos)
+	}
+}
+
+impl From<StackPos> for isize {
+	#[inline]
+	fn from(pos: StackPos) -> Self {
+		pos.0
+	}
+}
+
 impl Display for StackPos {
 	#[inline]
 	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
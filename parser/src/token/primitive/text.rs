@@ -1,9 +1,11 @@
 //! Parsing a literal text
 
-use crate::{Result, Stream};
-use crate::expression::Executable;
-use crate::token::{Operator, Tokenizable, primitive::Variable};
+use crate::{Result, Stream, Block, Token};
+use crate::expression::{Constructable, Constructor, Executable, PutBack};
+use crate::stream::{Context, Contexted};
+use crate::token::{Operator, ParenType, Tokenizable, primitive::Variable};
 use quest_core::Object;
+use std::fmt::{self, Display, Formatter};
 
 /// A literal text is actually just a `quest_core::Text`.
 pub use quest_core::types::Text;
@@ -15,7 +17,197 @@ impl Executable for Text {
 	}
 }
 
-fn try_tokenize_quoted<S: Stream>(stream: &mut S, quote: char) -> Result<Option<Text>> {
+/// Writes `text` as a single-quoted literal.
+pub(crate) fn fmt_quoted(text: &str, f: &mut Formatter) -> fmt::Result {
+	f.write_str("'")?;
+
+	for chr in text.chars() {
+		if chr == '\'' || chr == '\\' {
+			f.write_str("\\")?;
+		}
+
+		write!(f, "{}", chr)?;
+	}
+
+	f.write_str("'")
+}
+
+/// A piece of an [`Interpolated`] text literal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment {
+	/// Literal text that's included verbatim.
+	Text(Text),
+	/// An embedded `${...}` block, which is converted via `@text` when executed.
+	Code(Block)
+}
+
+/// A double-quoted text literal, which may contain embedded expressions (eg `"hi, ${name}!"`).
+///
+/// If no expressions are embedded, this is converted to a plain [`Text`] when turned into a
+/// [`Primitive`](super::Primitive).
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Interpolated(Vec<Segment>);
+
+impl Interpolated {
+	/// Returns whether any [`Segment::Code`]s are in this literal.
+	#[must_use]
+	pub fn is_interpolated(&self) -> bool {
+		self.0.iter().any(|segment| matches!(segment, Segment::Code(_)))
+	}
+
+	/// Concatenates all the [`Segment::Text`]s together, ignoring any [`Segment::Code`]s.
+	#[must_use]
+	pub fn into_text(self) -> Text {
+		self.0.into_iter()
+			.filter_map(|segment| match segment {
+				Segment::Text(text) => Some(text.to_string()),
+				Segment::Code(_) => None
+			})
+			.collect::<String>()
+			.into()
+	}
+
+	/// Gets the [`Segment`]s that make up this literal.
+	#[must_use]
+	pub fn segments(&self) -> &[Segment] {
+		&self.0
+	}
+
+	fn push_text(&mut self, text: &mut String) {
+		if !text.is_empty() {
+			self.0.push(Segment::Text(std::mem::take(text).into()));
+		}
+	}
+}
+
+impl From<Vec<Segment>> for Interpolated {
+	#[inline]
+	fn from(segments: Vec<Segment>) -> Self {
+		Self(segments)
+	}
+}
+
+impl Display for Interpolated {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		f.write_str("\"")?;
+
+		for segment in &self.0 {
+			match segment {
+				Segment::Text(text) =>
+					for chr in text.as_ref().chars() {
+						match chr {
+							'"' | '\\' | '$' => write!(f, "\\{}", chr)?,
+							'\n' => f.write_str("\\n")?,
+							'\t' => f.write_str("\\t")?,
+							'\r' => f.write_str("\\r")?,
+							'\0' => f.write_str("\\0")?,
+							other if other.is_control() => write!(f, "\\u{{{:x}}}", other as u32)?,
+							other => write!(f, "{}", other)?
+						}
+					},
+				Segment::Code(block) => write!(f, "${}", block)?
+			}
+		}
+
+		f.write_str("\"")
+	}
+}
+
+impl Executable for Interpolated {
+	fn execute(&self) -> quest_core::Result<Object> {
+		let mut text = String::new();
+
+		for segment in &self.0 {
+			match segment {
+				Segment::Text(segment) => text.push_str(segment.as_ref()),
+				Segment::Code(block) => {
+					let result = block.run_block()?.map(Object::from).unwrap_or_default();
+					text.push_str(result.call_downcast::<Text>()?.as_ref());
+				}
+			}
+		}
+
+		Ok(text.into())
+	}
+}
+
+/// Tokens read directly from a stream, used to parse the contents of a `${...}`.
+struct StreamTokens<'a, S>(&'a mut S);
+
+impl<S: Stream> Iterator for StreamTokens<'_, S> {
+	type Item = Result<Token>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		Token::try_parse(self.0).transpose()
+	}
+}
+
+impl<S: Stream> Contexted for StreamTokens<'_, S> {
+	fn context(&self) -> &Context {
+		self.0.context()
+	}
+}
+
+// parses the `...}` after the `${` within a double-quoted string.
+fn try_tokenize_interpolation<S: Stream>(stream: &mut S) -> Result<Block> {
+	let starting_context = stream.context().clone();
+	let mut ctor = Constructor::new(StreamTokens(stream));
+	ctor.put_back(Ok(Token::Left(ParenType::Curly)));
+
+	let block = Block::try_construct_primary(&mut ctor)?
+		.expect("we just put back a left curly paren");
+
+	if block.lines.is_empty() {
+		Err(parse_error!(context=starting_context, ExpectedExpression))
+	} else {
+		Ok(block)
+	}
+}
+
+// parses the `{...}` after a `\u`, which is the hex value of a unicode codepoint.
+fn try_tokenize_unicode_escape<S: Stream>(stream: &mut S, starting_context: &Context) -> Result<char> {
+	if !stream.next_if_starts_with("{")? {
+		return Err(parse_error!(stream, Message("expected `{` after `\\u`")));
+	}
+
+	let mut digits = String::new();
+
+	loop {
+		match stream.next().transpose()? {
+			Some('}') => break,
+			Some(chr) if chr.is_ascii_hexdigit() && digits.len() < 6 => digits.push(chr),
+			Some(_) => return Err(parse_error!(stream, Message("invalid unicode escape"))),
+			None => return Err(parse_error!(context=starting_context.clone(), UnterminatedQuote))
+		}
+	}
+
+	u32::from_str_radix(&digits, 16).ok()
+		.and_then(std::char::from_u32)
+		.ok_or_else(|| parse_error!(stream, Message("invalid unicode escape")))
+}
+
+// parses the two hex digits after a `\x`, which is an ascii character.
+fn try_tokenize_hex_escape<S: Stream>(stream: &mut S, starting_context: &Context) -> Result<char> {
+	let mut value = 0;
+
+	for _ in 0..2 {
+		match stream.next().transpose()? {
+			Some(chr) if chr.is_ascii_hexdigit() =>
+				value = value * 16 + chr.to_digit(16).expect("we just checked it's a hex digit"),
+			Some(_) => return Err(parse_error!(stream, Message("invalid hex escape"))),
+			None => return Err(parse_error!(context=starting_context.clone(), UnterminatedQuote))
+		}
+	}
+
+	if value <= 0x7f {
+		Ok(value as u8 as char)
+	} else {
+		Err(parse_error!(stream, Message("hex escapes must be at most `\\x7f`")))
+	}
+}
+
+fn try_tokenize_quoted<S: Stream>(stream: &mut S, quote: char) -> Result<Interpolated> {
+	let mut interpolated = Interpolated::default();
 	let mut text = String::new();
 
 	let starting_context = stream.context().clone();
@@ -25,14 +217,15 @@ fn try_tokenize_quoted<S: Stream>(stream: &mut S, quote: char) -> Result<Option<
 			'\\' if quote == '"' => match stream.next().transpose()? {
 				Some(chr @ '\\')
 					| Some(chr @ '\'')
+					| Some(chr @ '$')
 					| Some(chr @ '\"') => text.push(chr),
 				Some('n') => text.push('\n'),
 				Some('\n') => { /* do nothing */ },
 				Some('t') => text.push('\t'),
 				Some('r') => text.push('\r'),
 				Some('0') => text.push('\0'),
-				Some('u') | Some('U')
-					| Some('x') | Some('X') => todo!("additional string parsing"),
+				Some('u') => text.push(try_tokenize_unicode_escape(stream, &starting_context)?),
+				Some('x') => text.push(try_tokenize_hex_escape(stream, &starting_context)?),
 				Some(chr) => return Err(parse_error!(stream, BadEscapeChar(chr))),
 				None      => return Err(parse_error!(context=starting_context, UnterminatedQuote)),
 			},
@@ -41,7 +234,14 @@ fn try_tokenize_quoted<S: Stream>(stream: &mut S, quote: char) -> Result<Option<
 				Some(other) => { text.push('\\'); text.push(other); },
 				None => return Err(parse_error!(context=starting_context, UnterminatedQuote))
 			},
-			chr if chr == quote => return Ok(Some(text.into())),
+			'$' if quote == '"' && stream.next_if_starts_with("{")? => {
+				interpolated.push_text(&mut text);
+				interpolated.0.push(Segment::Code(try_tokenize_interpolation(stream)?));
+			},
+			chr if chr == quote => {
+				interpolated.push_text(&mut text);
+				return Ok(interpolated);
+			},
 			chr => text.push(chr)
 		}
 	}
@@ -81,7 +281,7 @@ impl Tokenizable for Text {
 	fn try_tokenize<S: Stream>(stream: &mut S) -> Result<Option<Self>> {
 		match stream.next().transpose()? {
 			Some('$') => try_tokenize_dollar_sign(stream),
-			Some(quote @ '\"') | Some(quote @ '\'') => try_tokenize_quoted(stream, quote),
+			Some('\'') => try_tokenize_quoted(stream, '\'').map(|text| Some(text.into_text())),
 			Some(chr) => {
 				unseek_char!(stream; chr);
 				Ok(None)
@@ -90,3 +290,82 @@ impl Tokenizable for Text {
 		}
 	}
 }
+
+impl Tokenizable for Interpolated {
+	fn try_tokenize<S: Stream>(stream: &mut S) -> Result<Option<Self>> {
+		match stream.next().transpose()? {
+			Some('"') => try_tokenize_quoted(stream, '"').map(Some),
+			Some(chr) => {
+				unseek_char!(stream; chr);
+				Ok(None)
+			},
+			None => Ok(None)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::stream::BufStream;
+
+	macro_rules! buf {
+		($n:expr) => { &mut BufStream::from($n) };
+	}
+
+	#[test]
+	fn plain_text() {
+		let text = Interpolated::try_tokenize(buf!(r#""a\$b\n""#)).unwrap().unwrap();
+		assert!(!text.is_interpolated());
+		assert_eq!(text.into_text().as_ref(), "a$b\n");
+
+		assert_eq!(Interpolated::try_tokenize(buf!("'a'")).unwrap(), None);
+		assert_eq!(Text::try_tokenize(buf!("'${a}'")).unwrap().unwrap().as_ref(), "${a}");
+	}
+
+	#[test]
+	fn interpolation() {
+		let text = Interpolated::try_tokenize(buf!(r#""x ${a + 1}, ${"y"}""#)).unwrap().unwrap();
+		assert!(text.is_interpolated());
+		assert_eq!(text.0.len(), 4);
+		assert!(matches!(&text.0[0], Segment::Text(t) if t.as_ref() == "x "));
+		assert!(matches!(&text.0[1], Segment::Code(_)));
+		assert!(matches!(&text.0[2], Segment::Text(t) if t.as_ref() == ", "));
+		assert!(matches!(&text.0[3], Segment::Code(_)));
+	}
+
+	#[test]
+	fn interpolation_errors() {
+		let err = Interpolated::try_tokenize(buf!("\"a\n ${ 1 + } b\"")).unwrap_err();
+		assert!(err.to_string().starts_with("<eval>:2:9: parse error, expected an expression"), "{}", err);
+
+		assert!(Interpolated::try_tokenize(buf!(r#""${}""#)).is_err());
+		assert!(Interpolated::try_tokenize(buf!(r#""${1""#)).is_err());
+	}
+
+	#[test]
+	fn escapes() {
+		let text = Interpolated::try_tokenize(buf!(r#""\u{e9}\u{1F600}\x41""#)).unwrap().unwrap();
+		assert_eq!(text.into_text().as_ref(), "é😀A");
+
+		assert!(Interpolated::try_tokenize(buf!(r#""\u{110000}""#)).is_err());
+		assert!(Interpolated::try_tokenize(buf!(r#""\u41""#)).is_err());
+		assert!(Interpolated::try_tokenize(buf!(r#""\x80""#)).is_err());
+	}
+
+	#[test]
+	fn display_round_trips() {
+		for source in [r#""café ${x} \$\"\\ naïve""#, "\"tab\tnul\0bell\u{7}\"", r#""日本語""#].iter() {
+			let text = Interpolated::try_tokenize(buf!(*source)).unwrap().unwrap();
+			let shown = text.to_string();
+			let reparsed = Interpolated::try_tokenize(buf!(shown.as_str())).unwrap().unwrap();
+
+			assert_eq!(reparsed.to_string(), shown);
+			assert_eq!(reparsed.segments().len(), text.segments().len());
+			assert_eq!(Interpolated::from(reparsed.segments().to_vec()).into_text(), text.clone().into_text());
+		}
+
+		let text = Interpolated::try_tokenize(buf!(r#""café ${x}""#)).unwrap().unwrap();
+		assert_eq!(text.to_string().split('$').next(), Some("\"café "));
+	}
+}
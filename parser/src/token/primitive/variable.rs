@@ -20,6 +20,13 @@ impl Executable for Variable {
 	}
 }
 
+impl From<quest_core::types::Text> for Variable {
+	#[inline]
+	fn from(name: quest_core::types::Text) -> Self {
+		Self(name)
+	}
+}
+
 impl From<Variable> for quest_core::types::Text {
 	#[inline]
 	fn from(val: Variable) -> Self {
@@ -27,6 +34,17 @@ impl From<Variable> for quest_core::types::Text {
 	}
 }
 
+impl Variable {
+	/// Checks to see if `name` can be written as a variable without any escapes.
+	#[must_use]
+	pub fn is_valid_name(name: &str) -> bool {
+		let name = name.strip_suffix('?').unwrap_or(name);
+		let mut chars = name.chars();
+
+		chars.next().is_some_and(is_variable_start) && chars.all(is_variable_body)
+	}
+}
+
 #[inline]
 fn is_variable_start(c: char) -> bool {
 	!c.is_ascii() || c.is_ascii_alphabetic() || c == '_' || c == '@'
@@ -48,18 +48,23 @@ fn parse_whitespace<S: Stream>(stream: &mut S) -> Result<bool> {
 enum CommentResult {
 	NoCommentFound,
 	CommentRemoved,
+	DocComment(String),
 	StopParsing
 }
 
 fn parse_comment<S: Stream>(stream: &mut S) -> Result<CommentResult> {
-	fn parse_line<S: Stream>(stream: &mut S) -> Result<()> {
+	fn parse_line<S: Stream>(stream: &mut S) -> Result<String> {
+		let mut line = String::new();
+
 		while let Some(chr) = stream.next().transpose()? {
 			if chr == '\n' {
 				break;
 			}
+
+			line.push(chr);
 		}
 
-		Ok(())
+		Ok(line)
 	}
 
 	fn parse_block<S: Stream>(stream: &mut S) -> Result<()> {
@@ -81,7 +86,16 @@ fn parse_comment<S: Stream>(stream: &mut S) -> Result<CommentResult> {
 	if stream.starts_with("##__EOF__##")? {
 		Ok(CommentResult::StopParsing)
 	} else if stream.starts_with("#")? {
-		parse_line(stream).and(Ok(CommentResult::CommentRemoved))
+		let line = parse_line(stream)?;
+
+		// `##` starts a doc comment, but `###...` lines, such as banners, don't.
+		match line.strip_prefix("##") {
+			Some(doc) if !doc.starts_with('#') => {
+				let doc = doc.strip_prefix(' ').unwrap_or(doc).trim_end();
+				Ok(CommentResult::DocComment(doc.to_string()))
+			},
+			_ => Ok(CommentResult::CommentRemoved)
+		}
 	} else if stream.next_if_starts_with("/*")? {
 		parse_block(stream).and(Ok(CommentResult::CommentRemoved))
 	} else {
@@ -90,16 +104,46 @@ fn parse_comment<S: Stream>(stream: &mut S) -> Result<CommentResult> {
 }
 
 impl Token {
-	pub fn try_parse<S: Stream>(stream: &mut S) -> Result<Option<Self>> {
+	/// Skips the whitespace and comments before the next token.
+	///
+	/// Returns `false` if there's nothing left to parse.
+	pub fn skip_ignored<S: Stream>(stream: &mut S) -> Result<bool> {
+		Self::skip_ignored_with_docs(stream, &mut None)
+	}
+
+	/// Skips the whitespace and comments before the next token, like [`skip_ignored`], and sets
+	/// `docs` to the lines of the `##` doc comments directly before it.
+	///
+	/// Any other comment between the doc comments and the token discards them.
+	///
+	/// [`skip_ignored`]: Self::skip_ignored
+	pub fn skip_ignored_with_docs<S: Stream>(stream: &mut S, docs: &mut Option<String>) -> Result<bool> {
 		if parse_whitespace(stream)? {
 			// do nothing
 			// return Ok(Some(Self::Endline(false)));
 		}
 
 		match parse_comment(stream)? {
-			CommentResult::StopParsing => return Ok(None),
-			CommentResult::CommentRemoved => return Self::try_parse(stream),
-			CommentResult::NoCommentFound => {}
+			CommentResult::StopParsing => Ok(false),
+			CommentResult::CommentRemoved => {
+				*docs = None;
+				Self::skip_ignored_with_docs(stream, docs)
+			},
+			CommentResult::DocComment(line) => {
+				match docs {
+					Some(docs) => { docs.push('\n'); docs.push_str(&line); },
+					None => *docs = Some(line)
+				}
+
+				Self::skip_ignored_with_docs(stream, docs)
+			},
+			CommentResult::NoCommentFound => Ok(true)
+		}
+	}
+
+	pub fn try_parse<S: Stream>(stream: &mut S) -> Result<Option<Self>> {
+		if !Self::skip_ignored(stream)? {
+			return Ok(None);
 		}
 
 		if let Some(prim) = Primitive::try_tokenize(stream)? {